@@ -0,0 +1,345 @@
+/*  aarch64.rs
+    MIT License
+
+    Copyright (c) 2024 Aidar Shigapov
+
+    Permission is hereby granted, free of charge, to any person obtaining a copy
+    of this software and associated documentation files (the "Software"), to deal
+    in the Software without restriction, including without limitation the rights
+    to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+    copies of the Software, and to permit persons to whom the Software is
+    furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in all
+    copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+    OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+    SOFTWARE.
+*/
+// aarch64 linux in GNU as syntax, linked against libc like the x86 asm. the data stack and the return stack get a register each:
+// x19 is the data stack pointer (it grows down, like rsp) and x20 the bottom of it, sp is the return stack.
+// bl leaves the return address in x30, so every statement keeps x30 in its frame on sp, next to its `times` counters
+
+use crate::{State, StateType, asm_label_name};
+use crate::lower::{lower, Op};
+
+const DATA_STACK_SIZE: usize = 8 << 20;
+
+const AARCH64_RUNTIME: &str = r##"	.section .rodata
+jalgo_int_fmt:
+	.asciz "%lli "
+jalgo_bytes_fmt:
+	.asciz "%.8s"
+jalgo_newline_fmt:
+	.asciz "\n"
+jalgo_int_scan_fmt:
+	.asciz "%lld"
+
+	.bss
+	.balign 16
+jalgo_read_int_value:
+	.skip 8
+jalgo_io_byte:
+	.skip 8
+jalgo_line_buffer:
+	.skip 4096
+jalgo_path_buffer:
+	.skip 4096
+jalgo_data_stack:
+	.skip {data_stack_size}
+jalgo_data_stack_end:
+
+	.text
+	.global main
+	.balign 4
+main:
+	adrp x19, jalgo_data_stack_end
+	add x19, x19, :lo12:jalgo_data_stack_end
+	mov x20, x19
+	bl st_start
+	mov x0, #0
+	bl exit
+
+jalgo_dump_stack:
+	// prints the data stack from the bottom to the top
+	str x30, [sp, #-16]!
+	mov x21, x20
+1:
+	cmp x21, x19
+	b.ls 2f
+	ldr x1, [x21, #-8]!
+	adrp x0, jalgo_int_fmt
+	add x0, x0, :lo12:jalgo_int_fmt
+	bl printf
+	b 1b
+2:
+	adrp x0, jalgo_newline_fmt
+	add x0, x0, :lo12:jalgo_newline_fmt
+	bl printf
+	ldr x30, [sp], #16
+	ret
+
+jalgo_read_line:
+	// pushes the line as a zero terminated stack string and its length, -1 on EOF
+	str x30, [sp, #-16]!
+	mov x21, #0
+1:
+	bl getchar
+	cmn w0, #1
+	b.eq 2f
+	cmp w0, #10
+	b.eq 3f
+	adrp x1, jalgo_line_buffer
+	add x1, x1, :lo12:jalgo_line_buffer
+	strb w0, [x1, x21]
+	add x21, x21, #1
+	cmp x21, #4096
+	b.lo 1b
+	b 3f
+2:
+	cbnz x21, 3f
+	mov x0, #-1
+	str x0, [x19, #-8]!
+	b 6f
+3:
+	str xzr, [x19, #-8]!
+	adrp x1, jalgo_line_buffer
+	add x1, x1, :lo12:jalgo_line_buffer
+	mov x2, x21
+4:
+	cbz x2, 5f
+	sub x2, x2, #1
+	ldrb w0, [x1, x2]
+	str x0, [x19, #-8]!
+	b 4b
+5:
+	str x21, [x19, #-8]!
+6:
+	ldr x30, [sp], #16
+	ret
+
+jalgo_pop_path:
+	// pops a zero terminated stack string into jalgo_path_buffer
+	adrp x1, jalgo_path_buffer
+	add x1, x1, :lo12:jalgo_path_buffer
+1:
+	ldr x0, [x19], #8
+	strb w0, [x1], #1
+	cbnz x0, 1b
+	ret
+
+jalgo_fread_byte:
+	// fd -> byte, -1 on EOF, -errno on error
+	ldr x0, [x19]
+	adrp x1, jalgo_io_byte
+	add x1, x1, :lo12:jalgo_io_byte
+	str xzr, [x1]
+	mov x2, #1
+	mov x8, #63
+	svc #0
+	cmp x0, #0
+	b.lt 1f
+	mov x0, #-1
+	b.eq 1f
+	adrp x1, jalgo_io_byte
+	ldr x0, [x1, :lo12:jalgo_io_byte]
+1:
+	str x0, [x19]
+	ret
+
+jalgo_fwrite_byte:
+	// fd byte -> 1, -errno on error. printf`s buffer goes out first, so fd 1 keeps the order
+	str x30, [sp, #-16]!
+	mov x0, #0
+	bl fflush
+	ldr x30, [sp], #16
+	ldp x2, x0, [x19], #8
+	adrp x1, jalgo_io_byte
+	add x1, x1, :lo12:jalgo_io_byte
+	strb w2, [x1]
+	mov x2, #1
+	mov x8, #64
+	svc #0
+	str x0, [x19]
+	ret
+"##;
+
+fn function_name(state: &State) -> String {
+    format!("st_{}", asm_label_name(&state.name))
+}
+
+// mov for what fits in 16 bits, movz and movk for the rest
+fn load_immediate(register: &str, value: i64) -> String {
+    if (-65536..65536).contains(&value) {
+        return format!("\tmov {}, #{}\n", register, value);
+    }
+    let mut out = String::new();
+    for shift in (0..64).step_by(16) {
+        let chunk = (value as u64 >> shift) & 0xffff;
+        if chunk != 0 {
+            out += format!("\t{} {}, #{}, lsl #{}\n", if out.is_empty() { "movz" } else { "movk" }, register, chunk, shift).as_str();
+        }
+    }
+    out
+}
+
+fn load_address(register: &str, label: &str) -> String {
+    format!("\tadrp {0}, {1}\n\tadd {0}, {0}, :lo12:{1}\n", register, label)
+}
+
+fn compile_primitive(template_arguments: &[Vec<String>], state: &State) -> String {
+    let template_argument = |index: usize, default: usize| if state.template_arguments_index == 0 {
+        default
+    } else {
+        template_arguments[state.template_arguments_index].get(index).expect("too few template arguments").parse::<usize>().expect("template argument must be integer")
+    };
+    let push = "\tstr x0, [x19, #-8]!\n";
+    // x0 is the top of the stack, x1 the one below. the result replaces both
+    let binary = |instruction: &str| format!("\tldp x0, x1, [x19] // {}\n\t{}\n\tstr x0, [x19, #8]!\n", state.name, instruction);
+    let compare = |condition: &str| binary(&format!("cmp x1, x0\n\tcset x0, {}", condition));
+    let load = |instruction: &str| format!("\tldr x0, [x19] // {}\n\t{} [x0]\n\tstr x0, [x19]\n", state.name, instruction);
+    let store = |instruction: &str| format!("\tldp x0, x1, [x19], #16 // {}\n\t{} [x1]\n", state.name, instruction);
+    match state.state_type {
+        StateType::Integer =>   load_immediate("x0", state.name.parse::<i64>().expect("invalid integer")) + push,
+        StateType::Data =>      load_address("x0", &format!("data_{}", asm_label_name(&state.name))) + push,
+        StateType::Var =>       load_address("x0", &format!("var_{}", asm_label_name(&state.name))) + push,
+        StateType::VarFetch =>  format!("\tadrp x0, var_{0}\n\tldr x0, [x0, :lo12:var_{0}]\n{1}", asm_label_name(&state.name[1..]), push),
+        StateType::VarStore =>  format!("\tldr x0, [x19], #8\n\tadrp x1, var_{0}\n\tstr x0, [x1, :lo12:var_{0}]\n", asm_label_name(&state.name[1..])),
+        StateType::StackHead => format!("\tmov x0, x19 // stack_head\n{}", push),
+        StateType::Depth =>     format!("\tsub x0, x20, x19 // depth\n\tlsr x0, x0, #3\n{}", push),
+        StateType::DumpStack => "\tbl jalgo_dump_stack\n".to_string(),
+        StateType::ReadFrom =>  load("ldr x0,"),
+        StateType::ReadU8 =>    load("ldrb w0,"),
+        StateType::ReadI8 =>    load("ldrsb x0,"),
+        StateType::ReadU16 =>   load("ldrh w0,"),
+        StateType::ReadI16 =>   load("ldrsh x0,"),
+        StateType::ReadU32 =>   load("ldr w0,"),
+        StateType::ReadI32 =>   load("ldrsw x0,"),
+        StateType::WriteTo =>   store("str x0,"),
+        StateType::WriteU8 =>   store("strb w0,"),
+        StateType::WriteU16 =>  store("strh w0,"),
+        StateType::WriteU32 =>  store("str w0,"),
+        StateType::Alloc =>     "\tldr x0, [x19] // alloc\n\tbl malloc\n\tstr x0, [x19]\n".to_string(),
+        StateType::Free =>      "\tldr x0, [x19], #8 // free\n\tbl free\n".to_string(),
+        StateType::Eq =>        compare("eq"),
+        StateType::Neq =>       compare("ne"),
+        StateType::More =>      compare("gt"),
+        StateType::Less =>      compare("lt"),
+        StateType::Sum =>       binary("add x0, x1, x0"),
+        StateType::Dif =>       binary("sub x0, x1, x0"),
+        StateType::Mul =>       binary("mul x0, x1, x0"),
+        StateType::Div =>       binary("sdiv x0, x1, x0"), // x/0 is 0 here instead of a crash
+        StateType::Inc =>       "\tldr x0, [x19] // inc\n\tadd x0, x0, #1\n\tstr x0, [x19]\n".to_string(),
+        StateType::Dec =>       "\tldr x0, [x19] // dec\n\tsub x0, x0, #1\n\tstr x0, [x19]\n".to_string(),
+        StateType::Dup =>       format!("\tldr x0, [x19] // dup\n{}", push.repeat(template_argument(0, 1))),
+        StateType::Pop =>       format!("\tadd x19, x19, #{}\n", template_argument(0, 1) * 8),
+        StateType::Swap => {
+            let (first, second) = (template_argument(0, 0) * 8, template_argument(1, 1) * 8);
+            format!("\tldr x0, [x19, #{0}] // swap\n\tldr x1, [x19, #{1}]\n\tstr x1, [x19, #{0}]\n\tstr x0, [x19, #{1}]\n", first, second)
+        }
+        StateType::Print =>     format!("\tldr x1, [x19], #8 // print\n{}\tbl printf\n", load_address("x0", "jalgo_int_fmt")),
+        StateType::WriteRaw =>  format!("\tmov x1, x19 // write\n{}\tbl printf\n\tadd x19, x19, #8\n", load_address("x0", "jalgo_bytes_fmt")),
+        StateType::ReadInt =>   format!("\tadrp x1, jalgo_read_int_value // read_int\n\tadd x1, x1, :lo12:jalgo_read_int_value\n\tstr xzr, [x1]\n{0}\tbl scanf\n\tsxtw x2, w0\n\tadrp x1, jalgo_read_int_value\n\tldr x0, [x1, :lo12:jalgo_read_int_value]\n{1}\tstr x2, [x19, #-8]!\n", load_address("x0", "jalgo_int_scan_fmt"), push),
+        StateType::ReadChar =>  format!("\tbl getchar // read_char\n\tsxtw x0, w0\n{}", push),
+        StateType::ReadLine =>  "\tbl jalgo_read_line\n".to_string(),
+        StateType::Fopen | StateType::FopenPtr => {
+            let mode = if state.template_arguments_index == 0 { "r" } else { template_arguments[state.template_arguments_index][0].as_str() };
+            let flags = match mode {
+                "r" => 0,       // O_RDONLY
+                "w" => 577,     // O_WRONLY | O_CREAT | O_TRUNC
+                "a" => 1089,    // O_WRONLY | O_CREAT | O_APPEND
+                _ => panic!("unknown fopen mode \"{}\". possible values: r | w | a", mode),
+            };
+            // openat(AT_FDCWD, path, flags, 0644)
+            let path = if state.state_type == StateType::FopenPtr {
+                format!("\tldr x1, [x19], #8 // fopen_ptr<{}>\n", mode)
+            } else {
+                format!("\tbl jalgo_pop_path // fopen<{}>\n{}", mode, load_address("x1", "jalgo_path_buffer"))
+            };
+            format!("{}\tmov x0, #-100\n\tmov x2, #{}\n\tmov x3, #420\n\tmov x8, #56\n\tsvc #0\n{}", path, flags, push)
+        }
+        StateType::FreadByte => "\tbl jalgo_fread_byte\n".to_string(),
+        StateType::FwriteByte =>"\tbl jalgo_fwrite_byte\n".to_string(),
+        StateType::Fclose =>    "\tldr x0, [x19] // fclose\n\tmov x8, #57\n\tsvc #0\n\tstr x0, [x19]\n".to_string(),
+        StateType::Exit =>      "\tldr x0, [x19], #8 // exit\n\tbl exit\n".to_string(),
+        _ => panic!("{} is not a primitive", state.name),
+    }
+}
+
+fn compile_function(states: &[State], template_arguments: &[Vec<String>], state: &State) -> String {
+    let function = lower(states, template_arguments, state);
+    let name = function_name(state);
+    let mut used_labels = vec![false; function.labels];
+    for op in function.ops.iter() {
+        if let Op::Jump(label) | Op::JumpIfNotPositive(label) | Op::TimesBegin{ exit: label, .. } | Op::TimesEnd{ again: label, .. } = op {
+            used_labels[*label] = true;
+        }
+    }
+    // x30 at [sp], the counters right after it
+    let frame_size = (8 + function.counters * 8).div_ceil(16) * 16;
+    let counter = |counter: usize| 8 + counter * 8;
+    let (prologue, epilogue) = if frame_size < 256 {
+        (format!("\tstr x30, [sp, #-{}]!\n", frame_size), format!("\tldr x30, [sp], #{}\n", frame_size))
+    } else {
+        (format!("\tsub sp, sp, #{0}\n\tstr x30, [sp]\n", frame_size), format!("\tldr x30, [sp]\n\tadd sp, sp, #{}\n", frame_size))
+    };
+    let mut out = format!("\t.balign 4\n{}: // {}\n", name, state.name);
+    out += prologue.as_str();
+    if function.ops.iter().any(|x| matches!(x, Op::Restart)) {
+        out += format!(".L{}_restart:\n", name).as_str();
+    }
+    for op in function.ops.iter() {
+        out += match op {
+            Op::Label(label) if used_labels[*label] => format!(".L{}_{}:\n", name, label),
+            Op::Label(_) => continue,
+            Op::Primitive(dep) => compile_primitive(template_arguments, dep),
+            Op::JumpIfNotPositive(label) => format!("\tldr x0, [x19], #8\n\tcmp x0, #0\n\tb.le .L{}_{}\n", name, label),
+            Op::Jump(label) => format!("\tb .L{}_{}\n", name, label),
+            Op::TimesBegin{ count, counter: i, exit } => {
+                let count = match count {
+                    Some(count) => load_immediate("x0", *count),
+                    None => "\tldr x0, [x19], #8\n".to_string(),
+                };
+                format!("{}\tstr x0, [sp, #{}] // times\n\tcmp x0, #0\n\tb.le .L{}_{}\n", count, counter(*i), name, exit)
+            }
+            Op::TimesEnd{ counter: i, again } => format!("\tldr x0, [sp, #{0}] // end of times\n\tsubs x0, x0, #1\n\tstr x0, [sp, #{0}]\n\tb.gt .L{1}_{2}\n", counter(*i), name, again),
+            Op::Call(dep) => format!("\tbl {}\n", function_name(dep)),
+            Op::TailCall(dep) => format!("{}\tb {}\n", epilogue, function_name(dep)),
+            Op::Restart => format!("\tb .L{}_restart\n", name),
+            Op::Return => format!("{}\tret\n", epilogue),
+        }.as_str();
+    }
+    out
+}
+
+// `functions` are the non-inlined statements, start included. exported ones are visible to the linker
+pub fn compile(states: &[State], template_arguments: &[Vec<String>], functions: &[&State], exports: &[&str], data_sections: &[&(String, Vec<u8>)], variables: &[&String]) -> String {
+    let mut out = AARCH64_RUNTIME.replace("{data_stack_size}", &DATA_STACK_SIZE.to_string());
+    for function in functions.iter().filter(|x| exports.contains(&x.name.as_str())) {
+        out += format!("\t.global {}\n", function_name(function)).as_str();
+    }
+    for function in functions.iter() {
+        out += "\n";
+        out += compile_function(states, template_arguments, function).as_str();
+    }
+    if !data_sections.is_empty() || !variables.is_empty() {
+        out += "\n\t.data\n\t.balign 8\n";
+        for variable in variables.iter() {
+            out += format!("var_{}: // {}\n\t.quad 0\n", asm_label_name(variable), variable).as_str();
+        }
+        for data in data_sections.iter() {
+            out += format!("data_{}: // {}\n", asm_label_name(&data.0), data.0).as_str();
+            if !data.1.is_empty() {
+                out += format!("\t.byte {}\n", data.1.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(", ")).as_str();
+            }
+        }
+    }
+    // the stack is not executable
+    out += "\n\t.section .note.GNU-stack, \"\", %progbits\n";
+    out
+}
@@ -0,0 +1,670 @@
+/*  assembler.rs
+    MIT License
+
+    Copyright (c) 2024 Aidar Shigapov
+
+    Permission is hereby granted, free of charge, to any person obtaining a copy
+    of this software and associated documentation files (the "Software"), to deal
+    in the Software without restriction, including without limitation the rights
+    to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+    copies of the Software, and to permit persons to whom the Software is
+    furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in all
+    copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+    OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+    SOFTWARE.
+*/
+// a tiny x86-64 assembler for the NASM subset that the compiler emits.
+// it knows just enough instructions for the primitives and the freestanding prelude, nothing more
+
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    Text,
+    Data,
+    Bss,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Register {
+    number: u8,
+    size: u8, // in bytes
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Memory {
+    pub base: Option<u8>,
+    pub index: Option<(u8, u8)>, // register, scale
+    pub displacement: i64,
+    pub symbol: Option<String>,  // added to the displacement
+    pub rip_relative: bool,
+    pub size: Option<u8>,
+}
+
+#[derive(Clone, PartialEq)]
+pub enum Operand {
+    Register(Register),
+    Immediate(i64),
+    Symbol(String),
+    Memory(Memory),
+}
+
+pub struct Assembled {
+    pub text: Vec<u8>,
+    pub data: Vec<u8>,
+    pub bss_size: u64,
+    pub data_address: u64, // .bss goes right after .data
+    pub labels: HashMap<String, u64>,
+}
+
+pub const REGISTERS_64: [&str; 16] = ["rax", "rcx", "rdx", "rbx", "rsp", "rbp", "rsi", "rdi", "r8", "r9", "r10", "r11", "r12", "r13", "r14", "r15"];
+const REGISTERS_32: [&str; 16] = ["eax", "ecx", "edx", "ebx", "esp", "ebp", "esi", "edi", "r8d", "r9d", "r10d", "r11d", "r12d", "r13d", "r14d", "r15d"];
+const REGISTERS_16: [&str; 16] = ["ax", "cx", "dx", "bx", "sp", "bp", "si", "di", "r8w", "r9w", "r10w", "r11w", "r12w", "r13w", "r14w", "r15w"];
+const REGISTERS_8: [&str; 16] = ["al", "cl", "dl", "bl", "spl", "bpl", "sil", "dil", "r8b", "r9b", "r10b", "r11b", "r12b", "r13b", "r14b", "r15b"];
+const CONDITIONS: [(&str, u8); 18] = [
+    ("o", 0x0), ("no", 0x1), ("b", 0x2), ("ae", 0x3), ("e", 0x4), ("z", 0x4), ("ne", 0x5), ("nz", 0x5), ("be", 0x6),
+    ("a", 0x7), ("s", 0x8), ("ns", 0x9), ("l", 0xc), ("ge", 0xd), ("le", 0xe), ("g", 0xf), ("c", 0x2), ("nc", 0x3),
+];
+
+fn parse_register(name: &str) -> Option<Register> {
+    for (size, names) in [(8, REGISTERS_64), (4, REGISTERS_32), (2, REGISTERS_16), (1, REGISTERS_8)] {
+        if let Some(number) = names.iter().position(|x| *x == name) {
+            return Some(Register { number: number as u8, size });
+        }
+    }
+    None
+}
+fn parse_number(str: &str) -> Option<i64> {
+    let (negative, digits) = match str.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, str),
+    };
+    let value = if let Some(hex) = digits.strip_prefix("0x") {
+        u64::from_str_radix(hex, 16).ok()? as i64
+    } else {
+        digits.parse::<u64>().ok()? as i64
+    };
+    Some(if negative { value.wrapping_neg() } else { value })
+}
+fn parse_size(str: &str) -> Option<u8> {
+    match str {
+        "byte" => Some(1),
+        "word" => Some(2),
+        "dword" => Some(4),
+        "qword" => Some(8),
+        _ => None,
+    }
+}
+pub fn parse_operand(str: &str) -> Result<Operand, String> {
+    let str = str.trim();
+    let (size, rest) = match str.split_once(' ') {
+        Some((first, rest)) if parse_size(first).is_some() => (parse_size(first), rest.trim()),
+        _ => (None, str),
+    };
+    if let Some(inner) = rest.strip_prefix('[').and_then(|x| x.strip_suffix(']')) {
+        let mut memory = Memory { base: None, index: None, displacement: 0, symbol: None, rip_relative: false, size };
+        let mut inner = inner.trim();
+        if let Some(rest) = inner.strip_prefix("rel ") {
+            memory.rip_relative = true;
+            inner = rest.trim();
+        }
+        // terms joined by + and -
+        let mut terms = Vec::new();
+        let mut sign = 1;
+        let mut start = 0;
+        for (position, char) in inner.char_indices() {
+            if char == '+' || char == '-' {
+                if !inner[start..position].trim().is_empty() {
+                    terms.push((sign, inner[start..position].trim()));
+                }
+                sign = if char == '-' { -1 } else { 1 };
+                start = position + 1;
+            }
+        }
+        terms.push((sign, inner[start..].trim()));
+        for (sign, term) in terms {
+            if let Some((register, scale)) = term.split_once('*') {
+                let register = parse_register(register.trim()).ok_or(format!("invalid index register in [{}]", inner))?;
+                let scale = scale.trim().parse::<u8>().map_err(|_| format!("invalid scale in [{}]", inner))?;
+                memory.index = Some((register.number, scale));
+            } else if let Some(register) = parse_register(term) {
+                if memory.base.is_none() {
+                    memory.base = Some(register.number);
+                } else {
+                    memory.index = Some((register.number, 1));
+                }
+            } else if let Some(number) = parse_number(term) {
+                memory.displacement += sign * number;
+            } else {
+                memory.symbol = Some(term.to_string());
+            }
+        }
+        return Ok(Operand::Memory(memory));
+    }
+    if let Some(register) = parse_register(rest) {
+        return Ok(Operand::Register(register));
+    }
+    if let Some(number) = parse_number(rest) {
+        return Ok(Operand::Immediate(number));
+    }
+    if rest.is_empty() || rest.contains(' ') {
+        return Err(format!("invalid operand \"{}\"", str));
+    }
+    Ok(Operand::Symbol(rest.to_string()))
+}
+// splits by commas outside of string literals
+pub fn split_arguments(str: &str) -> Vec<String> {
+    let mut arguments = vec![String::new()];
+    let mut in_string = false;
+    for char in str.chars() {
+        if char == '"' {
+            in_string = !in_string;
+        }
+        if char == ',' && !in_string {
+            arguments.push(String::new());
+        } else {
+            arguments.last_mut().unwrap().push(char);
+        }
+    }
+    arguments.iter().map(|x| x.trim().to_string()).filter(|x| !x.is_empty()).collect()
+}
+pub fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (position, char) in line.char_indices() {
+        if char == '"' {
+            in_string = !in_string;
+        } else if char == ';' && !in_string {
+            return &line[..position];
+        }
+    }
+    line
+}
+fn fits_i8(value: i64) -> bool {
+    value >= i8::MIN as i64 && value <= i8::MAX as i64
+}
+fn fits_i32(value: i64) -> bool {
+    value >= i32::MIN as i64 && value <= i32::MAX as i64
+}
+
+struct Assembler {
+    labels: HashMap<String, u64>,
+    final_pass: bool,
+}
+
+impl Assembler {
+    // the first pass doesn`t know the labels yet. every label dependent encoding has a fixed size, so any address will do
+    fn symbol(&self, name: &str, address: u64) -> Result<u64, String> {
+        match self.labels.get(name) {
+            Some(value) => Ok(*value),
+            None if !self.final_pass => Ok(address),
+            None => Err(format!("undefined label \"{}\"", name)),
+        }
+    }
+    fn relative32(&self, target: u64, next_instruction: u64) -> Result<[u8; 4], String> {
+        let relative = target.wrapping_sub(next_instruction) as i64;
+        if self.final_pass && !fits_i32(relative) {
+            return Err("jump target is too far".to_string());
+        }
+        Ok((relative as i32).to_le_bytes())
+    }
+
+    // [66] [REX] opcode ModRM [SIB] [displacement] [immediate]
+    #[allow(clippy::too_many_arguments)]
+    fn encode_modrm(&self, address: u64, size: u8, opcode: &[u8], reg: u8, rm: &Operand, immediate: &[u8], force_rex: bool) -> Result<Vec<u8>, String> {
+        let mut rex = if size == 8 { 0x48 } else { 0x40 };
+        if reg >= 8 {
+            rex |= 0x04;
+        }
+        let mut modrm_and_rest = Vec::new();
+        let mut rip_displacement: Option<u64> = None;
+        match rm {
+            Operand::Register(register) => {
+                if register.number >= 8 {
+                    rex |= 0x01;
+                }
+                modrm_and_rest.push(0xc0 | ((reg & 7) << 3) | (register.number & 7));
+            }
+            Operand::Memory(memory) => {
+                let mut displacement = memory.displacement;
+                if let Some(symbol) = &memory.symbol {
+                    displacement = displacement.wrapping_add(self.symbol(symbol, address)? as i64);
+                }
+                if memory.rip_relative {
+                    if memory.base.is_some() || memory.index.is_some() {
+                        return Err("rip relative addressing can`t use registers".to_string());
+                    }
+                    modrm_and_rest.push(((reg & 7) << 3) | 0b101);
+                    rip_displacement = Some(displacement as u64);
+                    modrm_and_rest.extend_from_slice(&[0; 4]);
+                } else {
+                    if let Some((index, _)) = memory.index {
+                        if index == 4 {
+                            return Err("rsp can`t be an index register".to_string());
+                        }
+                        if index >= 8 {
+                            rex |= 0x02;
+                        }
+                    }
+                    if memory.base.is_some_and(|x| x >= 8) {
+                        rex |= 0x01;
+                    }
+                    let scale_bits = |scale: u8| match scale {
+                        1 => Ok(0),
+                        2 => Ok(1),
+                        4 => Ok(2),
+                        8 => Ok(3),
+                        _ => Err(format!("invalid scale {}", scale)),
+                    };
+                    match memory.base {
+                        None => {
+                            // absolute [disp32] or [index * scale + disp32]
+                            if !fits_i32(displacement) {
+                                return Err("absolute address doesn`t fit in 32 bits".to_string());
+                            }
+                            modrm_and_rest.push(((reg & 7) << 3) | 0b100);
+                            let (index, scale) = memory.index.unwrap_or((4, 1));
+                            modrm_and_rest.push((scale_bits(scale)? << 6) | ((index & 7) << 3) | 0b101);
+                            modrm_and_rest.extend_from_slice(&(displacement as i32).to_le_bytes());
+                        }
+                        Some(base) => {
+                            // rbp and r13 can`t go without a displacement
+                            let mode = if memory.symbol.is_some() {
+                                0b10 // labels move between the passes, the size mustn`t
+                            } else if displacement == 0 && base & 7 != 5 {
+                                0b00
+                            } else if fits_i8(displacement) {
+                                0b01
+                            } else if fits_i32(displacement) {
+                                0b10
+                            } else {
+                                return Err("displacement doesn`t fit in 32 bits".to_string());
+                            };
+                            if memory.index.is_some() || base & 7 == 4 {
+                                let (index, scale) = memory.index.unwrap_or((4, 1));
+                                modrm_and_rest.push((mode << 6) | ((reg & 7) << 3) | 0b100);
+                                modrm_and_rest.push((scale_bits(scale)? << 6) | ((index & 7) << 3) | (base & 7));
+                            } else {
+                                modrm_and_rest.push((mode << 6) | ((reg & 7) << 3) | (base & 7));
+                            }
+                            if mode == 0b01 {
+                                modrm_and_rest.push(displacement as i8 as u8);
+                            } else if mode == 0b10 {
+                                modrm_and_rest.extend_from_slice(&(displacement as i32).to_le_bytes());
+                            }
+                        }
+                    }
+                }
+            }
+            _ => return Err("expected a register or memory operand".to_string()),
+        }
+        let mut out = Vec::new();
+        if size == 2 {
+            out.push(0x66);
+        }
+        if rex != 0x40 || force_rex {
+            out.push(rex);
+        }
+        out.extend_from_slice(opcode);
+        let modrm_position = out.len();
+        out.extend(modrm_and_rest);
+        out.extend_from_slice(immediate);
+        if let Some(target) = rip_displacement {
+            // relative to the end of the instruction, so it goes after the immediate is known
+            let displacement_position = modrm_position + 1;
+            let relative = self.relative32(target, address + out.len() as u64)?;
+            out[displacement_position..displacement_position + 4].copy_from_slice(&relative);
+        }
+        Ok(out)
+    }
+
+    fn immediate(&self, operand: &Operand, address: u64) -> Result<Option<i64>, String> {
+        match operand {
+            Operand::Immediate(value) => Ok(Some(*value)),
+            Operand::Symbol(name) => Ok(Some(self.symbol(name, address)? as i64)),
+            _ => Ok(None),
+        }
+    }
+
+    fn instruction(&self, address: u64, mnemonic: &str, operands: &[Operand]) -> Result<Vec<u8>, String> {
+        // byte registers 4..7 mean spl..dil only with a REX prefix
+        let needs_rex = |operand: &Operand| matches!(operand, Operand::Register(Register { number: 4..=7, size: 1 }));
+        let size_of = |operand: &Operand| match operand {
+            Operand::Register(register) => Some(register.size),
+            Operand::Memory(memory) => memory.size,
+            _ => None,
+        };
+        let operand_size = || -> Result<u8, String> {
+            operands.iter().find_map(size_of).ok_or(format!("operation size of {} is not specified", mnemonic))
+        };
+        let alu = |extension: u8| -> Result<Vec<u8>, String> {
+            let (destination, source) = (&operands[0], &operands[1]);
+            let size = operand_size()?;
+            let byte_offset = if size == 1 { 0 } else { 1 };
+            let force_rex = needs_rex(destination) || needs_rex(source);
+            match (destination, source) {
+                (_, Operand::Register(register)) => self.encode_modrm(address, size, &[(extension << 3) + byte_offset], register.number, destination, &[], force_rex),
+                (Operand::Register(register), Operand::Memory(_)) => self.encode_modrm(address, size, &[(extension << 3) + 2 + byte_offset], register.number, source, &[], force_rex),
+                (_, Operand::Immediate(value)) => {
+                    if size == 1 {
+                        self.encode_modrm(address, size, &[0x80], extension, destination, &[*value as u8], force_rex)
+                    } else if fits_i8(*value) {
+                        self.encode_modrm(address, size, &[0x83], extension, destination, &[*value as i8 as u8], force_rex)
+                    } else if size == 2 {
+                        self.encode_modrm(address, size, &[0x81], extension, destination, &(*value as i16).to_le_bytes(), force_rex)
+                    } else if fits_i32(*value) {
+                        self.encode_modrm(address, size, &[0x81], extension, destination, &(*value as i32).to_le_bytes(), force_rex)
+                    } else {
+                        Err(format!("immediate {} doesn`t fit in 32 bits", value))
+                    }
+                }
+                _ => Err(format!("invalid operands for {}", mnemonic)),
+            }
+        };
+        let unary = |opcode: u8, extension: u8| -> Result<Vec<u8>, String> {
+            let size = operand_size()?;
+            self.encode_modrm(address, size, &[if size == 1 { opcode - 1 } else { opcode }], extension, &operands[0], &[], needs_rex(&operands[0]))
+        };
+        let expect_count = |count: usize| if operands.len() == count { Ok(()) } else { Err(format!("{} expects {} operands", mnemonic, count)) };
+
+        if let Some(condition) = mnemonic.strip_prefix('j').and_then(|x| CONDITIONS.iter().find(|y| y.0 == x)) {
+            expect_count(1)?;
+            let target = self.immediate(&operands[0], address)?.ok_or("conditional jumps need a label")?;
+            let mut out = vec![0x0f, 0x80 + condition.1];
+            out.extend_from_slice(&self.relative32(target as u64, address + 6)?);
+            return Ok(out);
+        }
+        if let Some(condition) = mnemonic.strip_prefix("set").and_then(|x| CONDITIONS.iter().find(|y| y.0 == x)) {
+            expect_count(1)?;
+            return self.encode_modrm(address, 1, &[0x0f, 0x90 + condition.1], 0, &operands[0], &[], needs_rex(&operands[0]));
+        }
+        match mnemonic {
+            "ret" => Ok(vec![0xc3]),
+            "syscall" => Ok(vec![0x0f, 0x05]),
+            "cqo" => Ok(vec![0x48, 0x99]),
+            "nop" => Ok(vec![0x90]),
+            "push" | "pop" => {
+                expect_count(1)?;
+                let is_push = mnemonic == "push";
+                match &operands[0] {
+                    Operand::Register(register) if register.size == 8 => {
+                        let opcode = if is_push { 0x50 } else { 0x58 } + (register.number & 7);
+                        Ok(if register.number >= 8 { vec![0x41, opcode] } else { vec![opcode] })
+                    }
+                    Operand::Immediate(value) if is_push && fits_i32(*value) => {
+                        let mut out = vec![0x68];
+                        out.extend_from_slice(&(*value as i32).to_le_bytes());
+                        Ok(out)
+                    }
+                    Operand::Memory(_) => {
+                        if is_push {
+                            self.encode_modrm(address, 4, &[0xff], 6, &operands[0], &[], false)
+                        } else {
+                            self.encode_modrm(address, 4, &[0x8f], 0, &operands[0], &[], false)
+                        }
+                    }
+                    _ => Err(format!("invalid operand for {}", mnemonic)),
+                }
+            }
+            "add" => { expect_count(2)?; alu(0) }
+            "or" => { expect_count(2)?; alu(1) }
+            "and" => { expect_count(2)?; alu(4) }
+            "sub" => { expect_count(2)?; alu(5) }
+            "xor" => { expect_count(2)?; alu(6) }
+            "cmp" => { expect_count(2)?; alu(7) }
+            "test" => {
+                expect_count(2)?;
+                let size = operand_size()?;
+                match &operands[1] {
+                    Operand::Register(register) => self.encode_modrm(address, size, &[if size == 1 { 0x84 } else { 0x85 }], register.number, &operands[0], &[], needs_rex(&operands[0]) || needs_rex(&operands[1])),
+                    Operand::Immediate(value) if size == 1 => self.encode_modrm(address, size, &[0xf6], 0, &operands[0], &[*value as u8], needs_rex(&operands[0])),
+                    Operand::Immediate(value) => self.encode_modrm(address, size, &[0xf7], 0, &operands[0], &(*value as i32).to_le_bytes(), false),
+                    _ => Err("invalid operands for test".to_string()),
+                }
+            }
+            "mov" => {
+                expect_count(2)?;
+                let (destination, source) = (&operands[0], &operands[1]);
+                let size = operand_size()?;
+                let force_rex = needs_rex(destination) || needs_rex(source);
+                match (destination, source) {
+                    (_, Operand::Register(register)) => self.encode_modrm(address, size, &[if size == 1 { 0x88 } else { 0x89 }], register.number, destination, &[], force_rex),
+                    (Operand::Register(register), Operand::Memory(_)) => self.encode_modrm(address, size, &[if size == 1 { 0x8a } else { 0x8b }], register.number, source, &[], force_rex),
+                    (Operand::Register(register), Operand::Immediate(_) | Operand::Symbol(_)) => {
+                        let value = self.immediate(source, address)?.unwrap();
+                        if size == 8 && matches!(source, Operand::Immediate(_)) && fits_i32(value) {
+                            self.encode_modrm(address, 8, &[0xc7], 0, destination, &(value as i32).to_le_bytes(), false)
+                        } else if size == 8 {
+                            // labels always take the 10 byte form, so the size doesn`t depend on the address
+                            let mut out = vec![if register.number >= 8 { 0x49 } else { 0x48 }, 0xb8 + (register.number & 7)];
+                            out.extend_from_slice(&value.to_le_bytes());
+                            Ok(out)
+                        } else if size == 4 {
+                            let mut out = if register.number >= 8 { vec![0x41] } else { vec![] };
+                            out.push(0xb8 + (register.number & 7));
+                            out.extend_from_slice(&(value as i32).to_le_bytes());
+                            Ok(out)
+                        } else {
+                            Err("mov to 8 and 16 bit registers is not supported".to_string())
+                        }
+                    }
+                    (Operand::Memory(_), Operand::Immediate(value)) => match size {
+                        1 => self.encode_modrm(address, 1, &[0xc6], 0, destination, &[*value as u8], false),
+                        2 => self.encode_modrm(address, 2, &[0xc7], 0, destination, &(*value as i16).to_le_bytes(), false),
+                        _ if fits_i32(*value) => self.encode_modrm(address, size, &[0xc7], 0, destination, &(*value as i32).to_le_bytes(), false),
+                        _ => Err(format!("immediate {} doesn`t fit in 32 bits", value)),
+                    },
+                    _ => Err("invalid operands for mov".to_string()),
+                }
+            }
+            "lea" => {
+                expect_count(2)?;
+                match (&operands[0], &operands[1]) {
+                    (Operand::Register(register), Operand::Memory(_)) => self.encode_modrm(address, register.size, &[0x8d], register.number, &operands[1], &[], false),
+                    _ => Err("invalid operands for lea".to_string()),
+                }
+            }
+            "movzx" | "movsx" | "movsxd" => {
+                expect_count(2)?;
+                let Operand::Register(register) = &operands[0] else {
+                    return Err(format!("invalid operands for {}", mnemonic));
+                };
+                let source_size = size_of(&operands[1]).ok_or(format!("operation size of {} is not specified", mnemonic))?;
+                let opcode: &[u8] = match (mnemonic, source_size) {
+                    ("movzx", 1) => &[0x0f, 0xb6],
+                    ("movzx", 2) => &[0x0f, 0xb7],
+                    ("movsx", 1) => &[0x0f, 0xbe],
+                    ("movsx", 2) => &[0x0f, 0xbf],
+                    ("movsxd", 4) => &[0x63],
+                    _ => return Err(format!("invalid operands for {}", mnemonic)),
+                };
+                self.encode_modrm(address, register.size, opcode, register.number, &operands[1], &[], needs_rex(&operands[1]))
+            }
+            "inc" => { expect_count(1)?; unary(0xff, 0) }
+            "dec" => { expect_count(1)?; unary(0xff, 1) }
+            "not" => { expect_count(1)?; unary(0xf7, 2) }
+            "neg" => { expect_count(1)?; unary(0xf7, 3) }
+            "mul" => { expect_count(1)?; unary(0xf7, 4) }
+            "div" => { expect_count(1)?; unary(0xf7, 6) }
+            "idiv" => { expect_count(1)?; unary(0xf7, 7) }
+            "imul" => {
+                if operands.len() == 1 {
+                    return unary(0xf7, 5);
+                }
+                expect_count(2)?;
+                match &operands[0] {
+                    Operand::Register(register) => self.encode_modrm(address, register.size, &[0x0f, 0xaf], register.number, &operands[1], &[], false),
+                    _ => Err("invalid operands for imul".to_string()),
+                }
+            }
+            "shl" | "shr" | "sar" => {
+                expect_count(2)?;
+                let extension = match mnemonic { "shl" => 4, "shr" => 5, _ => 7 };
+                let Operand::Immediate(count) = operands[1] else {
+                    return Err(format!("{} supports only immediate counts", mnemonic));
+                };
+                let size = operand_size()?;
+                self.encode_modrm(address, size, &[if size == 1 { 0xc0 } else { 0xc1 }], extension, &operands[0], &[count as u8], needs_rex(&operands[0]))
+            }
+            "xchg" => {
+                expect_count(2)?;
+                match (&operands[0], &operands[1]) {
+                    (_, Operand::Register(register)) => self.encode_modrm(address, register.size, &[if register.size == 1 { 0x86 } else { 0x87 }], register.number, &operands[0], &[], false),
+                    _ => Err("invalid operands for xchg".to_string()),
+                }
+            }
+            "jmp" | "call" => {
+                expect_count(1)?;
+                match &operands[0] {
+                    Operand::Register(_) | Operand::Memory(_) => self.encode_modrm(address, 4, &[0xff], if mnemonic == "jmp" { 4 } else { 2 }, &operands[0], &[], false),
+                    _ => {
+                        let target = self.immediate(&operands[0], address)?.unwrap();
+                        let mut out = vec![if mnemonic == "jmp" { 0xe9 } else { 0xe8 }];
+                        out.extend_from_slice(&self.relative32(target as u64, address + 5)?);
+                        Ok(out)
+                    }
+                }
+            }
+            _ => Err(format!("unsupported instruction \"{}\"", mnemonic)),
+        }
+    }
+
+    fn data_directive(&self, directive: &str, arguments: &str, address: u64) -> Result<Vec<u8>, String> {
+        let mut out = Vec::new();
+        for argument in split_arguments(arguments) {
+            if let Some(string) = argument.strip_prefix('"').and_then(|x| x.strip_suffix('"')) {
+                if directive != "db" {
+                    return Err("strings are allowed only in db".to_string());
+                }
+                out.extend_from_slice(string.as_bytes());
+                continue;
+            }
+            let value = match parse_number(&argument) {
+                Some(value) => value,
+                None => self.symbol(&argument, address)? as i64,
+            };
+            match directive {
+                "db" => out.push(value as u8),
+                "dw" => out.extend_from_slice(&(value as u16).to_le_bytes()),
+                "dd" => out.extend_from_slice(&(value as u32).to_le_bytes()),
+                _ => out.extend_from_slice(&value.to_le_bytes()),
+            }
+        }
+        Ok(out)
+    }
+
+    // walks the source once. returns the bytes of .text and .data, the size of .bss and the labels (relative to their sections)
+    #[allow(clippy::type_complexity)]
+    fn pass(&self, source: &str, text_address: u64, data_address: u64, bss_address: u64) -> Result<(Vec<u8>, Vec<u8>, u64, HashMap<String, (Section, u64)>), String> {
+        let mut text = Vec::new();
+        let mut data = Vec::new();
+        let mut bss_size = 0u64;
+        let mut section = Section::Text;
+        let mut labels = HashMap::new();
+        for (line_number, line) in source.lines().enumerate() {
+            let error = |message: String| format!("line {}: {}: {}", line_number + 1, message, line.trim());
+            let mut line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            let offset = match section {
+                Section::Text => text.len() as u64,
+                Section::Data => data.len() as u64,
+                Section::Bss => bss_size,
+            };
+            let address = offset + match section {
+                Section::Text => text_address,
+                Section::Data => data_address,
+                Section::Bss => bss_address,
+            };
+            // `label:` and maybe something after it
+            if let Some((label, rest)) = line.split_once(':') {
+                let label = label.trim();
+                if !label.contains(' ') && !label.contains('"') && !label.contains('[') {
+                    if labels.insert(label.to_string(), (section, offset)).is_some() {
+                        return Err(error(format!("label \"{}\" is defined twice", label)));
+                    }
+                    line = rest.trim();
+                    if line.is_empty() {
+                        continue;
+                    }
+                }
+            }
+            let (mnemonic, arguments) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let arguments = arguments.trim();
+            match mnemonic {
+                "section" => {
+                    section = match arguments {
+                        ".text" => Section::Text,
+                        ".data" => Section::Data,
+                        ".bss" => Section::Bss,
+                        ".import" => Section::Data, // only externs live there
+                        _ => return Err(error(format!("unknown section \"{}\"", arguments))),
+                    };
+                }
+                "global" => { },
+                "extern" => return Err(error(format!("external symbol \"{}\" can`t be linked without a linker", arguments))),
+                "resb" | "resq" => {
+                    if section != Section::Bss {
+                        return Err(error("res* directives are allowed only in .bss".to_string()));
+                    }
+                    let count = parse_number(arguments).ok_or_else(|| error("invalid count".to_string()))? as u64;
+                    bss_size += count * if mnemonic == "resq" { 8 } else { 1 };
+                }
+                "db" | "dw" | "dd" | "dq" | "times" => {
+                    let bytes = if mnemonic == "times" {
+                        let (count, rest) = arguments.split_once(char::is_whitespace).ok_or_else(|| error("invalid times".to_string()))?;
+                        let count = parse_number(count).ok_or_else(|| error("invalid times count".to_string()))?;
+                        let (directive, arguments) = rest.trim().split_once(char::is_whitespace).ok_or_else(|| error("invalid times".to_string()))?;
+                        self.data_directive(directive, arguments, address).map_err(error)?.repeat(count as usize)
+                    } else {
+                        self.data_directive(mnemonic, arguments, address).map_err(error)?
+                    };
+                    match section {
+                        Section::Text => text.extend(bytes),
+                        Section::Data => data.extend(bytes),
+                        Section::Bss => return Err(error("initialized data in .bss".to_string())),
+                    }
+                }
+                _ => {
+                    if section != Section::Text {
+                        return Err(error("instructions are allowed only in .text".to_string()));
+                    }
+                    let operands = split_arguments(arguments).iter().map(|x| parse_operand(x)).collect::<Result<Vec<_>, _>>().map_err(error)?;
+                    text.extend(self.instruction(address, mnemonic, &operands).map_err(error)?);
+                }
+            }
+        }
+        Ok((text, data, bss_size, labels))
+    }
+}
+
+// assembles the source. `layout` gets the sizes of .text and .data(+.bss) and places them in memory
+pub fn assemble(source: &str, layout: impl Fn(u64, u64) -> (u64, u64)) -> Result<Assembled, String> {
+    let mut assembler = Assembler { labels: HashMap::new(), final_pass: false };
+    // the first pass finds out the sizes and where the labels are
+    let (text, data, bss_size, section_labels) = assembler.pass(source, 0, 0, 0)?;
+    let bss_offset = (data.len() as u64).next_multiple_of(16);
+    let (text_address, data_address) = layout(text.len() as u64, bss_offset + bss_size);
+    for (name, (section, offset)) in section_labels {
+        let base = match section {
+            Section::Text => text_address,
+            Section::Data => data_address,
+            Section::Bss => data_address + bss_offset,
+        };
+        assembler.labels.insert(name, base + offset);
+    }
+    assembler.final_pass = true;
+    let (final_text, final_data, final_bss_size, _) = assembler.pass(source, text_address, data_address, data_address + bss_offset)?;
+    if final_text.len() != text.len() || final_data.len() != data.len() || final_bss_size != bss_size {
+        return Err("instruction sizes changed between the passes".to_string());
+    }
+    Ok(Assembled { text: final_text, data: final_data, bss_size: bss_offset - data.len() as u64 + bss_size, data_address, labels: assembler.labels })
+}
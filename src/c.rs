@@ -0,0 +1,320 @@
+/*  c.rs
+    MIT License
+
+    Copyright (c) 2024 Aidar Shigapov
+
+    Permission is hereby granted, free of charge, to any person obtaining a copy
+    of this software and associated documentation files (the "Software"), to deal
+    in the Software without restriction, including without limitation the rights
+    to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+    copies of the Software, and to permit persons to whom the Software is
+    furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in all
+    copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+    OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+    SOFTWARE.
+*/
+// portable C99 output. the data stack is an array that grows down like rsp, so the stack_head arithmetic stays the same.
+// every non-inlined statement is a function, the control flow is goto
+
+use crate::{State, StateType, asm_label_name};
+use crate::lower::{lower, Op};
+
+const C_RUNTIME: &str = r##"#include <errno.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#define STACK_SIZE (1 << 20)
+#define MAX_FILES 256
+#define PUSH(x) (*--sp = (int64_t)(x))
+#define PTR(x) ((void *)(intptr_t)(x))
+#define WRAP(x) ((int64_t)(uint64_t)(x))
+#define ERROR_CODE() (errno ? -(int64_t)errno : -1)
+
+static int64_t stack[STACK_SIZE];
+static int64_t *sp = stack + STACK_SIZE;
+static FILE *files[MAX_FILES];
+
+#define DEFINE_LOAD(name, type) static inline int64_t name(int64_t address) { type value; memcpy(&value, PTR(address), sizeof value); return (int64_t)value; }
+#define DEFINE_STORE(name, type) static inline void name(int64_t address, int64_t value) { type x = (type)value; memcpy(PTR(address), &x, sizeof x); }
+DEFINE_LOAD(load_i64, int64_t)
+DEFINE_LOAD(load_u8, uint8_t)
+DEFINE_LOAD(load_i8, int8_t)
+DEFINE_LOAD(load_u16, uint16_t)
+DEFINE_LOAD(load_i16, int16_t)
+DEFINE_LOAD(load_u32, uint32_t)
+DEFINE_LOAD(load_i32, int32_t)
+DEFINE_STORE(store_i64, int64_t)
+DEFINE_STORE(store_u8, uint8_t)
+DEFINE_STORE(store_u16, uint16_t)
+DEFINE_STORE(store_u32, uint32_t)
+
+static inline void dump_stack(void) {
+    int64_t *i;
+    for (i = stack + STACK_SIZE; i > sp;) {
+        printf("%lld ", (long long)*--i);
+    }
+    printf("\n");
+}
+
+/* up to 8 bytes of the value, the lowest first, until a zero byte */
+static inline void write_raw(int64_t value) {
+    char bytes[8];
+    int i;
+    for (i = 0; i < 8; i++) {
+        bytes[i] = (char)((uint64_t)value >> (8 * i));
+    }
+    printf("%.8s", bytes);
+}
+
+static inline void read_int(void) {
+    long long value = 0;
+    int status = scanf("%lld", &value);
+    PUSH(value);
+    PUSH(status);
+}
+
+static inline void read_line(void) {
+    static unsigned char line[4096];
+    int64_t length = 0;
+    int c;
+    while ((c = getchar()) != EOF && c != '\n') {
+        line[length++] = (unsigned char)c;
+        if (length == (int64_t)sizeof line) {
+            break;
+        }
+    }
+    if (c == EOF && length == 0) {
+        PUSH(-1);
+        return;
+    }
+    PUSH(0);
+    for (c = (int)length; c > 0;) {
+        PUSH(line[--c]);
+    }
+    PUSH(length);
+}
+
+static inline FILE *file(int64_t fd) {
+    switch (fd) {
+    case 0: return stdin;
+    case 1: return stdout;
+    case 2: return stderr;
+    default: return fd > 2 && fd < MAX_FILES ? files[fd] : NULL;
+    }
+}
+
+static inline void file_open_path(const char *path, const char *mode) {
+    int fd;
+    for (fd = 3; fd < MAX_FILES && files[fd]; fd++) {
+    }
+    if (fd == MAX_FILES) {
+        PUSH(-24); /* EMFILE */
+        return;
+    }
+    errno = 0;
+    files[fd] = fopen(path, mode);
+    if (files[fd]) {
+        PUSH(fd);
+    } else {
+        PUSH(ERROR_CODE());
+    }
+}
+
+/* fopen: the path is a zero terminated stack string */
+static inline void file_open(const char *mode) {
+    char path[4096];
+    size_t length = 0;
+    int64_t c;
+    do {
+        c = *sp++;
+        if (length < sizeof path) {
+            path[length++] = (char)c;
+        }
+    } while (c != 0);
+    path[sizeof path - 1] = 0;
+    file_open_path(path, mode);
+}
+
+/* fopen_ptr: the path is a pointer to a zero terminated string */
+static inline void file_open_ptr(const char *mode) {
+    int64_t address = *sp++;
+    file_open_path((const char *)PTR(address), mode);
+}
+
+static inline void fread_byte(void) {
+    FILE *f = file(sp[0]);
+    int c;
+    if (!f) {
+        sp[0] = -9; /* EBADF */
+        return;
+    }
+    errno = 0;
+    c = fgetc(f);
+    sp[0] = c != EOF ? c : ferror(f) ? ERROR_CODE() : -1;
+}
+
+static inline void fwrite_byte(void) {
+    FILE *f = file(sp[1]);
+    if (!f) {
+        sp[1] = -9; /* EBADF */
+    } else {
+        errno = 0;
+        sp[1] = fputc((unsigned char)sp[0], f) != EOF ? 1 : ERROR_CODE();
+    }
+    sp++;
+}
+
+static inline void file_close(void) {
+    int64_t fd = sp[0];
+    if (fd > 2 && fd < MAX_FILES && files[fd]) {
+        fclose(files[fd]);
+        files[fd] = NULL;
+        sp[0] = 0;
+    } else {
+        sp[0] = -9; /* EBADF */
+    }
+}
+"##;
+
+fn function_name(state: &State) -> String {
+    format!("st_{}", asm_label_name(&state.name))
+}
+
+fn compile_primitive(template_arguments: &[Vec<String>], state: &State) -> String {
+    let template_argument = |index: usize, default: usize| if state.template_arguments_index == 0 {
+        default
+    } else {
+        template_arguments[state.template_arguments_index].get(index).expect("too few template arguments").parse::<usize>().expect("template argument must be integer")
+    };
+    let binary = |expression: &str| format!("sp[1] = {}; sp++;", expression);
+    match state.state_type {
+        StateType::Integer => {
+            let integer = state.name.parse::<i64>().expect("invalid integer");
+            if integer == i64::MIN { "PUSH(INT64_MIN);".to_string() } else { format!("PUSH(INT64_C({}));", integer) }
+        }
+        StateType::Data =>      format!("PUSH((intptr_t)data_{});", asm_label_name(&state.name)),
+        StateType::Var =>       format!("PUSH((intptr_t)&var_{});", asm_label_name(&state.name)),
+        StateType::VarFetch =>  format!("PUSH(var_{});", asm_label_name(&state.name[1..])),
+        StateType::VarStore =>  format!("var_{} = *sp++;", asm_label_name(&state.name[1..])),
+        StateType::StackHead => "{ int64_t head = (intptr_t)sp; PUSH(head); }".to_string(),
+        StateType::Depth =>     "{ int64_t depth = stack + STACK_SIZE - sp; PUSH(depth); }".to_string(),
+        StateType::DumpStack => "dump_stack();".to_string(),
+        StateType::ReadFrom =>  "sp[0] = load_i64(sp[0]);".to_string(),
+        StateType::ReadU8 =>    "sp[0] = load_u8(sp[0]);".to_string(),
+        StateType::ReadI8 =>    "sp[0] = load_i8(sp[0]);".to_string(),
+        StateType::ReadU16 =>   "sp[0] = load_u16(sp[0]);".to_string(),
+        StateType::ReadI16 =>   "sp[0] = load_i16(sp[0]);".to_string(),
+        StateType::ReadU32 =>   "sp[0] = load_u32(sp[0]);".to_string(),
+        StateType::ReadI32 =>   "sp[0] = load_i32(sp[0]);".to_string(),
+        StateType::WriteTo =>   "store_i64(sp[1], sp[0]); sp += 2;".to_string(),
+        StateType::WriteU8 =>   "store_u8(sp[1], sp[0]); sp += 2;".to_string(),
+        StateType::WriteU16 =>  "store_u16(sp[1], sp[0]); sp += 2;".to_string(),
+        StateType::WriteU32 =>  "store_u32(sp[1], sp[0]); sp += 2;".to_string(),
+        StateType::Alloc =>     "sp[0] = (intptr_t)malloc((size_t)sp[0]);".to_string(),
+        StateType::Free =>      "free(PTR(sp[0])); sp++;".to_string(),
+        StateType::Eq =>        binary("sp[1] == sp[0]"),
+        StateType::Neq =>       binary("sp[1] != sp[0]"),
+        StateType::More =>      binary("sp[1] > sp[0]"),
+        StateType::Less =>      binary("sp[1] < sp[0]"),
+        StateType::Sum =>       binary("WRAP((uint64_t)sp[1] + (uint64_t)sp[0])"),
+        StateType::Dif =>       binary("WRAP((uint64_t)sp[1] - (uint64_t)sp[0])"),
+        StateType::Mul =>       binary("WRAP((uint64_t)sp[1] * (uint64_t)sp[0])"),
+        StateType::Div =>       binary("sp[1] / sp[0]"),
+        StateType::Inc =>       "sp[0] = WRAP((uint64_t)sp[0] + 1);".to_string(),
+        StateType::Dec =>       "sp[0] = WRAP((uint64_t)sp[0] - 1);".to_string(),
+        StateType::Dup =>       vec!["sp--; sp[0] = sp[1];"; template_argument(0, 1)].join(" "),
+        StateType::Pop =>       format!("sp += {};", template_argument(0, 1)),
+        StateType::Swap =>      format!("{{ int64_t swap = sp[{0}]; sp[{0}] = sp[{1}]; sp[{1}] = swap; }}", template_argument(0, 0), template_argument(1, 1)),
+        StateType::Print =>     "printf(\"%lld \", (long long)sp[0]); sp++;".to_string(),
+        StateType::WriteRaw =>  "write_raw(sp[0]); sp++;".to_string(),
+        StateType::ReadInt =>   "read_int();".to_string(),
+        StateType::ReadChar =>  "PUSH(getchar());".to_string(),
+        StateType::ReadLine =>  "read_line();".to_string(),
+        StateType::Fopen | StateType::FopenPtr => {
+            let mode = if state.template_arguments_index == 0 { "r" } else { template_arguments[state.template_arguments_index][0].as_str() };
+            let function = if state.state_type == StateType::FopenPtr { "file_open_ptr" } else { "file_open" };
+            match mode {
+                "r" | "w" | "a" => format!("{}(\"{}b\");", function, mode),
+                _ => panic!("unknown fopen mode \"{}\". possible values: r | w | a", mode),
+            }
+        }
+        StateType::FreadByte => "fread_byte();".to_string(),
+        StateType::FwriteByte =>"fwrite_byte();".to_string(),
+        StateType::Fclose =>    "file_close();".to_string(),
+        StateType::Exit =>      "exit((int)sp[0]);".to_string(),
+        _ => panic!("{} is not a primitive", state.name),
+    }
+}
+
+fn compile_function(states: &[State], template_arguments: &[Vec<String>], state: &State, linkage: &str) -> String {
+    let function = lower(states, template_arguments, state);
+    let mut used_labels = vec![false; function.labels];
+    for op in function.ops.iter() {
+        if let Op::Jump(label) | Op::JumpIfNotPositive(label) | Op::TimesBegin{ exit: label, .. } | Op::TimesEnd{ again: label, .. } = op {
+            used_labels[*label] = true;
+        }
+    }
+    let mut out = format!("{}void {}(void) {{ /* {} */\n", linkage, function_name(state), state.name.replace("*/", "* /"));
+    if function.counters != 0 {
+        out += format!("    int64_t counters[{}];\n", function.counters).as_str();
+    }
+    if function.ops.iter().any(|x| matches!(x, Op::Restart)) {
+        out += "restart: ;\n";
+    }
+    for op in function.ops.iter() {
+        out += match op {
+            Op::Label(label) if used_labels[*label] => format!("l{}: ;\n", label),
+            Op::Label(_) => continue,
+            Op::Primitive(dep) if dep.state_type == StateType::Integer => format!("    {}\n", compile_primitive(template_arguments, dep)),
+            Op::Primitive(dep) => format!("    {} /* {} */\n", compile_primitive(template_arguments, dep), dep.name.replace("*/", "* /")),
+            Op::JumpIfNotPositive(label) => format!("    if (*sp++ <= 0) goto l{};\n", label),
+            Op::Jump(label) => format!("    goto l{};\n", label),
+            Op::TimesBegin{ count: None, counter, exit } => format!("    counters[{0}] = *sp++; /* times */\n    if (counters[{0}] <= 0) goto l{1};\n", counter, exit),
+            Op::TimesBegin{ count: Some(count), counter, exit } => format!("    counters[{0}] = {2}; /* times<{2}> */\n    if (counters[{0}] <= 0) goto l{1};\n", counter, exit, count),
+            Op::TimesEnd{ counter, again } => format!("    if (--counters[{}] > 0) goto l{}; /* end of times */\n", counter, again),
+            Op::Call(dep) => format!("    {}();\n", function_name(dep)),
+            Op::TailCall(dep) => format!("    {}();\n    return;\n", function_name(dep)),
+            Op::Restart => "    goto restart;\n".to_string(),
+            Op::Return => "    return;\n".to_string(),
+        }.as_str();
+    }
+    out += "}\n";
+    out
+}
+
+// `functions` are the non-inlined statements, start included. exported ones are visible to the linker
+pub fn compile(states: &[State], template_arguments: &[Vec<String>], functions: &[&State], exports: &[&str], data_sections: &[&(String, Vec<u8>)], variables: &[&String]) -> String {
+    let mut out = C_RUNTIME.to_string();
+    out += "\n";
+    for variable in variables.iter() {
+        out += format!("static int64_t var_{};\n", asm_label_name(variable)).as_str();
+    }
+    for data in data_sections.iter() {
+        if data.1.is_empty() {
+            out += format!("static unsigned char data_{}[1];\n", asm_label_name(&data.0)).as_str();
+        } else {
+            out += format!("static unsigned char data_{}[] = {{ {} }};\n", asm_label_name(&data.0), data.1.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(", ")).as_str();
+        }
+    }
+    out += "\n";
+    let linkage = |state: &State| if exports.contains(&state.name.as_str()) { "" } else { "static " };
+    for function in functions.iter() {
+        out += format!("{}void {}(void);\n", linkage(function), function_name(function)).as_str();
+    }
+    for function in functions.iter() {
+        out += "\n";
+        out += compile_function(states, template_arguments, function, linkage(function)).as_str();
+    }
+    out += "\nint main(void) {\n    st_start();\n    return 0;\n}\n";
+    out
+}
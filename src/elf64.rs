@@ -0,0 +1,83 @@
+/*  elf64.rs
+    MIT License
+
+    Copyright (c) 2024 Aidar Shigapov
+
+    Permission is hereby granted, free of charge, to any person obtaining a copy
+    of this software and associated documentation files (the "Software"), to deal
+    in the Software without restriction, including without limitation the rights
+    to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+    copies of the Software, and to permit persons to whom the Software is
+    furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in all
+    copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+    OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+    SOFTWARE.
+*/
+// writes static ELF64 executables for Linux x86-64, so compilation doesn`t need nasm and gcc
+
+use crate::assembler;
+
+const BASE_ADDRESS: u64 = 0x400000;
+const PAGE_SIZE: u64 = 0x1000;
+const ELF_HEADER_SIZE: u64 = 64;
+const PROGRAM_HEADER_SIZE: u64 = 56;
+const HEADERS_SIZE: u64 = ELF_HEADER_SIZE + PROGRAM_HEADER_SIZE * 2;
+
+fn program_header(out: &mut Vec<u8>, flags: u32, offset: u64, address: u64, file_size: u64, memory_size: u64) {
+    out.extend_from_slice(&1u32.to_le_bytes()); // PT_LOAD
+    out.extend_from_slice(&flags.to_le_bytes());
+    out.extend_from_slice(&offset.to_le_bytes());
+    out.extend_from_slice(&address.to_le_bytes()); // virtual address
+    out.extend_from_slice(&address.to_le_bytes()); // physical address
+    out.extend_from_slice(&file_size.to_le_bytes());
+    out.extend_from_slice(&memory_size.to_le_bytes());
+    out.extend_from_slice(&PAGE_SIZE.to_le_bytes()); // alignment
+}
+
+// assembles the program and lays it out as two segments:
+// the headers and .text (read + execute), then .data and .bss (read + write) from the next page
+pub fn build_executable(asm: &str, entry: &str) -> Result<Vec<u8>, String> {
+    let program = assembler::assemble(asm, |text_size, _| {
+        let text_address = BASE_ADDRESS + HEADERS_SIZE;
+        (text_address, (text_address + text_size).next_multiple_of(PAGE_SIZE))
+    })?;
+    let entry_address = *program.labels.get(entry).ok_or(format!("entry point \"{}\" is not defined", entry))?;
+    let text_size = HEADERS_SIZE + program.text.len() as u64;
+    let data_offset = program.data_address - BASE_ADDRESS;
+
+    let mut out = Vec::new();
+    // ELF header
+    out.extend_from_slice(b"\x7fELF");
+    out.extend_from_slice(&[2, 1, 1, 0]); // 64 bit, little endian, version 1, System V
+    out.extend_from_slice(&[0; 8]);
+    out.extend_from_slice(&2u16.to_le_bytes()); // ET_EXEC
+    out.extend_from_slice(&0x3eu16.to_le_bytes()); // x86-64
+    out.extend_from_slice(&1u32.to_le_bytes());
+    out.extend_from_slice(&entry_address.to_le_bytes());
+    out.extend_from_slice(&ELF_HEADER_SIZE.to_le_bytes()); // program headers offset
+    out.extend_from_slice(&0u64.to_le_bytes()); // no section headers
+    out.extend_from_slice(&0u32.to_le_bytes()); // flags
+    out.extend_from_slice(&(ELF_HEADER_SIZE as u16).to_le_bytes());
+    out.extend_from_slice(&(PROGRAM_HEADER_SIZE as u16).to_le_bytes());
+    out.extend_from_slice(&2u16.to_le_bytes()); // program headers count
+    out.extend_from_slice(&64u16.to_le_bytes()); // section header size
+    out.extend_from_slice(&0u16.to_le_bytes()); // section headers count
+    out.extend_from_slice(&0u16.to_le_bytes()); // section names index
+
+    program_header(&mut out, 5, 0, BASE_ADDRESS, text_size, text_size); // R + X
+    let data_size = program.data.len() as u64;
+    program_header(&mut out, 6, data_offset, program.data_address, data_size, data_size + program.bss_size); // R + W
+
+    out.extend_from_slice(&program.text);
+    out.resize(data_offset as usize, 0);
+    out.extend_from_slice(&program.data);
+    Ok(out)
+}
@@ -0,0 +1,190 @@
+/*  gas.rs
+    MIT License
+
+    Copyright (c) 2024 Aidar Shigapov
+
+    Permission is hereby granted, free of charge, to any person obtaining a copy
+    of this software and associated documentation files (the "Software"), to deal
+    in the Software without restriction, including without limitation the rights
+    to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+    copies of the Software, and to permit persons to whom the Software is
+    furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in all
+    copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+    OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+    SOFTWARE.
+*/
+// GNU as output. the compiler speaks the NASM subset that the builtin assembler reads, and this rewrites it line by line
+// into `.intel_syntax noprefix` GAS, so binutils alone can build it: the directives get their GAS names, memory operands
+// get `ptr`, the addresses of labels get `offset` and the @labels, which GAS doesn`t like, become local .L labels
+
+use crate::assembler::{Operand, REGISTERS_64, parse_operand, split_arguments, strip_comment};
+
+fn symbol(name: &str) -> String {
+    match name.strip_prefix('@') {
+        Some(name) => format!(".L{}", name),
+        None => name.to_string(),
+    }
+}
+
+fn size_name(size: u8) -> &'static str {
+    match size {
+        1 => "byte",
+        2 => "word",
+        4 => "dword",
+        _ => "qword",
+    }
+}
+
+fn operand(mnemonic: &str, str: &str) -> Result<String, String> {
+    Ok(match parse_operand(str)? {
+        Operand::Register(_) => str.trim().to_string(),
+        Operand::Immediate(value) => value.to_string(), // `push qword 1` is just `push 1`
+        // jumps and calls take the label itself, everything else takes its address
+        Operand::Symbol(name) if mnemonic == "call" || mnemonic.starts_with('j') => symbol(&name),
+        Operand::Symbol(name) => format!("offset {}", symbol(&name)),
+        Operand::Memory(memory) => {
+            let mut terms = Vec::new();
+            if memory.rip_relative {
+                terms.push("rip".to_string());
+            }
+            if let Some(base) = memory.base {
+                terms.push(REGISTERS_64[base as usize].to_string());
+            }
+            if let Some((index, scale)) = memory.index {
+                terms.push(format!("{} * {}", REGISTERS_64[index as usize], scale));
+            }
+            if let Some(name) = &memory.symbol {
+                terms.push(symbol(name));
+            }
+            let mut address = terms.join(" + ");
+            if address.is_empty() {
+                address = memory.displacement.to_string();
+            } else if memory.displacement < 0 {
+                address += format!(" - {}", memory.displacement.unsigned_abs()).as_str();
+            } else if memory.displacement > 0 {
+                address += format!(" + {}", memory.displacement).as_str();
+            }
+            match memory.size {
+                Some(size) => format!("{} ptr [{}]", size_name(size), address),
+                None => format!("[{}]", address),
+            }
+        }
+    })
+}
+
+// db, dw, dd and dq. strings go to .ascii, everything else to .byte, .short, .long and .quad
+fn data(directive: &str, arguments: &str) -> Result<String, String> {
+    let name = match directive {
+        "db" => ".byte",
+        "dw" => ".short",
+        "dd" => ".long",
+        "dq" => ".quad",
+        _ => return Err(format!("unknown data directive \"{}\"", directive)),
+    };
+    let mut out: Vec<String> = Vec::new();
+    let mut values: Vec<String> = Vec::new();
+    for argument in split_arguments(arguments) {
+        if let Some(string) = argument.strip_prefix('"').and_then(|x| x.strip_suffix('"')) {
+            if directive != "db" {
+                return Err("strings are allowed only in db".to_string());
+            }
+            if !values.is_empty() {
+                out.push(format!("{} {}", name, values.join(", ")));
+                values.clear();
+            }
+            // nasm doesn`t do escapes in "strings", gas does
+            out.push(format!(".ascii \"{}\"", string.replace('\\', "\\\\")));
+        } else {
+            values.push(symbol(&argument));
+        }
+    }
+    if !values.is_empty() {
+        out.push(format!("{} {}", name, values.join(", ")));
+    }
+    Ok(out.join("; "))
+}
+
+fn line(code: &str) -> Result<Option<String>, String> {
+    let mut code = code.trim();
+    let mut out = String::new();
+    // `label:` and maybe something after it
+    if let Some((label, rest)) = code.split_once(':') {
+        let label = label.trim();
+        if !label.contains(' ') && !label.contains('"') && !label.contains('[') {
+            out += format!("{}:", symbol(label)).as_str();
+            code = rest.trim();
+            if code.is_empty() {
+                return Ok(Some(out));
+            }
+            out += " ";
+        }
+    }
+    if code.is_empty() {
+        return Ok(Some(out));
+    }
+    let (mnemonic, arguments) = code.split_once(char::is_whitespace).unwrap_or((code, ""));
+    let arguments = arguments.trim();
+    out += match mnemonic {
+        "section" => match arguments {
+            ".import" => return Ok(None), // externs are implicit in gas
+            ".text" | ".data" | ".bss" => arguments.to_string(),
+            _ => format!(".section {}", arguments),
+        },
+        "extern" => return Ok(None),
+        "global" => format!(".globl {}", symbol(arguments)),
+        "resb" | "resq" => {
+            let count = arguments.parse::<u64>().map_err(|_| "invalid count".to_string())?;
+            format!(".zero {}", count * if mnemonic == "resq" { 8 } else { 1 })
+        }
+        "db" | "dw" | "dd" | "dq" => data(mnemonic, arguments)?,
+        "times" => {
+            let mut words = arguments.split_whitespace();
+            let (Some(count), Some(directive), Some(value), None) = (words.next(), words.next(), words.next(), words.next()) else {
+                return Err("invalid times".to_string());
+            };
+            let size = match directive {
+                "db" => 1,
+                "dw" => 2,
+                "dd" => 4,
+                "dq" => 8,
+                _ => return Err(format!("unknown data directive \"{}\"", directive)),
+            };
+            format!(".fill {}, {}, {}", count, size, value)
+        }
+        _ => {
+            let operands = split_arguments(arguments).iter().map(|x| operand(mnemonic, x)).collect::<Result<Vec<_>, _>>()?;
+            if operands.is_empty() { mnemonic.to_string() } else { format!("{} {}", mnemonic, operands.join(", ")) }
+        }
+    }.as_str();
+    Ok(Some(out))
+}
+
+// `elf` adds the note that keeps ld from making the stack executable (and from complaining about it)
+pub fn from_nasm(source: &str, elf: bool) -> String {
+    let mut out = "\t.intel_syntax noprefix\n".to_string();
+    for (line_number, nasm) in source.lines().enumerate() {
+        let code = strip_comment(nasm);
+        let indent = &code[..code.len() - code.trim_start().len()];
+        let comment = nasm[code.len()..].strip_prefix(';').map(|x| format!("# {}", x.trim()));
+        let converted = line(code).unwrap_or_else(|error| panic!("gas: line {}: {}: {}", line_number + 1, error, nasm.trim()));
+        match (converted, comment) {
+            (Some(converted), Some(comment)) if !converted.is_empty() => out += format!("{}{} {}\n", indent, converted, comment).as_str(),
+            (Some(_), Some(comment)) => out += format!("{}{}\n", indent, comment).as_str(),
+            (Some(converted), None) if !converted.is_empty() => out += format!("{}{}\n", indent, converted).as_str(),
+            (Some(_), None) => out += "\n",
+            (None, _) => {}
+        }
+    }
+    if elf {
+        out += "\t.section .note.GNU-stack,\"\",@progbits\n";
+    }
+    out
+}
@@ -0,0 +1,129 @@
+/*  jit.rs
+    MIT License
+
+    Copyright (c) 2024 Aidar Shigapov
+
+    Permission is hereby granted, free of charge, to any person obtaining a copy
+    of this software and associated documentation files (the "Software"), to deal
+    in the Software without restriction, including without limitation the rights
+    to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+    copies of the Software, and to permit persons to whom the Software is
+    furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in all
+    copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+    OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+    SOFTWARE.
+*/
+// `j` mode: the x86_64 asm is assembled by assembler.rs straight into an mmap`ed buffer and run in this process.
+// print, write_raw and exit call back into rust, everything else is the freestanding prelude as is
+
+use crate::assembler;
+use std::ffi::c_void;
+use std::io::Write;
+
+const PROT_READ: i32 = 1;
+const PROT_WRITE: i32 = 2;
+const PROT_EXEC: i32 = 4;
+const MAP_PRIVATE: i32 = 2;
+const MAP_ANONYMOUS: i32 = 0x20;
+const PAGE_SIZE: u64 = 0x1000;
+
+unsafe extern "C" {
+    fn mmap(address: *mut c_void, length: usize, protection: i32, flags: i32, fd: i32, offset: i64) -> *mut c_void;
+    fn mprotect(address: *mut c_void, length: usize, protection: i32) -> i32;
+}
+
+// rust functions are called with the stack aligned to 16 bytes. r9 and r10 are kept, the prelude`s own print doesn`t touch them
+const SERVICES_ASM: &str = "
+section .text
+@print_int:
+\t; rax - value
+\tmov rdi, rax
+\tmov rax, {print}
+\tjmp @call_rust
+
+@write_raw:
+\t; rsi - pointer
+\tmov rdi, rsi
+\tmov rax, {write_raw}
+\tjmp @call_rust
+
+@exit:
+\t; rdi - exit code
+\tmov rax, {exit}
+\tjmp @call_rust
+
+@call_rust:
+\t; rax - function, rdi - argument
+\tpush rbp
+\tmov rbp, rsp
+\tpush r9
+\tpush r10
+\tand rsp, -16
+\tcall rax
+\tmov r10, [rbp - 16]
+\tmov r9, [rbp - 8]
+\tmov rsp, rbp
+\tpop rbp
+\tret
+";
+
+// every service flushes, the prelude still writes the dump_stack newline and files with raw syscalls
+extern "C" fn print(value: i64) {
+    let mut stdout = std::io::stdout().lock();
+    write!(stdout, "{} ", value).expect("error on print");
+    stdout.flush().expect("error on print");
+}
+
+extern "C" fn write_raw(bytes: *const u8) {
+    let bytes = unsafe { std::slice::from_raw_parts(bytes, 8) };
+    let length = bytes.iter().position(|x| *x == 0).unwrap_or(8);
+    let mut stdout = std::io::stdout().lock();
+    stdout.write_all(&bytes[..length]).expect("error on write_raw");
+    stdout.flush().expect("error on write_raw");
+}
+
+extern "C" fn exit(code: i64) {
+    std::io::stdout().flush().expect("unable to flush stdout");
+    std::process::exit(code as i32);
+}
+
+pub fn run(asm: &str) -> ! {
+    let asm = asm.to_string() + &SERVICES_ASM
+        .replace("{print}", &(print as extern "C" fn(i64) as usize).to_string())
+        .replace("{write_raw}", &(write_raw as extern "C" fn(*const u8) as usize).to_string())
+        .replace("{exit}", &(exit as extern "C" fn(i64) as usize).to_string());
+
+    // the buffer is mapped once the sizes are known: .text, then .data and .bss from the next page
+    let buffer = std::cell::Cell::new((std::ptr::null_mut::<c_void>(), 0u64));
+    let program = assembler::assemble(&asm, |text_size, data_size| {
+        let text_length = text_size.next_multiple_of(PAGE_SIZE);
+        let length = text_length + data_size.next_multiple_of(PAGE_SIZE);
+        let address = unsafe { mmap(std::ptr::null_mut(), length as usize, PROT_READ | PROT_WRITE, MAP_PRIVATE | MAP_ANONYMOUS, -1, 0) };
+        if address as isize == -1 {
+            panic!("jit: unable to map {} bytes", length);
+        }
+        buffer.set((address, text_length));
+        (address as u64, address as u64 + text_length)
+    }).unwrap_or_else(|error| panic!("builtin assembler: {}", error));
+    let (address, text_length) = buffer.get();
+    let entry = *program.labels.get("_start").expect("jit: the prelude has no _start");
+
+    unsafe {
+        std::ptr::copy_nonoverlapping(program.text.as_ptr(), address as *mut u8, program.text.len());
+        // .bss is already zeroed by mmap
+        std::ptr::copy_nonoverlapping(program.data.as_ptr(), program.data_address as *mut u8, program.data.len());
+        if mprotect(address, text_length as usize, PROT_READ | PROT_EXEC) != 0 {
+            panic!("jit: unable to make the code executable");
+        }
+        let start: extern "C" fn() -> ! = std::mem::transmute(entry as usize);
+        start()
+    }
+}
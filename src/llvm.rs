@@ -0,0 +1,503 @@
+/*  llvm.rs
+    MIT License
+
+    Copyright (c) 2024 Aidar Shigapov
+
+    Permission is hereby granted, free of charge, to any person obtaining a copy
+    of this software and associated documentation files (the "Software"), to deal
+    in the Software without restriction, including without limitation the rights
+    to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+    copies of the Software, and to permit persons to whom the Software is
+    furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in all
+    copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+    OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+    SOFTWARE.
+*/
+// textual llvm ir for clang/llc. every non-inlined statement is a function that takes the data stack pointer and returns it back.
+// the pointer lives in an alloca, so the ir is dumb and mem2reg makes it fast. the runtime sits on libc (and __errno_location, so linux)
+
+use crate::{State, StateType, asm_label_name};
+use crate::lower::{lower, Op};
+
+const LLVM_RUNTIME: &str = r##"@int_fmt = private unnamed_addr constant [6 x i8] c"%lld \00"
+@bytes_fmt = private unnamed_addr constant [5 x i8] c"%.8s\00"
+@newline_fmt = private unnamed_addr constant [2 x i8] c"\0A\00"
+@int_scan_fmt = private unnamed_addr constant [5 x i8] c"%lld\00"
+@stack = internal global [1048576 x i64] zeroinitializer
+@line_buffer = internal global [4096 x i8] zeroinitializer
+@path_buffer = internal global [4096 x i8] zeroinitializer
+
+declare i32 @printf(ptr, ...)
+declare i32 @scanf(ptr, ...)
+declare i32 @getchar()
+declare ptr @malloc(i64)
+declare void @free(ptr)
+declare void @exit(i32) noreturn
+declare i32 @fflush(ptr)
+declare i32 @open(ptr, i32, ...)
+declare i64 @read(i32, ptr, i64)
+declare i64 @write(i32, ptr, i64)
+declare i32 @close(i32)
+declare ptr @__errno_location()
+
+; prints the data stack from the bottom to the top
+define internal void @jalgo_dump_stack(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %cell = phi ptr [ getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0), %entry ], [ %next, %body ]
+  %more = icmp ugt ptr %cell, %sp
+  br i1 %more, label %body, label %end
+body:
+  %next = getelementptr i64, ptr %cell, i64 -1
+  %value = load i64, ptr %next
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %value)
+  br label %loop
+end:
+  call i32 (ptr, ...) @printf(ptr @newline_fmt)
+  ret void
+}
+
+; up to 8 bytes of the value until a zero byte
+define internal void @jalgo_write_raw(i64 %value) {
+  %bytes = alloca i64
+  store i64 %value, ptr %bytes
+  call i32 (ptr, ...) @printf(ptr @bytes_fmt, ptr %bytes)
+  ret void
+}
+
+; pushes the value and the scanf status
+define internal ptr @jalgo_read_int(ptr %sp) {
+  %value = alloca i64
+  store i64 0, ptr %value
+  %status = call i32 (ptr, ...) @scanf(ptr @int_scan_fmt, ptr %value)
+  %loaded = load i64, ptr %value
+  %status64 = sext i32 %status to i64
+  %value_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 %loaded, ptr %value_cell
+  %new_sp = getelementptr i64, ptr %sp, i64 -2
+  store i64 %status64, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pushes the line as a zero terminated stack string and its length, -1 on EOF
+define internal ptr @jalgo_read_line(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %length = phi i64 [ 0, %entry ], [ %next_length, %append ]
+  %c = call i32 @getchar()
+  %eof = icmp eq i32 %c, -1
+  br i1 %eof, label %at_eof, label %not_eof
+not_eof:
+  %newline = icmp eq i32 %c, 10
+  br i1 %newline, label %done, label %append
+append:
+  %byte = trunc i32 %c to i8
+  %slot = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %length
+  store i8 %byte, ptr %slot
+  %next_length = add i64 %length, 1
+  %full = icmp eq i64 %next_length, 4096
+  br i1 %full, label %done, label %loop
+at_eof:
+  %empty = icmp eq i64 %length, 0
+  br i1 %empty, label %nothing, label %done
+nothing:
+  %eof_sp = getelementptr i64, ptr %sp, i64 -1
+  store i64 -1, ptr %eof_sp
+  ret ptr %eof_sp
+done:
+  %final_length = phi i64 [ %length, %not_eof ], [ %next_length, %append ], [ %length, %at_eof ]
+  %zero_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 0, ptr %zero_cell
+  br label %copy
+copy:
+  %i = phi i64 [ 0, %done ], [ %next_i, %copy_byte ]
+  %copy_more = icmp ult i64 %i, %final_length
+  br i1 %copy_more, label %copy_byte, label %copy_end
+copy_byte:
+  %source = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %i
+  %loaded = load i8, ptr %source
+  %extended = zext i8 %loaded to i64
+  %from_end = sub i64 %i, %final_length
+  %offset = sub i64 %from_end, 1
+  %target = getelementptr i64, ptr %sp, i64 %offset
+  store i64 %extended, ptr %target
+  %next_i = add i64 %i, 1
+  br label %copy
+copy_end:
+  %length_offset = sub i64 -2, %final_length
+  %new_sp = getelementptr i64, ptr %sp, i64 %length_offset
+  store i64 %final_length, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pops a zero terminated stack string into @path_buffer
+define internal ptr @jalgo_pop_path(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %i = phi i64 [ 0, %entry ], [ %next, %loop ]
+  %cell = getelementptr i64, ptr %sp, i64 %i
+  %value = load i64, ptr %cell
+  %byte = trunc i64 %value to i8
+  %slot = getelementptr [4096 x i8], ptr @path_buffer, i64 0, i64 %i
+  store i8 %byte, ptr %slot
+  %next = add i64 %i, 1
+  %more = icmp ne i64 %value, 0
+  br i1 %more, label %loop, label %end
+end:
+  %new_sp = getelementptr i64, ptr %sp, i64 %next
+  ret ptr %new_sp
+}
+
+; -1 from libc becomes -errno, like the raw syscalls
+define internal i64 @jalgo_result(i64 %result) {
+entry:
+  %failed = icmp slt i64 %result, 0
+  br i1 %failed, label %error, label %ok
+ok:
+  ret i64 %result
+error:
+  %errno_pointer = call ptr @__errno_location()
+  %errno = load i32, ptr %errno_pointer
+  %errno64 = sext i32 %errno to i64
+  %code = sub i64 0, %errno64
+  ret i64 %code
+}
+
+define internal i64 @jalgo_fopen(ptr %path, i32 %flags) {
+  %fd = call i32 (ptr, i32, ...) @open(ptr %path, i32 %flags, i32 420)
+  %fd64 = sext i32 %fd to i64
+  %result = call i64 @jalgo_result(i64 %fd64)
+  ret i64 %result
+}
+
+; fd -> byte, -1 on EOF, -errno on error
+define internal i64 @jalgo_fread_byte(i64 %fd) {
+  %byte = alloca i8
+  store i8 0, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @read(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  %loaded = load i8, ptr %byte
+  %extended = zext i8 %loaded to i64
+  %is_byte = icmp eq i64 %count, 1
+  %is_eof = icmp eq i64 %count, 0
+  %eof_or_error = select i1 %is_eof, i64 -1, i64 %result
+  %value = select i1 %is_byte, i64 %extended, i64 %eof_or_error
+  ret i64 %value
+}
+
+; fd byte -> 1, -errno on error. printf output goes first
+define internal i64 @jalgo_fwrite_byte(i64 %fd, i64 %value) {
+  call i32 @fflush(ptr null)
+  %byte = alloca i8
+  %truncated = trunc i64 %value to i8
+  store i8 %truncated, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @write(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  ret i64 %result
+}
+
+define internal i64 @jalgo_fclose(i64 %fd) {
+  call i32 @fflush(ptr null)
+  %fd32 = trunc i64 %fd to i32
+  %status = call i32 @close(i32 %fd32)
+  %status64 = sext i32 %status to i64
+  %result = call i64 @jalgo_result(i64 %status64)
+  ret i64 %result
+}
+"##;
+
+// keeps the numbering of the temporaries and starts a new basic block when something follows a terminator
+#[derive(Default)]
+struct Emitter {
+    out: String,
+    next: usize,
+    terminated: bool,
+}
+impl Emitter {
+    fn instruction(&mut self, text: &str) {
+        if self.terminated {
+            // unreachable code after a jump still needs a block
+            self.next += 1;
+            self.out += format!("b{}:\n", self.next).as_str();
+            self.terminated = false;
+        }
+        self.out += format!("  {}\n", text).as_str();
+    }
+    fn terminator(&mut self, text: &str) {
+        self.instruction(text);
+        self.terminated = true;
+    }
+    fn assign(&mut self, text: &str) -> String {
+        self.next += 1;
+        let name = format!("%t{}", self.next);
+        self.instruction(format!("{} = {}", name, text).as_str());
+        name
+    }
+    fn label(&mut self, name: &str) {
+        if !self.terminated {
+            self.out += format!("  br label %{}\n", name).as_str();
+        }
+        self.out += format!("{}:\n", name).as_str();
+        self.terminated = false;
+    }
+    // a conditional jump. falls through to a fresh block
+    fn branch(&mut self, condition: &str, target: &str) {
+        self.next += 1;
+        let next_block = format!("b{}", self.next);
+        self.terminator(format!("br i1 {}, label %{}, label %{}", condition, target, next_block).as_str());
+        self.label(&next_block);
+    }
+    fn cell(&mut self, sp: &str, index: usize) -> String {
+        if index == 0 {
+            sp.to_string()
+        } else {
+            self.assign(format!("getelementptr i64, ptr {}, i64 {}", sp, index).as_str())
+        }
+    }
+    fn load_sp(&mut self) -> String {
+        self.assign("load ptr, ptr %sp")
+    }
+    // pops `pops` values (the top is the first), and pushes what `body` returns (the top is the first too)
+    fn stack_op(&mut self, pops: usize, body: impl FnOnce(&mut Emitter, &str, &[String]) -> Vec<String>) {
+        let sp = self.load_sp();
+        let values = (0..pops).map(|i| {
+            let cell = self.cell(&sp, i);
+            self.assign(format!("load i64, ptr {}", cell).as_str())
+        }).collect::<Vec<_>>();
+        let results = body(self, &sp, &values);
+        let new_sp = if pops == results.len() {
+            sp
+        } else {
+            let new_sp = self.assign(format!("getelementptr i64, ptr {}, i64 {}", sp, pops as i64 - results.len() as i64).as_str());
+            self.instruction(format!("store ptr {}, ptr %sp", new_sp).as_str());
+            new_sp
+        };
+        for (i, result) in results.iter().enumerate() {
+            let cell = self.cell(&new_sp, i);
+            self.instruction(format!("store i64 {}, ptr {}", result, cell).as_str());
+        }
+    }
+    // a runtime function that takes the stack pointer and returns the new one
+    fn stack_call(&mut self, function: &str) {
+        let sp = self.load_sp();
+        let new_sp = self.assign(format!("call ptr @{}(ptr {})", function, sp).as_str());
+        self.instruction(format!("store ptr {}, ptr %sp", new_sp).as_str());
+    }
+}
+
+fn function_name(state: &State) -> String {
+    format!("st_{}", asm_label_name(&state.name))
+}
+
+fn compile_primitive(e: &mut Emitter, template_arguments: &[Vec<String>], state: &State) {
+    let template_argument = |index: usize, default: usize| if state.template_arguments_index == 0 {
+        default
+    } else {
+        template_arguments[state.template_arguments_index].get(index).expect("too few template arguments").parse::<usize>().expect("template argument must be integer")
+    };
+    let load = |e: &mut Emitter, address: &str, size: usize, signed: bool| {
+        let pointer = e.assign(format!("inttoptr i64 {} to ptr", address).as_str());
+        if size == 64 {
+            return e.assign(format!("load i64, ptr {}, align 1", pointer).as_str());
+        }
+        let value = e.assign(format!("load i{}, ptr {}, align 1", size, pointer).as_str());
+        e.assign(format!("{} i{} {} to i64", if signed { "sext" } else { "zext" }, size, value).as_str())
+    };
+    let store = |e: &mut Emitter, address: &str, value: &str, size: usize| {
+        let pointer = e.assign(format!("inttoptr i64 {} to ptr", address).as_str());
+        let value = if size == 64 { value.to_string() } else { e.assign(format!("trunc i64 {} to i{}", value, size).as_str()) };
+        e.instruction(format!("store i{} {}, ptr {}, align 1", size, value, pointer).as_str());
+    };
+    let binary = |e: &mut Emitter, instruction: &str| e.stack_op(2, |e, _, x| vec![e.assign(format!("{} i64 {}, {}", instruction, x[1], x[0]).as_str())]);
+    let compare = |e: &mut Emitter, condition: &str| e.stack_op(2, |e, _, x| {
+        let flag = e.assign(format!("icmp {} i64 {}, {}", condition, x[1], x[0]).as_str());
+        vec![e.assign(format!("zext i1 {} to i64", flag).as_str())]
+    });
+    match state.state_type {
+        StateType::Integer =>   e.stack_op(0, |_, _, _| vec![state.name.parse::<i64>().expect("invalid integer").to_string()]),
+        StateType::Data =>      e.stack_op(0, |e, _, _| vec![e.assign(format!("ptrtoint ptr @data_{} to i64", asm_label_name(&state.name)).as_str())]),
+        StateType::Var =>       e.stack_op(0, |e, _, _| vec![e.assign(format!("ptrtoint ptr @var_{} to i64", asm_label_name(&state.name)).as_str())]),
+        StateType::VarFetch =>  e.stack_op(0, |e, _, _| vec![e.assign(format!("load i64, ptr @var_{}", asm_label_name(&state.name[1..])).as_str())]),
+        StateType::VarStore =>  e.stack_op(1, |e, _, x| { e.instruction(format!("store i64 {}, ptr @var_{}", x[0], asm_label_name(&state.name[1..])).as_str()); vec![] }),
+        StateType::StackHead => e.stack_op(0, |e, sp, _| vec![e.assign(format!("ptrtoint ptr {} to i64", sp).as_str())]),
+        StateType::Depth =>     e.stack_op(0, |e, sp, _| {
+            let top = e.assign(format!("ptrtoint ptr {} to i64", sp).as_str());
+            let bottom = e.assign("ptrtoint ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0) to i64");
+            let size = e.assign(format!("sub i64 {}, {}", bottom, top).as_str());
+            vec![e.assign(format!("lshr i64 {}, 3", size).as_str())]
+        }),
+        StateType::DumpStack => {
+            let sp = e.load_sp();
+            e.instruction(format!("call void @jalgo_dump_stack(ptr {})", sp).as_str());
+        }
+        StateType::ReadFrom =>  e.stack_op(1, |e, _, x| vec![load(e, &x[0], 64, false)]),
+        StateType::ReadU8 =>    e.stack_op(1, |e, _, x| vec![load(e, &x[0], 8, false)]),
+        StateType::ReadI8 =>    e.stack_op(1, |e, _, x| vec![load(e, &x[0], 8, true)]),
+        StateType::ReadU16 =>   e.stack_op(1, |e, _, x| vec![load(e, &x[0], 16, false)]),
+        StateType::ReadI16 =>   e.stack_op(1, |e, _, x| vec![load(e, &x[0], 16, true)]),
+        StateType::ReadU32 =>   e.stack_op(1, |e, _, x| vec![load(e, &x[0], 32, false)]),
+        StateType::ReadI32 =>   e.stack_op(1, |e, _, x| vec![load(e, &x[0], 32, true)]),
+        StateType::WriteTo =>   e.stack_op(2, |e, _, x| { store(e, &x[1], &x[0], 64); vec![] }),
+        StateType::WriteU8 =>   e.stack_op(2, |e, _, x| { store(e, &x[1], &x[0], 8); vec![] }),
+        StateType::WriteU16 =>  e.stack_op(2, |e, _, x| { store(e, &x[1], &x[0], 16); vec![] }),
+        StateType::WriteU32 =>  e.stack_op(2, |e, _, x| { store(e, &x[1], &x[0], 32); vec![] }),
+        StateType::Alloc =>     e.stack_op(1, |e, _, x| {
+            let pointer = e.assign(format!("call ptr @malloc(i64 {})", x[0]).as_str());
+            vec![e.assign(format!("ptrtoint ptr {} to i64", pointer).as_str())]
+        }),
+        StateType::Free =>      e.stack_op(1, |e, _, x| {
+            let pointer = e.assign(format!("inttoptr i64 {} to ptr", x[0]).as_str());
+            e.instruction(format!("call void @free(ptr {})", pointer).as_str());
+            vec![]
+        }),
+        StateType::Eq =>        compare(e, "eq"),
+        StateType::Neq =>       compare(e, "ne"),
+        StateType::More =>      compare(e, "sgt"),
+        StateType::Less =>      compare(e, "slt"),
+        StateType::Sum =>       binary(e, "add"),
+        StateType::Dif =>       binary(e, "sub"),
+        StateType::Mul =>       binary(e, "mul"),
+        StateType::Div =>       binary(e, "sdiv"),
+        StateType::Inc =>       e.stack_op(1, |e, _, x| vec![e.assign(format!("add i64 {}, 1", x[0]).as_str())]),
+        StateType::Dec =>       e.stack_op(1, |e, _, x| vec![e.assign(format!("sub i64 {}, 1", x[0]).as_str())]),
+        StateType::Dup =>       e.stack_op(1, |_, _, x| vec![x[0].clone(); template_argument(0, 1) + 1]),
+        StateType::Pop =>       e.stack_op(template_argument(0, 1), |_, _, _| vec![]),
+        StateType::Swap => {
+            let (first, second) = (template_argument(0, 0), template_argument(1, 1));
+            e.stack_op(first.max(second) + 1, |_, _, x| {
+                let mut x = x.to_vec();
+                x.swap(first, second);
+                x
+            });
+        }
+        StateType::Print =>     e.stack_op(1, |e, _, x| { e.instruction(format!("call i32 (ptr, ...) @printf(ptr @int_fmt, i64 {})", x[0]).as_str()); vec![] }),
+        StateType::WriteRaw =>  e.stack_op(1, |e, _, x| { e.instruction(format!("call void @jalgo_write_raw(i64 {})", x[0]).as_str()); vec![] }),
+        StateType::ReadInt =>   e.stack_call("jalgo_read_int"),
+        StateType::ReadChar =>  e.stack_op(0, |e, _, _| {
+            let c = e.assign("call i32 @getchar()");
+            vec![e.assign(format!("sext i32 {} to i64", c).as_str())]
+        }),
+        StateType::ReadLine =>  e.stack_call("jalgo_read_line"),
+        StateType::Fopen | StateType::FopenPtr => {
+            let mode = if state.template_arguments_index == 0 { "r" } else { template_arguments[state.template_arguments_index][0].as_str() };
+            let flags = match mode {
+                "r" => 0,
+                "w" => 1 | 64 | 512,   // O_WRONLY | O_CREAT | O_TRUNC
+                "a" => 1 | 64 | 1024,  // O_WRONLY | O_CREAT | O_APPEND
+                _ => panic!("unknown fopen mode \"{}\". possible values: r | w | a", mode),
+            };
+            if state.state_type == StateType::FopenPtr {
+                e.stack_op(1, |e, _, x| {
+                    let path = e.assign(format!("inttoptr i64 {} to ptr", x[0]).as_str());
+                    vec![e.assign(format!("call i64 @jalgo_fopen(ptr {}, i32 {})", path, flags).as_str())]
+                });
+            } else {
+                e.stack_call("jalgo_pop_path");
+                e.stack_op(0, |e, _, _| vec![e.assign(format!("call i64 @jalgo_fopen(ptr @path_buffer, i32 {})", flags).as_str())]);
+            }
+        }
+        StateType::FreadByte => e.stack_op(1, |e, _, x| vec![e.assign(format!("call i64 @jalgo_fread_byte(i64 {})", x[0]).as_str())]),
+        StateType::FwriteByte =>e.stack_op(2, |e, _, x| vec![e.assign(format!("call i64 @jalgo_fwrite_byte(i64 {}, i64 {})", x[1], x[0]).as_str())]),
+        StateType::Fclose =>    e.stack_op(1, |e, _, x| vec![e.assign(format!("call i64 @jalgo_fclose(i64 {})", x[0]).as_str())]),
+        StateType::Exit =>      e.stack_op(1, |e, _, x| {
+            let code = e.assign(format!("trunc i64 {} to i32", x[0]).as_str());
+            e.instruction(format!("call void @exit(i32 {})", code).as_str());
+            vec![]
+        }),
+        _ => panic!("{} is not a primitive", state.name),
+    }
+}
+
+fn compile_function(states: &[State], template_arguments: &[Vec<String>], state: &State, linkage: &str) -> String {
+    let function = lower(states, template_arguments, state);
+    let mut e = Emitter::default();
+    e.out += format!("; {}\ndefine {}ptr @{}(ptr %sp.arg) {{\nentry:\n", state.name, linkage, function_name(state)).as_str();
+    e.instruction("%sp = alloca ptr");
+    for counter in 0..function.counters {
+        e.instruction(format!("%counter{} = alloca i64", counter).as_str());
+    }
+    e.instruction("store ptr %sp.arg, ptr %sp");
+    // __self__goto__ jumps here
+    e.label("restart");
+    for op in function.ops.iter() {
+        match op {
+            Op::Label(label) => e.label(&format!("l{}", label)),
+            Op::Primitive(dep) => compile_primitive(&mut e, template_arguments, dep),
+            Op::JumpIfNotPositive(label) => {
+                let mut condition = String::new();
+                e.stack_op(1, |e, _, x| { condition = e.assign(format!("icmp sle i64 {}, 0", x[0]).as_str()); vec![] });
+                e.branch(&condition, &format!("l{}", label));
+            }
+            Op::Jump(label) => e.terminator(format!("br label %l{}", label).as_str()),
+            Op::TimesBegin{ count, counter, exit } => {
+                let count = match count {
+                    Some(count) => count.to_string(),
+                    None => {
+                        let mut value = String::new();
+                        e.stack_op(1, |_, _, x| { value = x[0].clone(); vec![] });
+                        value
+                    }
+                };
+                e.instruction(format!("store i64 {}, ptr %counter{}", count, counter).as_str());
+                let condition = e.assign(format!("icmp sle i64 {}, 0", count).as_str());
+                e.branch(&condition, &format!("l{}", exit));
+            }
+            Op::TimesEnd{ counter, again } => {
+                let value = e.assign(format!("load i64, ptr %counter{}", counter).as_str());
+                let decremented = e.assign(format!("sub i64 {}, 1", value).as_str());
+                e.instruction(format!("store i64 {}, ptr %counter{}", decremented, counter).as_str());
+                let condition = e.assign(format!("icmp sgt i64 {}, 0", decremented).as_str());
+                e.branch(&condition, &format!("l{}", again));
+            }
+            Op::Call(dep) => e.stack_call(&function_name(dep)),
+            Op::TailCall(dep) => {
+                // musttail makes it a real jump, so chains of statements run in constant stack space
+                let sp = e.load_sp();
+                let new_sp = e.assign(format!("musttail call ptr @{}(ptr {})", function_name(dep), sp).as_str());
+                e.terminator(format!("ret ptr {}", new_sp).as_str());
+            }
+            Op::Restart => e.terminator("br label %restart"),
+            Op::Return => {
+                let sp = e.load_sp();
+                e.terminator(format!("ret ptr {}", sp).as_str());
+            }
+        }
+    }
+    if !e.terminated {
+        e.instruction("unreachable");
+    }
+    e.out += "}\n";
+    e.out
+}
+
+// `functions` are the non-inlined statements, start included. exported ones are visible to the linker
+pub fn compile(states: &[State], template_arguments: &[Vec<String>], functions: &[&State], exports: &[&str], data_sections: &[&(String, Vec<u8>)], variables: &[&String]) -> String {
+    let mut out = LLVM_RUNTIME.to_string();
+    out += "\n";
+    for variable in variables.iter() {
+        out += format!("@var_{} = internal global i64 0\n", asm_label_name(variable)).as_str();
+    }
+    for data in data_sections.iter() {
+        let bytes = data.1.iter().map(|x| if x.is_ascii_alphanumeric() || *x == b' ' { (*x as char).to_string() } else { format!("\\{:02X}", x) }).collect::<String>();
+        out += format!("@data_{} = internal global [{} x i8] c\"{}\"\n", asm_label_name(&data.0), data.1.len(), bytes).as_str();
+    }
+    for function in functions.iter() {
+        out += "\n";
+        out += compile_function(states, template_arguments, function, if exports.contains(&function.name.as_str()) { "" } else { "internal " }).as_str();
+    }
+    out += "\ndefine i32 @main() {\n  %sp = call ptr @st_start(ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0))\n  ret i32 0\n}\n";
+    out
+}
@@ -0,0 +1,110 @@
+/*  lower.rs
+    MIT License
+
+    Copyright (c) 2024 Aidar Shigapov
+
+    Permission is hereby granted, free of charge, to any person obtaining a copy
+    of this software and associated documentation files (the "Software"), to deal
+    in the Software without restriction, including without limitation the rights
+    to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+    copies of the Software, and to permit persons to whom the Software is
+    furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in all
+    copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+    OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+    SOFTWARE.
+*/
+// a statement body flattened to jumps between numbered labels, with the inlined statements already pasted in.
+// the backends that don`t need the x86 register tricks (c, ...) are written on top of it
+
+use crate::{State, StateType, ControlFlowJump, resolve_control_flow, is_tail_position};
+
+pub enum Op<'a> {
+    Label(usize),
+    Primitive(&'a State),                                           // integers, data, vars and the builtins
+    JumpIfNotPositive(usize),                                       // pops the condition of `if` and `do`
+    Jump(usize),
+    TimesBegin { count: Option<i64>, counter: usize, exit: usize }, // pops the count unless it is times<N>. jumps to exit if it isn`t positive
+    TimesEnd { counter: usize, again: usize },                      // decrements the counter and jumps back while it`s positive
+    Call(&'a State),
+    TailCall(&'a State),                                            // a call right before the return
+    Restart,                                                        // __self__goto__ and the tail __self__ jump to the beginning
+    Return,
+}
+
+pub struct Function<'a> {
+    pub ops: Vec<Op<'a>>,
+    pub labels: usize,      // labels are numbered from 0
+    pub counters: usize,    // the `times` counters that are alive at the same time
+}
+
+pub fn lower<'a>(states: &'a [State], template_arguments: &[Vec<String>], state: &'a State) -> Function<'a> {
+    let mut function = Function{ ops: Vec::new(), labels: 0, counters: 0 };
+    lower_body(states, template_arguments, state, &mut function, 0);
+    function
+}
+
+fn lower_body<'a>(states: &'a [State], template_arguments: &[Vec<String>], state: &'a State, function: &mut Function<'a>, counter_base: usize) {
+    let inlined = state.inlinable;
+    let control_flow = resolve_control_flow(states, state);
+    let first_label = function.labels;
+    function.labels += state.deps.len() + 1;
+    let label = |position: usize| first_label + position;
+    let mut labels = vec![false; state.deps.len() + 1];
+    for jump in control_flow.jumps.iter() {
+        if let ControlFlowJump::To(target) | ControlFlowJump::TimesLoop(target) = jump {
+            labels[*target] = true;
+        }
+    }
+    if inlined && control_flow.jumps.contains(&ControlFlowJump::Return) {
+        labels[state.deps.len()] = true;
+    }
+    for (position, i) in state.deps.iter().enumerate() {
+        let dep = states.get(*i).expect("invalid statement index");
+        if labels[position] {
+            function.ops.push(Op::Label(label(position)));
+        }
+        let counter = counter_base + control_flow.loop_counters[position];
+        let op = match (&dep.state_type, control_flow.jumps[position]) {
+            (StateType::Additional, _) if dep.inlinable => {
+                lower_body(states, template_arguments, dep, function, counter);
+                continue;
+            }
+            (StateType::If | StateType::Do, ControlFlowJump::To(target)) => Op::JumpIfNotPositive(label(target)),
+            (StateType::Else | StateType::Return, ControlFlowJump::Return) if inlined => Op::Jump(label(state.deps.len())),
+            (StateType::Else | StateType::Return, ControlFlowJump::Return) => Op::Return,
+            (StateType::Else | StateType::End, ControlFlowJump::To(target)) => Op::Jump(label(target)),
+            (StateType::Times, ControlFlowJump::To(target)) => {
+                function.counters = function.counters.max(counter + 1);
+                let count = if dep.template_arguments_index == 0 {
+                    None
+                } else {
+                    Some(template_arguments[dep.template_arguments_index][0].parse::<i64>().expect("template argument in times must be integer"))
+                };
+                Op::TimesBegin{ count, counter, exit: label(target) }
+            }
+            (StateType::End, ControlFlowJump::TimesLoop(target)) => Op::TimesEnd{ counter: counter - 1, again: label(target) },
+            (StateType::While | StateType::Then | StateType::End, _) => continue,
+            (StateType::SelfCall, _) if is_tail_position(states, state, &control_flow, position) => Op::Restart,
+            (StateType::SelfCall, _) => Op::Call(state),
+            (StateType::SelfGoto, _) => Op::Restart,
+            (StateType::Additional, _) if !inlined && state.name != "start" && is_tail_position(states, state, &control_flow, position) => Op::TailCall(dep),
+            (StateType::Additional, _) => Op::Call(dep),
+            _ => Op::Primitive(dep),
+        };
+        function.ops.push(op);
+    }
+    if labels[state.deps.len()] {
+        function.ops.push(Op::Label(label(state.deps.len())));
+    }
+    if !inlined {
+        function.ops.push(Op::Return);
+    }
+}
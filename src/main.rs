@@ -21,30 +21,67 @@
     OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
     SOFTWARE.
 */
+mod aarch64;
+mod assembler;
+mod c;
+mod elf64;
+mod gas;
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+mod jit;
+mod llvm;
+mod lower;
+#[cfg(feature = "wasm-host")]
+mod wasm_host;
+mod wat;
+
 use std::*;
 use std::process::exit;
 use collections::hash_map::HashMap;
+use collections::BTreeMap;
 use clap::{App, Arg};
-use io::Write;
+use io::{BufRead, Write};
 
 macro_rules! INT_FMT {
     () => {
         "\tpush qword {}\n"
     };
 }
-macro_rules! BIG_INT_FMT { // x > i32.max
+macro_rules! BIG_INT_FMT { // x doesn`t fit in i32
     () => {
         "\tmov rax, {}\n\tpush rax\n"
     };
 }
 
-const ASM_CODE_BEGIN_WIN64: &str = "section .import\n\textern printf\n\textern exit\n\nsection .data\n\t@int_fmt: db \"%lli \", 0\n\t@bytes_fmt: db \"%.8s\", 0\n\t@second_stack: times 1024 dq 0\n\t@stack_size: dq 0\n\nsection .text\n\tglobal WinMain\n\nWinMain:\n\tjmp start\n\n@share_to_second_stack:\n\t; arg - rax\n\tmov rbx, @second_stack\n\tmov rcx, @stack_size\n\tmov rcx, [rcx]\n\tmov qword [rbx + rcx * 8], rax\n\tmov rcx, @stack_size\n\tinc qword [rcx]\n\tret\n\n@get_pop_second_stack:\n\t; return - rax\n\tmov rbx, @second_stack\n\tmov rcx, @stack_size\n\tmov rcx, [rcx]\n\tmov rax, qword [rbx + rcx * 8 - 8]\n\tmov rcx, @stack_size\n\tdec qword [rcx]\n\tret\n";
-const ASM_CODE_BEGIN_LINUX: &str = "section .import\n\textern printf\n\textern exit\n\nsection .data\n\t@int_fmt: db \"%lli \", 0\n\t@bytes_fmt: db \"%.8s\", 0\n\t\n\t@second_stack: times 1024 dq 0\n\t@stack_size: dq 0\n\nsection .text\n\tglobal main\n\nmain:\n\tjmp start\n\n@share_to_second_stack:\n\t; arg - rax\n\tmov rbx, @second_stack\n\tmov rcx, @stack_size\n\tmov rcx, [rcx]\n\tmov qword [rbx + rcx * 8], rax\n\tmov rcx, @stack_size\n\tinc qword [rcx]\n\tret\n\n@get_pop_second_stack:\n\t; return - rax\n\tmov rbx, @second_stack\n\tmov rcx, @stack_size\n\tmov rcx, [rcx]\n\tmov rax, qword [rbx + rcx * 8 - 8]\n\tmov rcx, @stack_size\n\tdec qword [rcx]\n\tret\n";
+const ASM_CODE_BEGIN_WIN64: &str = "section .import\n\textern printf\n\textern scanf\n\textern getchar\n\textern _open\n\textern _read\n\textern _write\n\textern _close\n\textern malloc\n\textern free\n\textern fflush\n\textern exit\n\nsection .data\n\t@int_fmt: db \"%lli \", 0\n\t@bytes_fmt: db \"%.8s\", 0\n\t@newline_fmt: db 10, 0\n\t@int_scan_fmt: db \"%lld\", 0\n\t@read_int_value: dq 0\n\t@line_buffer: times 4096 db 0\n\t@path_buffer: times 4096 db 0\n\t@io_byte: dq 0\n\t@second_stack: times 1024 dq 0\n\t@stack_size: dq 0\n\t@stack_begin: dq 0\n\nsection .text\n\tglobal WinMain\n\nWinMain:\n\tlea rax, [rsp + 8]\n\tmov rbx, @stack_begin\n\tmov qword [rbx], rax\n\tjmp start\n\n@share_to_second_stack:\n\t; arg - rax\n\tmov rbx, @second_stack\n\tmov rcx, @stack_size\n\tmov rcx, [rcx]\n\tmov qword [rbx + rcx * 8], rax\n\tmov rcx, @stack_size\n\tinc qword [rcx]\n\tret\n\n@get_pop_second_stack:\n\t; return - rax\n\tmov rbx, @second_stack\n\tmov rcx, @stack_size\n\tmov rcx, [rcx]\n\tmov rax, qword [rbx + rcx * 8 - 8]\n\tmov rcx, @stack_size\n\tdec qword [rcx]\n\tret\n\n@dump_stack:\n\t; prints the data stack from the bottom to the top\n\tpush r12\n\tpush r13\n\tmov r12, @stack_begin\n\tmov r12, [r12]\n\tlea r13, [rsp + 24]\n@dump_stack_loop:\n\tcmp r12, r13\n\tjbe @dump_stack_end\n\tsub r12, 8\n\tlea rcx, [rel @int_fmt]\n\tmov rdx, [r12]\n\tsub rsp, 32\n\tcall printf\n\tadd rsp, 32\n\tjmp @dump_stack_loop\n@dump_stack_end:\n\tlea rcx, [rel @newline_fmt]\n\tsub rsp, 32\n\tcall printf\n\tadd rsp, 32\n\tpop r13\n\tpop r12\n\tret\n\n@read_line:\n\t; pushes the line as a zero terminated stack string and its length, -1 on EOF\n\tpop r12\n\txor r13, r13\n@read_line_loop:\n\tmov rbp, rsp\n\tand rsp, -16\n\tsub rsp, 32\n\tcall getchar\n\tmov rsp, rbp\n\tcmp eax, -1\n\tje @read_line_eof\n\tcmp eax, 10\n\tje @read_line_done\n\tmov rbx, @line_buffer\n\tmov byte [rbx + r13], al\n\tinc r13\n\tcmp r13, 4096\n\tjb @read_line_loop\n\tjmp @read_line_done\n@read_line_eof:\n\ttest r13, r13\n\tjnz @read_line_done\n\tpush qword -1\n\tjmp r12\n@read_line_done:\n\tpush qword 0\n\tmov rcx, r13\n@read_line_push:\n\ttest rcx, rcx\n\tjz @read_line_end\n\tdec rcx\n\tmov rbx, @line_buffer\n\tmovzx rax, byte [rbx + rcx]\n\tpush rax\n\tjmp @read_line_push\n@read_line_end:\n\tpush r13\n\tjmp r12\n\n\n@pop_path:\n\t; pops a zero terminated stack string into @path_buffer\n\tpop rdx\n\tmov rbx, @path_buffer\n@pop_path_loop:\n\tpop rax\n\tmov byte [rbx], al\n\tinc rbx\n\ttest rax, rax\n\tjnz @pop_path_loop\n\tjmp rdx\n\n@fread_byte:\n\t; fd -> byte, -1 on EOF or error\n\tpop r12\n\tpop rcx\n\tmov rdx, @io_byte\n\tmov qword [rdx], 0\n\tmov r8, 1\n\tmov rbp, rsp\n\tand rsp, -16\n\tsub rsp, 32\n\tcall _read\n\tmov rsp, rbp\n\tmovsxd rax, eax\n\ttest rax, rax\n\tjs @fread_byte_end\n\tmov rax, -1\n\tjz @fread_byte_end\n\tmov rax, @io_byte\n\tmov rax, [rax]\n@fread_byte_end:\n\tpush rax\n\tjmp r12\n\n@fwrite_byte:\n\t; fd byte -> 1, -1 on error. printf`s buffer goes out first, so fd 1 keeps the order\n\tmov rbp, rsp\n\tand rsp, -16\n\tsub rsp, 32\n\txor rcx, rcx\n\tcall fflush\n\tmov rsp, rbp\n\tpop r12\n\tpop rax\n\tmov rdx, @io_byte\n\tmov qword [rdx], rax\n\tpop rcx\n\tmov r8, 1\n\tmov rbp, rsp\n\tand rsp, -16\n\tsub rsp, 32\n\tcall _write\n\tmov rsp, rbp\n\tmovsxd rax, eax\n\tpush rax\n\tjmp r12\n";
+const ASM_CODE_BEGIN_LINUX: &str = "section .import\n\textern printf\n\textern scanf\n\textern getchar\n\textern malloc\n\textern free\n\textern fflush\n\textern exit\n\nsection .data\n\t@int_fmt: db \"%lli \", 0\n\t@bytes_fmt: db \"%.8s\", 0\n\t@newline_fmt: db 10, 0\n\t@int_scan_fmt: db \"%lld\", 0\n\t@read_int_value: dq 0\n\t@line_buffer: times 4096 db 0\n\t@path_buffer: times 4096 db 0\n\t@io_byte: dq 0\n\t\n\t@second_stack: times 1024 dq 0\n\t@stack_size: dq 0\n\t@stack_begin: dq 0\n\nsection .text\n\tglobal main\n\nmain:\n\tlea rax, [rsp + 8]\n\tmov rbx, @stack_begin\n\tmov qword [rbx], rax\n\tjmp start\n\n@share_to_second_stack:\n\t; arg - rax\n\tmov rbx, @second_stack\n\tmov rcx, @stack_size\n\tmov rcx, [rcx]\n\tmov qword [rbx + rcx * 8], rax\n\tmov rcx, @stack_size\n\tinc qword [rcx]\n\tret\n\n@get_pop_second_stack:\n\t; return - rax\n\tmov rbx, @second_stack\n\tmov rcx, @stack_size\n\tmov rcx, [rcx]\n\tmov rax, qword [rbx + rcx * 8 - 8]\n\tmov rcx, @stack_size\n\tdec qword [rcx]\n\tret\n\n@dump_stack:\n\t; prints the data stack from the bottom to the top\n\tpush r12\n\tpush r13\n\tmov r12, @stack_begin\n\tmov r12, [r12]\n\tlea r13, [rsp + 24]\n@dump_stack_loop:\n\tcmp r12, r13\n\tjbe @dump_stack_end\n\tsub r12, 8\n\txor rax, rax\n\tlea rdi, [rel @int_fmt]\n\tmov rsi, [r12]\n\tsub rsp, 32\n\tcall printf\n\tadd rsp, 32\n\tjmp @dump_stack_loop\n@dump_stack_end:\n\txor rax, rax\n\tlea rdi, [rel @newline_fmt]\n\tsub rsp, 32\n\tcall printf\n\tadd rsp, 32\n\tpop r13\n\tpop r12\n\tret\n\n@read_line:\n\t; pushes the line as a zero terminated stack string and its length, -1 on EOF\n\tpop r12\n\txor r13, r13\n@read_line_loop:\n\tmov rbp, rsp\n\tand rsp, -16\n\tsub rsp, 32\n\tcall getchar\n\tmov rsp, rbp\n\tcmp eax, -1\n\tje @read_line_eof\n\tcmp eax, 10\n\tje @read_line_done\n\tmov rbx, @line_buffer\n\tmov byte [rbx + r13], al\n\tinc r13\n\tcmp r13, 4096\n\tjb @read_line_loop\n\tjmp @read_line_done\n@read_line_eof:\n\ttest r13, r13\n\tjnz @read_line_done\n\tpush qword -1\n\tjmp r12\n@read_line_done:\n\tpush qword 0\n\tmov rcx, r13\n@read_line_push:\n\ttest rcx, rcx\n\tjz @read_line_end\n\tdec rcx\n\tmov rbx, @line_buffer\n\tmovzx rax, byte [rbx + rcx]\n\tpush rax\n\tjmp @read_line_push\n@read_line_end:\n\tpush r13\n\tjmp r12\n\n\n@pop_path:\n\t; pops a zero terminated stack string into @path_buffer\n\tpop rdx\n\tmov rbx, @path_buffer\n@pop_path_loop:\n\tpop rax\n\tmov byte [rbx], al\n\tinc rbx\n\ttest rax, rax\n\tjnz @pop_path_loop\n\tjmp rdx\n\n@fread_byte:\n\t; fd -> byte, -1 on EOF, -errno on error\n\tpop r8\n\tpop rdi\n\tmov rsi, @io_byte\n\tmov qword [rsi], 0\n\tmov rdx, 1\n\txor rax, rax\n\tsyscall\n\ttest rax, rax\n\tjs @fread_byte_end\n\tmov rax, -1\n\tjz @fread_byte_end\n\tmov rax, @io_byte\n\tmov rax, [rax]\n@fread_byte_end:\n\tpush rax\n\tjmp r8\n\n@fwrite_byte:\n\t; fd byte -> 1, -errno on error. printf`s buffer goes out first, so fd 1 keeps the order\n\tmov rbp, rsp\n\tand rsp, -16\n\txor rdi, rdi\n\tcall fflush\n\tmov rsp, rbp\n\tpop r8\n\tpop rax\n\tmov rsi, @io_byte\n\tmov qword [rsi], rax\n\tpop rdi\n\tmov rdx, 1\n\tmov rax, 1\n\tsyscall\n\tpush rax\n\tjmp r8\n";
+// the same libc, but mach-o wants the underscores and every call with an aligned stack
+const ASM_CODE_BEGIN_MACOS: &str = "\textern _printf\n\textern _scanf\n\textern _getchar\n\textern _open\n\textern _read\n\textern _write\n\textern _close\n\textern _malloc\n\textern _free\n\textern _fflush\n\textern _exit\n\nsection .data\n\t@int_fmt: db \"%lli \", 0\n\t@bytes_fmt: db \"%.8s\", 0\n\t@newline_fmt: db 10, 0\n\t@int_scan_fmt: db \"%lld\", 0\n\t@read_int_value: dq 0\n\t@line_buffer: times 4096 db 0\n\t@path_buffer: times 4096 db 0\n\t@io_byte: dq 0\n\t\n\t@second_stack: times 1024 dq 0\n\t@stack_size: dq 0\n\t@stack_begin: dq 0\n\nsection .text\n\tglobal _main\n\n_main:\n\tlea rax, [rsp + 8]\n\tmov rbx, @stack_begin\n\tmov qword [rbx], rax\n\tjmp start\n\n@share_to_second_stack:\n\t; arg - rax\n\tmov rbx, @second_stack\n\tmov rcx, @stack_size\n\tmov rcx, [rcx]\n\tmov qword [rbx + rcx * 8], rax\n\tmov rcx, @stack_size\n\tinc qword [rcx]\n\tret\n\n@get_pop_second_stack:\n\t; return - rax\n\tmov rbx, @second_stack\n\tmov rcx, @stack_size\n\tmov rcx, [rcx]\n\tmov rax, qword [rbx + rcx * 8 - 8]\n\tmov rcx, @stack_size\n\tdec qword [rcx]\n\tret\n\n@dump_stack:\n\t; prints the data stack from the bottom to the top\n\tpush r12\n\tpush r13\n\tmov r12, @stack_begin\n\tmov r12, [r12]\n\tlea r13, [rsp + 24]\n@dump_stack_loop:\n\tcmp r12, r13\n\tjbe @dump_stack_end\n\tsub r12, 8\n\txor rax, rax\n\tlea rdi, [rel @int_fmt]\n\tmov rsi, [r12]\n\tmov rbp, rsp\n\tand rsp, -16\n\tcall _printf\n\tmov rsp, rbp\n\tjmp @dump_stack_loop\n@dump_stack_end:\n\txor rax, rax\n\tlea rdi, [rel @newline_fmt]\n\tmov rbp, rsp\n\tand rsp, -16\n\tcall _printf\n\tmov rsp, rbp\n\tpop r13\n\tpop r12\n\tret\n\n@read_line:\n\t; pushes the line as a zero terminated stack string and its length, -1 on EOF\n\tpop r12\n\txor r13, r13\n@read_line_loop:\n\tmov rbp, rsp\n\tand rsp, -16\n\tcall _getchar\n\tmov rsp, rbp\n\tcmp eax, -1\n\tje @read_line_eof\n\tcmp eax, 10\n\tje @read_line_done\n\tmov rbx, @line_buffer\n\tmov byte [rbx + r13], al\n\tinc r13\n\tcmp r13, 4096\n\tjb @read_line_loop\n\tjmp @read_line_done\n@read_line_eof:\n\ttest r13, r13\n\tjnz @read_line_done\n\tpush qword -1\n\tjmp r12\n@read_line_done:\n\tpush qword 0\n\tmov rcx, r13\n@read_line_push:\n\ttest rcx, rcx\n\tjz @read_line_end\n\tdec rcx\n\tmov rbx, @line_buffer\n\tmovzx rax, byte [rbx + rcx]\n\tpush rax\n\tjmp @read_line_push\n@read_line_end:\n\tpush r13\n\tjmp r12\n\n\n@pop_path:\n\t; pops a zero terminated stack string into @path_buffer\n\tpop rdx\n\tmov rbx, @path_buffer\n@pop_path_loop:\n\tpop rax\n\tmov byte [rbx], al\n\tinc rbx\n\ttest rax, rax\n\tjnz @pop_path_loop\n\tjmp rdx\n\n@fread_byte:\n\t; fd -> byte, -1 on EOF or error\n\tpop r12\n\tpop rdi\n\tmov rsi, @io_byte\n\tmov qword [rsi], 0\n\tmov rdx, 1\n\tmov rbp, rsp\n\tand rsp, -16\n\tcall _read\n\tmov rsp, rbp\n\ttest rax, rax\n\tjs @fread_byte_end\n\tmov rax, -1\n\tjz @fread_byte_end\n\tmov rax, @io_byte\n\tmov rax, [rax]\n@fread_byte_end:\n\tpush rax\n\tjmp r12\n\n@fwrite_byte:\n\t; fd byte -> 1, -1 on error. printf`s buffer goes out first, so fd 1 keeps the order\n\tmov rbp, rsp\n\tand rsp, -16\n\txor rdi, rdi\n\tcall _fflush\n\tmov rsp, rbp\n\tpop r12\n\tpop rax\n\tmov rsi, @io_byte\n\tmov qword [rsi], rax\n\tpop rdi\n\tmov rdx, 1\n\tmov rbp, rsp\n\tand rsp, -16\n\tcall _write\n\tmov rsp, rbp\n\tpush rax\n\tjmp r12\n";
+// no libc at all: raw syscalls, a hand-written itoa and mmap for the heap
+const ASM_CODE_BEGIN_LINUX_FREESTANDING: &str = "section .data\n\t@newline: db 10\n\t@itoa_buffer: times 31 db 0\n@itoa_buffer_end:\n\tdb 32\n\t@peek_char: dq -2\n\nsection .bss\n\t@stdin_byte: resq 1\n\t@line_buffer: resb 4096\n\t@path_buffer: resb 4096\n\t@io_byte: resq 1\n\t@second_stack: resq 1024\n\t@stack_size: resq 1\n\t@stack_begin: resq 1\n\nsection .text\n\tglobal _start\n\n_start:\n\tlea rax, [rsp + 8]\n\tmov rbx, @stack_begin\n\tmov qword [rbx], rax\n\tjmp start\n\n@share_to_second_stack:\n\t; arg - rax\n\tmov rbx, @second_stack\n\tmov rcx, @stack_size\n\tmov rcx, [rcx]\n\tmov qword [rbx + rcx * 8], rax\n\tmov rcx, @stack_size\n\tinc qword [rcx]\n\tret\n\n@get_pop_second_stack:\n\t; return - rax\n\tmov rbx, @second_stack\n\tmov rcx, @stack_size\n\tmov rcx, [rcx]\n\tmov rax, qword [rbx + rcx * 8 - 8]\n\tmov rcx, @stack_size\n\tdec qword [rcx]\n\tret\n\n@dump_stack:\n\t; prints the data stack from the bottom to the top\n\tpush r12\n\tpush r13\n\tmov r12, @stack_begin\n\tmov r12, [r12]\n\tlea r13, [rsp + 24]\n@dump_stack_loop:\n\tcmp r12, r13\n\tjbe @dump_stack_end\n\tsub r12, 8\n\tmov rax, [r12]\n\tcall @print_int\n\tjmp @dump_stack_loop\n@dump_stack_end:\n\tmov rsi, @newline\n\tmov rdx, 1\n\tmov rdi, 1\n\tmov rax, 1\n\tsyscall\n\tpop r13\n\tpop r12\n\tret\n\n@getchar:\n\t; return - the next byte of stdin, -1 on EOF\n\tmov rax, @peek_char\n\tmov rax, [rax]\n\tcmp rax, -2\n\tje @getchar_read\n\tmov rcx, @peek_char\n\tmov qword [rcx], -2\n\tret\n@getchar_read:\n\tmov rsi, @stdin_byte\n\tmov qword [rsi], 0\n\txor rdi, rdi\n\tmov rdx, 1\n\txor rax, rax\n\tsyscall\n\ttest rax, rax\n\tjle @getchar_eof\n\tmov rax, @stdin_byte\n\tmov rax, [rax]\n\tret\n@getchar_eof:\n\tmov rax, -1\n\tret\n\n@read_int:\n\t; pushes the value and 1, 0 0 if there is no number, 0 -1 on EOF\n\tpop r12\n@read_int_skip:\n\tcall @getchar\n\tcmp rax, -1\n\tje @read_int_eof\n\tcmp rax, 32\n\tje @read_int_skip\n\tcmp rax, 9\n\tjl @read_int_sign\n\tcmp rax, 13\n\tjle @read_int_skip\n@read_int_sign:\n\txor r13, r13\n\txor rbx, rbx\n\tcmp rax, 43\n\tje @read_int_after_sign\n\tcmp rax, 45\n\tjne @read_int_first\n\tmov rbx, 1\n@read_int_after_sign:\n\tcall @getchar\n@read_int_first:\n\tcmp rax, 48\n\tjl @read_int_fail\n\tcmp rax, 57\n\tjg @read_int_fail\n@read_int_digit:\n\tmov rcx, 10\n\timul r13, rcx\n\tsub rax, 48\n\tadd r13, rax\n\tcall @getchar\n\tcmp rax, 48\n\tjl @read_int_done\n\tcmp rax, 57\n\tjle @read_int_digit\n@read_int_done:\n\tmov rcx, @peek_char\n\tmov qword [rcx], rax\n\ttest rbx, rbx\n\tjz @read_int_push\n\tneg r13\n@read_int_push:\n\tpush r13\n\tpush qword 1\n\tjmp r12\n@read_int_fail:\n\tmov rcx, @peek_char\n\tmov qword [rcx], rax\n\tpush qword 0\n\tpush qword 0\n\tjmp r12\n@read_int_eof:\n\tpush qword 0\n\tpush qword -1\n\tjmp r12\n\n@read_line:\n\t; pushes the line as a zero terminated stack string and its length, -1 on EOF\n\tpop r12\n\txor r13, r13\n@read_line_loop:\n\tcall @getchar\n\tcmp rax, -1\n\tje @read_line_eof\n\tcmp rax, 10\n\tje @read_line_done\n\tmov rbx, @line_buffer\n\tmov byte [rbx + r13], al\n\tinc r13\n\tcmp r13, 4096\n\tjb @read_line_loop\n\tjmp @read_line_done\n@read_line_eof:\n\ttest r13, r13\n\tjnz @read_line_done\n\tpush qword -1\n\tjmp r12\n@read_line_done:\n\tpush qword 0\n\tmov rcx, r13\n@read_line_push:\n\ttest rcx, rcx\n\tjz @read_line_end\n\tdec rcx\n\tmov rbx, @line_buffer\n\tmovzx rax, byte [rbx + rcx]\n\tpush rax\n\tjmp @read_line_push\n@read_line_end:\n\tpush r13\n\tjmp r12\n\n@alloc:\n\t; rsi - size, return - pointer to a fresh mmap, 0 on error. the size is kept right before the block\n\tadd rsi, 8\n\tmov rbx, rsi\n\txor rdi, rdi\n\tmov rdx, 3\n\tmov r10, 34\n\tmov r8, -1\n\txor r9, r9\n\tmov rax, 9\n\tsyscall\n\tcmp rax, -4096\n\tja @alloc_error\n\tmov qword [rax], rbx\n\tadd rax, 8\n\tret\n@alloc_error:\n\txor rax, rax\n\tret\n\n@free:\n\t; rdi - pointer from @alloc\n\ttest rdi, rdi\n\tjz @free_end\n\tsub rdi, 8\n\tmov rsi, [rdi]\n\tmov rax, 11\n\tsyscall\n@free_end:\n\tret\n\n@pop_path:\n\t; pops a zero terminated stack string into @path_buffer\n\tpop rdx\n\tmov rbx, @path_buffer\n@pop_path_loop:\n\tpop rax\n\tmov byte [rbx], al\n\tinc rbx\n\ttest rax, rax\n\tjnz @pop_path_loop\n\tjmp rdx\n\n@fread_byte:\n\t; fd -> byte, -1 on EOF, -errno on error\n\tpop r8\n\tpop rdi\n\tmov rsi, @io_byte\n\tmov qword [rsi], 0\n\tmov rdx, 1\n\txor rax, rax\n\tsyscall\n\ttest rax, rax\n\tjs @fread_byte_end\n\tmov rax, -1\n\tjz @fread_byte_end\n\tmov rax, @io_byte\n\tmov rax, [rax]\n@fread_byte_end:\n\tpush rax\n\tjmp r8\n\n@fwrite_byte:\n\t; fd byte -> 1, -errno on error\n\tpop r8\n\tpop rax\n\tmov rsi, @io_byte\n\tmov qword [rsi], rax\n\tpop rdi\n\tmov rdx, 1\n\tmov rax, 1\n\tsyscall\n\tpush rax\n\tjmp r8\n";
+// print, write_raw and exit of the freestanding prelude. the jit brings its own that call back into rust
+const FREESTANDING_SERVICES_ASM: &str = "\n@print_int:\n\t; rax - value, prints it and a space\n\tmov rsi, @itoa_buffer_end\n\tmov r8, rax\n\ttest rax, rax\n\tjns @print_int_loop\n\tneg rax\n@print_int_loop:\n\txor rdx, rdx\n\tmov rcx, 10\n\tdiv rcx\n\tadd rdx, 48\n\tdec rsi\n\tmov byte [rsi], dl\n\ttest rax, rax\n\tjnz @print_int_loop\n\ttest r8, r8\n\tjns @print_int_write\n\tdec rsi\n\tmov byte [rsi], 45\n@print_int_write:\n\tmov rdx, @itoa_buffer_end\n\tsub rdx, rsi\n\tinc rdx\n\tmov rdi, 1\n\tmov rax, 1\n\tsyscall\n\tret\n\n@write_raw:\n\t; rsi - pointer, writes up to 8 bytes until a zero byte\n\txor rdx, rdx\n@write_raw_length:\n\tcmp rdx, 8\n\tje @write_raw_write\n\tcmp byte [rsi + rdx], 0\n\tje @write_raw_write\n\tinc rdx\n\tjmp @write_raw_length\n@write_raw_write:\n\tmov rdi, 1\n\tmov rax, 1\n\tsyscall\n\tret\n\n@exit:\n\t; rdi - exit code\n\tmov rax, 231\n\tsyscall\n";
 // |
 const STACK_HEAD_ASM: &str = "\tpush rsp ; stack_head\n";
+const DEPTH_ASM: &str = "\tmov rax, @stack_begin ; depth\n\tmov rax, [rax]\n\tsub rax, rsp\n\tshr rax, 3\n\tpush rax\n";
+const DUMP_STACK_ASM: &str = "\tcall @dump_stack\n";
+// |
+const READ_CHAR_ASM: &str = "\tmov rbp, rsp ; read_char\n\tand rsp, -16\n\tsub rsp, 32\n\tcall getchar\n\tmov rsp, rbp\n\tmovsxd rax, eax\n\tpush rax\n";
+const READ_LINE_ASM: &str = "\tcall @read_line\n";
+// |
+const FREAD_BYTE_ASM: &str = "\tcall @fread_byte\n";
+const FWRITE_BYTE_ASM: &str = "\tcall @fwrite_byte\n";
 // |
 const READ_FROM_ASM: &str = "\tpop rax ; read_from\n\tpush qword [rax]\n";
 const WRITE_TO_ASM: &str = "\tpop rax ; write_to\n\tpop rbx\n\tmov qword [rbx], rax\n";
+const READ_U8_ASM: &str = "\tpop rax ; read_u8\n\tmovzx rax, byte [rax]\n\tpush rax\n";
+const READ_I8_ASM: &str = "\tpop rax ; read_i8\n\tmovsx rax, byte [rax]\n\tpush rax\n";
+const READ_U16_ASM: &str = "\tpop rax ; read_u16\n\tmovzx rax, word [rax]\n\tpush rax\n";
+const READ_I16_ASM: &str = "\tpop rax ; read_i16\n\tmovsx rax, word [rax]\n\tpush rax\n";
+const READ_U32_ASM: &str = "\tpop rax ; read_u32\n\tmov eax, dword [rax]\n\tpush rax\n";
+const READ_I32_ASM: &str = "\tpop rax ; read_i32\n\tmovsxd rax, dword [rax]\n\tpush rax\n";
+const WRITE_U8_ASM: &str = "\tpop rax ; write_u8\n\tpop rbx\n\tmov byte [rbx], al\n";
+const WRITE_U16_ASM: &str = "\tpop rax ; write_u16\n\tpop rbx\n\tmov word [rbx], ax\n";
+const WRITE_U32_ASM: &str = "\tpop rax ; write_u32\n\tpop rbx\n\tmov dword [rbx], eax\n";
 // |
 const DUP_ASM: &str = "\tpush qword [rsp] ; dup\n";
 const POP_ASM: &str = "\tadd rsp, 8\n";
@@ -66,29 +103,197 @@ const SUM_ASM: &str = "\tpop rax ; sum\n\tadd qword [rsp], rax\n";
 const DIF_ASM: &str = "\tpop rax ; dif\n\tsub qword [rsp], rax\n";
 // |
 const MUL_ASM: &str = "\tpop rax ; mul\n\tpop rbx\n\tmul rbx\n\tpush rax\n";
-const DIV_ASM: &str = "\tpop rbx ; div\n\tpop rax\n\tdiv rbx\n\tpush rax\n";
+const DIV_ASM: &str = "\tpop rbx ; div\n\tpop rax\n\tcqo\n\tidiv rbx\n\tpush rax\n";
 // |
 const PRINT_ASM_WIN64: &str = "\tlea rcx, [rel @int_fmt] ; print\n\tpop rdx\n\tsub rsp, 32\n\tcall printf\n\tadd rsp, 32\n";
 const WRITE_RAW_ASM_WIN64: &str = "\tlea rcx, [rel @bytes_fmt] ; write\n\tmov rdx, rsp\n\tsub rsp, 56\n\tcall printf\n\tadd rsp, 64\n"; // "sub rsp, 56" = "add rsp, 8"(pop) + "sub rsp, 64"
+const READ_INT_ASM_WIN64: &str = "\tmov rdx, @read_int_value ; read_int\n\tmov qword [rdx], 0\n\tlea rcx, [rel @int_scan_fmt]\n\tmov rbp, rsp\n\tand rsp, -16\n\tsub rsp, 32\n\tcall scanf\n\tmov rsp, rbp\n\tmovsxd rax, eax\n\tmov rbx, @read_int_value\n\tpush qword [rbx]\n\tpush rax\n";
+const ALLOC_ASM_WIN64: &str = "\tpop rcx ; alloc\n\tmov rbp, rsp\n\tand rsp, -16\n\tsub rsp, 32\n\tcall malloc\n\tmov rsp, rbp\n\tpush rax\n";
+const FREE_ASM_WIN64: &str = "\tpop rcx ; free\n\tmov rbp, rsp\n\tand rsp, -16\n\tsub rsp, 32\n\tcall free\n\tmov rsp, rbp\n";
+const FCLOSE_ASM_WIN64: &str = "\tpop rcx ; fclose\n\tmov rbp, rsp\n\tand rsp, -16\n\tsub rsp, 32\n\tcall _close\n\tmov rsp, rbp\n\tmovsxd rax, eax\n\tpush rax\n";
 const EXIT_ASM_WIN64: &str = "\tpop rcx\n\tcall exit\n";
 const SUCCESFUL_EXIT_ASM_WIN64: &str = "\tadd rsp, 32\n\txor rcx, rcx\n\tcall exit\n";
 // |
 const PRINT_ASM_LINUX: &str = "\txor rax, rax ; print\n\tlea rdi, [rel @int_fmt]\n\tpop rsi\n\tsub rsp, 32\n\tcall printf\n\tadd rsp, 32\n";
-const WRITE_RAW_ASM_LINUX: &str = "\txor rax, rax ; print\n\tlea rdi, [rel @bytes_fmt]\n\tpop rsi\n\tsub rsp, 56\n\tcall printf\n\tadd rsp, 64\n"; // "sub rsp, 56" = "add rsp, 8"(pop) + "sub rsp, 64"
+const WRITE_RAW_ASM_LINUX: &str = "\txor rax, rax ; write\n\tlea rdi, [rel @bytes_fmt]\n\tmov rsi, rsp\n\tsub rsp, 56\n\tcall printf\n\tadd rsp, 64\n"; // "sub rsp, 56" = "add rsp, 8"(pop) + "sub rsp, 64"
+const READ_INT_ASM_LINUX: &str = "\tmov rsi, @read_int_value ; read_int\n\tmov qword [rsi], 0\n\txor rax, rax\n\tlea rdi, [rel @int_scan_fmt]\n\tmov rbp, rsp\n\tand rsp, -16\n\tsub rsp, 32\n\tcall scanf\n\tmov rsp, rbp\n\tmovsxd rax, eax\n\tmov rbx, @read_int_value\n\tpush qword [rbx]\n\tpush rax\n";
+const ALLOC_ASM_LINUX: &str = "\tpop rdi ; alloc\n\tmov rbp, rsp\n\tand rsp, -16\n\tcall malloc\n\tmov rsp, rbp\n\tpush rax\n";
+const FREE_ASM_LINUX: &str = "\tpop rdi ; free\n\tmov rbp, rsp\n\tand rsp, -16\n\tcall free\n\tmov rsp, rbp\n";
+const FCLOSE_ASM_LINUX: &str = "\tpop rdi ; fclose\n\tmov rax, 3\n\tsyscall\n\tpush rax\n";
 const EXIT_ASM_LINUX: &str = "\tpop rdi\n\tcall exit\n";
 const SUCCESFUL_EXIT_ASM_LINUX: &str = "\txor rdi, rdi\n\tcall exit\n";
+// |
+const PRINT_ASM_MACOS: &str = "\txor rax, rax ; print\n\tlea rdi, [rel @int_fmt]\n\tpop rsi\n\tmov rbp, rsp\n\tand rsp, -16\n\tcall _printf\n\tmov rsp, rbp\n";
+const WRITE_RAW_ASM_MACOS: &str = "\txor rax, rax ; write\n\tlea rdi, [rel @bytes_fmt]\n\tmov rsi, rsp\n\tmov rbp, rsp\n\tand rsp, -16\n\tcall _printf\n\tmov rsp, rbp\n\tadd rsp, 8\n";
+const READ_INT_ASM_MACOS: &str = "\tmov rsi, @read_int_value ; read_int\n\tmov qword [rsi], 0\n\txor rax, rax\n\tlea rdi, [rel @int_scan_fmt]\n\tmov rbp, rsp\n\tand rsp, -16\n\tcall _scanf\n\tmov rsp, rbp\n\tmovsxd rax, eax\n\tmov rbx, @read_int_value\n\tpush qword [rbx]\n\tpush rax\n";
+const READ_CHAR_ASM_MACOS: &str = "\tmov rbp, rsp ; read_char\n\tand rsp, -16\n\tcall _getchar\n\tmov rsp, rbp\n\tmovsxd rax, eax\n\tpush rax\n";
+const ALLOC_ASM_MACOS: &str = "\tpop rdi ; alloc\n\tmov rbp, rsp\n\tand rsp, -16\n\tcall _malloc\n\tmov rsp, rbp\n\tpush rax\n";
+const FREE_ASM_MACOS: &str = "\tpop rdi ; free\n\tmov rbp, rsp\n\tand rsp, -16\n\tcall _free\n\tmov rsp, rbp\n";
+const FCLOSE_ASM_MACOS: &str = "\tpop rdi ; fclose\n\tmov rbp, rsp\n\tand rsp, -16\n\tcall _close\n\tmov rsp, rbp\n\tmovsxd rax, eax\n\tpush rax\n";
+const EXIT_ASM_MACOS: &str = "\tpop rdi\n\tand rsp, -16\n\tcall _exit\n";
+const SUCCESFUL_EXIT_ASM_MACOS: &str = "\txor rdi, rdi\n\tand rsp, -16\n\tcall _exit\n";
+
+const PRINT_ASM_FREESTANDING: &str = "\tpop rax ; print\n\tcall @print_int\n";
+const WRITE_RAW_ASM_FREESTANDING: &str = "\tmov rsi, rsp ; write\n\tcall @write_raw\n\tadd rsp, 8\n";
+const READ_INT_ASM_FREESTANDING: &str = "\tcall @read_int\n";
+const READ_CHAR_ASM_FREESTANDING: &str = "\tcall @getchar ; read_char\n\tpush rax\n";
+const ALLOC_ASM_FREESTANDING: &str = "\tpop rsi ; alloc\n\tcall @alloc\n\tpush rax\n";
+const FREE_ASM_FREESTANDING: &str = "\tpop rdi ; free\n\tcall @free\n";
+const EXIT_ASM_FREESTANDING: &str = "\tpop rdi\n\tcall @exit\n";
+const SUCCESFUL_EXIT_ASM_FREESTANDING: &str = "\txor rdi, rdi\n\tcall @exit\n";
+
+// calling conventions
+// second stack: rsp is the data stack, return addresses are moved to @second_stack
+const SECOND_STACK_PROLOGUE_ASM: &str = "\tpop rax\n\tcall @share_to_second_stack\n";
+const SECOND_STACK_EPILOGUE_ASM: &str = "\tcall @get_pop_second_stack\n\tpush rax\n\tret\n\tpop rax\n";
+// native: rsp and r15 are swapped around every call, so call/ret work with the return stack and the primitives work with the data stack as usual
+const NATIVE_PROLOGUE_ASM: &str = "\txchg rsp, r15\n";
+const NATIVE_EPILOGUE_ASM: &str = "\txchg rsp, r15\n\tret\n";
+const NATIVE_ENTRY_ASM: &str = "\tpop rax\n\tmov r15, rsp\n\tmov rsp, @return_stack_end\n\tcall start\n";
+const NATIVE_RETURN_STACK_ASM: &str = "\nsection .bss\n\t@return_stack: resq 65536\n@return_stack_end:\n";
+
+// c::compile, llvm::compile, wat::compile and aarch64::compile: states, template arguments, functions, exports, data, variables -> source
+type SourceBackend = fn(&[State], &[Vec<String>], &[&State], &[&str], &[&(String, Vec<u8>)], &[&String]) -> String;
+
+#[derive(Clone, Copy, PartialEq)]
+enum CallingConvention {
+    SecondStack,
+    Native,
+}
+// what the x86_64 asm is written for: the prelude, the libc calling convention, the entry symbol and the object format
+#[derive(Clone, Copy, PartialEq)]
+enum Platform {
+    Linux,
+    Win64,
+    MacOS,
+}
+impl Platform {
+    fn host() -> Platform {
+        if cfg!(target_os = "windows") {
+            Platform::Win64
+        } else if cfg!(target_os = "macos") {
+            Platform::MacOS
+        } else {
+            Platform::Linux
+        }
+    }
+}
+struct CompileOptions {
+    calling_convention: CallingConvention,
+    register_cache: bool,
+    freestanding: bool, // syscalls instead of libc. linux only
+    platform: Platform,
+}
 
+// the top cells of the data stack that live in registers instead of memory (the last one is the top of the stack).
+// it is spilled (pushed back) before everything that needs the real stack: calls, branches, labels, stack_head, libc...
+const CACHE_REGISTERS: [&str; 2] = ["r8", "r9"];
+#[derive(Default)]
+struct RegisterCache {
+    cells: Vec<&'static str>,
+}
+impl RegisterCache {
+    fn free_register(&self) -> &'static str {
+        CACHE_REGISTERS.iter().find(|x| !self.cells.contains(x)).expect("no free cache register")
+    }
+    // a register for a new top cell. spills the bottom cached cell if every register is busy
+    fn push(&mut self, out: &mut String) -> &'static str {
+        if self.cells.len() == CACHE_REGISTERS.len() {
+            *out += format!("\tpush {}\n", self.cells.remove(0)).as_str();
+        }
+        let register = self.free_register();
+        self.cells.push(register);
+        register
+    }
+    // loads cells from memory until at least `count` top cells are cached
+    fn ensure(&mut self, count: usize, out: &mut String) {
+        while self.cells.len() < count {
+            let register = self.free_register();
+            *out += format!("\tpop {}\n", register).as_str();
+            self.cells.insert(0, register);
+        }
+    }
+    fn top(&self, index: usize) -> &'static str {
+        self.cells[self.cells.len() - 1 - index]
+    }
+    fn spill(&mut self) -> String {
+        let out = self.cells.iter().map(|x| format!("\tpush {}\n", x)).collect();
+        self.cells.clear();
+        out
+    }
+}
+
+fn generate_call_asm(name: &str, options: &CompileOptions) -> String {
+    match options.calling_convention {
+        CallingConvention::SecondStack => format!("\tcall {}\n", name),
+        CallingConvention::Native => format!("\txchg rsp, r15\n\tcall {}\n\txchg rsp, r15\n", name),
+    }
+}
 fn generate_swap_asm(first_argument: usize, second_argument: usize) -> String {
     format!("\tmov rax, qword [rsp + {first}] ; swap<{first_argument},{second_argument}>\n\tmov rbx, qword [rsp + {second}]\n\tmov qword [rsp + {first}], rbx\n\tmov qword [rsp + {second}], rax\n", first = first_argument * 8,  second = second_argument * 8)
 }
 
+// fopen<r>, fopen<w>, fopen<a> and the same for fopen_ptr, which takes the path as a pointer to a zero terminated string
+fn generate_fopen_asm(mode: &str, from_pointer: bool, options: &CompileOptions) -> String {
+    let path_register = if options.platform == Platform::Win64 { "rcx" } else { "rdi" };
+    let path = if from_pointer {
+        format!("\tpop {path_register} ; fopen_ptr<{mode}>\n")
+    } else {
+        format!("\tcall @pop_path ; fopen<{mode}>\n\tmov {path_register}, @path_buffer\n")
+    };
+    if options.platform == Platform::Win64 {
+        // _O_BINARY | ...
+        let flags = match mode {
+            "r" => 0x8000,
+            "w" => 0x8000 | 0x0301,
+            "a" => 0x8000 | 0x0109,
+            _ => panic!("unknown fopen mode \"{}\". possible values: r | w | a", mode),
+        };
+        format!("{path}\tmov rdx, {flags}\n\tmov r8, 420\n\tmov rbp, rsp\n\tand rsp, -16\n\tsub rsp, 32\n\tcall _open\n\tmov rsp, rbp\n\tmovsxd rax, eax\n\tpush rax\n")
+    } else if options.platform == Platform::MacOS {
+        let flags = match mode {
+            "r" => 0,
+            "w" => 1 | 0x200 | 0x400, // O_WRONLY | O_CREAT | O_TRUNC
+            "a" => 1 | 0x200 | 8,     // O_WRONLY | O_CREAT | O_APPEND
+            _ => panic!("unknown fopen mode \"{}\". possible values: r | w | a", mode),
+        };
+        format!("{path}\tmov rsi, {flags}\n\tmov rdx, 420\n\txor rax, rax\n\tmov rbp, rsp\n\tand rsp, -16\n\tcall _open\n\tmov rsp, rbp\n\tmovsxd rax, eax\n\tpush rax\n")
+    } else {
+        let flags = match mode {
+            "r" => 0,
+            "w" => 1 | 64 | 512,   // O_WRONLY | O_CREAT | O_TRUNC
+            "a" => 1 | 64 | 1024,  // O_WRONLY | O_CREAT | O_APPEND
+            _ => panic!("unknown fopen mode \"{}\". possible values: r | w | a", mode),
+        };
+        format!("{path}\tmov rax, 2\n\tmov rsi, {flags}\n\tmov rdx, 420\n\tsyscall\n\tpush rax\n")
+    }
+}
+
 #[derive(Eq, PartialEq)]
 #[derive(Hash)]
+#[derive(Default)]
 enum StateType {
     Integer,
+    Data,
+    Var,
+    VarFetch,
+    VarStore,
     StackHead,
+    Depth,
+    DumpStack,
     ReadFrom,
     WriteTo,
+    ReadU8,
+    ReadI8,
+    ReadU16,
+    ReadI16,
+    ReadU32,
+    ReadI32,
+    WriteU8,
+    WriteU16,
+    WriteU32,
+    Alloc,
+    Free,
     Eq,
     Neq,
     More,
@@ -103,31 +308,58 @@ enum StateType {
     Div,
     If,
     Else,
+    Then,
+    While,
+    Do,
+    Times,
+    End,
+    Return,
     Swap,
     SelfCall,
     SelfGoto,
     Print,
     WriteRaw,
+    ReadInt,
+    ReadChar,
+    ReadLine,
+    Fopen,
+    FopenPtr,
+    FreadByte,
+    FwriteByte,
+    Fclose,
     Exit,
+    #[default]
     Additional,
 }
 
 type StackValueType = i64;
-// const STACK_VALUE_SIZE: usize = size_of::<StackValueType>();
+const STACK_VALUE_SIZE: usize = size_of::<StackValueType>();
 const STACK_VALUE_SIZE_AS_SV: StackValueType = size_of::<StackValueType>() as StackValueType;
 
-impl Default for StateType {
-    fn default() -> Self {
-        StateType::Additional
-    }
-}
 impl Clone for StateType {
     fn clone(&self) -> Self {
         match *self {
             StateType::Integer =>   StateType::Integer,
+            StateType::Data =>      StateType::Data,
+            StateType::Var =>       StateType::Var,
+            StateType::VarFetch =>  StateType::VarFetch,
+            StateType::VarStore =>  StateType::VarStore,
             StateType::StackHead => StateType::StackHead,
+            StateType::Depth =>     StateType::Depth,
+            StateType::DumpStack => StateType::DumpStack,
             StateType::ReadFrom =>  StateType::ReadFrom,
             StateType::WriteTo =>   StateType::WriteTo,
+            StateType::ReadU8 =>    StateType::ReadU8,
+            StateType::ReadI8 =>    StateType::ReadI8,
+            StateType::ReadU16 =>   StateType::ReadU16,
+            StateType::ReadI16 =>   StateType::ReadI16,
+            StateType::ReadU32 =>   StateType::ReadU32,
+            StateType::ReadI32 =>   StateType::ReadI32,
+            StateType::WriteU8 =>   StateType::WriteU8,
+            StateType::WriteU16 =>  StateType::WriteU16,
+            StateType::WriteU32 =>  StateType::WriteU32,
+            StateType::Alloc =>     StateType::Alloc,
+            StateType::Free =>      StateType::Free,
             StateType::Eq =>        StateType::Eq,
             StateType::Neq =>       StateType::Neq,
             StateType::More =>      StateType::More,
@@ -142,11 +374,25 @@ impl Clone for StateType {
             StateType::Div =>       StateType::Div,
             StateType::If =>        StateType::If,
             StateType::Else =>      StateType::Else,
+            StateType::Then =>      StateType::Then,
+            StateType::While =>     StateType::While,
+            StateType::Do =>        StateType::Do,
+            StateType::Times =>     StateType::Times,
+            StateType::End =>       StateType::End,
+            StateType::Return =>    StateType::Return,
             StateType::Swap   =>    StateType::Swap,
             StateType::SelfCall =>  StateType::SelfCall,
             StateType::SelfGoto =>  StateType::SelfGoto,
             StateType::Print =>     StateType::Print,
             StateType::WriteRaw =>  StateType::WriteRaw,
+            StateType::ReadInt =>   StateType::ReadInt,
+            StateType::ReadChar =>  StateType::ReadChar,
+            StateType::ReadLine =>  StateType::ReadLine,
+            StateType::Fopen =>     StateType::Fopen,
+            StateType::FopenPtr =>  StateType::FopenPtr,
+            StateType::FreadByte => StateType::FreadByte,
+            StateType::FwriteByte =>StateType::FwriteByte,
+            StateType::Fclose =>    StateType::Fclose,
             StateType::Exit =>      StateType::Exit,
             StateType::Additional =>StateType::Additional,
         }
@@ -159,35 +405,328 @@ struct State {
     name: String,
     deps: Vec<usize>, // deps id`s
     state_type: StateType,
-    inlinable: bool, // inlinable states cannot contain 'if', 'else', '__self__', '__self__goto__'
+    inlinable: bool, // inlinable states are compiled right into their callers. see is_inlinable
     template_arguments_index: usize,
 }
 
+// simulated heap. addresses start from HEAP_BEGIN and never overlap the stack_head values
+const HEAP_BEGIN: StackValueType = 0x10000000;
+const HEAP_BLOCK_GAP: StackValueType = 16; // free space between blocks, so the overflows don`t land in the next block
+
+struct HeapBlock {
+    data: Vec<u8>,
+    freed: bool, // freed blocks are kept to catch double free and use after free
+    is_data: bool, // `data` blocks cannot be freed
+}
+
+// everything the interpreter keeps besides the stack
+#[derive(Default)]
+struct InterpreterContext {
+    files: HashMap<StackValueType, fs::File>, // fd -> file. 0, 1 and 2 are stdin, stdout and stderr
+    heap: BTreeMap<StackValueType, HeapBlock>, // address -> block
+    heap_end: StackValueType,
+    data_addresses: HashMap<String, StackValueType>, // data name -> address
+}
+
+impl InterpreterContext {
+    fn alloc(&mut self, size: StackValueType) -> StackValueType {
+        assert!(size >= 0, "negative size on alloc");
+        let address = self.heap_end.max(HEAP_BEGIN);
+        self.heap_end = address + (size + HEAP_BLOCK_GAP + 15) / 16 * 16;
+        self.heap.insert(address, HeapBlock{ data: vec![0; size as usize], freed: false, is_data: false });
+        address
+    }
+    fn add_data(&mut self, name: &str, bytes: &[u8]) {
+        let address = self.alloc(bytes.len() as StackValueType);
+        let block = self.heap.get_mut(&address).unwrap();
+        block.data.copy_from_slice(bytes);
+        block.is_data = true;
+        self.data_addresses.insert(name.to_string(), address);
+    }
+    fn free(&mut self, address: StackValueType) {
+        if address == 0 {
+            return;
+        }
+        match self.heap.get_mut(&address) {
+            Some(block) if block.freed => panic!("double free of {:#x}", address),
+            Some(block) if block.is_data => panic!("free of {:#x}, which is a data block", address),
+            Some(block) => block.freed = true,
+            None => panic!("free of {:#x}, which was not returned by alloc", address),
+        }
+    }
+    // fopen<mode>. pushes the fd or -errno
+    fn fopen(&mut self, path: &str, mode: &str) -> StackValueType {
+        let mut options = fs::OpenOptions::new();
+        match mode {
+            "r" => options.read(true),
+            "w" => options.write(true).create(true).truncate(true),
+            "a" => options.append(true).create(true),
+            _ => panic!("unknown fopen mode \"{}\". possible values: r | w | a", mode),
+        };
+        match options.open(path) {
+            Ok(file) => {
+                let fd = (3..).find(|x| !self.files.contains_key(x)).expect("too many open files");
+                self.files.insert(fd, file);
+                fd
+            }
+            Err(error) => io_error_code(&error),
+        }
+    }
+    fn fread_byte(&mut self, fd: StackValueType) -> StackValueType {
+        let mut byte = [0u8; 1];
+        let result = match fd {
+            0 => {
+                io::stdout().flush().expect("error on fread_byte");
+                Ok(read_stdin_byte().map_or(0, |x| { byte[0] = x; 1 }))
+            }
+            _ => match self.files.get_mut(&fd) {
+                Some(file) => io::Read::read(file, &mut byte),
+                None => Err(io::Error::from_raw_os_error(9)), // EBADF
+            },
+        };
+        match result {
+            Ok(0) => -1,
+            Ok(_) => byte[0] as StackValueType,
+            Err(error) => io_error_code(&error),
+        }
+    }
+    fn fwrite_byte(&mut self, fd: StackValueType, byte: StackValueType) -> StackValueType {
+        let byte = [byte as u8];
+        let result = match fd {
+            1 => io::stdout().write(&byte),
+            2 => io::stderr().write(&byte),
+            _ => match self.files.get_mut(&fd) {
+                Some(file) => file.write(&byte),
+                None => Err(io::Error::from_raw_os_error(9)), // EBADF
+            },
+        };
+        result.map_or_else(|error| io_error_code(&error), |x| x as StackValueType)
+    }
+    fn fclose(&mut self, fd: StackValueType) -> StackValueType {
+        if self.files.remove(&fd).is_some() { 0 } else { -9 } // EBADF
+    }
+    // a zero terminated string in alloc`ed or data memory
+    fn c_string(&mut self, address: StackValueType, operation: &str) -> String {
+        let mut bytes = Vec::new();
+        loop {
+            let byte = self.memory(address + bytes.len() as StackValueType, 1, operation)[0];
+            if byte == 0 {
+                break;
+            }
+            bytes.push(byte);
+        }
+        String::from_utf8_lossy(&bytes).to_string()
+    }
+    // checks that [address, address + size) lies in a live block
+    fn memory(&mut self, address: StackValueType, size: usize, operation: &str) -> &mut [u8] {
+        let (begin, block) = self.heap.range_mut(..=address).next_back()
+            .unwrap_or_else(|| panic!("{} at {:#x}, which is not a heap address. only alloc`ed memory is allowed in interpriter mode", operation, address));
+        let offset = (address - begin) as usize;
+        if block.freed {
+            panic!("{} at {:#x}, which is already freed", operation, address);
+        }
+        if offset + size > block.data.len() {
+            panic!("out of bounds {} at {:#x}. the block {:#x} is {} bytes long", operation, address, begin, block.data.len());
+        }
+        &mut block.data[offset..offset + size]
+    }
+}
+
+fn io_error_code(error: &io::Error) -> StackValueType {
+    -(error.raw_os_error().unwrap_or(1) as StackValueType)
+}
+
 fn numeric_state(str: String) -> State {
     State{name: str, state_type: StateType::Integer, deps: Vec::<usize>::new(), inlinable: false, template_arguments_index: 0 }
 }
-fn separate_template_and_name(str: &String) -> (String, String) {
-    if let Some(start_pos) = str.bytes().enumerate().position(|x| x.1 == '<' as u8) {
+// splits the code by whitespaces. string literals ("hello world") are kept as one token, quotes included.
+// a quote inside /* ... */ is just a character, the comment is plain text
+fn tokenize(code: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = code.chars().peekable();
+    let mut in_comment = false;
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        let mut token = c.to_string();
+        if c == '"' && !in_comment {
+            loop {
+                let c = chars.next().expect("unterminated string literal");
+                token.push(c);
+                if c == '\\' {
+                    token.push(chars.next().expect("unterminated string literal"));
+                } else if c == '"' {
+                    break;
+                }
+            }
+        } else {
+            while let Some(c) = chars.next_if(|x| !x.is_whitespace()) {
+                token.push(c);
+            }
+        }
+        if in_comment {
+            in_comment = token != "*/";
+        } else {
+            in_comment = token == "/*";
+        }
+        tokens.push(token);
+    }
+    tokens
+}
+// "hello\n" -> hello + line feed + zero byte
+fn parse_string_literal(token: &str) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut chars = token[1..token.len() - 1].chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next().expect("unterminated escape sequence") {
+                'n' => bytes.push(b'\n'),
+                't' => bytes.push(b'\t'),
+                'r' => bytes.push(b'\r'),
+                '0' => bytes.push(0),
+                '\\' => bytes.push(b'\\'),
+                '"' => bytes.push(b'"'),
+                x => panic!("unknown escape sequence \\{}", x),
+            }
+        } else {
+            let mut buffer = [0u8; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+        }
+    }
+    bytes.push(0);
+    bytes
+}
+// data and var names go to the asm labels as is, so everything except [A-Za-z0-9_] is escaped
+fn asm_label_name(name: &str) -> String {
+    name.chars().map(|x| if x.is_ascii_alphanumeric() || x == '_' { x.to_string() } else { format!("_{:x}_", x as u32) }).collect()
+}
+fn separate_template_and_name(str: &str) -> (String, String) {
+    if let Some(start_pos) = str.bytes().enumerate().position(|x| x.1 == b'<') {
        (str[..start_pos].to_string(), str[start_pos + 1..str.len() - 1].to_string())
     } else  {
-        (str.clone(), "".to_string())
+        (str.to_string(), "".to_string())
     }
     
 }
-fn execute_statement(states: &Vec<State>, template_arguments: &Vec<Vec<String>>, state: &State, stack: &mut Vec::<StackValueType>) -> Option<()> {
+fn peek_stdin_byte() -> Option<u8> {
+    io::stdin().lock().fill_buf().ok()?.first().copied()
+}
+fn read_stdin_byte() -> Option<u8> {
+    let byte = peek_stdin_byte()?;
+    io::stdin().lock().consume(1);
+    Some(byte)
+}
+// same as scanf("%lld"): returns value and status. 1 - ok, 0 - not a number, -1 - EOF
+fn read_stdin_int() -> (StackValueType, StackValueType) {
+    io::stdout().flush().expect("error on read_int");
+    while peek_stdin_byte().is_some_and(|x| x.is_ascii_whitespace()) {
+        read_stdin_byte();
+    }
+    let mut sign = 1;
+    match peek_stdin_byte() {
+        None => return (0, -1),
+        Some(b'-') => { sign = -1; read_stdin_byte(); },
+        Some(b'+') => { read_stdin_byte(); },
+        _ => { },
+    }
+    let mut value: StackValueType = 0;
+    let mut digits = 0;
+    while let Some(digit) = peek_stdin_byte().filter(|x| x.is_ascii_digit()) {
+        value = value.wrapping_mul(10).wrapping_add((digit - b'0') as StackValueType);
+        digits += 1;
+        read_stdin_byte();
+    }
+    if digits == 0 {
+        (0, 0)
+    } else {
+        (value.wrapping_mul(sign), 1) // -9223372036854775808 has no positive counterpart, so it wraps into itself
+    }
+}
+// pops a zero terminated stack string. the first character is on the top
+fn pop_stack_string(stack: &mut Vec::<StackValueType>) -> Option<String> {
+    let mut bytes = Vec::new();
+    loop {
+        let value = stack.pop()?;
+        if value == 0 {
+            break;
+        }
+        bytes.push(value as u8);
+    }
+    Some(String::from_utf8_lossy(&bytes).to_string())
+}
+fn execute_statement<'a>(states: &'a Vec<State>, template_arguments: &Vec<Vec<String>>, state: &'a State, stack: &mut Vec::<StackValueType>, context: &mut InterpreterContext) -> Option<()> {
     match state.state_type {
         StateType::Integer => {
             stack.push(state.name.parse::<StackValueType>().ok()?);
         }
+        StateType::Data => {
+            stack.push(*context.data_addresses.get(&state.name).expect("invalid data name"));
+        }
+        StateType::Var => {
+            stack.push(*context.data_addresses.get(&state.name).expect("invalid var name"));
+        }
+        StateType::VarFetch => {
+            let address = *context.data_addresses.get(&state.name[1..]).expect("invalid var name");
+            let bytes = context.memory(address, STACK_VALUE_SIZE, &state.name);
+            stack.push(StackValueType::from_le_bytes(bytes.try_into().ok()?));
+        }
+        StateType::VarStore => {
+            let value = stack.pop().unwrap_or_else(|| panic!("stack is empty on {}", state.name));
+            let address = *context.data_addresses.get(&state.name[1..]).expect("invalid var name");
+            context.memory(address, STACK_VALUE_SIZE, &state.name).copy_from_slice(&value.to_le_bytes());
+        }
         StateType::StackHead => {
             let stack_len = stack.len();
             stack.push((stack_len as StackValueType) * STACK_VALUE_SIZE_AS_SV);
         }
+        StateType::Depth => {
+            stack.push(stack.len() as StackValueType);
+        }
+        StateType::DumpStack => {
+            for value in stack.iter() {
+                print!("{} ", value);
+            }
+            println!();
+        }
         StateType::ReadFrom => {
-            panic!("read_from not allowed in interpriter mode");
+            let address = stack.pop().expect("stack is empty on read_from");
+            let bytes = context.memory(address, STACK_VALUE_SIZE, "read_from");
+            stack.push(StackValueType::from_le_bytes(bytes.try_into().ok()?));
         }
         StateType::WriteTo => {
-            panic!("write_to not allowed in interpriter mode");
+            let value = stack.pop().expect("stack is empty on write_to");
+            let address = stack.pop().expect("stack is empty on write_to");
+            context.memory(address, STACK_VALUE_SIZE, "write_to").copy_from_slice(&value.to_le_bytes());
+        }
+        StateType::ReadU8 | StateType::ReadI8 | StateType::ReadU16 | StateType::ReadI16 | StateType::ReadU32 | StateType::ReadI32 => {
+            let address = stack.pop().expect("stack is empty on read");
+            let value = match state.state_type {
+                StateType::ReadU8 =>  u8::from_le_bytes(context.memory(address, 1, "read_u8").try_into().ok()?) as StackValueType,
+                StateType::ReadI8 =>  i8::from_le_bytes(context.memory(address, 1, "read_i8").try_into().ok()?) as StackValueType,
+                StateType::ReadU16 => u16::from_le_bytes(context.memory(address, 2, "read_u16").try_into().ok()?) as StackValueType,
+                StateType::ReadI16 => i16::from_le_bytes(context.memory(address, 2, "read_i16").try_into().ok()?) as StackValueType,
+                StateType::ReadU32 => u32::from_le_bytes(context.memory(address, 4, "read_u32").try_into().ok()?) as StackValueType,
+                _ =>                  i32::from_le_bytes(context.memory(address, 4, "read_i32").try_into().ok()?) as StackValueType,
+            };
+            stack.push(value);
+        }
+        StateType::WriteU8 | StateType::WriteU16 | StateType::WriteU32 => {
+            let value = stack.pop().expect("stack is empty on write");
+            let address = stack.pop().expect("stack is empty on write");
+            match state.state_type {
+                StateType::WriteU8 =>  context.memory(address, 1, "write_u8").copy_from_slice(&(value as u8).to_le_bytes()),
+                StateType::WriteU16 => context.memory(address, 2, "write_u16").copy_from_slice(&(value as u16).to_le_bytes()),
+                _ =>                   context.memory(address, 4, "write_u32").copy_from_slice(&(value as u32).to_le_bytes()),
+            }
+        }
+        StateType::Alloc => {
+            let size = stack.pop().expect("stack is empty on alloc");
+            stack.push(context.alloc(size));
+        }
+        StateType::Free => {
+            let address = stack.pop().expect("stack is empty on free");
+            context.free(address);
         }
         StateType::Eq => {
             let first_argument = stack.pop().expect("stack is empty on eq");
@@ -197,7 +736,7 @@ fn execute_statement(states: &Vec<State>, template_arguments: &Vec<Vec<String>>,
         StateType::Neq => {
             let first_argument = stack.pop().expect("stack is empty on neq");
             let second_argument = stack.pop().expect("stack is empty on neq");
-            stack.push((second_argument == first_argument) as StackValueType);
+            stack.push((second_argument != first_argument) as StackValueType);
         }
         StateType::More => {
             let first_argument = stack.pop().expect("stack is empty on more");
@@ -210,7 +749,7 @@ fn execute_statement(states: &Vec<State>, template_arguments: &Vec<Vec<String>>,
             stack.push((second_argument < first_argument) as StackValueType);
         }
         StateType::Dup => {
-            let last = stack.last().ok_or_else(|| "stack is empty on dup".to_string()).ok()?.clone();
+            let last = stack.last().ok_or_else(|| "stack is empty on dup".to_string()).ok().copied()?;
             if state.template_arguments_index == 0 {
                 stack.push(last);
             } else {
@@ -276,80 +815,404 @@ fn execute_statement(states: &Vec<State>, template_arguments: &Vec<Vec<String>>,
         }
         StateType::WriteRaw => {
             let value = stack.pop().expect("stack is empty on write_raw");
-            std::io::stdout().write(value.to_be_bytes().as_slice()).expect("error on write_raw");
+            // same bytes as the compiled code writes: little endian, up to the first zero
+            let bytes = value.to_le_bytes();
+            let length = bytes.iter().position(|&byte| byte == 0).unwrap_or(bytes.len());
+            std::io::stdout().write_all(&bytes[..length]).expect("error on write_raw");
+        }
+        StateType::ReadInt => {
+            let (value, status) = read_stdin_int();
+            stack.push(value);
+            stack.push(status);
+        }
+        StateType::ReadChar => {
+            io::stdout().flush().expect("error on read_char");
+            stack.push(read_stdin_byte().map_or(-1, |x| x as StackValueType));
+        }
+        StateType::ReadLine => {
+            io::stdout().flush().expect("error on read_line");
+            let mut line = Vec::new();
+            let mut eof = true;
+            while let Some(byte) = read_stdin_byte() {
+                eof = false;
+                if byte == b'\n' {
+                    break;
+                }
+                line.push(byte);
+            }
+            if eof {
+                stack.push(-1);
+            } else {
+                stack.push(0);
+                stack.extend(line.iter().rev().map(|x| *x as StackValueType));
+                stack.push(line.len() as StackValueType);
+            }
+        }
+        StateType::Fopen | StateType::FopenPtr => {
+            let mode = if state.template_arguments_index == 0 {
+                "r"
+            } else {
+                template_arguments[state.template_arguments_index][0].as_str()
+            };
+            let path = if state.state_type == StateType::FopenPtr {
+                let address = stack.pop().expect("stack is empty on fopen_ptr");
+                context.c_string(address, "fopen_ptr")
+            } else {
+                pop_stack_string(stack).expect("stack is empty on fopen")
+            };
+            stack.push(context.fopen(&path, mode));
+        }
+        StateType::FreadByte => {
+            let fd = stack.pop().expect("stack is empty on fread_byte");
+            stack.push(context.fread_byte(fd));
+        }
+        StateType::FwriteByte => {
+            let byte = stack.pop().expect("stack is empty on fwrite_byte");
+            let fd = stack.pop().expect("stack is empty on fwrite_byte");
+            stack.push(context.fwrite_byte(fd, byte));
+        }
+        StateType::Fclose => {
+            let fd = stack.pop().expect("stack is empty on fclose");
+            stack.push(context.fclose(fd));
         }
         StateType::Exit => {
             let value = stack.pop().expect("stack is empty on exit");
             exit(value as i32);
         }
         _ => {
-            let mut else_count = 0;
-            let mut if_count = 0;
-            for i in state.deps.iter() {
-                let dep =  states.get(*i).expect("invalid statement index");
-                if dep.state_type == StateType::Else {
-                    else_count += 1;
-                } else if dep.state_type == StateType::If {
-                    if_count += 1;
+            let mut state = state;
+            let mut control_flow = resolve_control_flow(states, state);
+            let mut loop_counters = Vec::<StackValueType>::new();
+            let mut position = 0;
+            while position < state.deps.len() {
+                let dep =  states.get(state.deps[position]).expect("invalid statement index");
+                match (&dep.state_type, control_flow.jumps[position]) {
+                    (StateType::If | StateType::Do, ControlFlowJump::To(target)) => {
+                        if stack.pop()? <= 0 {
+                            position = target;
+                            continue;
+                        }
+                    }
+                    (StateType::Else | StateType::Return, ControlFlowJump::Return) => break,
+                    (StateType::Else | StateType::End, ControlFlowJump::To(target)) => {
+                        position = target;
+                        continue;
+                    }
+                    (StateType::Times, ControlFlowJump::To(target)) => {
+                        let count = if dep.template_arguments_index == 0 {
+                            stack.pop()?
+                        } else {
+                            template_arguments[dep.template_arguments_index][0].parse::<StackValueType>().expect("template argument in times must be integer")
+                        };
+                        if count <= 0 {
+                            position = target;
+                            continue;
+                        }
+                        loop_counters.push(count);
+                    }
+                    (StateType::End, ControlFlowJump::TimesLoop(target)) => {
+                        let counter = loop_counters.last_mut().expect("invalid times counter");
+                        *counter -= 1;
+                        if *counter > 0 {
+                            position = target;
+                            continue;
+                        }
+                        loop_counters.pop();
+                    }
+                    (StateType::While | StateType::Then | StateType::End, _) => { },
+                    (StateType::SelfCall, _) => {
+                        if is_tail_position(states, state, &control_flow, position) {
+                            loop_counters.clear();
+                            position = 0;
+                            continue;
+                        }
+                        execute_statement(states, template_arguments, state, stack, context)?;
+                    }
+                    (StateType::SelfGoto, _) => {
+                        loop_counters.clear();
+                        position = 0;
+                        continue;
+                    }
+                    _ => {
+                        if dep.state_type == StateType::Additional && is_tail_position(states, state, &control_flow, position) {
+                            // tail call. the callee takes this frame
+                            state = dep;
+                            control_flow = resolve_control_flow(states, state);
+                            loop_counters.clear();
+                            position = 0;
+                            continue;
+                        }
+                        execute_statement(states, template_arguments, dep, stack, context)?;
+                    }
                 }
+                position += 1;
             }
-            assert_eq!(if_count, else_count);
-
-            let mut if_happens = false;
-            let mut iter =  state.deps.iter();
-            loop {
-                let io = iter.as_slice().first();
-                if io.is_none() {
-                    break;
+        }
+    }
+    Some(())
+}
+#[derive(Clone, Copy, PartialEq)]
+enum ControlFlowJump {
+    Next,
+    To(usize),          // `if`, `do` and `times` jump on false (zero count), `else` with `end` and `end` of `while` always jump
+    Return,             // `return` and `else` without `end` return from the statement
+    TimesLoop(usize),   // `end` of `times` decrements the counter and jumps back while it's positive
+}
+struct ControlFlow {
+    jumps: Vec<ControlFlowJump>,    // dep position -> jump
+    loop_counters: Vec<usize>,      // dep position -> count of the `times` counters on the second stack
+}
+// `else` returns from the statement, so its `if` is done
+fn close_returning_else(frames: &mut Vec<(StateType, usize, usize)>, jumps: &mut [ControlFlowJump]) {
+    while let Some((StateType::Else, else_position, _)) = frames.last() {
+        jumps[*else_position] = ControlFlowJump::Return;
+        frames.pop();
+    }
+}
+// matches `if`/`else`, `if`/`then`, `if`/`else`/`end`, `while`/`do`/`end` and `times`/`end` of the statement body
+fn resolve_control_flow(states: &[State], state: &State) -> ControlFlow {
+    let mut jumps = vec![ControlFlowJump::Next; state.deps.len()];
+    let mut loop_counters = vec![0; state.deps.len()];
+    let mut frames: Vec<(StateType, usize, usize)> = Vec::new(); // kind, position, position of the `while` for `do`
+    let mut counters = 0;
+    for (position, i) in state.deps.iter().enumerate() {
+        let dep = states.get(*i).expect("invalid statement index");
+        loop_counters[position] = counters;
+        match dep.state_type {
+            StateType::If | StateType::While => {
+                frames.push((dep.state_type.clone(), position, position));
+            }
+            StateType::Times => {
+                frames.push((StateType::Times, position, position));
+                counters += 1;
+            }
+            StateType::Else => {
+                close_returning_else(&mut frames, &mut jumps);
+                match frames.pop() {
+                    Some((StateType::If, if_position, _)) => {
+                        jumps[if_position] = ControlFlowJump::To(position + 1);
+                        frames.push((StateType::Else, position, position));
+                    },
+                    _ => panic!("unexpected `else` in {} statement", state.name),
                 }
-                let i = io.unwrap();
-                let dep =  states.get(*i).expect("invalid statement index");
-                if dep.state_type ==  StateType::If {
-                    let last = stack.pop()?;
-                    if last <= 0 {
-                        if_happens = true;
-                    }
+            }
+            StateType::Return => {
+                jumps[position] = ControlFlowJump::Return;
+            }
+            StateType::Then => {
+                close_returning_else(&mut frames, &mut jumps);
+                match frames.pop() {
+                    Some((StateType::If, if_position, _)) => jumps[if_position] = ControlFlowJump::To(position + 1),
+                    _ => panic!("`then` without `if` in {} statement", state.name),
                 }
-                if if_happens  {
-                    if dep.state_type ==  StateType::Else {
-                        if_happens = false;
-                    }
-                } else if dep.state_type ==  StateType::Else {
-                    break;
-                } else if dep.state_type == StateType::SelfCall {
-                    execute_statement(states, template_arguments, state, stack)?;
-                } else if dep.state_type == StateType::SelfGoto {
-                    iter =  state.deps.iter();
-                    continue;
-                } else {
-                    execute_statement(states, template_arguments, dep, stack)?;
+            }
+            StateType::Do => {
+                close_returning_else(&mut frames, &mut jumps);
+                match frames.pop() {
+                    Some((StateType::While, while_position, _)) => frames.push((StateType::Do, position, while_position)),
+                    _ => panic!("`do` without `while` in {} statement", state.name),
+                }
+            }
+            StateType::End => {
+                match frames.pop() {
+                    Some((StateType::Else, else_position, _)) => {
+                        jumps[else_position] = ControlFlowJump::To(position + 1);
+                    },
+                    Some((StateType::Do, do_position, while_position)) => {
+                        jumps[do_position] = ControlFlowJump::To(position + 1);
+                        jumps[position] = ControlFlowJump::To(while_position);
+                    },
+                    Some((StateType::Times, times_position, _)) => {
+                        jumps[times_position] = ControlFlowJump::To(position + 1);
+                        jumps[position] = ControlFlowJump::TimesLoop(times_position + 1);
+                        counters -= 1;
+                    },
+                    Some((StateType::While, _, _)) => panic!("`while` without `do` in {} statement", state.name),
+                    _ => panic!("unexpected `end` in {} statement", state.name),
                 }
-                iter.next();
             }
+            _ => { },
         }
     }
-    Some(())
+    for frame in frames {
+        match frame.0 {
+            StateType::Else => jumps[frame.1] = ControlFlowJump::Return,
+            StateType::If => panic!("for every `if`, there should be a corresponding `else` or `then`. check {} statement", state.name),
+            _ => panic!("for every `while` and `times`, there should be a corresponding `end`. check {} statement", state.name),
+        }
+    }
+    ControlFlow{ jumps, loop_counters }
+}
+// marks every state that can be reached from the roots through deps
+fn reachable_states(states: &[State], roots: &[usize]) -> Vec<bool> {
+    let mut reachable = vec![false; states.len()];
+    let mut queue = roots.to_vec();
+    while let Some(index) = queue.pop() {
+        if reachable[index] {
+            continue;
+        }
+        reachable[index] = true;
+        queue.extend(states[index].deps.iter().filter(|x| !reachable[**x]));
+    }
+    reachable
+}
+// nothing but `then` and `end` of conditionals runs between the position and the return from the statement
+fn is_tail_position(states: &[State], state: &State, control_flow: &ControlFlow, position: usize) -> bool {
+    let mut position = position + 1;
+    while position < state.deps.len() {
+        let dep = states.get(state.deps[position]).expect("invalid statement index");
+        match (&dep.state_type, control_flow.jumps[position]) {
+            (StateType::Else | StateType::Return, ControlFlowJump::Return) => return true,
+            (StateType::Else, ControlFlowJump::To(target)) => position = target,
+            (StateType::Then | StateType::End, ControlFlowJump::Next) => position += 1,
+            _ => return false,
+        }
+    }
+    true
 }
-fn is_inlinable(states: &Vec<State>, state: &State) -> bool {
+// statements up to this cost are inlined without an `inline` attribute
+const INLINE_COST_LIMIT: usize = 24;
+
+#[derive(Clone, Copy, PartialEq)]
+enum InlineAttribute {
+    None,
+    Inline,
+    NoInline,
+}
+
+// the size of a statement after inlining. primitives and calls cost 1, control flow costs 2 (a label and a jump), inlined statements cost their bodies
+fn inline_cost(states: &[State], state: &State) -> usize {
+    state.deps.iter().map(|i| {
+        let dep = states.get(*i).expect("invalid statement index");
+        match dep.state_type {
+            StateType::Additional if dep.inlinable => inline_cost(states, dep),
+            StateType::If | StateType::Else | StateType::Then | StateType::While | StateType::Do | StateType::Times | StateType::End | StateType::Return => 2,
+            _ => 1,
+        }
+    }).sum()
+}
+// returns the decision and the reason for --emit=inline-report
+fn is_inlinable(states: &[State], state: &State, attribute: InlineAttribute) -> (bool, String) {
     if state.name == "start" {
-        return false
+        return (false, "entry point".to_string())
+    }
+    if state.deps.iter().any(|i| matches!(states.get(*i).expect("invalid statement index").state_type, StateType::SelfCall | StateType::SelfGoto)) {
+        if attribute == InlineAttribute::Inline {
+            eprintln!("warning: {} uses __self__ or __self__goto__ and cannot be inlined, `inline` is ignored", state.name);
+        }
+        return (false, "uses __self__ or __self__goto__".to_string())
     }
-    for i in state.deps.iter().enumerate() {
-        let dep =  states.get(*i.1).expect("invalid statement index");
-        if  (dep.state_type == StateType::If) || 
-            (dep.state_type == StateType::Else) || 
-            (dep.state_type == StateType::SelfCall) ||
-            (dep.state_type == StateType::SelfGoto) {
-                return false
+    let cost = inline_cost(states, state);
+    match attribute {
+        InlineAttribute::NoInline => (false, format!("noinline attribute (cost {})", cost)),
+        InlineAttribute::Inline => (true, format!("inline attribute (cost {})", cost)),
+        InlineAttribute::None if cost > INLINE_COST_LIMIT => (false, format!("cost {} > {}", cost, INLINE_COST_LIMIT)),
+        InlineAttribute::None => (true, format!("cost {} <= {}", cost, INLINE_COST_LIMIT)),
+    }
+}
+// compiles a primitive (or an integer) on top of the register cache.
+// primitives that the cache doesn`t know spill it and fall back to compile_statement
+fn compile_cached(states: &Vec<State>, template_arguments: &Vec<Vec<String>>, state: &State, options: &CompileOptions, cache: &mut RegisterCache) -> String {
+    let mut out = String::new();
+    let template_argument = |index: usize| template_arguments[state.template_arguments_index].get(index).map(|x| x.parse::<usize>().expect("template argument must be integer"));
+    match state.state_type {
+        StateType::Integer => {
+            let register = cache.push(&mut out);
+            out += format!("\tmov {}, {}\n", register, state.name).as_str();
+        }
+        StateType::Data | StateType::Var => {
+            let register = cache.push(&mut out);
+            let prefix = if state.state_type == StateType::Data { "data" } else { "var" };
+            out += format!("\tmov {}, @{}_{} ; {}\n", register, prefix, asm_label_name(&state.name), state.name).as_str();
+        }
+        StateType::VarFetch => {
+            let register = cache.push(&mut out);
+            out += format!("\tmov {}, qword [rel @var_{}] ; {}\n", register, asm_label_name(&state.name[1..]), state.name).as_str();
+        }
+        StateType::VarStore => {
+            cache.ensure(1, &mut out);
+            out += format!("\tmov qword [rel @var_{}], {} ; {}\n", asm_label_name(&state.name[1..]), cache.top(0), state.name).as_str();
+            cache.cells.pop();
+        }
+        StateType::Dup => {
+            let count = if state.template_arguments_index == 0 { 1 } else { template_argument(0).unwrap() };
+            for _ in 0..count {
+                cache.ensure(1, &mut out);
+                let top = cache.top(0);
+                let register = cache.push(&mut out);
+                out += format!("\tmov {}, {} ; dup\n", register, top).as_str();
+            }
+        }
+        StateType::Pop => {
+            let count = if state.template_arguments_index == 0 { 1 } else { template_argument(0).unwrap() };
+            for _ in 0..count {
+                if cache.cells.pop().is_none() {
+                    out += POP_ASM;
+                }
+            }
+        }
+        StateType::Swap if state.template_arguments_index == 0 || (template_argument(0), template_argument(1)) == (Some(0), Some(1)) => {
+            // no code at all, just rename the registers
+            cache.ensure(2, &mut out);
+            let length = cache.cells.len();
+            cache.cells.swap(length - 1, length - 2);
+        }
+        StateType::Inc | StateType::Dec => {
+            cache.ensure(1, &mut out);
+            out += format!("\t{} {}\n", if state.state_type == StateType::Inc { "inc" } else { "dec" }, cache.top(0)).as_str();
+        }
+        StateType::ReadFrom => {
+            cache.ensure(1, &mut out);
+            out += format!("\tmov {0}, qword [{0}] ; read_from\n", cache.top(0)).as_str();
+        }
+        StateType::WriteTo => {
+            cache.ensure(2, &mut out);
+            out += format!("\tmov qword [{}], {} ; write_to\n", cache.top(1), cache.top(0)).as_str();
+            cache.cells.truncate(cache.cells.len() - 2);
+        }
+        StateType::Sum | StateType::Dif | StateType::Mul | StateType::Div | StateType::Eq | StateType::Neq | StateType::More | StateType::Less => {
+            cache.ensure(2, &mut out);
+            let (first, second) = (cache.top(1), cache.top(0));
+            out += match state.state_type {
+                StateType::Sum => format!("\tadd {}, {} ; sum\n", first, second),
+                StateType::Dif => format!("\tsub {}, {} ; dif\n", first, second),
+                StateType::Mul => format!("\timul {}, {} ; mul\n", first, second),
+                StateType::Div => format!("\tmov rax, {0} ; div\n\tcqo\n\tidiv {1}\n\tmov {0}, rax\n", first, second),
+                _ => {
+                    let condition = match state.state_type {
+                        StateType::Eq => "e",
+                        StateType::Neq => "ne",
+                        StateType::More => "g",
+                        _ => "l",
+                    };
+                    format!("\tcmp {0}, {1} ; {2}\n\tset{3} al\n\tmovzx {0}, al\n", first, second, state.name, condition)
+                }
+            }.as_str();
+            cache.cells.pop();
+        }
+        _ => {
+            out += cache.spill().as_str();
+            out += compile_statement(states, template_arguments, state, options).expect("compiling error").as_str();
         }
     }
-    return true
+    out
 }
-fn compile_statement(states: &Vec<State>, template_arguments: &Vec<Vec<String>>, state: &State) -> Option<String> {
+fn compile_statement(states: &Vec<State>, template_arguments: &Vec<Vec<String>>, state: &State, options: &CompileOptions) -> Option<String> {
     let mut map = HashMap::new();
     map.insert(StateType::StackHead,    STACK_HEAD_ASM);
+    map.insert(StateType::Depth,        DEPTH_ASM);
+    map.insert(StateType::DumpStack,    DUMP_STACK_ASM);
     map.insert(StateType::ReadFrom,     READ_FROM_ASM);
     map.insert(StateType::WriteTo,      WRITE_TO_ASM);
+    map.insert(StateType::ReadU8,       READ_U8_ASM);
+    map.insert(StateType::ReadI8,       READ_I8_ASM);
+    map.insert(StateType::ReadU16,      READ_U16_ASM);
+    map.insert(StateType::ReadI16,      READ_I16_ASM);
+    map.insert(StateType::ReadU32,      READ_U32_ASM);
+    map.insert(StateType::ReadI32,      READ_I32_ASM);
+    map.insert(StateType::WriteU8,      WRITE_U8_ASM);
+    map.insert(StateType::WriteU16,     WRITE_U16_ASM);
+    map.insert(StateType::WriteU32,     WRITE_U32_ASM);
     map.insert(StateType::Eq,           EQ_ASM);
     map.insert(StateType::Neq,          NEQ_ASM);
     map.insert(StateType::More,         MORE_ASM);
@@ -360,14 +1223,44 @@ fn compile_statement(states: &Vec<State>, template_arguments: &Vec<Vec<String>>,
     map.insert(StateType::Dif,          DIF_ASM);
     map.insert(StateType::Mul,          MUL_ASM);
     map.insert(StateType::Div,          DIV_ASM);
+    map.insert(StateType::ReadChar,     READ_CHAR_ASM);
+    map.insert(StateType::ReadLine,     READ_LINE_ASM);
+    map.insert(StateType::FreadByte,    FREAD_BYTE_ASM);
+    map.insert(StateType::FwriteByte,   FWRITE_BYTE_ASM);
 
-    if cfg!(target_os = "windows") {
+    if options.platform == Platform::Win64 {
         map.insert(StateType::Print,        PRINT_ASM_WIN64);
         map.insert(StateType::WriteRaw,     WRITE_RAW_ASM_WIN64);
+        map.insert(StateType::ReadInt,      READ_INT_ASM_WIN64);
+        map.insert(StateType::Fclose,       FCLOSE_ASM_WIN64);
+        map.insert(StateType::Alloc,        ALLOC_ASM_WIN64);
+        map.insert(StateType::Free,         FREE_ASM_WIN64);
         map.insert(StateType::Exit,         EXIT_ASM_WIN64);
+    } else if options.freestanding {
+        map.insert(StateType::Print,        PRINT_ASM_FREESTANDING);
+        map.insert(StateType::WriteRaw,     WRITE_RAW_ASM_FREESTANDING);
+        map.insert(StateType::ReadInt,      READ_INT_ASM_FREESTANDING);
+        map.insert(StateType::ReadChar,     READ_CHAR_ASM_FREESTANDING);
+        map.insert(StateType::Fclose,       FCLOSE_ASM_LINUX);
+        map.insert(StateType::Alloc,        ALLOC_ASM_FREESTANDING);
+        map.insert(StateType::Free,         FREE_ASM_FREESTANDING);
+        map.insert(StateType::Exit,         EXIT_ASM_FREESTANDING);
+    } else if options.platform == Platform::MacOS {
+        map.insert(StateType::Print,        PRINT_ASM_MACOS);
+        map.insert(StateType::WriteRaw,     WRITE_RAW_ASM_MACOS);
+        map.insert(StateType::ReadInt,      READ_INT_ASM_MACOS);
+        map.insert(StateType::ReadChar,     READ_CHAR_ASM_MACOS);
+        map.insert(StateType::Fclose,       FCLOSE_ASM_MACOS);
+        map.insert(StateType::Alloc,        ALLOC_ASM_MACOS);
+        map.insert(StateType::Free,         FREE_ASM_MACOS);
+        map.insert(StateType::Exit,         EXIT_ASM_MACOS);
     } else {
         map.insert(StateType::Print,        PRINT_ASM_LINUX);
         map.insert(StateType::WriteRaw,     WRITE_RAW_ASM_LINUX);
+        map.insert(StateType::ReadInt,      READ_INT_ASM_LINUX);
+        map.insert(StateType::Fclose,       FCLOSE_ASM_LINUX);
+        map.insert(StateType::Alloc,        ALLOC_ASM_LINUX);
+        map.insert(StateType::Free,         FREE_ASM_LINUX);
         map.insert(StateType::Exit,         EXIT_ASM_LINUX);
     }
 
@@ -394,10 +1287,17 @@ fn compile_statement(states: &Vec<State>, template_arguments: &Vec<Vec<String>>,
                 return Some(generate_swap_asm(0, 1));
             } else {
                 let my_template_arguments = &template_arguments[state.template_arguments_index];
-                let first_index = my_template_arguments.get(0).expect("too few template arguments in swap").parse::<usize>().ok()?;
+                let first_index = my_template_arguments.first().expect("too few template arguments in swap").parse::<usize>().ok()?;
                 let second_index = my_template_arguments.get(1).expect("too few template arguments in swap").parse::<usize>().ok()?;
                 return Some(generate_swap_asm(first_index, second_index));
             }
+        }, StateType::Fopen | StateType::FopenPtr => {
+            let from_pointer = state.state_type == StateType::FopenPtr;
+            if state.template_arguments_index == 0 {
+                return Some(generate_fopen_asm("r", from_pointer, options));
+            } else {
+                return Some(generate_fopen_asm(&template_arguments[state.template_arguments_index][0], from_pointer, options));
+            }
         },
         _ => { },
     }
@@ -405,9 +1305,18 @@ fn compile_statement(states: &Vec<State>, template_arguments: &Vec<Vec<String>>,
     if let Some(asm) = map.get(&state.state_type) {
         return Some(asm.to_string());
     }
+    if state.state_type == StateType::Data {
+        return Some(format!("\tmov rax, @data_{} ; {}\n\tpush rax\n", asm_label_name(&state.name), state.name));
+    }
+    match state.state_type {
+        StateType::Var => return Some(format!("\tmov rax, @var_{} ; {}\n\tpush rax\n", asm_label_name(&state.name), state.name)),
+        StateType::VarFetch => return Some(format!("\tpush qword [rel @var_{}] ; {}\n", asm_label_name(&state.name[1..]), state.name)),
+        StateType::VarStore => return Some(format!("\tpop qword [rel @var_{}] ; {}\n", asm_label_name(&state.name[1..]), state.name)),
+        _ => { },
+    }
     if state.state_type == StateType::Integer {
         let integer = state.name.parse::<i64>().expect("invalid integer");
-        if integer > i32::MAX as i64 {
+        if integer > i32::MAX as i64 || integer < i32::MIN as i64 {
             return Some(format!(BIG_INT_FMT!(), state.name));
         } else {
             return Some(format!(INT_FMT!(), state.name));
@@ -415,99 +1324,193 @@ fn compile_statement(states: &Vec<State>, template_arguments: &Vec<Vec<String>>,
     }
     let mut out = String::new();
     if state.inlinable {
-        let mut iter =  state.deps.iter();
-        loop {
-            let io = iter.as_slice().first();
-            if io.is_none() {
-                break;
-            }
-            let i = io.unwrap();
-            let dep = states.get(*i).expect("invalid statement index");
-            
-            if dep.inlinable || dep.state_type == StateType::Integer {
-                out += compile_statement(states, template_arguments, dep).expect("compiling error").as_str();
-            } else {
-                out += "\tcall ";
-                out += dep.name.as_str();
-                out += "\n";
-            }
-            iter.next();
-        }
+        out += compile_body(states, template_arguments, state, options, &state.name, &mut RegisterCache::default()).as_str();
     } else {
-        let statement_exit = if state.name == "start" { 
-            if cfg!(target_os = "windows") {
-                SUCCESFUL_EXIT_ASM_WIN64
-            } else {
-                SUCCESFUL_EXIT_ASM_LINUX
-            }
-        } else {
-            "\tcall @get_pop_second_stack\n\tpush rax\n\tret\n\tpop rax\n"
-        };
-
         out += state.name.as_str();
-        out += ":\n\tpop rax\n\tcall @share_to_second_stack\n";
+        out += ":\n";
+        out += if options.calling_convention == CallingConvention::Native { NATIVE_PROLOGUE_ASM } else { SECOND_STACK_PROLOGUE_ASM };
         out += "@";
         out += state.name.as_str();
         out += "_jump_position_you_know:\n";
-        
-        {
-            let mut else_count = 0;
-            let mut if_count = 0;
-            for i in state.deps.iter() {
-                let dep = states.get(*i).expect("invalid statement index");
-                if dep.state_type == StateType::Else {
-                    else_count += 1;
-                } else if dep.state_type == StateType::If {
-                    if_count += 1;
+        out += compile_body(states, template_arguments, state, options, &state.name, &mut RegisterCache::default()).as_str();
+    }
+    Some(out)
+}
+// compiles the deps of a statement. an inlinable statement is compiled right into its caller:
+// its labels get the caller`s `label_prefix`, and its returns jump to the end of the inlined body
+fn compile_body(states: &Vec<State>, template_arguments: &Vec<Vec<String>>, state: &State, options: &CompileOptions, label_prefix: &str, cache: &mut RegisterCache) -> String {
+    let mut out = String::new();
+    let inlined = state.inlinable;
+    let end_label = format!("@{}_label{}", label_prefix, state.deps.len());
+    let statement_exit = if inlined {
+        format!("\tjmp {}\n", end_label)
+    } else if state.name == "start" {
+        if options.platform == Platform::Win64 {
+            SUCCESFUL_EXIT_ASM_WIN64.to_string()
+        } else if options.freestanding {
+            SUCCESFUL_EXIT_ASM_FREESTANDING.to_string()
+        } else if options.platform == Platform::MacOS {
+            SUCCESFUL_EXIT_ASM_MACOS.to_string()
+        } else {
+            SUCCESFUL_EXIT_ASM_LINUX.to_string()
+        }
+    } else if options.calling_convention == CallingConvention::Native {
+        NATIVE_EPILOGUE_ASM.to_string()
+    } else {
+        SECOND_STACK_EPILOGUE_ASM.to_string()
+    };
+
+    let control_flow = resolve_control_flow(states, state);
+    let mut labels = vec![false; state.deps.len() + 1];
+    for jump in control_flow.jumps.iter() {
+        if let ControlFlowJump::To(target) | ControlFlowJump::TimesLoop(target) = jump {
+            labels[*target] = true;
+        }
+    }
+    if inlined && control_flow.jumps.contains(&ControlFlowJump::Return) {
+        labels[state.deps.len()] = true;
+    }
+    let label = |position: usize| format!("@{}_label{}", label_prefix, position);
+    let drop_loop_counters = |position: usize| if control_flow.loop_counters[position] == 0 {
+        String::new()
+    } else if options.calling_convention == CallingConvention::Native {
+        format!("\tadd r15, {}\n", control_flow.loop_counters[position] * 8)
+    } else {
+        format!("\tmov rcx, @stack_size\n\tsub qword [rcx], {}\n", control_flow.loop_counters[position])
+    };
+    for (position, i) in state.deps.iter().enumerate() {
+        let dep = states.get(*i).expect("invalid statement index");
+        if labels[position] {
+            out += cache.spill().as_str();
+            out += format!("{}:\n", label(position)).as_str();
+        }
+        if dep.inlinable && dep.state_type == StateType::Additional {
+            out += compile_body(states, template_arguments, dep, options, &format!("{}_inline{}_{}", label_prefix, position, dep.name), cache).as_str();
+            continue;
+        }
+        let cached = options.register_cache && (dep.inlinable || dep.state_type == StateType::Integer) && !matches!(dep.state_type,
+            StateType::If | StateType::Else | StateType::Then | StateType::While | StateType::Do | StateType::Times | StateType::End | StateType::Return | StateType::SelfCall | StateType::SelfGoto);
+        if cached {
+            out += compile_cached(states, template_arguments, dep, options, cache).as_str();
+            continue;
+        }
+
+        if !matches!(dep.state_type, StateType::If | StateType::Do) {
+            out += cache.spill().as_str();
+        }
+        match (&dep.state_type, control_flow.jumps[position]) {
+            (StateType::If | StateType::Do, ControlFlowJump::To(target)) => {
+                if options.register_cache {
+                    cache.ensure(1, &mut out);
+                    let condition = cache.cells.pop().unwrap();
+                    out += cache.spill().as_str();
+                    out += format!("\tcmp {}, 0\n", condition).as_str();
+                } else {
+                    out += "\tpop rax\n";
+                    out += "\tcmp rax, 0\n";
                 }
+                out += format!("\tjle {}\n", label(target)).as_str();
             }
-            assert!(if_count == else_count, "for every `if`, there should be a corresponding `else`. check {} statement", state.name);
-        }
-        let mut iter =  state.deps.iter();
-        let mut if_count = 0;
-        let mut if_stack = Vec::new();
-        loop {
-            let io = iter.as_slice().first();
-            if io.is_none() {
-                break;
+            (StateType::Else | StateType::Return, ControlFlowJump::Return) => {
+                out += drop_loop_counters(position).as_str();
+                out += statement_exit.as_str();
+            }
+            (StateType::Else | StateType::End, ControlFlowJump::To(target)) => {
+                out += format!("\tjmp {}\n", label(target)).as_str();
             }
-            let i = io.unwrap();
-            let dep = states.get(*i).expect("invalid statement index");
-            
-            if dep.state_type ==  StateType::If {
-                if_count += 1;
-                if_stack.push(if_count);
-                out += "\tpop rax\n";
+            (StateType::Times, ControlFlowJump::To(target)) => {
+                if dep.template_arguments_index == 0 {
+                    out += "\tpop rax ; times\n";
+                } else {
+                    let count = template_arguments[dep.template_arguments_index][0].parse::<StackValueType>().expect("template argument in times must be integer");
+                    out += format!("\tmov rax, {} ; times<{}>\n", count, count).as_str();
+                }
                 out += "\tcmp rax, 0\n";
-                out += format!("\tjle @{}_else{}\n", state.name, if_count).as_str();
-                
-            } else if dep.state_type ==  StateType::Else {
-                out += format!("{}@{}_else{}:\n", statement_exit, state.name, if_stack.pop().expect("unexpected else")).as_str();
-
-            } else if dep.state_type == StateType::SelfCall {
-                out += "\tcall ";
-                out += state.name.as_str();
-                out += "\n";
-
-            } else if dep.state_type == StateType::SelfGoto {
+                out += format!("\tjle {}\n", label(target)).as_str();
+                if options.calling_convention == CallingConvention::Native {
+                    out += "\tsub r15, 8\n\tmov qword [r15], rax\n";
+                } else {
+                    out += "\tcall @share_to_second_stack\n";
+                }
+            }
+            (StateType::End, ControlFlowJump::TimesLoop(target)) => {
+                if options.calling_convention == CallingConvention::Native {
+                    out += "\tdec qword [r15] ; end of times\n";
+                    out += format!("\tjg {}\n", label(target)).as_str();
+                    out += "\tadd r15, 8\n";
+                } else {
+                    out += "\tmov rbx, @second_stack ; end of times\n\tmov rcx, @stack_size\n\tmov rcx, [rcx]\n\tdec qword [rbx + rcx * 8 - 8]\n";
+                    out += format!("\tjg {}\n", label(target)).as_str();
+                    out += "\tmov rcx, @stack_size\n\tdec qword [rcx]\n";
+                }
+            }
+            (StateType::While | StateType::Then | StateType::End, _) => { },
+            (StateType::SelfCall, _) => {
+                if is_tail_position(states, state, &control_flow, position) {
+                    out += drop_loop_counters(position).as_str();
+                    out += "\tjmp @";
+                    out += state.name.as_str();
+                    out += "_jump_position_you_know ; tail call\n";
+                } else {
+                    out += generate_call_asm(&state.name, options).as_str();
+                }
+            }
+            (StateType::SelfGoto, _) => {
+                out += drop_loop_counters(position).as_str();
                 out += "\tjmp @";
                 out += state.name.as_str();
                 out += "_jump_position_you_know\n";
-
-            } else {
+            }
+            _ => {
                 if dep.inlinable || dep.state_type == StateType::Integer {
-                    out += compile_statement(states, template_arguments, dep).expect("compilation error").as_str();
-                } else {
-                    out += "\tcall ";
+                    out += compile_statement(states, template_arguments, dep, options).expect("compilation error").as_str();
+                } else if !inlined && state.name != "start" && is_tail_position(states, state, &control_flow, position) {
+                    // tail call. the callee returns right to our caller, using our return address on the second stack
+                    out += drop_loop_counters(position).as_str();
+                    out += "\tjmp @";
                     out += dep.name.as_str();
-                    out += "\n";
+                    out += "_jump_position_you_know ; tail call\n";
+                } else {
+                    out += generate_call_asm(&dep.name, options).as_str();
                 }
             }
-            iter.next();
         }
-        out += statement_exit;
     }
-    return Some(out)
+    if inlined {
+        // the registers are still fine after the inlined body, unless somebody jumps to its end
+        if labels[state.deps.len()] {
+            out += cache.spill().as_str();
+            out += format!("{}:\n", end_label).as_str();
+        }
+    } else {
+        out += cache.spill().as_str();
+        if labels[state.deps.len()] {
+            out += format!("{}:\n", label(state.deps.len())).as_str();
+        }
+        out += statement_exit.as_str();
+    }
+    out
+}
+
+// runs a build tool to completion. its output goes to stderr, and any failure stops the compilation
+fn run_tool(tool: &str, args: &[&str]) {
+    let output = match std::process::Command::new(tool).args(args).output() {
+        Ok(output) => output,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            eprintln!("error: `{}` not found. install it and make sure it is in PATH", tool);
+            exit(1);
+        }
+        Err(error) => {
+            eprintln!("error: unable to run `{}`: {}", tool, error);
+            exit(1);
+        }
+    };
+    io::stderr().write_all(&output.stdout).expect("unable to write to stderr");
+    io::stderr().write_all(&output.stderr).expect("unable to write to stderr");
+    if !output.status.success() {
+        eprintln!("error: `{} {}` failed ({})", tool, args.join(" "), output.status);
+        exit(1);
+    }
 }
 
 fn main() {
@@ -537,23 +1540,119 @@ fn main() {
                 .short("m")
                 .long("interprutation/compilation mode")
                 .value_name("String")
-                .help("sets the interprutation/compilation mode.\n\tposible values: c | i")
+                .help("sets the interprutation/compilation mode.\n\tposible values: c | i | w | j\n\tw - compiles to wasm and runs it in the embedded wasmi\n\tj - assembles the x86_64 asm in memory and runs it right away")
                 .index(3),
         )
+        .arg(
+            Arg::with_name("calling-convention")
+                .long("calling-convention")
+                .value_name("String")
+                .possible_values(&["second-stack", "native"])
+                .default_value("second-stack")
+                .help("sets the calling convention of the compiled code.\n\tsecond-stack - return addresses are moved to a separate stack\n\tnative - call/ret and the data stack swap rsp and r15"),
+        )
+        .arg(
+            Arg::with_name("target")
+                .long("target")
+                .value_name("String")
+                .possible_values(&["x86_64", "linux-x86_64", "win64", "macos-x86_64", "c", "wasm"])
+                .default_value("x86_64")
+                .help("sets what the program is compiled to.\n\tx86_64 - nasm assembly for the os jalgo runs on\n\tlinux-x86_64, win64, macos-x86_64 - nasm assembly for that os\n\tc - portable C99, built with cc\n\twasm - WebAssembly text, written to <output>.wat"),
+        )
+        .arg(
+            Arg::with_name("arch")
+                .long("arch")
+                .value_name("String")
+                .possible_values(&["x86_64", "aarch64"])
+                .default_value("x86_64")
+                .help("sets the cpu the asm is written for.\n\tx86_64 - nasm, as above\n\taarch64 - GNU as for aarch64 linux, built with gcc"),
+        )
+        .arg(
+            Arg::with_name("asm-syntax")
+                .long("asm-syntax")
+                .value_name("String")
+                .possible_values(&["nasm", "gas"])
+                .default_value("nasm")
+                .help("sets the syntax of the x86_64 asm.\n\tnasm - built with nasm\n\tgas - .intel_syntax noprefix, built with as"),
+        )
+        .arg(
+            Arg::with_name("emit")
+                .long("emit")
+                .value_name("String")
+                .possible_values(&["asm", "llvm", "inline-report"])
+                .default_value("asm")
+                .help("sets what the compilation produces.\n\tasm - the program\n\tllvm - the program as llvm ir, built with clang\n\tinline-report - which statements are inlined and why"),
+        )
+        .arg(
+            Arg::with_name("assembler")
+                .long("assembler")
+                .value_name("String")
+                .possible_values(&["nasm", "builtin"])
+                .default_value("nasm")
+                .help("sets how the executable is built.\n\tnasm - nasm and gcc with libc\n\tbuiltin - a static linux executable without libc, no external tools"),
+        )
+        .arg(
+            Arg::with_name("no-libc")
+                .long("no-libc")
+                .help("uses raw linux syscalls instead of libc. the executable is linked with ld into a tiny static binary"),
+        )
+        .arg(
+            Arg::with_name("keep-temps")
+                .long("keep-temps")
+                .help("keeps the .nasm and .o files next to the executable"),
+        )
+        .arg(
+            Arg::with_name("register-cache")
+                .long("register-cache")
+                .help("keeps the top two cells of the data stack in registers between calls and branches"),
+        )
         .get_matches();
 
     let input_file = matches.value_of("input").unwrap();
     let output_file = matches.value_of("output");
     let mode = matches.value_of("mode");
+    let options = CompileOptions {
+        calling_convention: if matches.value_of("calling-convention") == Some("native") { CallingConvention::Native } else { CallingConvention::SecondStack },
+        register_cache: matches.is_present("register-cache"),
+        // the jit runs the freestanding prelude too, just with its own print, write_raw and exit
+        freestanding: matches.is_present("no-libc") || matches.value_of("assembler") == Some("builtin") || mode == Some("j"),
+        platform: match matches.value_of("target") {
+            Some("linux-x86_64") => Platform::Linux,
+            Some("win64") => Platform::Win64,
+            Some("macos-x86_64") => Platform::MacOS,
+            _ => Platform::host(),
+        },
+    };
 
     let code = std::fs::read_to_string(input_file).expect("Unable to read file");
 
     let mut states: Vec<State> = vec![
         State{name: "stack_head".to_string(),       state_type: StateType::StackHead,   deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "depth".to_string(),            state_type: StateType::Depth,       deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "dump_stack".to_string(),       state_type: StateType::DumpStack,   deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
         State{name: "read_from".to_string(),        state_type: StateType::ReadFrom,    deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
         State{name: "write_to".to_string(),         state_type: StateType::WriteTo,     deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "read_u8".to_string(),          state_type: StateType::ReadU8,      deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "read_i8".to_string(),          state_type: StateType::ReadI8,      deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "read_u16".to_string(),         state_type: StateType::ReadU16,     deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "read_i16".to_string(),         state_type: StateType::ReadI16,     deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "read_u32".to_string(),         state_type: StateType::ReadU32,     deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "read_i32".to_string(),         state_type: StateType::ReadI32,     deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "write_u8".to_string(),         state_type: StateType::WriteU8,     deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "write_u16".to_string(),        state_type: StateType::WriteU16,    deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "write_u32".to_string(),        state_type: StateType::WriteU32,    deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "alloc".to_string(),            state_type: StateType::Alloc,       deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "free".to_string(),             state_type: StateType::Free,        deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
         State{name: "print".to_string(),            state_type: StateType::Print,       deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
         State{name: "write_raw".to_string(),        state_type: StateType::WriteRaw,    deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "read_int".to_string(),         state_type: StateType::ReadInt,     deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "read_char".to_string(),        state_type: StateType::ReadChar,    deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "read_line".to_string(),        state_type: StateType::ReadLine,    deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "fopen".to_string(),            state_type: StateType::Fopen,       deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "fopen_ptr".to_string(),        state_type: StateType::FopenPtr,    deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "fread_byte".to_string(),       state_type: StateType::FreadByte,   deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "fwrite_byte".to_string(),      state_type: StateType::FwriteByte,  deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "fclose".to_string(),           state_type: StateType::Fclose,      deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
         State{name: "eq".to_string(),               state_type: StateType::Eq,          deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
         State{name: "neq".to_string(),              state_type: StateType::Neq,         deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
         State{name: "more".to_string(),             state_type: StateType::More,        deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
@@ -568,6 +1667,12 @@ fn main() {
         State{name: "dec".to_string(),              state_type: StateType::Dec,         deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
         State{name: "if".to_string(),               state_type: StateType::If,          deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
         State{name: "else".to_string(),             state_type: StateType::Else,        deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "then".to_string(),             state_type: StateType::Then,        deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "while".to_string(),            state_type: StateType::While,       deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "do".to_string(),               state_type: StateType::Do,          deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "times".to_string(),            state_type: StateType::Times,       deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "end".to_string(),              state_type: StateType::End,         deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
+        State{name: "return".to_string(),           state_type: StateType::Return,      deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
         State{name: "swap".to_string(),             state_type: StateType::Swap,        deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
         State{name: "__self__".to_string(),         state_type: StateType::SelfCall,    deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
         State{name: "__self__goto__".to_string(),   state_type: StateType::SelfGoto,    deps: Vec::new(), inlinable: true, template_arguments_index: 0 },
@@ -575,12 +1680,24 @@ fn main() {
     ];
     let mut template_arguments: Vec<Vec<String>> = vec![vec![String::new()]];
     let mut last_state = State::default();
-    let tokens = code.split_whitespace();
+    let tokens = tokenize(&code);
+
+    let mut data_sections: Vec<(String, Vec<u8>)> = Vec::new(); // name, initial bytes
+    let mut last_data: Option<(String, Vec<u8>)> = None;
+    let mut variables: Vec<String> = Vec::new();
+    let mut inline_attribute = InlineAttribute::None;
+    let mut inline_report: Vec<String> = Vec::new();
+    let mut export_next = false;
+    let mut exports: Vec<usize> = Vec::new(); // exported statements are kept even if start never uses them
 
     let mut state_colon = false;
     let mut in_state = false;
+    let mut in_data = false;
+    let mut in_var = false;
+    let mut var_semicolon = false;
+    let mut data_colon = false;
     let mut in_comment = false;
-    for i in tokens.enumerate() {
+    for i in tokens.iter().map(|x| x.as_str()).enumerate() {
         if i.1.is_empty() {
             continue;
         }
@@ -600,34 +1717,88 @@ fn main() {
             if last_state.name.get(0..1).expect("too small statement name") == "@" {
                 panic!("the first character of the state name cannot be '@'");
             }
+            if states.iter().any(|x| x.name == i.1 && (x.state_type == StateType::Data || x.state_type == StateType::Var)) {
+                panic!("\"{}\" is already defined as data or var", i.1);
+            }
             in_state = false;
+        } else if in_data {
+            if states.iter().any(|x| x.name == i.1) {
+                panic!("\"{}\" is already defined", i.1);
+            }
+            if i.1.get(0..1).expect("too small data name") == "@" {
+                panic!("the first character of the data name cannot be '@'");
+            }
+            last_data = Some((i.1.to_string(), Vec::new()));
+            in_data = false;
+        } else if in_var {
+            if states.iter().any(|x| x.name == i.1) {
+                panic!("\"{}\" is already defined", i.1);
+            }
+            if i.1.starts_with(['@', '!']) {
+                panic!("the first character of the var name cannot be '@' or '!'");
+            }
+            // `name` - address, `@name` - fetch, `!name` - store
+            states.push(State{name: i.1.to_string(),        state_type: StateType::Var,       deps: Vec::new(), inlinable: true, template_arguments_index: 0 });
+            states.push(State{name: format!("@{}", i.1),    state_type: StateType::VarFetch,  deps: Vec::new(), inlinable: true, template_arguments_index: 0 });
+            states.push(State{name: format!("!{}", i.1),    state_type: StateType::VarStore,  deps: Vec::new(), inlinable: true, template_arguments_index: 0 });
+            variables.push(i.1.to_string());
+            in_var = false;
+            var_semicolon = true;
+        } else if var_semicolon {
+            if i.1 != ";" {
+                panic!("expected ';' after var {}", variables.last().unwrap());
+            }
+            var_semicolon = false;
+        } else if data_colon {
+            let data = last_data.as_mut().expect("data without name");
+            if i.1 == ";" {
+                data_colon = false;
+                states.push(State{name: data.0.clone(), state_type: StateType::Data, deps: Vec::new(), inlinable: true, template_arguments_index: 0 });
+                data_sections.push(last_data.take().unwrap());
+            } else if i.1.starts_with('"') {
+                data.1.extend(parse_string_literal(i.1));
+            } else if let Ok(num) = i.1.parse::<StackValueType>() {
+                data.1.extend_from_slice(&num.to_le_bytes());
+            } else {
+                panic!("invalid data value \"{}\" in {}. only integers and string literals are allowed", i.1, data.0);
+            }
         } else if state_colon {
             if i.1 == ";" {
                 state_colon = false;
+                resolve_control_flow(&states, &last_state); // panics on unmatched if/else/while/do/times/end
                 // inline-check
-                if is_inlinable(&states, &last_state) {
-                    last_state.inlinable = true;
+                let (mut inlinable, mut reason) = is_inlinable(&states, &last_state, inline_attribute);
+                if export_next {
+                    (inlinable, reason) = (false, "exported".to_string());
+                    exports.push(states.len());
+                    export_next = false;
                 }
+                last_state.inlinable = inlinable;
+                inline_report.push(format!("{}: {}, {}", last_state.name, if inlinable { "inlined" } else { "not inlined" }, reason));
+                inline_attribute = InlineAttribute::None;
 
                 states.push(last_state.clone());
                 last_state = State::default();
                 continue;
             }
-            if i.1.get(0..1).expect("too small statement name") == "@" {
+            if i.1.get(0..1).expect("too small statement name") == "@" && !states.iter().any(|x| x.name == i.1) {
                 panic!("the first character of the state name cannot be '@'");
             }
+            if i.1.starts_with('"') {
+                panic!("string literals are allowed only in data. check {} statement", last_state.name);
+            }
             if let Ok(num) = i.1.parse::<StackValueType>() {
                 last_state.deps.push(states.len());
                 states.push(numeric_state(num.to_string()));
                 continue;
             }
-            let template_separated_name = separate_template_and_name(&i.1.to_string());
+            let template_separated_name = separate_template_and_name(i.1);
             if template_separated_name.1.is_empty() {
                 last_state.deps.push(
                     states
                         .iter()
                         .position (|x| x.name == i.1 )
-                        .expect(&format!("invalid statement \"{}\"", i.1)))
+                        .unwrap_or_else(|| panic!("invalid statement \"{}\"", i.1)))
             } else {
                 if let Some(template_statement) =
                     states
@@ -647,84 +1818,235 @@ fn main() {
 
         } else if i.1 == "st" {
             in_state = true;
+        } else if i.1 == "inline" || i.1 == "noinline" {
+            if inline_attribute != InlineAttribute::None {
+                panic!("too many inline attributes before st");
+            }
+            inline_attribute = if i.1 == "inline" { InlineAttribute::Inline } else { InlineAttribute::NoInline };
+        } else if i.1 == "export" {
+            export_next = true;
+        } else if (i.1 == "data" || i.1 == "var") && (inline_attribute != InlineAttribute::None || export_next) {
+            panic!("inline, noinline and export attributes are allowed only before st");
+        } else if i.1 == "data" {
+            in_data = true;
+        } else if i.1 == "var" {
+            in_var = true;
         } else if i.1 == ":" {
-            state_colon = true;
+            if last_data.is_some() {
+                data_colon = true;
+            } else {
+                state_colon = true;
+            }
         }
     }
 
+    let start_index = states.iter().position(|x| x.name == "start").expect("entry point \"start\" doesnt exist");
+    let reachable = reachable_states(&states, &[vec![start_index], exports.clone()].concat());
+    let is_used = |name: &str| states.iter().enumerate().any(|x| reachable[x.0] && (x.1.name == name || x.1.name == format!("@{}", name) || x.1.name == format!("!{}", name)) && x.1.state_type != StateType::Additional);
+    for (index, state) in states.iter().enumerate() {
+        if state.state_type == StateType::Additional && !reachable[index] && !state.name.starts_with('_') && state.template_arguments_index == 0 {
+            eprintln!("warning: statement `{}` is never used", state.name);
+        }
+    }
+    for data in data_sections.iter().filter(|x| !x.0.starts_with('_') && !is_used(&x.0)) {
+        eprintln!("warning: data `{}` is never used", data.0);
+    }
+    for variable in variables.iter().filter(|x| !x.starts_with('_') && !is_used(x)) {
+        eprintln!("warning: variable `{}` is never used", variable);
+    }
+
     if let Some(mode) = mode {
         if mode == "i" {
             let mut stack = Vec::<StackValueType>::new();
-            execute_statement(&states, &template_arguments, states.iter().enumerate().find(|x| x.1.name == "start").expect("entry point \"start\" doesnt exist").1, &mut stack);
+            let mut context = InterpreterContext::default();
+            for data in data_sections.iter() {
+                context.add_data(&data.0, &data.1);
+            }
+            for variable in variables.iter() {
+                context.add_data(variable, &[0; STACK_VALUE_SIZE]);
+            }
+            execute_statement(&states, &template_arguments, &states[start_index], &mut stack, &mut context);
             return;
-        } else if mode != "c" {
+        } else if mode != "c" && mode != "w" && mode != "j" {
             panic!("unknown mode. check --help")
         }
     }
 
-    let asm_code_begin;
-    if cfg!(target_os = "windows") {
-        asm_code_begin = ASM_CODE_BEGIN_WIN64
+    if matches.value_of("emit") == Some("inline-report") {
+        for line in inline_report.iter() {
+            println!("{}", line);
+        }
+        return;
+    }
+
+    let data_sections = data_sections.iter().filter(|x| is_used(&x.0)).collect::<Vec<_>>();
+    let variables = variables.iter().filter(|x| is_used(x)).collect::<Vec<_>>();
+
+    if matches.value_of("arch") == Some("aarch64") && (matches.value_of("target") != Some("x86_64") || matches.value_of("emit") == Some("llvm")) {
+        panic!("--arch is for the asm only");
+    }
+    // an aarch64 host has gcc for itself, everything else needs the cross one
+    let aarch64_compiler = if cfg!(target_arch = "aarch64") { "gcc" } else { "aarch64-linux-gnu-gcc" };
+
+    // the source backends: c, llvm ir, wat and aarch64 asm. wat is left as is, the rest are built with cc, clang and gcc
+    let source_backend = if mode == Some("w") || matches.value_of("target") == Some("wasm") {
+        Some((wat::compile as SourceBackend, "wat", None, vec![]))
+    } else if matches.value_of("emit") == Some("llvm") {
+        Some((llvm::compile as SourceBackend, "ll", Some("clang"), vec!["-O2"]))
+    } else if matches.value_of("target") == Some("c") {
+        Some((c::compile as SourceBackend, "c", Some("cc"), vec!["-std=c99", "-O2"]))
+    } else if matches.value_of("arch") == Some("aarch64") {
+        Some((aarch64::compile as SourceBackend, "s", Some(aarch64_compiler), vec![]))
+    } else {
+        None
+    };
+    if let Some((compile, extension, compiler, compiler_args)) = source_backend {
+        if mode == Some("j") {
+            panic!("`j` mode runs the x86_64 asm only");
+        }
+        if options.freestanding {
+            panic!("--no-libc and the builtin assembler are for the x86_64 asm only");
+        }
+        let mut functions: Vec<&State> = Vec::new();
+        for i in states.iter().enumerate().filter(|x| reachable[x.0] && !x.1.inlinable && x.1.state_type == StateType::Additional) {
+            if !functions.iter().any(|x| x.name == i.1.name) {
+                functions.push(i.1);
+            }
+        }
+        let compiled_code = compile(&states, &template_arguments, &functions, &exports.iter().map(|x| states[*x].name.as_str()).collect::<Vec<_>>(), &data_sections, &variables);
+        if mode == Some("w") {
+            #[cfg(feature = "wasm-host")]
+            std::process::exit(wasm_host::run(&compiled_code));
+            #[cfg(not(feature = "wasm-host"))]
+            panic!("jalgo was built without the wasm-host feature, so `w` mode is unavailable");
+        }
+        if let Some(output_file) = output_file
+            && let Some(compiler) = compiler
+        {
+            let output_source_file_string = format!("{}.{}", output_file, extension);
+            let output_result_file_string = if cfg!(target_os = "windows") {
+                output_file.to_string() + ".exe"
+            } else {
+                output_file.to_string()
+            };
+            std::fs::write(output_source_file_string.clone(), compiled_code).expect("unable to write file");
+            run_tool(compiler, &[compiler_args, vec![&output_source_file_string, "-o", &output_result_file_string]].concat());
+            if !matches.is_present("keep-temps") && let Err(error) = std::fs::remove_file(&output_source_file_string) {
+                eprintln!("warning: unable to remove {}: {}", output_source_file_string, error);
+            }
+        } else if let Some(output_file) = output_file {
+            std::fs::write(format!("{}.{}", output_file, extension), compiled_code).expect("unable to write file");
+        } else {
+            print!("{}", compiled_code);
+        }
+        return;
+    }
+
+    if options.freestanding && options.platform != Platform::Linux {
+        panic!("--no-libc, the builtin assembler and `j` mode produce only linux executables");
+    }
+    let asm_code_begin = match options.platform {
+        Platform::Win64 => ASM_CODE_BEGIN_WIN64,
+        Platform::MacOS => ASM_CODE_BEGIN_MACOS,
+        Platform::Linux if options.freestanding => ASM_CODE_BEGIN_LINUX_FREESTANDING,
+        Platform::Linux => ASM_CODE_BEGIN_LINUX,
+    };
+
+    let mut compiled_code = if options.calling_convention == CallingConvention::Native {
+        asm_code_begin.replace("\tjmp start\n", NATIVE_ENTRY_ASM)
     } else {
-        asm_code_begin = ASM_CODE_BEGIN_LINUX
+        asm_code_begin.to_string()
     };
+    if options.freestanding && mode != Some("j") {
+        compiled_code += FREESTANDING_SERVICES_ASM;
+    }
+    for export in exports.iter() {
+        compiled_code += format!("\tglobal {}\n", states[*export].name).as_str();
+    }
+    // only the statements that start or the exports can reach, the rest is dead code
+    for i in states.iter().enumerate().filter(|x| reachable[x.0] && !x.1.inlinable && x.1.state_type != StateType::Integer) {
+        compiled_code += compile_statement(&states, &template_arguments, i.1, &options).expect("compilation error").as_str();
+    }
+    if !data_sections.is_empty() || !variables.is_empty() {
+        compiled_code += "\nsection .data\n";
+        for variable in variables.iter() {
+            compiled_code += format!("\t@var_{}: dq 0 ; {}\n", asm_label_name(variable), variable).as_str();
+        }
+        for data in data_sections.iter() {
+            compiled_code += format!("@data_{}: ; {}\n", asm_label_name(&data.0), data.0).as_str();
+            if !data.1.is_empty() {
+                compiled_code += format!("\tdb {}\n", data.1.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(", ")).as_str();
+            }
+        }
+    }
+    if options.calling_convention == CallingConvention::Native {
+        compiled_code += NATIVE_RETURN_STACK_ASM;
+    }
 
-    let mut compiled_code = format!("{}",asm_code_begin);
-    for i in states.iter().filter(|x| !x.inlinable).enumerate() {
-        compiled_code += compile_statement(&states, &template_arguments, i.1).expect("compilation error").as_str();
+    if mode == Some("j") {
+        #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+        jit::run(&compiled_code);
+        #[cfg(not(all(target_os = "linux", target_arch = "x86_64")))]
+        panic!("`j` mode runs x86-64 machine code in this process, so it is for x86-64 linux only");
     }
 
-    if let Some(output_file) = output_file {
-        let output_nasm_file_string = output_file.to_string() + ".nasm";
+    // the compiler always speaks nasm, gas.rs translates it at the very end
+    let gas_syntax = matches.value_of("asm-syntax") == Some("gas");
+    if let (Some(output_file), Some("builtin")) = (output_file, matches.value_of("assembler")) {
+        let executable = elf64::build_executable(&compiled_code, "_start").unwrap_or_else(|error| panic!("builtin assembler: {}", error));
+        std::fs::write(output_file, executable).expect("unable to write file");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(output_file, std::fs::Permissions::from_mode(0o755)).expect("unable to make the output executable");
+        }
+    } else if let Some(output_file) = output_file {
+        // gcc and ld only link for the os they run on, so for another --target the object file is the result
+        let cross = options.platform != Platform::host();
+        if gas_syntax && cross {
+            panic!("--asm-syntax gas is assembled by the `as` of this machine, so it can`t cross to another --target");
+        }
+        let output_asm_file_string = output_file.to_string() + if gas_syntax { ".s" } else { ".nasm" };
         let output_obj_file_string = output_file.to_string() + ".o";
-        let output_result_file_string;
-        if cfg!(target_os = "windows") {
-            output_result_file_string = output_file.to_string() + ".exe";
+        let output_result_file_string = if options.platform == Platform::Win64 {
+            output_file.to_string() + ".exe"
         } else {
-            output_result_file_string = output_file.to_string();
+            output_file.to_string()
         };
-        std::fs::write(output_nasm_file_string.clone(), compiled_code).expect("unable to write file");
-        if cfg!(target_os = "windows") {
-            if let Err(error_nasm) = std::process::Command::
-                new("nasm").
-                arg("-fwin64").
-                arg(output_nasm_file_string).
-                arg("-o").
-                arg(output_obj_file_string.clone()).
-                spawn() {
-                    eprintln!("{}", error_nasm);
-                }
-                
-            if let Err(error_gcc) = std::process::Command::
-                new("gcc").
-                arg(output_obj_file_string).
-                arg("-o").
-                arg(output_result_file_string).
-                spawn() {
-                    eprintln!("{}", error_gcc);
-                }
+        std::fs::write(output_asm_file_string.clone(), if gas_syntax { gas::from_nasm(&compiled_code, options.platform == Platform::Linux) } else { compiled_code }).expect("unable to write file");
+        let object_format = match options.platform {
+            Platform::Linux => "-felf64",
+            Platform::Win64 => "-fwin64",
+            Platform::MacOS => "-fmacho64",
+        };
+        if gas_syntax {
+            run_tool("as", &[&output_asm_file_string, "-o", &output_obj_file_string]);
         } else {
-            if let Err(error_nasm) = std::process::Command::
-                new("nasm").
-                arg("-felf64").
-                arg(output_nasm_file_string).
-                arg("-o").
-                arg(output_obj_file_string.clone()).
-                spawn() {
-                    eprintln!("{}", error_nasm);
-                }
-                
-            if let Err(error_gcc) = std::process::Command::
-                new("gcc").
-                arg("-no-pie").
-                arg(output_obj_file_string).
-                arg("-o").
-                arg(output_result_file_string).
-                spawn() {
-                    eprintln!("{}", error_gcc);
+            run_tool("nasm", &[object_format, &output_asm_file_string, "-o", &output_obj_file_string]);
+        }
+        if cross {
+            eprintln!("note: {} is for another os, link it there", output_obj_file_string);
+        } else if options.platform == Platform::Win64 {
+            run_tool("gcc", &[&output_obj_file_string, "-o", &output_result_file_string]);
+        } else if options.platform == Platform::MacOS {
+            // the asm loads absolute addresses, which a pie executable doesn`t allow
+            run_tool("cc", &["-Wl,-no_pie", &output_obj_file_string, "-o", &output_result_file_string]);
+        } else if options.freestanding {
+            // nothing to link with, _start is ours
+            run_tool("ld", &["-static", &output_obj_file_string, "-o", &output_result_file_string]);
+        } else {
+            run_tool("gcc", &["-no-pie", &output_obj_file_string, "-o", &output_result_file_string]);
+        }
+        if !matches.is_present("keep-temps") {
+            let temps = if cross { vec![output_asm_file_string] } else { vec![output_asm_file_string, output_obj_file_string] };
+            for temp in temps {
+                if let Err(error) = std::fs::remove_file(&temp) {
+                    eprintln!("warning: unable to remove {}: {}", temp, error);
                 }
+            }
         }
-
+    } else if gas_syntax {
+        println!("{}", gas::from_nasm(&compiled_code, options.platform == Platform::Linux));
     } else {
         println!("{}", compiled_code);
     }
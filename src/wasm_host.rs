@@ -0,0 +1,89 @@
+/*  wasm_host.rs
+    MIT License
+
+    Copyright (c) 2024 Aidar Shigapov
+
+    Permission is hereby granted, free of charge, to any person obtaining a copy
+    of this software and associated documentation files (the "Software"), to deal
+    in the Software without restriction, including without limitation the rights
+    to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+    copies of the Software, and to permit persons to whom the Software is
+    furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in all
+    copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+    OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+    SOFTWARE.
+*/
+// runs the wat backend output in wasmi, an interpreter written in rust, so `w` mode needs nothing but jalgo itself.
+// the "jalgo" imports are the only way out of the sandbox. files work like in the interpreter
+
+use std::io::{self, Write};
+use wasmi::{Caller, Config, Engine, Extern, Linker, Memory, Module, StackLimits, Store};
+use wasmi::core::Trap;
+use crate::{InterpreterContext, StackValueType, read_stdin_byte, read_stdin_int};
+
+fn memory(caller: &Caller<'_, InterpreterContext>) -> Memory {
+    caller.get_export("memory").and_then(Extern::into_memory).expect("the module has no memory")
+}
+
+fn define_imports(linker: &mut Linker<InterpreterContext>) -> Result<(), wasmi::errors::LinkerError> {
+    linker.func_wrap("jalgo", "print", |value: i64| print!("{} ", value))?;
+    linker.func_wrap("jalgo", "write_raw", |value: i64| {
+        let bytes = value.to_le_bytes();
+        let length = bytes.iter().position(|x| *x == 0).unwrap_or(bytes.len());
+        io::stdout().write_all(&bytes[..length]).expect("error on write_raw");
+    })?;
+    linker.func_wrap("jalgo", "read_int", |mut caller: Caller<'_, InterpreterContext>, address: i32| -> Result<i64, Trap> {
+        let (value, status) = read_stdin_int();
+        memory(&caller).write(&mut caller, address as usize, &value.to_le_bytes()).map_err(|error| Trap::new(error.to_string()))?;
+        Ok(status)
+    })?;
+    linker.func_wrap("jalgo", "read_char", || {
+        io::stdout().flush().expect("error on read_char");
+        read_stdin_byte().map_or(-1, |x| x as StackValueType)
+    })?;
+    linker.func_wrap("jalgo", "fopen", |mut caller: Caller<'_, InterpreterContext>, path: i32, mode: i32| {
+        let data = memory(&caller).data(&caller);
+        let path = &data[path as usize..];
+        let path = String::from_utf8_lossy(&path[..path.iter().position(|x| *x == 0).unwrap_or(path.len())]).to_string();
+        caller.data_mut().fopen(&path, ["r", "w", "a"][mode as usize])
+    })?;
+    linker.func_wrap("jalgo", "fread_byte", |mut caller: Caller<'_, InterpreterContext>, fd: i64| caller.data_mut().fread_byte(fd))?;
+    linker.func_wrap("jalgo", "fwrite_byte", |mut caller: Caller<'_, InterpreterContext>, fd: i64, byte: i64| caller.data_mut().fwrite_byte(fd, byte))?;
+    linker.func_wrap("jalgo", "fclose", |mut caller: Caller<'_, InterpreterContext>, fd: i64| caller.data_mut().fclose(fd))?;
+    linker.func_wrap("jalgo", "exit", |code: i64| -> Result<(), Trap> { Err(Trap::i32_exit(code as i32)) })?;
+    Ok(())
+}
+
+// runs `start` and returns the exit code
+pub fn run(wat: &str) -> i32 {
+    let binary = wat::parse_str(wat).unwrap_or_else(|error| panic!("invalid wat: {}", error));
+    let mut config = Config::default();
+    config.wasm_tail_call(true);
+    // deep recursion is fine in the other modes, so it is here too
+    config.set_stack_limits(StackLimits::new(1024, 1 << 24, 1 << 16).expect("invalid stack limits"));
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, binary.as_slice()).unwrap_or_else(|error| panic!("invalid wasm: {}", error));
+    let mut store = Store::new(&engine, InterpreterContext::default());
+    let mut linker = Linker::new(&engine);
+    define_imports(&mut linker).expect("unable to define the imports");
+    let instance = linker.instantiate(&mut store, &module).and_then(|x| x.start(&mut store)).unwrap_or_else(|error| panic!("unable to instantiate: {}", error));
+    let start = instance.get_typed_func::<(), ()>(&store, "start").expect("no start in the module");
+    let code = match start.call(&mut store, ()) {
+        Ok(()) => 0,
+        Err(trap) => trap.i32_exit_status().unwrap_or_else(|| {
+            io::stdout().flush().expect("unable to flush stdout");
+            eprintln!("error: {}", trap);
+            1
+        }),
+    };
+    io::stdout().flush().expect("unable to flush stdout");
+    code
+}
@@ -0,0 +1,422 @@
+/*  wat.rs
+    MIT License
+
+    Copyright (c) 2024 Aidar Shigapov
+
+    Permission is hereby granted, free of charge, to any person obtaining a copy
+    of this software and associated documentation files (the "Software"), to deal
+    in the Software without restriction, including without limitation the rights
+    to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+    copies of the Software, and to permit persons to whom the Software is
+    furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in all
+    copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+    OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+    SOFTWARE.
+*/
+// webassembly text. the data stack, data, vars and the heap live in the linear memory, so stack_head, read_from and write_to
+// are plain memory addresses. io goes through the "jalgo" imports (see wasm_host.rs), everything else is wasm.
+// wasm has no goto, so the functions with labels are a `loop` around a `br_table` that jumps to the segment between two labels
+
+use crate::{State, StateType, asm_label_name};
+use crate::lower::{lower, Op};
+
+// the memory layout. 0 stays null
+const SCRATCH: usize = 16;      // read_int puts the value here
+const LINE_BUFFER: usize = 32;
+const PATH_BUFFER: usize = LINE_BUFFER + 4096;
+const DATA_BEGIN: usize = PATH_BUFFER + 4096;
+const STACK_SIZE: usize = 8 << 20;
+const PAGE_SIZE: usize = 65536;
+
+const WAT_RUNTIME: &str = r##"  (import "jalgo" "print" (func $print (param i64)))
+  (import "jalgo" "write_raw" (func $write_raw (param i64)))
+  (import "jalgo" "read_int" (func $read_int (param i32) (result i64)))
+  (import "jalgo" "read_char" (func $read_char (result i64)))
+  (import "jalgo" "fopen" (func $fopen (param i32 i32) (result i64)))
+  (import "jalgo" "fread_byte" (func $fread_byte (param i64) (result i64)))
+  (import "jalgo" "fwrite_byte" (func $fwrite_byte (param i64 i64) (result i64)))
+  (import "jalgo" "fclose" (func $fclose (param i64) (result i64)))
+  (import "jalgo" "exit" (func $exit (param i64)))
+  (memory (export "memory") {pages})
+  (global $sp (mut i32) (i32.const {stack_end}))
+  (global $heap_end (mut i32) (i32.const {stack_end}))
+
+  (func $push (param $value i64)
+    global.get $sp
+    i32.const 8
+    i32.sub
+    global.set $sp
+    global.get $sp
+    local.get $value
+    i64.store)
+
+  (func $pop (result i64)
+    global.get $sp
+    i64.load
+    global.get $sp
+    i32.const 8
+    i32.add
+    global.set $sp)
+
+  ;; prints the data stack from the bottom to the top
+  (func $dump_stack (local $cell i32)
+    i32.const {stack_end}
+    local.set $cell
+    block $done
+      loop $next
+        local.get $cell
+        global.get $sp
+        i32.le_u
+        br_if $done
+        local.get $cell
+        i32.const 8
+        i32.sub
+        local.tee $cell
+        i64.load
+        call $print
+        br $next
+      end
+    end
+    i64.const 10
+    call $write_raw)
+
+  ;; pushes the line as a zero terminated stack string and its length, -1 on EOF
+  (func $read_line (local $length i32) (local $c i64)
+    block $done
+      block $eof
+        loop $next
+          call $read_char
+          local.tee $c
+          i64.const -1
+          i64.eq
+          br_if $eof
+          local.get $c
+          i64.const 10
+          i64.eq
+          br_if $done
+          local.get $length
+          local.get $c
+          i64.store8 offset={line_buffer}
+          local.get $length
+          i32.const 1
+          i32.add
+          local.tee $length
+          i32.const 4096
+          i32.lt_u
+          br_if $next
+        end
+        br $done
+      end
+      local.get $length
+      br_if $done
+      i64.const -1
+      call $push
+      return
+    end
+    i64.const 0
+    call $push
+    local.get $length
+    i64.extend_i32_u
+    local.set $c
+    block $pushed
+      loop $next
+        local.get $c
+        i64.eqz
+        br_if $pushed
+        local.get $c
+        i64.const 1
+        i64.sub
+        local.tee $c
+        i32.wrap_i64
+        i64.load8_u offset={line_buffer}
+        call $push
+        br $next
+      end
+    end
+    local.get $length
+    i64.extend_i32_u
+    call $push)
+
+  ;; pops a zero terminated stack string to the path buffer
+  (func $pop_path (local $length i32) (local $c i64)
+    loop $next
+      local.get $length
+      call $pop
+      local.tee $c
+      i64.store8 offset={path_buffer}
+      local.get $length
+      i32.const 1
+      i32.add
+      i32.const 4095
+      i32.and
+      local.set $length
+      local.get $c
+      i64.const 0
+      i64.ne
+      br_if $next
+    end)
+
+  ;; a bump allocator on top of memory.grow. free doesn`t give the memory back, but the blocks come zeroed
+  (func $alloc (param $size i64) (result i64) (local $address i32) (local $end i64) (local $pages i32)
+    local.get $size
+    i64.const 0
+    i64.lt_s
+    if
+      i64.const 0
+      return
+    end
+    global.get $heap_end
+    local.tee $address
+    i64.extend_i32_u
+    local.get $size
+    i64.const 15
+    i64.add
+    i64.const -16
+    i64.and
+    i64.add
+    local.tee $end
+    i64.const 4294967296
+    i64.gt_u
+    if
+      i64.const 0
+      return
+    end
+    local.get $end
+    i64.const 65535
+    i64.add
+    i64.const 16
+    i64.shr_u
+    i32.wrap_i64
+    memory.size
+    i32.sub
+    local.tee $pages
+    i32.const 0
+    i32.gt_s
+    if
+      local.get $pages
+      memory.grow
+      i32.const -1
+      i32.eq
+      if
+        i64.const 0
+        return
+      end
+    end
+    local.get $end
+    i32.wrap_i64
+    global.set $heap_end
+    local.get $address
+    i64.extend_i32_u)
+"##;
+
+fn function_name(state: &State) -> String {
+    format!("$st_{}", asm_label_name(&state.name))
+}
+
+// "text" with everything but letters, digits and spaces escaped
+fn wat_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|x| if x.is_ascii_alphanumeric() || *x == b' ' || *x == b'_' { (*x as char).to_string() } else { format!("\\{:02x}", x) }).collect()
+}
+
+struct Layout {
+    addresses: Vec<(String, usize)>, // data and var names -> addresses
+    stack_end: usize,
+}
+impl Layout {
+    fn address(&self, name: &str) -> usize {
+        self.addresses.iter().find(|x| x.0 == name).expect("invalid data or var name").1
+    }
+}
+
+fn compile_primitive(layout: &Layout, template_arguments: &[Vec<String>], state: &State) -> String {
+    let template_argument = |index: usize, default: usize| if state.template_arguments_index == 0 {
+        default
+    } else {
+        template_arguments[state.template_arguments_index].get(index).expect("too few template arguments").parse::<usize>().expect("template argument must be integer")
+    };
+    let binary = |instruction: &str| format!("call $pop\nlocal.set $b\ncall $pop\nlocal.get $b\n{}\ncall $push", instruction);
+    let compare = |instruction: &str| format!("call $pop\nlocal.set $b\ncall $pop\nlocal.get $b\n{}\ni64.extend_i32_u\ncall $push", instruction);
+    let load = |instruction: &str| format!("call $pop\ni32.wrap_i64\n{}\ncall $push", instruction);
+    let store = |instruction: &str| format!("call $pop\nlocal.set $b\ncall $pop\ni32.wrap_i64\nlocal.get $b\n{}", instruction);
+    match state.state_type {
+        StateType::Integer =>   format!("i64.const {}\ncall $push", state.name.parse::<i64>().expect("invalid integer")),
+        StateType::Data | StateType::Var => format!("i64.const {}\ncall $push", layout.address(&state.name)),
+        StateType::VarFetch =>  format!("i32.const {}\ni64.load\ncall $push", layout.address(&state.name[1..])),
+        StateType::VarStore =>  format!("i32.const {}\ncall $pop\ni64.store", layout.address(&state.name[1..])),
+        StateType::StackHead => "global.get $sp\ni64.extend_i32_u\ncall $push".to_string(),
+        StateType::Depth =>     format!("i32.const {}\nglobal.get $sp\ni32.sub\ni32.const 3\ni32.shr_u\ni64.extend_i32_u\ncall $push", layout.stack_end),
+        StateType::DumpStack => "call $dump_stack".to_string(),
+        StateType::ReadFrom =>  load("i64.load"),
+        StateType::ReadU8 =>    load("i64.load8_u"),
+        StateType::ReadI8 =>    load("i64.load8_s"),
+        StateType::ReadU16 =>   load("i64.load16_u"),
+        StateType::ReadI16 =>   load("i64.load16_s"),
+        StateType::ReadU32 =>   load("i64.load32_u"),
+        StateType::ReadI32 =>   load("i64.load32_s"),
+        StateType::WriteTo =>   store("i64.store"),
+        StateType::WriteU8 =>   store("i64.store8"),
+        StateType::WriteU16 =>  store("i64.store16"),
+        StateType::WriteU32 =>  store("i64.store32"),
+        StateType::Alloc =>     "call $pop\ncall $alloc\ncall $push".to_string(),
+        StateType::Free =>      "call $pop\ndrop".to_string(),
+        StateType::Eq =>        compare("i64.eq"),
+        StateType::Neq =>       compare("i64.ne"),
+        StateType::More =>      compare("i64.gt_s"),
+        StateType::Less =>      compare("i64.lt_s"),
+        StateType::Sum =>       binary("i64.add"),
+        StateType::Dif =>       binary("i64.sub"),
+        StateType::Mul =>       binary("i64.mul"),
+        StateType::Div =>       binary("i64.div_s"),
+        StateType::Inc =>       "call $pop\ni64.const 1\ni64.add\ncall $push".to_string(),
+        StateType::Dec =>       "call $pop\ni64.const 1\ni64.sub\ncall $push".to_string(),
+        StateType::Dup =>       format!("call $pop\nlocal.tee $a\ncall $push{}", "\nlocal.get $a\ncall $push".repeat(template_argument(0, 1))),
+        StateType::Pop =>       format!("global.get $sp\ni32.const {}\ni32.add\nglobal.set $sp", template_argument(0, 1) * 8),
+        StateType::Swap => {
+            let (first, second) = (template_argument(0, 0) * 8, template_argument(1, 1) * 8);
+            format!("global.get $sp\ni64.load offset={0}\nlocal.set $a\nglobal.get $sp\nglobal.get $sp\ni64.load offset={1}\ni64.store offset={0}\nglobal.get $sp\nlocal.get $a\ni64.store offset={1}", first, second)
+        }
+        StateType::Print =>     "call $pop\ncall $print".to_string(),
+        StateType::WriteRaw =>  "call $pop\ncall $write_raw".to_string(),
+        StateType::ReadInt =>   format!("i32.const {0}\ncall $read_int\nlocal.set $a\ni32.const {0}\ni64.load\ncall $push\nlocal.get $a\ncall $push", SCRATCH),
+        StateType::ReadChar =>  "call $read_char\ncall $push".to_string(),
+        StateType::ReadLine =>  "call $read_line".to_string(),
+        StateType::Fopen | StateType::FopenPtr => {
+            let mode = if state.template_arguments_index == 0 { "r" } else { template_arguments[state.template_arguments_index][0].as_str() };
+            let mode = match mode {
+                "r" => 0,
+                "w" => 1,
+                "a" => 2,
+                _ => panic!("unknown fopen mode \"{}\". possible values: r | w | a", mode),
+            };
+            if state.state_type == StateType::FopenPtr {
+                format!("call $pop\ni32.wrap_i64\ni32.const {}\ncall $fopen\ncall $push", mode)
+            } else {
+                format!("call $pop_path\ni32.const {}\ni32.const {}\ncall $fopen\ncall $push", PATH_BUFFER, mode)
+            }
+        }
+        StateType::FreadByte => "call $pop\ncall $fread_byte\ncall $push".to_string(),
+        StateType::FwriteByte =>"call $pop\nlocal.set $b\ncall $pop\nlocal.get $b\ncall $fwrite_byte\ncall $push".to_string(),
+        StateType::Fclose =>    "call $pop\ncall $fclose\ncall $push".to_string(),
+        StateType::Exit =>      "call $pop\ncall $exit".to_string(),
+        _ => panic!("{} is not a primitive", state.name),
+    }
+}
+
+fn compile_function(states: &[State], template_arguments: &[Vec<String>], layout: &Layout, state: &State) -> String {
+    let function = lower(states, template_arguments, state);
+    // segment 0 starts at the beginning (for __self__goto__), then one segment per jump target
+    let mut segments = vec![None; function.labels];
+    let mut segment_count = 1;
+    for op in function.ops.iter() {
+        if let Op::Jump(label) | Op::JumpIfNotPositive(label) | Op::TimesBegin{ exit: label, .. } | Op::TimesEnd{ again: label, .. } = op
+            && segments[*label].is_none() {
+            segments[*label] = Some(0);
+        }
+    }
+    for op in function.ops.iter() {
+        if let Op::Label(label) = op && segments[*label].is_some() {
+            segments[*label] = Some(segment_count);
+            segment_count += 1;
+        }
+    }
+    let segment = |label: usize| segments[label].expect("jump to an unused label");
+    let jump = |target: usize| format!("i32.const {}\nlocal.set $label\nbr $dispatch", target);
+    let mut code: Vec<String> = vec![String::new(); segment_count];
+    let mut current = 0;
+    for op in function.ops.iter() {
+        let text = match op {
+            Op::Label(label) => {
+                if let Some(next) = segments[*label] {
+                    current = next;
+                }
+                continue;
+            }
+            Op::Primitive(dep) => compile_primitive(layout, template_arguments, dep),
+            Op::JumpIfNotPositive(label) => format!("call $pop\ni64.const 0\ni64.le_s\nif\n{}\nend", jump(segment(*label))),
+            Op::Jump(label) => jump(segment(*label)),
+            Op::TimesBegin{ count, counter, exit } => {
+                let count = match count {
+                    Some(count) => format!("i64.const {}", count),
+                    None => "call $pop".to_string(),
+                };
+                format!("{}\nlocal.tee $counter{}\ni64.const 0\ni64.le_s\nif\n{}\nend", count, counter, jump(segment(*exit)))
+            }
+            Op::TimesEnd{ counter, again } => format!("local.get $counter{0}\ni64.const 1\ni64.sub\nlocal.tee $counter{0}\ni64.const 0\ni64.gt_s\nif\n{1}\nend", counter, jump(segment(*again))),
+            Op::Call(dep) => format!("call {}", function_name(dep)),
+            Op::TailCall(dep) => format!("return_call {}", function_name(dep)),
+            Op::Restart => jump(0),
+            Op::Return => "return".to_string(),
+        };
+        code[current] += text.as_str();
+        code[current] += "\n";
+    }
+
+    let mut out = format!("  ;; {}\n  (func {} (local $a i64) (local $b i64) (local $label i32)", state.name, function_name(state));
+    for counter in 0..function.counters {
+        out += format!(" (local $counter{} i64)", counter).as_str();
+    }
+    out += "\n";
+    let mut body = String::new();
+    let indent = |text: &str, depth: usize| text.lines().map(|x| format!("{}{}\n", "  ".repeat(depth), x)).collect::<String>();
+    if segment_count == 1 && !function.ops.iter().any(|x| matches!(x, Op::Restart)) {
+        body += indent(&code[0], 2).as_str();
+    } else {
+        body += "    loop $dispatch\n";
+        for segment in (0..segment_count).rev() {
+            body += indent(&format!("block $s{}", segment), 3 + segment_count - 1 - segment).as_str();
+        }
+        body += indent(&format!("local.get $label\nbr_table {}", (0..segment_count).map(|x| format!("$s{}", x)).collect::<Vec<_>>().join(" ")), 3 + segment_count).as_str();
+        for (segment, code) in code.iter().enumerate() {
+            body += indent("end", 3 + segment_count - 1 - segment).as_str();
+            body += indent(code, 3 + segment_count - 1 - segment).as_str();
+        }
+        body += "    end\n";
+    }
+    out += body.as_str();
+    out += "  )\n";
+    out
+}
+
+// `functions` are the non-inlined statements, start included. start and the exported ones are exported from the module
+pub fn compile(states: &[State], template_arguments: &[Vec<String>], functions: &[&State], exports: &[&str], data_sections: &[&(String, Vec<u8>)], variables: &[&String]) -> String {
+    let mut addresses = Vec::new();
+    let mut end = DATA_BEGIN;
+    for variable in variables.iter() {
+        addresses.push((variable.to_string(), end));
+        end += 8;
+    }
+    for data in data_sections.iter() {
+        addresses.push((data.0.clone(), end));
+        end += data.1.len().div_ceil(8) * 8;
+    }
+    let stack_end = end.div_ceil(16) * 16 + STACK_SIZE;
+    let layout = Layout{ addresses, stack_end };
+
+    let mut out = "(module\n".to_string();
+    out += WAT_RUNTIME
+        .replace("{pages}", &(stack_end.div_ceil(PAGE_SIZE) + 1).to_string())
+        .replace("{stack_end}", &stack_end.to_string())
+        .replace("{line_buffer}", &LINE_BUFFER.to_string())
+        .replace("{path_buffer}", &PATH_BUFFER.to_string()).as_str();
+    for data in data_sections.iter().filter(|x| !x.1.is_empty()) {
+        out += format!("  (data (i32.const {}) \"{}\") ;; {}\n", layout.address(&data.0), wat_string(&data.1), asm_label_name(&data.0)).as_str();
+    }
+    for function in functions.iter() {
+        out += "\n";
+        out += compile_function(states, template_arguments, &layout, function).as_str();
+    }
+    out += "\n";
+    for function in functions.iter().filter(|x| x.name == "start" || exports.contains(&x.name.as_str())) {
+        out += format!("  (export \"{}\" (func {}))\n", wat_string(function.name.as_bytes()), function_name(function)).as_str();
+    }
+    out += ")\n";
+    out
+}
@@ -0,0 +1,63 @@
+/*  aarch64.rs
+    MIT License
+
+    Copyright (c) 2024 Aidar Shigapov
+
+    Permission is hereby granted, free of charge, to any person obtaining a copy
+    of this software and associated documentation files (the "Software"), to deal
+    in the Software without restriction, including without limitation the rights
+    to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+    copies of the Software, and to permit persons to whom the Software is
+    furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in all
+    copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+    OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+    SOFTWARE.
+*/
+// the aarch64 asm of every program against tests/golden/aarch64, and the programs built from it against the interpreter`s golden runs.
+// off an aarch64 linux host that takes the cross gcc and qemu-aarch64 with the cross sysroot in /usr/aarch64-linux-gnu
+
+mod common;
+
+use common::*;
+use std::process::Command;
+
+#[test]
+fn aarch64_asm_matches_golden() {
+    for program in programs() {
+        let directory = work_dir("aarch64-asm", &program);
+        let asm = run(jalgo().arg(&program.path).arg("--arch").arg("aarch64"), &directory, &[]);
+        assert_eq!(asm.status, Some(0), "{}", program.name);
+        check_golden(&format!("aarch64/{}.s", program.name), &asm.stdout);
+    }
+}
+
+#[test]
+fn aarch64_matches_interpreter() {
+    let native = cfg!(all(target_os = "linux", target_arch = "aarch64"));
+    let tools = if native { has_tool("gcc") } else { has_tool("qemu-aarch64") && has_tool("aarch64-linux-gnu-gcc") };
+    if !tools {
+        eprintln!("skipping: no gcc on this aarch64 host, or no qemu-aarch64 and aarch64-linux-gnu-gcc on the others");
+        return;
+    }
+    for (program, expected) in cases() {
+        let directory = work_dir("aarch64", &program);
+        jalgo_ok(&[program.path.to_str().unwrap(), "prog", "--arch", "aarch64"], &directory);
+        let mut command = if native {
+            Command::new(directory.join("prog"))
+        } else {
+            let mut command = Command::new("qemu-aarch64");
+            command.env("QEMU_LD_PREFIX", "/usr/aarch64-linux-gnu").arg(directory.join("prog"));
+            command
+        };
+        let actual = run(&mut command, &directory, &program.stdin);
+        assert_eq!(actual, expected, "{}", program.name);
+    }
+}
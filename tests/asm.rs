@@ -0,0 +1,75 @@
+/*  asm.rs
+    MIT License
+
+    Copyright (c) 2024 Aidar Shigapov
+
+    Permission is hereby granted, free of charge, to any person obtaining a copy
+    of this software and associated documentation files (the "Software"), to deal
+    in the Software without restriction, including without limitation the rights
+    to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+    copies of the Software, and to permit persons to whom the Software is
+    furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in all
+    copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+    OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+    SOFTWARE.
+*/
+// the nasm asm of every program in every variant, and the inliner`s report, against tests/golden, so every instruction a change touches shows up in the diff.
+// the preludes differ between the oses, so the golden files are the linux ones
+
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use common::*;
+
+// the golden directory and the extra jalgo arguments of each code generation variant
+const VARIANTS: &[(&str, &[&str])] = &[
+    ("asm", &[]),
+    ("asm-native", &["--calling-convention", "native"]),
+    ("asm-register-cache", &["--register-cache"]),
+];
+
+#[test]
+fn asm_matches_golden() {
+    for (golden, extra_args) in VARIANTS {
+        for program in programs() {
+            let directory = work_dir(golden, &program);
+            let asm = run(jalgo().arg(&program.path).args(*extra_args), &directory, &[]);
+            assert_eq!(asm.status, Some(0), "jalgo {:?} failed on {}", extra_args, program.name);
+            check_golden(&format!("{}/{}.nasm", golden, program.name), &asm.stdout);
+        }
+    }
+}
+
+#[test]
+fn inline_report_matches_golden() {
+    for program in programs() {
+        let directory = work_dir("inline-report", &program);
+        let report = run(jalgo().arg(&program.path).arg("--emit").arg("inline-report"), &directory, &[]);
+        assert_eq!(report.status, Some(0), "jalgo --emit inline-report failed on {}", program.name);
+        check_golden(&format!("inline-report/{}.txt", program.name), &report.stdout);
+    }
+}
+
+#[test]
+fn dead_code_is_dropped_and_warned_about() {
+    let program = programs().into_iter().find(|x| x.name == "dead_code").expect("no dead_code program");
+    let output = jalgo().arg(&program.path).output().expect("unable to run jalgo");
+    assert_eq!(String::from_utf8_lossy(&output.stderr), concat!(
+        "warning: statement `only_helper_uses_me` is never used\n",
+        "warning: statement `helper` is never used\n",
+        "warning: data `greeting` is never used\n",
+        "warning: variable `counter` is never used\n",
+    ));
+    let asm = String::from_utf8_lossy(&output.stdout);
+    assert!(!asm.contains("\nhelper:\n") && !asm.contains("\nonly_helper_uses_me:\n") && !asm.contains("\n_private:\n"), "dead statements are compiled");
+    assert!(asm.contains("\napi:\n"), "the exported statement is dropped");
+}
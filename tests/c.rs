@@ -0,0 +1,42 @@
+/*  c.rs
+    MIT License
+
+    Copyright (c) 2024 Aidar Shigapov
+
+    Permission is hereby granted, free of charge, to any person obtaining a copy
+    of this software and associated documentation files (the "Software"), to deal
+    in the Software without restriction, including without limitation the rights
+    to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+    copies of the Software, and to permit persons to whom the Software is
+    furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in all
+    copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+    OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+    SOFTWARE.
+*/
+// builds every program with --target c and cc, runs it and compares it with the interpreter`s golden run
+
+mod common;
+
+use common::*;
+
+#[test]
+fn c_matches_interpreter() {
+    if !has_tool("cc") {
+        eprintln!("skipping: cc is not installed");
+        return;
+    }
+    for (program, expected) in cases() {
+        let directory = work_dir("c", &program);
+        jalgo_ok(&[program.path.to_str().unwrap(), "prog", "--target", "c"], &directory);
+        let actual = run(&mut executable(&directory), &directory, &program.stdin);
+        assert_eq!(actual, expected, "{}", program.name);
+    }
+}
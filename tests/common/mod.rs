@@ -0,0 +1,146 @@
+/*  mod.rs
+    MIT License
+
+    Copyright (c) 2024 Aidar Shigapov
+
+    Permission is hereby granted, free of charge, to any person obtaining a copy
+    of this software and associated documentation files (the "Software"), to deal
+    in the Software without restriction, including without limitation the rights
+    to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+    copies of the Software, and to permit persons to whom the Software is
+    furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in all
+    copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+    OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+    SOFTWARE.
+*/
+// helpers for the integration tests: the programs, running jalgo and what it builds, and the golden files.
+// every program runs in its own directory under CARGO_TARGET_TMPDIR, so the files it writes don`t end up in the repo.
+// JALGO_BLESS=1 rewrites the golden files instead of comparing with them
+
+#![allow(dead_code)] // each test binary uses its own part of this
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+// the interpreter allows read_from and write_to on alloc and data memory only,
+// so the golden runs of the programs that read the data stack through stack_head come from the builtin assembler
+pub const NOT_INTERPRETED: &[&str] = &["rule110"];
+
+pub struct Program {
+    pub name: String,
+    pub path: PathBuf,
+    pub stdin: Vec<u8>,
+}
+
+// what a program printed and how it exited
+#[derive(Debug, PartialEq)]
+pub struct Run {
+    pub stdout: String,
+    pub status: Option<i32>,
+}
+
+impl Run {
+    // the golden file is the exit status on the first line and then the stdout as is
+    pub fn to_golden(&self) -> String {
+        match self.status {
+            Some(status) => format!("exit {}\n{}", status, self.stdout),
+            None => format!("killed\n{}", self.stdout),
+        }
+    }
+
+    pub fn from_golden(golden: &str) -> Run {
+        let (status, stdout) = golden.split_once('\n').expect("the golden run has no status line");
+        Run{ stdout: stdout.to_string(), status: status.strip_prefix("exit ").map(|x| x.parse().expect("bad exit status in the golden run")) }
+    }
+}
+
+pub fn root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+// examples/*.jalgo and the small feature programs in tests/programs, with their .stdin next to them
+pub fn programs() -> Vec<Program> {
+    let mut programs = Vec::new();
+    for directory in ["examples", "tests/programs"] {
+        let mut paths = std::fs::read_dir(root().join(directory)).expect("unable to read the programs")
+            .map(|x| x.expect("unable to read the programs").path())
+            .filter(|x| x.extension().is_some_and(|x| x == "jalgo"))
+            .collect::<Vec<_>>();
+        paths.sort();
+        for path in paths {
+            let stdin = std::fs::read(path.with_extension("stdin")).unwrap_or_default();
+            programs.push(Program{ name: path.file_stem().unwrap().to_string_lossy().into_owned(), path, stdin });
+        }
+    }
+    programs
+}
+
+// a fresh directory for one program of one test
+pub fn work_dir(test: &str, program: &Program) -> PathBuf {
+    let directory = Path::new(env!("CARGO_TARGET_TMPDIR")).join(test).join(&program.name);
+    if directory.exists() {
+        std::fs::remove_dir_all(&directory).expect("unable to clean the work directory");
+    }
+    std::fs::create_dir_all(&directory).expect("unable to create the work directory");
+    directory
+}
+
+// the executable jalgo builds as `prog` in `directory`
+pub fn executable(directory: &Path) -> Command {
+    Command::new(directory.join(if cfg!(target_os = "windows") { "prog.exe" } else { "prog" }))
+}
+
+pub fn has_tool(name: &str) -> bool {
+    Command::new(name).arg("--version").stdout(Stdio::null()).stderr(Stdio::null()).status().is_ok()
+}
+
+// runs a command in `directory` with `stdin`, the same way for jalgo itself and for what it builds
+pub fn run(command: &mut Command, directory: &Path, stdin: &[u8]) -> Run {
+    let mut child = command.current_dir(directory).stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::inherit())
+        .spawn().unwrap_or_else(|error| panic!("unable to run {:?}: {}", command, error));
+    // the programs may exit before reading everything, so a broken pipe is fine here
+    let _ = child.stdin.take().unwrap().write_all(stdin);
+    let output = child.wait_with_output().expect("unable to wait for the program");
+    Run{ stdout: String::from_utf8_lossy(&output.stdout).into_owned(), status: output.status.code() }
+}
+
+pub fn jalgo() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_jalgo"))
+}
+
+// runs jalgo and panics if it fails, for the steps that build something
+pub fn jalgo_ok(args: &[&str], directory: &Path) {
+    let run = run(jalgo().args(args), directory, &[]);
+    assert_eq!(run.status, Some(0), "jalgo {:?} failed", args);
+}
+
+// every program with what it should do, from tests/golden/runs. tests/interpreter.rs keeps them in sync with `i` mode
+pub fn cases() -> Vec<(Program, Run)> {
+    programs().into_iter().map(|program| {
+        let path = root().join("tests/golden/runs").join(format!("{}.txt", program.name));
+        let golden = std::fs::read_to_string(&path).unwrap_or_else(|_| panic!("no golden file {}, run the tests with JALGO_BLESS=1", path.display()));
+        let expected = Run::from_golden(&golden);
+        (program, expected)
+    }).collect()
+}
+
+// compares `actual` with tests/golden/<path>, or writes it there with JALGO_BLESS=1
+pub fn check_golden(path: &str, actual: &str) {
+    let path = root().join("tests/golden").join(path);
+    if std::env::var_os("JALGO_BLESS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).expect("unable to create the golden directory");
+        std::fs::write(&path, actual).expect("unable to write the golden file");
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| panic!("no golden file {}, run the tests with JALGO_BLESS=1", path.display()));
+    assert!(expected == actual, "{} is out of date, run the tests with JALGO_BLESS=1 and check the diff", path.display());
+}
@@ -0,0 +1,720 @@
+	.section .rodata
+jalgo_int_fmt:
+	.asciz "%lli "
+jalgo_bytes_fmt:
+	.asciz "%.8s"
+jalgo_newline_fmt:
+	.asciz "\n"
+jalgo_int_scan_fmt:
+	.asciz "%lld"
+
+	.bss
+	.balign 16
+jalgo_read_int_value:
+	.skip 8
+jalgo_io_byte:
+	.skip 8
+jalgo_line_buffer:
+	.skip 4096
+jalgo_path_buffer:
+	.skip 4096
+jalgo_data_stack:
+	.skip 8388608
+jalgo_data_stack_end:
+
+	.text
+	.global main
+	.balign 4
+main:
+	adrp x19, jalgo_data_stack_end
+	add x19, x19, :lo12:jalgo_data_stack_end
+	mov x20, x19
+	bl st_start
+	mov x0, #0
+	bl exit
+
+jalgo_dump_stack:
+	// prints the data stack from the bottom to the top
+	str x30, [sp, #-16]!
+	mov x21, x20
+1:
+	cmp x21, x19
+	b.ls 2f
+	ldr x1, [x21, #-8]!
+	adrp x0, jalgo_int_fmt
+	add x0, x0, :lo12:jalgo_int_fmt
+	bl printf
+	b 1b
+2:
+	adrp x0, jalgo_newline_fmt
+	add x0, x0, :lo12:jalgo_newline_fmt
+	bl printf
+	ldr x30, [sp], #16
+	ret
+
+jalgo_read_line:
+	// pushes the line as a zero terminated stack string and its length, -1 on EOF
+	str x30, [sp, #-16]!
+	mov x21, #0
+1:
+	bl getchar
+	cmn w0, #1
+	b.eq 2f
+	cmp w0, #10
+	b.eq 3f
+	adrp x1, jalgo_line_buffer
+	add x1, x1, :lo12:jalgo_line_buffer
+	strb w0, [x1, x21]
+	add x21, x21, #1
+	cmp x21, #4096
+	b.lo 1b
+	b 3f
+2:
+	cbnz x21, 3f
+	mov x0, #-1
+	str x0, [x19, #-8]!
+	b 6f
+3:
+	str xzr, [x19, #-8]!
+	adrp x1, jalgo_line_buffer
+	add x1, x1, :lo12:jalgo_line_buffer
+	mov x2, x21
+4:
+	cbz x2, 5f
+	sub x2, x2, #1
+	ldrb w0, [x1, x2]
+	str x0, [x19, #-8]!
+	b 4b
+5:
+	str x21, [x19, #-8]!
+6:
+	ldr x30, [sp], #16
+	ret
+
+jalgo_pop_path:
+	// pops a zero terminated stack string into jalgo_path_buffer
+	adrp x1, jalgo_path_buffer
+	add x1, x1, :lo12:jalgo_path_buffer
+1:
+	ldr x0, [x19], #8
+	strb w0, [x1], #1
+	cbnz x0, 1b
+	ret
+
+jalgo_fread_byte:
+	// fd -> byte, -1 on EOF, -errno on error
+	ldr x0, [x19]
+	adrp x1, jalgo_io_byte
+	add x1, x1, :lo12:jalgo_io_byte
+	str xzr, [x1]
+	mov x2, #1
+	mov x8, #63
+	svc #0
+	cmp x0, #0
+	b.lt 1f
+	mov x0, #-1
+	b.eq 1f
+	adrp x1, jalgo_io_byte
+	ldr x0, [x1, :lo12:jalgo_io_byte]
+1:
+	str x0, [x19]
+	ret
+
+jalgo_fwrite_byte:
+	// fd byte -> 1, -errno on error. printf`s buffer goes out first, so fd 1 keeps the order
+	str x30, [sp, #-16]!
+	mov x0, #0
+	bl fflush
+	ldr x30, [sp], #16
+	ldp x2, x0, [x19], #8
+	adrp x1, jalgo_io_byte
+	add x1, x1, :lo12:jalgo_io_byte
+	strb w2, [x1]
+	mov x2, #1
+	mov x8, #64
+	svc #0
+	str x0, [x19]
+	ret
+
+	.balign 4
+st_twice: // twice
+	str x30, [sp, #-16]!
+	mov x0, #2
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // mul
+	mul x0, x1, x0
+	str x0, [x19, #8]!
+	ldr x30, [sp], #16
+	ret
+
+	.balign 4
+st_start: // start
+	str x30, [sp, #-16]!
+	mov x0, #0
+	str x0, [x19, #-8]!
+	ldr x0, [x19], #8
+	cmp x0, #0
+	b.le .Lst_start_42
+	mov x0, #0
+	str x0, [x19, #-8]!
+	b .Lst_start_43
+.Lst_start_42:
+	mov x0, #1
+	str x0, [x19, #-8]!
+.Lst_start_43:
+	ldr x1, [x19], #8 // print
+	adrp x0, jalgo_int_fmt
+	add x0, x0, :lo12:jalgo_int_fmt
+	bl printf
+	mov x0, #5
+	str x0, [x19, #-8]!
+	ldr x0, [x19], #8
+	cmp x0, #0
+	b.le .Lst_start_47
+	mov x0, #0
+	str x0, [x19, #-8]!
+	b .Lst_start_48
+.Lst_start_47:
+	mov x0, #1
+	str x0, [x19, #-8]!
+.Lst_start_48:
+	ldr x1, [x19], #8 // print
+	adrp x0, jalgo_int_fmt
+	add x0, x0, :lo12:jalgo_int_fmt
+	bl printf
+	mov x0, #0
+	str x0, [x19, #-8]!
+	ldr x0, [x19], #8
+	cmp x0, #0
+	b.le .Lst_start_52
+	mov x0, #0
+	str x0, [x19, #-8]!
+	b .Lst_start_53
+.Lst_start_52:
+	mov x0, #1
+	str x0, [x19, #-8]!
+.Lst_start_53:
+	ldr x0, [x19], #8
+	cmp x0, #0
+	b.le .Lst_start_57
+	mov x0, #0
+	str x0, [x19, #-8]!
+	b .Lst_start_58
+.Lst_start_57:
+	mov x0, #1
+	str x0, [x19, #-8]!
+.Lst_start_58:
+	ldr x1, [x19], #8 // print
+	adrp x0, jalgo_int_fmt
+	add x0, x0, :lo12:jalgo_int_fmt
+	bl printf
+	mov x0, #-3
+	str x0, [x19, #-8]!
+	ldr x0, [x19] // dup
+	str x0, [x19, #-8]!
+	mov x0, #0
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // less
+	cmp x1, x0
+	cset x0, lt
+	str x0, [x19, #8]!
+	ldr x0, [x19], #8
+	cmp x0, #0
+	b.le .Lst_start_67
+	add x19, x19, #8
+	mov x0, #0
+	str x0, [x19, #-8]!
+	b .Lst_start_74
+.Lst_start_67:
+	ldr x0, [x19] // dup
+	str x0, [x19, #-8]!
+	mov x0, #9
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // more
+	cmp x1, x0
+	cset x0, gt
+	str x0, [x19, #8]!
+	ldr x0, [x19], #8
+	cmp x0, #0
+	b.le .Lst_start_74
+	add x19, x19, #8
+	mov x0, #9
+	str x0, [x19, #-8]!
+.Lst_start_74:
+	ldr x1, [x19], #8 // print
+	adrp x0, jalgo_int_fmt
+	add x0, x0, :lo12:jalgo_int_fmt
+	bl printf
+	mov x0, #4
+	str x0, [x19, #-8]!
+	ldr x0, [x19] // dup
+	str x0, [x19, #-8]!
+	mov x0, #0
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // less
+	cmp x1, x0
+	cset x0, lt
+	str x0, [x19, #8]!
+	ldr x0, [x19], #8
+	cmp x0, #0
+	b.le .Lst_start_83
+	add x19, x19, #8
+	mov x0, #0
+	str x0, [x19, #-8]!
+	b .Lst_start_90
+.Lst_start_83:
+	ldr x0, [x19] // dup
+	str x0, [x19, #-8]!
+	mov x0, #9
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // more
+	cmp x1, x0
+	cset x0, gt
+	str x0, [x19, #8]!
+	ldr x0, [x19], #8
+	cmp x0, #0
+	b.le .Lst_start_90
+	add x19, x19, #8
+	mov x0, #9
+	str x0, [x19, #-8]!
+.Lst_start_90:
+	ldr x1, [x19], #8 // print
+	adrp x0, jalgo_int_fmt
+	add x0, x0, :lo12:jalgo_int_fmt
+	bl printf
+	mov x0, #12
+	str x0, [x19, #-8]!
+	ldr x0, [x19] // dup
+	str x0, [x19, #-8]!
+	mov x0, #0
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // less
+	cmp x1, x0
+	cset x0, lt
+	str x0, [x19, #8]!
+	ldr x0, [x19], #8
+	cmp x0, #0
+	b.le .Lst_start_99
+	add x19, x19, #8
+	mov x0, #0
+	str x0, [x19, #-8]!
+	b .Lst_start_106
+.Lst_start_99:
+	ldr x0, [x19] // dup
+	str x0, [x19, #-8]!
+	mov x0, #9
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // more
+	cmp x1, x0
+	cset x0, gt
+	str x0, [x19, #8]!
+	ldr x0, [x19], #8
+	cmp x0, #0
+	b.le .Lst_start_106
+	add x19, x19, #8
+	mov x0, #9
+	str x0, [x19, #-8]!
+.Lst_start_106:
+	ldr x1, [x19], #8 // print
+	adrp x0, jalgo_int_fmt
+	add x0, x0, :lo12:jalgo_int_fmt
+	bl printf
+	mov x0, #5
+	str x0, [x19, #-8]!
+	mov x0, #0
+	str x0, [x19, #-8]!
+	ldr x0, [x19, #0] // swap
+	ldr x1, [x19, #8]
+	str x1, [x19, #0]
+	str x0, [x19, #8]
+	ldr x0, [x19], #8
+	str x0, [sp, #8] // times
+	cmp x0, #0
+	b.le .Lst_start_112
+.Lst_start_110:
+	ldr x0, [x19] // inc
+	add x0, x0, #1
+	str x0, [x19]
+	ldr x0, [sp, #8] // end of times
+	subs x0, x0, #1
+	str x0, [sp, #8]
+	b.gt .Lst_start_110
+.Lst_start_112:
+	ldr x1, [x19], #8 // print
+	adrp x0, jalgo_int_fmt
+	add x0, x0, :lo12:jalgo_int_fmt
+	bl printf
+	mov x0, #3
+	str x0, [x19, #-8]!
+	mov x0, #0
+	str x0, [x19, #-8]!
+	ldr x0, [x19, #0] // swap
+	ldr x1, [x19, #8]
+	str x1, [x19, #0]
+	str x0, [x19, #8]
+	ldr x0, [x19], #8
+	str x0, [sp, #8] // times
+	cmp x0, #0
+	b.le .Lst_start_118
+.Lst_start_116:
+	ldr x0, [x19] // inc
+	add x0, x0, #1
+	str x0, [x19]
+	ldr x0, [sp, #8] // end of times
+	subs x0, x0, #1
+	str x0, [sp, #8]
+	b.gt .Lst_start_116
+.Lst_start_118:
+	mov x0, #0
+	str x0, [x19, #-8]!
+	ldr x0, [x19, #0] // swap
+	ldr x1, [x19, #8]
+	str x1, [x19, #0]
+	str x0, [x19, #8]
+	ldr x0, [x19], #8
+	str x0, [sp, #8] // times
+	cmp x0, #0
+	b.le .Lst_start_124
+.Lst_start_122:
+	ldr x0, [x19] // inc
+	add x0, x0, #1
+	str x0, [x19]
+	ldr x0, [sp, #8] // end of times
+	subs x0, x0, #1
+	str x0, [sp, #8]
+	b.gt .Lst_start_122
+.Lst_start_124:
+	ldr x1, [x19], #8 // print
+	adrp x0, jalgo_int_fmt
+	add x0, x0, :lo12:jalgo_int_fmt
+	bl printf
+	mov x0, #21
+	str x0, [x19, #-8]!
+	bl st_twice
+	ldr x1, [x19], #8 // print
+	adrp x0, jalgo_int_fmt
+	add x0, x0, :lo12:jalgo_int_fmt
+	bl printf
+	mov x0, #0
+	str x0, [x19, #-8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	ldr x1, [x19], #8 // print
+	adrp x0, jalgo_int_fmt
+	add x0, x0, :lo12:jalgo_int_fmt
+	bl printf
+	mov x0, #1
+	str x0, [x19, #-8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	mov x0, #1
+	str x0, [x19, #-8]!
+	ldp x0, x1, [x19] // sum
+	add x0, x1, x0
+	str x0, [x19, #8]!
+	ldr x1, [x19], #8 // print
+	adrp x0, jalgo_int_fmt
+	add x0, x0, :lo12:jalgo_int_fmt
+	bl printf
+	sub x0, x20, x19 // depth
+	lsr x0, x0, #3
+	str x0, [x19, #-8]!
+	ldr x1, [x19], #8 // print
+	adrp x0, jalgo_int_fmt
+	add x0, x0, :lo12:jalgo_int_fmt
+	bl printf
+	ldr x30, [sp], #16
+	ret
+
+	.section .note.GNU-stack, "", %progbits
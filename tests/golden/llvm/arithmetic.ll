@@ -0,0 +1,692 @@
+@int_fmt = private unnamed_addr constant [6 x i8] c"%lld \00"
+@bytes_fmt = private unnamed_addr constant [5 x i8] c"%.8s\00"
+@newline_fmt = private unnamed_addr constant [2 x i8] c"\0A\00"
+@int_scan_fmt = private unnamed_addr constant [5 x i8] c"%lld\00"
+@stack = internal global [1048576 x i64] zeroinitializer
+@line_buffer = internal global [4096 x i8] zeroinitializer
+@path_buffer = internal global [4096 x i8] zeroinitializer
+
+declare i32 @printf(ptr, ...)
+declare i32 @scanf(ptr, ...)
+declare i32 @getchar()
+declare ptr @malloc(i64)
+declare void @free(ptr)
+declare void @exit(i32) noreturn
+declare i32 @fflush(ptr)
+declare i32 @open(ptr, i32, ...)
+declare i64 @read(i32, ptr, i64)
+declare i64 @write(i32, ptr, i64)
+declare i32 @close(i32)
+declare ptr @__errno_location()
+
+; prints the data stack from the bottom to the top
+define internal void @jalgo_dump_stack(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %cell = phi ptr [ getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0), %entry ], [ %next, %body ]
+  %more = icmp ugt ptr %cell, %sp
+  br i1 %more, label %body, label %end
+body:
+  %next = getelementptr i64, ptr %cell, i64 -1
+  %value = load i64, ptr %next
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %value)
+  br label %loop
+end:
+  call i32 (ptr, ...) @printf(ptr @newline_fmt)
+  ret void
+}
+
+; up to 8 bytes of the value until a zero byte
+define internal void @jalgo_write_raw(i64 %value) {
+  %bytes = alloca i64
+  store i64 %value, ptr %bytes
+  call i32 (ptr, ...) @printf(ptr @bytes_fmt, ptr %bytes)
+  ret void
+}
+
+; pushes the value and the scanf status
+define internal ptr @jalgo_read_int(ptr %sp) {
+  %value = alloca i64
+  store i64 0, ptr %value
+  %status = call i32 (ptr, ...) @scanf(ptr @int_scan_fmt, ptr %value)
+  %loaded = load i64, ptr %value
+  %status64 = sext i32 %status to i64
+  %value_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 %loaded, ptr %value_cell
+  %new_sp = getelementptr i64, ptr %sp, i64 -2
+  store i64 %status64, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pushes the line as a zero terminated stack string and its length, -1 on EOF
+define internal ptr @jalgo_read_line(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %length = phi i64 [ 0, %entry ], [ %next_length, %append ]
+  %c = call i32 @getchar()
+  %eof = icmp eq i32 %c, -1
+  br i1 %eof, label %at_eof, label %not_eof
+not_eof:
+  %newline = icmp eq i32 %c, 10
+  br i1 %newline, label %done, label %append
+append:
+  %byte = trunc i32 %c to i8
+  %slot = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %length
+  store i8 %byte, ptr %slot
+  %next_length = add i64 %length, 1
+  %full = icmp eq i64 %next_length, 4096
+  br i1 %full, label %done, label %loop
+at_eof:
+  %empty = icmp eq i64 %length, 0
+  br i1 %empty, label %nothing, label %done
+nothing:
+  %eof_sp = getelementptr i64, ptr %sp, i64 -1
+  store i64 -1, ptr %eof_sp
+  ret ptr %eof_sp
+done:
+  %final_length = phi i64 [ %length, %not_eof ], [ %next_length, %append ], [ %length, %at_eof ]
+  %zero_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 0, ptr %zero_cell
+  br label %copy
+copy:
+  %i = phi i64 [ 0, %done ], [ %next_i, %copy_byte ]
+  %copy_more = icmp ult i64 %i, %final_length
+  br i1 %copy_more, label %copy_byte, label %copy_end
+copy_byte:
+  %source = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %i
+  %loaded = load i8, ptr %source
+  %extended = zext i8 %loaded to i64
+  %from_end = sub i64 %i, %final_length
+  %offset = sub i64 %from_end, 1
+  %target = getelementptr i64, ptr %sp, i64 %offset
+  store i64 %extended, ptr %target
+  %next_i = add i64 %i, 1
+  br label %copy
+copy_end:
+  %length_offset = sub i64 -2, %final_length
+  %new_sp = getelementptr i64, ptr %sp, i64 %length_offset
+  store i64 %final_length, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pops a zero terminated stack string into @path_buffer
+define internal ptr @jalgo_pop_path(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %i = phi i64 [ 0, %entry ], [ %next, %loop ]
+  %cell = getelementptr i64, ptr %sp, i64 %i
+  %value = load i64, ptr %cell
+  %byte = trunc i64 %value to i8
+  %slot = getelementptr [4096 x i8], ptr @path_buffer, i64 0, i64 %i
+  store i8 %byte, ptr %slot
+  %next = add i64 %i, 1
+  %more = icmp ne i64 %value, 0
+  br i1 %more, label %loop, label %end
+end:
+  %new_sp = getelementptr i64, ptr %sp, i64 %next
+  ret ptr %new_sp
+}
+
+; -1 from libc becomes -errno, like the raw syscalls
+define internal i64 @jalgo_result(i64 %result) {
+entry:
+  %failed = icmp slt i64 %result, 0
+  br i1 %failed, label %error, label %ok
+ok:
+  ret i64 %result
+error:
+  %errno_pointer = call ptr @__errno_location()
+  %errno = load i32, ptr %errno_pointer
+  %errno64 = sext i32 %errno to i64
+  %code = sub i64 0, %errno64
+  ret i64 %code
+}
+
+define internal i64 @jalgo_fopen(ptr %path, i32 %flags) {
+  %fd = call i32 (ptr, i32, ...) @open(ptr %path, i32 %flags, i32 420)
+  %fd64 = sext i32 %fd to i64
+  %result = call i64 @jalgo_result(i64 %fd64)
+  ret i64 %result
+}
+
+; fd -> byte, -1 on EOF, -errno on error
+define internal i64 @jalgo_fread_byte(i64 %fd) {
+  %byte = alloca i8
+  store i8 0, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @read(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  %loaded = load i8, ptr %byte
+  %extended = zext i8 %loaded to i64
+  %is_byte = icmp eq i64 %count, 1
+  %is_eof = icmp eq i64 %count, 0
+  %eof_or_error = select i1 %is_eof, i64 -1, i64 %result
+  %value = select i1 %is_byte, i64 %extended, i64 %eof_or_error
+  ret i64 %value
+}
+
+; fd byte -> 1, -errno on error. printf output goes first
+define internal i64 @jalgo_fwrite_byte(i64 %fd, i64 %value) {
+  call i32 @fflush(ptr null)
+  %byte = alloca i8
+  %truncated = trunc i64 %value to i8
+  store i8 %truncated, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @write(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  ret i64 %result
+}
+
+define internal i64 @jalgo_fclose(i64 %fd) {
+  call i32 @fflush(ptr null)
+  %fd32 = trunc i64 %fd to i32
+  %status = call i32 @close(i32 %fd32)
+  %status64 = sext i32 %status to i64
+  %result = call i64 @jalgo_result(i64 %status64)
+  ret i64 %result
+}
+
+
+; start
+define internal ptr @st_start(ptr %sp.arg) {
+entry:
+  %sp = alloca ptr
+  store ptr %sp.arg, ptr %sp
+  br label %restart
+restart:
+  %t1 = load ptr, ptr %sp
+  %t2 = getelementptr i64, ptr %t1, i64 -1
+  store ptr %t2, ptr %sp
+  store i64 3, ptr %t2
+  %t3 = load ptr, ptr %sp
+  %t4 = getelementptr i64, ptr %t3, i64 -1
+  store ptr %t4, ptr %sp
+  store i64 4, ptr %t4
+  %t5 = load ptr, ptr %sp
+  %t6 = load i64, ptr %t5
+  %t7 = getelementptr i64, ptr %t5, i64 1
+  %t8 = load i64, ptr %t7
+  %t9 = mul i64 %t8, %t6
+  %t10 = getelementptr i64, ptr %t5, i64 1
+  store ptr %t10, ptr %sp
+  store i64 %t9, ptr %t10
+  %t11 = load ptr, ptr %sp
+  %t12 = getelementptr i64, ptr %t11, i64 -1
+  store ptr %t12, ptr %sp
+  store i64 7, ptr %t12
+  %t13 = load ptr, ptr %sp
+  %t14 = load i64, ptr %t13
+  %t15 = getelementptr i64, ptr %t13, i64 1
+  %t16 = load i64, ptr %t15
+  %t17 = add i64 %t16, %t14
+  %t18 = getelementptr i64, ptr %t13, i64 1
+  store ptr %t18, ptr %sp
+  store i64 %t17, ptr %t18
+  %t19 = load ptr, ptr %sp
+  %t20 = load i64, ptr %t19
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t20)
+  %t21 = getelementptr i64, ptr %t19, i64 1
+  store ptr %t21, ptr %sp
+  %t22 = load ptr, ptr %sp
+  %t23 = getelementptr i64, ptr %t22, i64 -1
+  store ptr %t23, ptr %sp
+  store i64 10, ptr %t23
+  %t24 = load ptr, ptr %sp
+  %t25 = getelementptr i64, ptr %t24, i64 -1
+  store ptr %t25, ptr %sp
+  store i64 3, ptr %t25
+  %t26 = load ptr, ptr %sp
+  %t27 = load i64, ptr %t26
+  %t28 = getelementptr i64, ptr %t26, i64 1
+  %t29 = load i64, ptr %t28
+  %t30 = sub i64 %t29, %t27
+  %t31 = getelementptr i64, ptr %t26, i64 1
+  store ptr %t31, ptr %sp
+  store i64 %t30, ptr %t31
+  %t32 = load ptr, ptr %sp
+  %t33 = load i64, ptr %t32
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t33)
+  %t34 = getelementptr i64, ptr %t32, i64 1
+  store ptr %t34, ptr %sp
+  %t35 = load ptr, ptr %sp
+  %t36 = getelementptr i64, ptr %t35, i64 -1
+  store ptr %t36, ptr %sp
+  store i64 3, ptr %t36
+  %t37 = load ptr, ptr %sp
+  %t38 = getelementptr i64, ptr %t37, i64 -1
+  store ptr %t38, ptr %sp
+  store i64 10, ptr %t38
+  %t39 = load ptr, ptr %sp
+  %t40 = load i64, ptr %t39
+  %t41 = getelementptr i64, ptr %t39, i64 1
+  %t42 = load i64, ptr %t41
+  %t43 = sub i64 %t42, %t40
+  %t44 = getelementptr i64, ptr %t39, i64 1
+  store ptr %t44, ptr %sp
+  store i64 %t43, ptr %t44
+  %t45 = load ptr, ptr %sp
+  %t46 = load i64, ptr %t45
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t46)
+  %t47 = getelementptr i64, ptr %t45, i64 1
+  store ptr %t47, ptr %sp
+  %t48 = load ptr, ptr %sp
+  %t49 = getelementptr i64, ptr %t48, i64 -1
+  store ptr %t49, ptr %sp
+  store i64 100, ptr %t49
+  %t50 = load ptr, ptr %sp
+  %t51 = getelementptr i64, ptr %t50, i64 -1
+  store ptr %t51, ptr %sp
+  store i64 7, ptr %t51
+  %t52 = load ptr, ptr %sp
+  %t53 = load i64, ptr %t52
+  %t54 = getelementptr i64, ptr %t52, i64 1
+  %t55 = load i64, ptr %t54
+  %t56 = sdiv i64 %t55, %t53
+  %t57 = getelementptr i64, ptr %t52, i64 1
+  store ptr %t57, ptr %sp
+  store i64 %t56, ptr %t57
+  %t58 = load ptr, ptr %sp
+  %t59 = load i64, ptr %t58
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t59)
+  %t60 = getelementptr i64, ptr %t58, i64 1
+  store ptr %t60, ptr %sp
+  %t61 = load ptr, ptr %sp
+  %t62 = getelementptr i64, ptr %t61, i64 -1
+  store ptr %t62, ptr %sp
+  store i64 -7, ptr %t62
+  %t63 = load ptr, ptr %sp
+  %t64 = getelementptr i64, ptr %t63, i64 -1
+  store ptr %t64, ptr %sp
+  store i64 2, ptr %t64
+  %t65 = load ptr, ptr %sp
+  %t66 = load i64, ptr %t65
+  %t67 = getelementptr i64, ptr %t65, i64 1
+  %t68 = load i64, ptr %t67
+  %t69 = sdiv i64 %t68, %t66
+  %t70 = getelementptr i64, ptr %t65, i64 1
+  store ptr %t70, ptr %sp
+  store i64 %t69, ptr %t70
+  %t71 = load ptr, ptr %sp
+  %t72 = load i64, ptr %t71
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t72)
+  %t73 = getelementptr i64, ptr %t71, i64 1
+  store ptr %t73, ptr %sp
+  %t74 = load ptr, ptr %sp
+  %t75 = getelementptr i64, ptr %t74, i64 -1
+  store ptr %t75, ptr %sp
+  store i64 7, ptr %t75
+  %t76 = load ptr, ptr %sp
+  %t77 = getelementptr i64, ptr %t76, i64 -1
+  store ptr %t77, ptr %sp
+  store i64 -2, ptr %t77
+  %t78 = load ptr, ptr %sp
+  %t79 = load i64, ptr %t78
+  %t80 = getelementptr i64, ptr %t78, i64 1
+  %t81 = load i64, ptr %t80
+  %t82 = sdiv i64 %t81, %t79
+  %t83 = getelementptr i64, ptr %t78, i64 1
+  store ptr %t83, ptr %sp
+  store i64 %t82, ptr %t83
+  %t84 = load ptr, ptr %sp
+  %t85 = load i64, ptr %t84
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t85)
+  %t86 = getelementptr i64, ptr %t84, i64 1
+  store ptr %t86, ptr %sp
+  %t87 = load ptr, ptr %sp
+  %t88 = getelementptr i64, ptr %t87, i64 -1
+  store ptr %t88, ptr %sp
+  store i64 -100, ptr %t88
+  %t89 = load ptr, ptr %sp
+  %t90 = getelementptr i64, ptr %t89, i64 -1
+  store ptr %t90, ptr %sp
+  store i64 -7, ptr %t90
+  %t91 = load ptr, ptr %sp
+  %t92 = load i64, ptr %t91
+  %t93 = getelementptr i64, ptr %t91, i64 1
+  %t94 = load i64, ptr %t93
+  %t95 = sdiv i64 %t94, %t92
+  %t96 = getelementptr i64, ptr %t91, i64 1
+  store ptr %t96, ptr %sp
+  store i64 %t95, ptr %t96
+  %t97 = load ptr, ptr %sp
+  %t98 = load i64, ptr %t97
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t98)
+  %t99 = getelementptr i64, ptr %t97, i64 1
+  store ptr %t99, ptr %sp
+  %t100 = load ptr, ptr %sp
+  %t101 = getelementptr i64, ptr %t100, i64 -1
+  store ptr %t101, ptr %sp
+  store i64 1, ptr %t101
+  %t102 = load ptr, ptr %sp
+  %t103 = getelementptr i64, ptr %t102, i64 -1
+  store ptr %t103, ptr %sp
+  store i64 2, ptr %t103
+  %t104 = load ptr, ptr %sp
+  %t105 = load i64, ptr %t104
+  %t106 = getelementptr i64, ptr %t104, i64 1
+  %t107 = load i64, ptr %t106
+  store i64 %t107, ptr %t104
+  %t108 = getelementptr i64, ptr %t104, i64 1
+  store i64 %t105, ptr %t108
+  %t109 = load ptr, ptr %sp
+  %t110 = load i64, ptr %t109
+  %t111 = getelementptr i64, ptr %t109, i64 1
+  %t112 = load i64, ptr %t111
+  %t113 = sub i64 %t112, %t110
+  %t114 = getelementptr i64, ptr %t109, i64 1
+  store ptr %t114, ptr %sp
+  store i64 %t113, ptr %t114
+  %t115 = load ptr, ptr %sp
+  %t116 = load i64, ptr %t115
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t116)
+  %t117 = getelementptr i64, ptr %t115, i64 1
+  store ptr %t117, ptr %sp
+  %t118 = load ptr, ptr %sp
+  %t119 = getelementptr i64, ptr %t118, i64 -1
+  store ptr %t119, ptr %sp
+  store i64 5, ptr %t119
+  %t120 = load ptr, ptr %sp
+  %t121 = load i64, ptr %t120
+  %t122 = getelementptr i64, ptr %t120, i64 -1
+  store ptr %t122, ptr %sp
+  store i64 %t121, ptr %t122
+  %t123 = getelementptr i64, ptr %t122, i64 1
+  store i64 %t121, ptr %t123
+  %t124 = load ptr, ptr %sp
+  %t125 = load i64, ptr %t124
+  %t126 = getelementptr i64, ptr %t124, i64 1
+  %t127 = load i64, ptr %t126
+  %t128 = mul i64 %t127, %t125
+  %t129 = getelementptr i64, ptr %t124, i64 1
+  store ptr %t129, ptr %sp
+  store i64 %t128, ptr %t129
+  %t130 = load ptr, ptr %sp
+  %t131 = load i64, ptr %t130
+  %t132 = getelementptr i64, ptr %t130, i64 -1
+  store ptr %t132, ptr %sp
+  store i64 %t131, ptr %t132
+  %t133 = getelementptr i64, ptr %t132, i64 1
+  store i64 %t131, ptr %t133
+  %t134 = load ptr, ptr %sp
+  %t135 = load i64, ptr %t134
+  %t136 = getelementptr i64, ptr %t134, i64 1
+  %t137 = load i64, ptr %t136
+  %t138 = add i64 %t137, %t135
+  %t139 = getelementptr i64, ptr %t134, i64 1
+  store ptr %t139, ptr %sp
+  store i64 %t138, ptr %t139
+  %t140 = load ptr, ptr %sp
+  %t141 = load i64, ptr %t140
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t141)
+  %t142 = getelementptr i64, ptr %t140, i64 1
+  store ptr %t142, ptr %sp
+  %t143 = load ptr, ptr %sp
+  %t144 = getelementptr i64, ptr %t143, i64 -1
+  store ptr %t144, ptr %sp
+  store i64 1, ptr %t144
+  %t145 = load ptr, ptr %sp
+  %t146 = getelementptr i64, ptr %t145, i64 -1
+  store ptr %t146, ptr %sp
+  store i64 2, ptr %t146
+  %t147 = load ptr, ptr %sp
+  %t148 = getelementptr i64, ptr %t147, i64 -1
+  store ptr %t148, ptr %sp
+  store i64 3, ptr %t148
+  %t149 = load ptr, ptr %sp
+  %t150 = load i64, ptr %t149
+  %t151 = getelementptr i64, ptr %t149, i64 1
+  %t152 = load i64, ptr %t151
+  %t153 = getelementptr i64, ptr %t149, i64 2
+  %t154 = load i64, ptr %t153
+  store i64 %t154, ptr %t149
+  %t155 = getelementptr i64, ptr %t149, i64 1
+  store i64 %t152, ptr %t155
+  %t156 = getelementptr i64, ptr %t149, i64 2
+  store i64 %t150, ptr %t156
+  %t157 = load ptr, ptr %sp
+  %t158 = load i64, ptr %t157
+  %t159 = getelementptr i64, ptr %t157, i64 1
+  %t160 = load i64, ptr %t159
+  %t161 = sub i64 %t160, %t158
+  %t162 = getelementptr i64, ptr %t157, i64 1
+  store ptr %t162, ptr %sp
+  store i64 %t161, ptr %t162
+  %t163 = load ptr, ptr %sp
+  %t164 = load i64, ptr %t163
+  %t165 = getelementptr i64, ptr %t163, i64 1
+  %t166 = load i64, ptr %t165
+  %t167 = sub i64 %t166, %t164
+  %t168 = getelementptr i64, ptr %t163, i64 1
+  store ptr %t168, ptr %sp
+  store i64 %t167, ptr %t168
+  %t169 = load ptr, ptr %sp
+  %t170 = load i64, ptr %t169
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t170)
+  %t171 = getelementptr i64, ptr %t169, i64 1
+  store ptr %t171, ptr %sp
+  %t172 = load ptr, ptr %sp
+  %t173 = getelementptr i64, ptr %t172, i64 -1
+  store ptr %t173, ptr %sp
+  store i64 7, ptr %t173
+  %t174 = load ptr, ptr %sp
+  %t175 = getelementptr i64, ptr %t174, i64 -1
+  store ptr %t175, ptr %sp
+  store i64 8, ptr %t175
+  %t176 = load ptr, ptr %sp
+  %t177 = getelementptr i64, ptr %t176, i64 -1
+  store ptr %t177, ptr %sp
+  store i64 9, ptr %t177
+  %t178 = load ptr, ptr %sp
+  %t179 = load i64, ptr %t178
+  %t180 = getelementptr i64, ptr %t178, i64 1
+  %t181 = load i64, ptr %t180
+  %t182 = getelementptr i64, ptr %t178, i64 2
+  store ptr %t182, ptr %sp
+  %t183 = load ptr, ptr %sp
+  %t184 = load i64, ptr %t183
+  %t185 = add i64 %t184, 1
+  store i64 %t185, ptr %t183
+  %t186 = load ptr, ptr %sp
+  %t187 = load i64, ptr %t186
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t187)
+  %t188 = getelementptr i64, ptr %t186, i64 1
+  store ptr %t188, ptr %sp
+  %t189 = load ptr, ptr %sp
+  %t190 = getelementptr i64, ptr %t189, i64 -1
+  store ptr %t190, ptr %sp
+  store i64 4, ptr %t190
+  %t191 = load ptr, ptr %sp
+  %t192 = load i64, ptr %t191
+  %t193 = getelementptr i64, ptr %t191, i64 -1
+  store ptr %t193, ptr %sp
+  store i64 %t192, ptr %t193
+  %t194 = getelementptr i64, ptr %t193, i64 1
+  store i64 %t192, ptr %t194
+  %t195 = load ptr, ptr %sp
+  %t196 = load i64, ptr %t195
+  %t197 = getelementptr i64, ptr %t195, i64 1
+  %t198 = load i64, ptr %t197
+  %t199 = mul i64 %t198, %t196
+  %t200 = getelementptr i64, ptr %t195, i64 1
+  store ptr %t200, ptr %sp
+  store i64 %t199, ptr %t200
+  %t201 = load ptr, ptr %sp
+  %t202 = load i64, ptr %t201
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t202)
+  %t203 = getelementptr i64, ptr %t201, i64 1
+  store ptr %t203, ptr %sp
+  %t204 = load ptr, ptr %sp
+  %t205 = getelementptr i64, ptr %t204, i64 -1
+  store ptr %t205, ptr %sp
+  store i64 1, ptr %t205
+  %t206 = load ptr, ptr %sp
+  %t207 = getelementptr i64, ptr %t206, i64 -1
+  store ptr %t207, ptr %sp
+  store i64 2, ptr %t207
+  %t208 = load ptr, ptr %sp
+  %t209 = load i64, ptr %t208
+  %t210 = getelementptr i64, ptr %t208, i64 1
+  %t211 = load i64, ptr %t210
+  %t212 = icmp eq i64 %t211, %t209
+  %t213 = zext i1 %t212 to i64
+  %t214 = getelementptr i64, ptr %t208, i64 1
+  store ptr %t214, ptr %sp
+  store i64 %t213, ptr %t214
+  %t215 = load ptr, ptr %sp
+  %t216 = getelementptr i64, ptr %t215, i64 -1
+  store ptr %t216, ptr %sp
+  store i64 2, ptr %t216
+  %t217 = load ptr, ptr %sp
+  %t218 = getelementptr i64, ptr %t217, i64 -1
+  store ptr %t218, ptr %sp
+  store i64 2, ptr %t218
+  %t219 = load ptr, ptr %sp
+  %t220 = load i64, ptr %t219
+  %t221 = getelementptr i64, ptr %t219, i64 1
+  %t222 = load i64, ptr %t221
+  %t223 = icmp eq i64 %t222, %t220
+  %t224 = zext i1 %t223 to i64
+  %t225 = getelementptr i64, ptr %t219, i64 1
+  store ptr %t225, ptr %sp
+  store i64 %t224, ptr %t225
+  %t226 = load ptr, ptr %sp
+  %t227 = getelementptr i64, ptr %t226, i64 -1
+  store ptr %t227, ptr %sp
+  store i64 1, ptr %t227
+  %t228 = load ptr, ptr %sp
+  %t229 = getelementptr i64, ptr %t228, i64 -1
+  store ptr %t229, ptr %sp
+  store i64 2, ptr %t229
+  %t230 = load ptr, ptr %sp
+  %t231 = load i64, ptr %t230
+  %t232 = getelementptr i64, ptr %t230, i64 1
+  %t233 = load i64, ptr %t232
+  %t234 = icmp ne i64 %t233, %t231
+  %t235 = zext i1 %t234 to i64
+  %t236 = getelementptr i64, ptr %t230, i64 1
+  store ptr %t236, ptr %sp
+  store i64 %t235, ptr %t236
+  %t237 = load ptr, ptr %sp
+  %t238 = getelementptr i64, ptr %t237, i64 -1
+  store ptr %t238, ptr %sp
+  store i64 3, ptr %t238
+  %t239 = load ptr, ptr %sp
+  %t240 = getelementptr i64, ptr %t239, i64 -1
+  store ptr %t240, ptr %sp
+  store i64 2, ptr %t240
+  %t241 = load ptr, ptr %sp
+  %t242 = load i64, ptr %t241
+  %t243 = getelementptr i64, ptr %t241, i64 1
+  %t244 = load i64, ptr %t243
+  %t245 = icmp sgt i64 %t244, %t242
+  %t246 = zext i1 %t245 to i64
+  %t247 = getelementptr i64, ptr %t241, i64 1
+  store ptr %t247, ptr %sp
+  store i64 %t246, ptr %t247
+  %t248 = load ptr, ptr %sp
+  %t249 = getelementptr i64, ptr %t248, i64 -1
+  store ptr %t249, ptr %sp
+  store i64 3, ptr %t249
+  %t250 = load ptr, ptr %sp
+  %t251 = getelementptr i64, ptr %t250, i64 -1
+  store ptr %t251, ptr %sp
+  store i64 2, ptr %t251
+  %t252 = load ptr, ptr %sp
+  %t253 = load i64, ptr %t252
+  %t254 = getelementptr i64, ptr %t252, i64 1
+  %t255 = load i64, ptr %t254
+  %t256 = icmp slt i64 %t255, %t253
+  %t257 = zext i1 %t256 to i64
+  %t258 = getelementptr i64, ptr %t252, i64 1
+  store ptr %t258, ptr %sp
+  store i64 %t257, ptr %t258
+  %t259 = load ptr, ptr %sp
+  %t260 = load i64, ptr %t259
+  %t261 = getelementptr i64, ptr %t259, i64 1
+  %t262 = load i64, ptr %t261
+  %t263 = add i64 %t262, %t260
+  %t264 = getelementptr i64, ptr %t259, i64 1
+  store ptr %t264, ptr %sp
+  store i64 %t263, ptr %t264
+  %t265 = load ptr, ptr %sp
+  %t266 = load i64, ptr %t265
+  %t267 = getelementptr i64, ptr %t265, i64 1
+  %t268 = load i64, ptr %t267
+  %t269 = add i64 %t268, %t266
+  %t270 = getelementptr i64, ptr %t265, i64 1
+  store ptr %t270, ptr %sp
+  store i64 %t269, ptr %t270
+  %t271 = load ptr, ptr %sp
+  %t272 = load i64, ptr %t271
+  %t273 = getelementptr i64, ptr %t271, i64 1
+  %t274 = load i64, ptr %t273
+  %t275 = add i64 %t274, %t272
+  %t276 = getelementptr i64, ptr %t271, i64 1
+  store ptr %t276, ptr %sp
+  store i64 %t275, ptr %t276
+  %t277 = load ptr, ptr %sp
+  %t278 = load i64, ptr %t277
+  %t279 = getelementptr i64, ptr %t277, i64 1
+  %t280 = load i64, ptr %t279
+  %t281 = add i64 %t280, %t278
+  %t282 = getelementptr i64, ptr %t277, i64 1
+  store ptr %t282, ptr %sp
+  store i64 %t281, ptr %t282
+  %t283 = load ptr, ptr %sp
+  %t284 = load i64, ptr %t283
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t284)
+  %t285 = getelementptr i64, ptr %t283, i64 1
+  store ptr %t285, ptr %sp
+  %t286 = load ptr, ptr %sp
+  %t287 = getelementptr i64, ptr %t286, i64 -1
+  store ptr %t287, ptr %sp
+  store i64 -5, ptr %t287
+  %t288 = load ptr, ptr %sp
+  %t289 = load i64, ptr %t288
+  %t290 = sub i64 %t289, 1
+  store i64 %t290, ptr %t288
+  %t291 = load ptr, ptr %sp
+  %t292 = load i64, ptr %t291
+  %t293 = sub i64 %t292, 1
+  store i64 %t293, ptr %t291
+  %t294 = load ptr, ptr %sp
+  %t295 = getelementptr i64, ptr %t294, i64 -1
+  store ptr %t295, ptr %sp
+  store i64 2, ptr %t295
+  %t296 = load ptr, ptr %sp
+  %t297 = load i64, ptr %t296
+  %t298 = getelementptr i64, ptr %t296, i64 1
+  %t299 = load i64, ptr %t298
+  %t300 = mul i64 %t299, %t297
+  %t301 = getelementptr i64, ptr %t296, i64 1
+  store ptr %t301, ptr %sp
+  store i64 %t300, ptr %t301
+  %t302 = load ptr, ptr %sp
+  %t303 = load i64, ptr %t302
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t303)
+  %t304 = getelementptr i64, ptr %t302, i64 1
+  store ptr %t304, ptr %sp
+  %t305 = load ptr, ptr %sp
+  %t306 = ptrtoint ptr %t305 to i64
+  %t307 = ptrtoint ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0) to i64
+  %t308 = sub i64 %t307, %t306
+  %t309 = lshr i64 %t308, 3
+  %t310 = getelementptr i64, ptr %t305, i64 -1
+  store ptr %t310, ptr %sp
+  store i64 %t309, ptr %t310
+  %t311 = load ptr, ptr %sp
+  %t312 = load i64, ptr %t311
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t312)
+  %t313 = getelementptr i64, ptr %t311, i64 1
+  store ptr %t313, ptr %sp
+  %t314 = load ptr, ptr %sp
+  ret ptr %t314
+}
+
+define i32 @main() {
+  %sp = call ptr @st_start(ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0))
+  ret i32 0
+}
@@ -0,0 +1,821 @@
+@int_fmt = private unnamed_addr constant [6 x i8] c"%lld \00"
+@bytes_fmt = private unnamed_addr constant [5 x i8] c"%.8s\00"
+@newline_fmt = private unnamed_addr constant [2 x i8] c"\0A\00"
+@int_scan_fmt = private unnamed_addr constant [5 x i8] c"%lld\00"
+@stack = internal global [1048576 x i64] zeroinitializer
+@line_buffer = internal global [4096 x i8] zeroinitializer
+@path_buffer = internal global [4096 x i8] zeroinitializer
+
+declare i32 @printf(ptr, ...)
+declare i32 @scanf(ptr, ...)
+declare i32 @getchar()
+declare ptr @malloc(i64)
+declare void @free(ptr)
+declare void @exit(i32) noreturn
+declare i32 @fflush(ptr)
+declare i32 @open(ptr, i32, ...)
+declare i64 @read(i32, ptr, i64)
+declare i64 @write(i32, ptr, i64)
+declare i32 @close(i32)
+declare ptr @__errno_location()
+
+; prints the data stack from the bottom to the top
+define internal void @jalgo_dump_stack(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %cell = phi ptr [ getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0), %entry ], [ %next, %body ]
+  %more = icmp ugt ptr %cell, %sp
+  br i1 %more, label %body, label %end
+body:
+  %next = getelementptr i64, ptr %cell, i64 -1
+  %value = load i64, ptr %next
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %value)
+  br label %loop
+end:
+  call i32 (ptr, ...) @printf(ptr @newline_fmt)
+  ret void
+}
+
+; up to 8 bytes of the value until a zero byte
+define internal void @jalgo_write_raw(i64 %value) {
+  %bytes = alloca i64
+  store i64 %value, ptr %bytes
+  call i32 (ptr, ...) @printf(ptr @bytes_fmt, ptr %bytes)
+  ret void
+}
+
+; pushes the value and the scanf status
+define internal ptr @jalgo_read_int(ptr %sp) {
+  %value = alloca i64
+  store i64 0, ptr %value
+  %status = call i32 (ptr, ...) @scanf(ptr @int_scan_fmt, ptr %value)
+  %loaded = load i64, ptr %value
+  %status64 = sext i32 %status to i64
+  %value_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 %loaded, ptr %value_cell
+  %new_sp = getelementptr i64, ptr %sp, i64 -2
+  store i64 %status64, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pushes the line as a zero terminated stack string and its length, -1 on EOF
+define internal ptr @jalgo_read_line(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %length = phi i64 [ 0, %entry ], [ %next_length, %append ]
+  %c = call i32 @getchar()
+  %eof = icmp eq i32 %c, -1
+  br i1 %eof, label %at_eof, label %not_eof
+not_eof:
+  %newline = icmp eq i32 %c, 10
+  br i1 %newline, label %done, label %append
+append:
+  %byte = trunc i32 %c to i8
+  %slot = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %length
+  store i8 %byte, ptr %slot
+  %next_length = add i64 %length, 1
+  %full = icmp eq i64 %next_length, 4096
+  br i1 %full, label %done, label %loop
+at_eof:
+  %empty = icmp eq i64 %length, 0
+  br i1 %empty, label %nothing, label %done
+nothing:
+  %eof_sp = getelementptr i64, ptr %sp, i64 -1
+  store i64 -1, ptr %eof_sp
+  ret ptr %eof_sp
+done:
+  %final_length = phi i64 [ %length, %not_eof ], [ %next_length, %append ], [ %length, %at_eof ]
+  %zero_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 0, ptr %zero_cell
+  br label %copy
+copy:
+  %i = phi i64 [ 0, %done ], [ %next_i, %copy_byte ]
+  %copy_more = icmp ult i64 %i, %final_length
+  br i1 %copy_more, label %copy_byte, label %copy_end
+copy_byte:
+  %source = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %i
+  %loaded = load i8, ptr %source
+  %extended = zext i8 %loaded to i64
+  %from_end = sub i64 %i, %final_length
+  %offset = sub i64 %from_end, 1
+  %target = getelementptr i64, ptr %sp, i64 %offset
+  store i64 %extended, ptr %target
+  %next_i = add i64 %i, 1
+  br label %copy
+copy_end:
+  %length_offset = sub i64 -2, %final_length
+  %new_sp = getelementptr i64, ptr %sp, i64 %length_offset
+  store i64 %final_length, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pops a zero terminated stack string into @path_buffer
+define internal ptr @jalgo_pop_path(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %i = phi i64 [ 0, %entry ], [ %next, %loop ]
+  %cell = getelementptr i64, ptr %sp, i64 %i
+  %value = load i64, ptr %cell
+  %byte = trunc i64 %value to i8
+  %slot = getelementptr [4096 x i8], ptr @path_buffer, i64 0, i64 %i
+  store i8 %byte, ptr %slot
+  %next = add i64 %i, 1
+  %more = icmp ne i64 %value, 0
+  br i1 %more, label %loop, label %end
+end:
+  %new_sp = getelementptr i64, ptr %sp, i64 %next
+  ret ptr %new_sp
+}
+
+; -1 from libc becomes -errno, like the raw syscalls
+define internal i64 @jalgo_result(i64 %result) {
+entry:
+  %failed = icmp slt i64 %result, 0
+  br i1 %failed, label %error, label %ok
+ok:
+  ret i64 %result
+error:
+  %errno_pointer = call ptr @__errno_location()
+  %errno = load i32, ptr %errno_pointer
+  %errno64 = sext i32 %errno to i64
+  %code = sub i64 0, %errno64
+  ret i64 %code
+}
+
+define internal i64 @jalgo_fopen(ptr %path, i32 %flags) {
+  %fd = call i32 (ptr, i32, ...) @open(ptr %path, i32 %flags, i32 420)
+  %fd64 = sext i32 %fd to i64
+  %result = call i64 @jalgo_result(i64 %fd64)
+  ret i64 %result
+}
+
+; fd -> byte, -1 on EOF, -errno on error
+define internal i64 @jalgo_fread_byte(i64 %fd) {
+  %byte = alloca i8
+  store i8 0, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @read(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  %loaded = load i8, ptr %byte
+  %extended = zext i8 %loaded to i64
+  %is_byte = icmp eq i64 %count, 1
+  %is_eof = icmp eq i64 %count, 0
+  %eof_or_error = select i1 %is_eof, i64 -1, i64 %result
+  %value = select i1 %is_byte, i64 %extended, i64 %eof_or_error
+  ret i64 %value
+}
+
+; fd byte -> 1, -errno on error. printf output goes first
+define internal i64 @jalgo_fwrite_byte(i64 %fd, i64 %value) {
+  call i32 @fflush(ptr null)
+  %byte = alloca i8
+  %truncated = trunc i64 %value to i8
+  store i8 %truncated, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @write(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  ret i64 %result
+}
+
+define internal i64 @jalgo_fclose(i64 %fd) {
+  call i32 @fflush(ptr null)
+  %fd32 = trunc i64 %fd to i32
+  %status = call i32 @close(i32 %fd32)
+  %status64 = sext i32 %status to i64
+  %result = call i64 @jalgo_result(i64 %status64)
+  ret i64 %result
+}
+
+
+; start
+define internal ptr @st_start(ptr %sp.arg) {
+entry:
+  %sp = alloca ptr
+  store ptr %sp.arg, ptr %sp
+  br label %restart
+restart:
+  %t1 = load ptr, ptr %sp
+  %t2 = getelementptr i64, ptr %t1, i64 -1
+  store ptr %t2, ptr %sp
+  store i64 -5, ptr %t2
+  %t3 = load ptr, ptr %sp
+  %t4 = load i64, ptr %t3
+  %t5 = getelementptr i64, ptr %t3, i64 -1
+  store ptr %t5, ptr %sp
+  store i64 %t4, ptr %t5
+  %t6 = getelementptr i64, ptr %t5, i64 1
+  store i64 %t4, ptr %t6
+  %t7 = load ptr, ptr %sp
+  %t8 = getelementptr i64, ptr %t7, i64 -1
+  store ptr %t8, ptr %sp
+  store i64 0, ptr %t8
+  %t9 = load ptr, ptr %sp
+  %t10 = load i64, ptr %t9
+  %t11 = getelementptr i64, ptr %t9, i64 1
+  %t12 = load i64, ptr %t11
+  %t13 = icmp slt i64 %t12, %t10
+  %t14 = zext i1 %t13 to i64
+  %t15 = getelementptr i64, ptr %t9, i64 1
+  store ptr %t15, ptr %sp
+  store i64 %t14, ptr %t15
+  %t16 = load ptr, ptr %sp
+  %t17 = load i64, ptr %t16
+  %t18 = icmp sle i64 %t17, 0
+  %t19 = getelementptr i64, ptr %t16, i64 1
+  store ptr %t19, ptr %sp
+  br i1 %t18, label %l43, label %b20
+b20:
+  %t21 = load ptr, ptr %sp
+  %t22 = load i64, ptr %t21
+  %t23 = getelementptr i64, ptr %t21, i64 1
+  store ptr %t23, ptr %sp
+  %t24 = load ptr, ptr %sp
+  %t25 = getelementptr i64, ptr %t24, i64 -1
+  store ptr %t25, ptr %sp
+  store i64 -1, ptr %t25
+  br label %l50
+l43:
+  %t26 = load ptr, ptr %sp
+  %t27 = load i64, ptr %t26
+  %t28 = getelementptr i64, ptr %t26, i64 -1
+  store ptr %t28, ptr %sp
+  store i64 %t27, ptr %t28
+  %t29 = getelementptr i64, ptr %t28, i64 1
+  store i64 %t27, ptr %t29
+  %t30 = load ptr, ptr %sp
+  %t31 = load i64, ptr %t30
+  %t32 = icmp sle i64 %t31, 0
+  %t33 = getelementptr i64, ptr %t30, i64 1
+  store ptr %t33, ptr %sp
+  br i1 %t32, label %l48, label %b34
+b34:
+  %t35 = load ptr, ptr %sp
+  %t36 = load i64, ptr %t35
+  %t37 = getelementptr i64, ptr %t35, i64 1
+  store ptr %t37, ptr %sp
+  %t38 = load ptr, ptr %sp
+  %t39 = getelementptr i64, ptr %t38, i64 -1
+  store ptr %t39, ptr %sp
+  store i64 1, ptr %t39
+  br label %l50
+l48:
+  %t40 = load ptr, ptr %sp
+  %t41 = load i64, ptr %t40
+  %t42 = getelementptr i64, ptr %t40, i64 1
+  store ptr %t42, ptr %sp
+  %t43 = load ptr, ptr %sp
+  %t44 = getelementptr i64, ptr %t43, i64 -1
+  store ptr %t44, ptr %sp
+  store i64 0, ptr %t44
+  br label %l50
+l50:
+  %t45 = load ptr, ptr %sp
+  %t46 = load i64, ptr %t45
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t46)
+  %t47 = getelementptr i64, ptr %t45, i64 1
+  store ptr %t47, ptr %sp
+  %t48 = load ptr, ptr %sp
+  %t49 = getelementptr i64, ptr %t48, i64 -1
+  store ptr %t49, ptr %sp
+  store i64 0, ptr %t49
+  %t50 = load ptr, ptr %sp
+  %t51 = load i64, ptr %t50
+  %t52 = getelementptr i64, ptr %t50, i64 -1
+  store ptr %t52, ptr %sp
+  store i64 %t51, ptr %t52
+  %t53 = getelementptr i64, ptr %t52, i64 1
+  store i64 %t51, ptr %t53
+  %t54 = load ptr, ptr %sp
+  %t55 = getelementptr i64, ptr %t54, i64 -1
+  store ptr %t55, ptr %sp
+  store i64 0, ptr %t55
+  %t56 = load ptr, ptr %sp
+  %t57 = load i64, ptr %t56
+  %t58 = getelementptr i64, ptr %t56, i64 1
+  %t59 = load i64, ptr %t58
+  %t60 = icmp slt i64 %t59, %t57
+  %t61 = zext i1 %t60 to i64
+  %t62 = getelementptr i64, ptr %t56, i64 1
+  store ptr %t62, ptr %sp
+  store i64 %t61, ptr %t62
+  %t63 = load ptr, ptr %sp
+  %t64 = load i64, ptr %t63
+  %t65 = icmp sle i64 %t64, 0
+  %t66 = getelementptr i64, ptr %t63, i64 1
+  store ptr %t66, ptr %sp
+  br i1 %t65, label %l58, label %b67
+b67:
+  %t68 = load ptr, ptr %sp
+  %t69 = load i64, ptr %t68
+  %t70 = getelementptr i64, ptr %t68, i64 1
+  store ptr %t70, ptr %sp
+  %t71 = load ptr, ptr %sp
+  %t72 = getelementptr i64, ptr %t71, i64 -1
+  store ptr %t72, ptr %sp
+  store i64 -1, ptr %t72
+  br label %l65
+l58:
+  %t73 = load ptr, ptr %sp
+  %t74 = load i64, ptr %t73
+  %t75 = getelementptr i64, ptr %t73, i64 -1
+  store ptr %t75, ptr %sp
+  store i64 %t74, ptr %t75
+  %t76 = getelementptr i64, ptr %t75, i64 1
+  store i64 %t74, ptr %t76
+  %t77 = load ptr, ptr %sp
+  %t78 = load i64, ptr %t77
+  %t79 = icmp sle i64 %t78, 0
+  %t80 = getelementptr i64, ptr %t77, i64 1
+  store ptr %t80, ptr %sp
+  br i1 %t79, label %l63, label %b81
+b81:
+  %t82 = load ptr, ptr %sp
+  %t83 = load i64, ptr %t82
+  %t84 = getelementptr i64, ptr %t82, i64 1
+  store ptr %t84, ptr %sp
+  %t85 = load ptr, ptr %sp
+  %t86 = getelementptr i64, ptr %t85, i64 -1
+  store ptr %t86, ptr %sp
+  store i64 1, ptr %t86
+  br label %l65
+l63:
+  %t87 = load ptr, ptr %sp
+  %t88 = load i64, ptr %t87
+  %t89 = getelementptr i64, ptr %t87, i64 1
+  store ptr %t89, ptr %sp
+  %t90 = load ptr, ptr %sp
+  %t91 = getelementptr i64, ptr %t90, i64 -1
+  store ptr %t91, ptr %sp
+  store i64 0, ptr %t91
+  br label %l65
+l65:
+  %t92 = load ptr, ptr %sp
+  %t93 = load i64, ptr %t92
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t93)
+  %t94 = getelementptr i64, ptr %t92, i64 1
+  store ptr %t94, ptr %sp
+  %t95 = load ptr, ptr %sp
+  %t96 = getelementptr i64, ptr %t95, i64 -1
+  store ptr %t96, ptr %sp
+  store i64 5, ptr %t96
+  %t97 = load ptr, ptr %sp
+  %t98 = load i64, ptr %t97
+  %t99 = getelementptr i64, ptr %t97, i64 -1
+  store ptr %t99, ptr %sp
+  store i64 %t98, ptr %t99
+  %t100 = getelementptr i64, ptr %t99, i64 1
+  store i64 %t98, ptr %t100
+  %t101 = load ptr, ptr %sp
+  %t102 = getelementptr i64, ptr %t101, i64 -1
+  store ptr %t102, ptr %sp
+  store i64 0, ptr %t102
+  %t103 = load ptr, ptr %sp
+  %t104 = load i64, ptr %t103
+  %t105 = getelementptr i64, ptr %t103, i64 1
+  %t106 = load i64, ptr %t105
+  %t107 = icmp slt i64 %t106, %t104
+  %t108 = zext i1 %t107 to i64
+  %t109 = getelementptr i64, ptr %t103, i64 1
+  store ptr %t109, ptr %sp
+  store i64 %t108, ptr %t109
+  %t110 = load ptr, ptr %sp
+  %t111 = load i64, ptr %t110
+  %t112 = icmp sle i64 %t111, 0
+  %t113 = getelementptr i64, ptr %t110, i64 1
+  store ptr %t113, ptr %sp
+  br i1 %t112, label %l73, label %b114
+b114:
+  %t115 = load ptr, ptr %sp
+  %t116 = load i64, ptr %t115
+  %t117 = getelementptr i64, ptr %t115, i64 1
+  store ptr %t117, ptr %sp
+  %t118 = load ptr, ptr %sp
+  %t119 = getelementptr i64, ptr %t118, i64 -1
+  store ptr %t119, ptr %sp
+  store i64 -1, ptr %t119
+  br label %l80
+l73:
+  %t120 = load ptr, ptr %sp
+  %t121 = load i64, ptr %t120
+  %t122 = getelementptr i64, ptr %t120, i64 -1
+  store ptr %t122, ptr %sp
+  store i64 %t121, ptr %t122
+  %t123 = getelementptr i64, ptr %t122, i64 1
+  store i64 %t121, ptr %t123
+  %t124 = load ptr, ptr %sp
+  %t125 = load i64, ptr %t124
+  %t126 = icmp sle i64 %t125, 0
+  %t127 = getelementptr i64, ptr %t124, i64 1
+  store ptr %t127, ptr %sp
+  br i1 %t126, label %l78, label %b128
+b128:
+  %t129 = load ptr, ptr %sp
+  %t130 = load i64, ptr %t129
+  %t131 = getelementptr i64, ptr %t129, i64 1
+  store ptr %t131, ptr %sp
+  %t132 = load ptr, ptr %sp
+  %t133 = getelementptr i64, ptr %t132, i64 -1
+  store ptr %t133, ptr %sp
+  store i64 1, ptr %t133
+  br label %l80
+l78:
+  %t134 = load ptr, ptr %sp
+  %t135 = load i64, ptr %t134
+  %t136 = getelementptr i64, ptr %t134, i64 1
+  store ptr %t136, ptr %sp
+  %t137 = load ptr, ptr %sp
+  %t138 = getelementptr i64, ptr %t137, i64 -1
+  store ptr %t138, ptr %sp
+  store i64 0, ptr %t138
+  br label %l80
+l80:
+  %t139 = load ptr, ptr %sp
+  %t140 = load i64, ptr %t139
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t140)
+  %t141 = getelementptr i64, ptr %t139, i64 1
+  store ptr %t141, ptr %sp
+  %t142 = load ptr, ptr %sp
+  %t143 = getelementptr i64, ptr %t142, i64 -1
+  store ptr %t143, ptr %sp
+  store i64 20, ptr %t143
+  %t144 = load ptr, ptr %sp
+  %t145 = load i64, ptr %t144
+  %t146 = getelementptr i64, ptr %t144, i64 -1
+  store ptr %t146, ptr %sp
+  store i64 %t145, ptr %t146
+  %t147 = getelementptr i64, ptr %t146, i64 1
+  store i64 %t145, ptr %t147
+  %t148 = load ptr, ptr %sp
+  %t149 = getelementptr i64, ptr %t148, i64 -1
+  store ptr %t149, ptr %sp
+  store i64 10, ptr %t149
+  %t150 = load ptr, ptr %sp
+  %t151 = load i64, ptr %t150
+  %t152 = getelementptr i64, ptr %t150, i64 1
+  %t153 = load i64, ptr %t152
+  %t154 = icmp sgt i64 %t153, %t151
+  %t155 = zext i1 %t154 to i64
+  %t156 = getelementptr i64, ptr %t150, i64 1
+  store ptr %t156, ptr %sp
+  store i64 %t155, ptr %t156
+  %t157 = load ptr, ptr %sp
+  %t158 = load i64, ptr %t157
+  %t159 = icmp sle i64 %t158, 0
+  %t160 = getelementptr i64, ptr %t157, i64 1
+  store ptr %t160, ptr %sp
+  br i1 %t159, label %l88, label %b161
+b161:
+  %t162 = load ptr, ptr %sp
+  %t163 = getelementptr i64, ptr %t162, i64 -1
+  store ptr %t163, ptr %sp
+  store i64 100, ptr %t163
+  %t164 = load ptr, ptr %sp
+  %t165 = load i64, ptr %t164
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t165)
+  %t166 = getelementptr i64, ptr %t164, i64 1
+  store ptr %t166, ptr %sp
+  br label %l88
+l88:
+  %t167 = load ptr, ptr %sp
+  %t168 = load i64, ptr %t167
+  %t169 = getelementptr i64, ptr %t167, i64 -1
+  store ptr %t169, ptr %sp
+  store i64 %t168, ptr %t169
+  %t170 = getelementptr i64, ptr %t169, i64 1
+  store i64 %t168, ptr %t170
+  %t171 = load ptr, ptr %sp
+  %t172 = getelementptr i64, ptr %t171, i64 -1
+  store ptr %t172, ptr %sp
+  store i64 2, ptr %t172
+  %t173 = load ptr, ptr %sp
+  %t174 = load i64, ptr %t173
+  %t175 = getelementptr i64, ptr %t173, i64 1
+  %t176 = load i64, ptr %t175
+  %t177 = icmp slt i64 %t176, %t174
+  %t178 = zext i1 %t177 to i64
+  %t179 = getelementptr i64, ptr %t173, i64 1
+  store ptr %t179, ptr %sp
+  store i64 %t178, ptr %t179
+  %t180 = load ptr, ptr %sp
+  %t181 = load i64, ptr %t180
+  %t182 = icmp sle i64 %t181, 0
+  %t183 = getelementptr i64, ptr %t180, i64 1
+  store ptr %t183, ptr %sp
+  br i1 %t182, label %l95, label %b184
+b184:
+  %t185 = load ptr, ptr %sp
+  %t186 = getelementptr i64, ptr %t185, i64 -1
+  store ptr %t186, ptr %sp
+  store i64 1, ptr %t186
+  %t187 = load ptr, ptr %sp
+  %t188 = load i64, ptr %t187
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t188)
+  %t189 = getelementptr i64, ptr %t187, i64 1
+  store ptr %t189, ptr %sp
+  br label %l98
+l95:
+  %t190 = load ptr, ptr %sp
+  %t191 = getelementptr i64, ptr %t190, i64 -1
+  store ptr %t191, ptr %sp
+  store i64 2, ptr %t191
+  %t192 = load ptr, ptr %sp
+  %t193 = load i64, ptr %t192
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t193)
+  %t194 = getelementptr i64, ptr %t192, i64 1
+  store ptr %t194, ptr %sp
+  br label %l98
+l98:
+  %t195 = load ptr, ptr %sp
+  %t196 = load i64, ptr %t195
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t196)
+  %t197 = getelementptr i64, ptr %t195, i64 1
+  store ptr %t197, ptr %sp
+  %t198 = load ptr, ptr %sp
+  %t199 = getelementptr i64, ptr %t198, i64 -1
+  store ptr %t199, ptr %sp
+  store i64 1, ptr %t199
+  %t200 = load ptr, ptr %sp
+  %t201 = load i64, ptr %t200
+  %t202 = getelementptr i64, ptr %t200, i64 -1
+  store ptr %t202, ptr %sp
+  store i64 %t201, ptr %t202
+  %t203 = getelementptr i64, ptr %t202, i64 1
+  store i64 %t201, ptr %t203
+  %t204 = load ptr, ptr %sp
+  %t205 = getelementptr i64, ptr %t204, i64 -1
+  store ptr %t205, ptr %sp
+  store i64 10, ptr %t205
+  %t206 = load ptr, ptr %sp
+  %t207 = load i64, ptr %t206
+  %t208 = getelementptr i64, ptr %t206, i64 1
+  %t209 = load i64, ptr %t208
+  %t210 = icmp sgt i64 %t209, %t207
+  %t211 = zext i1 %t210 to i64
+  %t212 = getelementptr i64, ptr %t206, i64 1
+  store ptr %t212, ptr %sp
+  store i64 %t211, ptr %t212
+  %t213 = load ptr, ptr %sp
+  %t214 = load i64, ptr %t213
+  %t215 = icmp sle i64 %t214, 0
+  %t216 = getelementptr i64, ptr %t213, i64 1
+  store ptr %t216, ptr %sp
+  br i1 %t215, label %l107, label %b217
+b217:
+  %t218 = load ptr, ptr %sp
+  %t219 = getelementptr i64, ptr %t218, i64 -1
+  store ptr %t219, ptr %sp
+  store i64 100, ptr %t219
+  %t220 = load ptr, ptr %sp
+  %t221 = load i64, ptr %t220
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t221)
+  %t222 = getelementptr i64, ptr %t220, i64 1
+  store ptr %t222, ptr %sp
+  br label %l107
+l107:
+  %t223 = load ptr, ptr %sp
+  %t224 = load i64, ptr %t223
+  %t225 = getelementptr i64, ptr %t223, i64 -1
+  store ptr %t225, ptr %sp
+  store i64 %t224, ptr %t225
+  %t226 = getelementptr i64, ptr %t225, i64 1
+  store i64 %t224, ptr %t226
+  %t227 = load ptr, ptr %sp
+  %t228 = getelementptr i64, ptr %t227, i64 -1
+  store ptr %t228, ptr %sp
+  store i64 2, ptr %t228
+  %t229 = load ptr, ptr %sp
+  %t230 = load i64, ptr %t229
+  %t231 = getelementptr i64, ptr %t229, i64 1
+  %t232 = load i64, ptr %t231
+  %t233 = icmp slt i64 %t232, %t230
+  %t234 = zext i1 %t233 to i64
+  %t235 = getelementptr i64, ptr %t229, i64 1
+  store ptr %t235, ptr %sp
+  store i64 %t234, ptr %t235
+  %t236 = load ptr, ptr %sp
+  %t237 = load i64, ptr %t236
+  %t238 = icmp sle i64 %t237, 0
+  %t239 = getelementptr i64, ptr %t236, i64 1
+  store ptr %t239, ptr %sp
+  br i1 %t238, label %l114, label %b240
+b240:
+  %t241 = load ptr, ptr %sp
+  %t242 = getelementptr i64, ptr %t241, i64 -1
+  store ptr %t242, ptr %sp
+  store i64 1, ptr %t242
+  %t243 = load ptr, ptr %sp
+  %t244 = load i64, ptr %t243
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t244)
+  %t245 = getelementptr i64, ptr %t243, i64 1
+  store ptr %t245, ptr %sp
+  br label %l117
+l114:
+  %t246 = load ptr, ptr %sp
+  %t247 = getelementptr i64, ptr %t246, i64 -1
+  store ptr %t247, ptr %sp
+  store i64 2, ptr %t247
+  %t248 = load ptr, ptr %sp
+  %t249 = load i64, ptr %t248
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t249)
+  %t250 = getelementptr i64, ptr %t248, i64 1
+  store ptr %t250, ptr %sp
+  br label %l117
+l117:
+  %t251 = load ptr, ptr %sp
+  %t252 = load i64, ptr %t251
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t252)
+  %t253 = getelementptr i64, ptr %t251, i64 1
+  store ptr %t253, ptr %sp
+  %t254 = load ptr, ptr %sp
+  %t255 = getelementptr i64, ptr %t254, i64 -1
+  store ptr %t255, ptr %sp
+  store i64 5, ptr %t255
+  %t256 = load ptr, ptr %sp
+  %t257 = load i64, ptr %t256
+  %t258 = getelementptr i64, ptr %t256, i64 -1
+  store ptr %t258, ptr %sp
+  store i64 %t257, ptr %t258
+  %t259 = getelementptr i64, ptr %t258, i64 1
+  store i64 %t257, ptr %t259
+  %t260 = load ptr, ptr %sp
+  %t261 = getelementptr i64, ptr %t260, i64 -1
+  store ptr %t261, ptr %sp
+  store i64 10, ptr %t261
+  %t262 = load ptr, ptr %sp
+  %t263 = load i64, ptr %t262
+  %t264 = getelementptr i64, ptr %t262, i64 1
+  %t265 = load i64, ptr %t264
+  %t266 = icmp sgt i64 %t265, %t263
+  %t267 = zext i1 %t266 to i64
+  %t268 = getelementptr i64, ptr %t262, i64 1
+  store ptr %t268, ptr %sp
+  store i64 %t267, ptr %t268
+  %t269 = load ptr, ptr %sp
+  %t270 = load i64, ptr %t269
+  %t271 = icmp sle i64 %t270, 0
+  %t272 = getelementptr i64, ptr %t269, i64 1
+  store ptr %t272, ptr %sp
+  br i1 %t271, label %l126, label %b273
+b273:
+  %t274 = load ptr, ptr %sp
+  %t275 = getelementptr i64, ptr %t274, i64 -1
+  store ptr %t275, ptr %sp
+  store i64 100, ptr %t275
+  %t276 = load ptr, ptr %sp
+  %t277 = load i64, ptr %t276
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t277)
+  %t278 = getelementptr i64, ptr %t276, i64 1
+  store ptr %t278, ptr %sp
+  br label %l126
+l126:
+  %t279 = load ptr, ptr %sp
+  %t280 = load i64, ptr %t279
+  %t281 = getelementptr i64, ptr %t279, i64 -1
+  store ptr %t281, ptr %sp
+  store i64 %t280, ptr %t281
+  %t282 = getelementptr i64, ptr %t281, i64 1
+  store i64 %t280, ptr %t282
+  %t283 = load ptr, ptr %sp
+  %t284 = getelementptr i64, ptr %t283, i64 -1
+  store ptr %t284, ptr %sp
+  store i64 2, ptr %t284
+  %t285 = load ptr, ptr %sp
+  %t286 = load i64, ptr %t285
+  %t287 = getelementptr i64, ptr %t285, i64 1
+  %t288 = load i64, ptr %t287
+  %t289 = icmp slt i64 %t288, %t286
+  %t290 = zext i1 %t289 to i64
+  %t291 = getelementptr i64, ptr %t285, i64 1
+  store ptr %t291, ptr %sp
+  store i64 %t290, ptr %t291
+  %t292 = load ptr, ptr %sp
+  %t293 = load i64, ptr %t292
+  %t294 = icmp sle i64 %t293, 0
+  %t295 = getelementptr i64, ptr %t292, i64 1
+  store ptr %t295, ptr %sp
+  br i1 %t294, label %l133, label %b296
+b296:
+  %t297 = load ptr, ptr %sp
+  %t298 = getelementptr i64, ptr %t297, i64 -1
+  store ptr %t298, ptr %sp
+  store i64 1, ptr %t298
+  %t299 = load ptr, ptr %sp
+  %t300 = load i64, ptr %t299
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t300)
+  %t301 = getelementptr i64, ptr %t299, i64 1
+  store ptr %t301, ptr %sp
+  br label %l136
+l133:
+  %t302 = load ptr, ptr %sp
+  %t303 = getelementptr i64, ptr %t302, i64 -1
+  store ptr %t303, ptr %sp
+  store i64 2, ptr %t303
+  %t304 = load ptr, ptr %sp
+  %t305 = load i64, ptr %t304
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t305)
+  %t306 = getelementptr i64, ptr %t304, i64 1
+  store ptr %t306, ptr %sp
+  br label %l136
+l136:
+  %t307 = load ptr, ptr %sp
+  %t308 = load i64, ptr %t307
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t308)
+  %t309 = getelementptr i64, ptr %t307, i64 1
+  store ptr %t309, ptr %sp
+  %t310 = load ptr, ptr %sp
+  %t311 = getelementptr i64, ptr %t310, i64 -1
+  store ptr %t311, ptr %sp
+  store i64 1, ptr %t311
+  %t312 = load ptr, ptr %sp
+  %t313 = load i64, ptr %t312
+  %t314 = icmp sle i64 %t313, 0
+  %t315 = getelementptr i64, ptr %t312, i64 1
+  store ptr %t315, ptr %sp
+  br i1 %t314, label %l28, label %b316
+b316:
+  %t317 = load ptr, ptr %sp
+  %t318 = getelementptr i64, ptr %t317, i64 -1
+  store ptr %t318, ptr %sp
+  store i64 0, ptr %t318
+  %t319 = load ptr, ptr %sp
+  %t320 = load i64, ptr %t319
+  %t321 = icmp sle i64 %t320, 0
+  %t322 = getelementptr i64, ptr %t319, i64 1
+  store ptr %t322, ptr %sp
+  br i1 %t321, label %l22, label %b323
+b323:
+  %t324 = load ptr, ptr %sp
+  %t325 = getelementptr i64, ptr %t324, i64 -1
+  store ptr %t325, ptr %sp
+  store i64 11, ptr %t325
+  %t326 = load ptr, ptr %sp
+  %t327 = load i64, ptr %t326
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t327)
+  %t328 = getelementptr i64, ptr %t326, i64 1
+  store ptr %t328, ptr %sp
+  br label %l25
+l22:
+  %t329 = load ptr, ptr %sp
+  %t330 = getelementptr i64, ptr %t329, i64 -1
+  store ptr %t330, ptr %sp
+  store i64 12, ptr %t330
+  %t331 = load ptr, ptr %sp
+  %t332 = load i64, ptr %t331
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t332)
+  %t333 = getelementptr i64, ptr %t331, i64 1
+  store ptr %t333, ptr %sp
+  br label %l25
+l25:
+  %t334 = load ptr, ptr %sp
+  %t335 = getelementptr i64, ptr %t334, i64 -1
+  store ptr %t335, ptr %sp
+  store i64 13, ptr %t335
+  %t336 = load ptr, ptr %sp
+  %t337 = load i64, ptr %t336
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t337)
+  %t338 = getelementptr i64, ptr %t336, i64 1
+  store ptr %t338, ptr %sp
+  br label %l28
+l28:
+  %t339 = load ptr, ptr %sp
+  %t340 = getelementptr i64, ptr %t339, i64 -1
+  store ptr %t340, ptr %sp
+  store i64 0, ptr %t340
+  %t341 = load ptr, ptr %sp
+  %t342 = load i64, ptr %t341
+  %t343 = icmp sle i64 %t342, 0
+  %t344 = getelementptr i64, ptr %t341, i64 1
+  store ptr %t344, ptr %sp
+  br i1 %t343, label %l33, label %b345
+b345:
+  %t346 = load ptr, ptr %sp
+  %t347 = getelementptr i64, ptr %t346, i64 -1
+  store ptr %t347, ptr %sp
+  store i64 14, ptr %t347
+  %t348 = load ptr, ptr %sp
+  %t349 = load i64, ptr %t348
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t349)
+  %t350 = getelementptr i64, ptr %t348, i64 1
+  store ptr %t350, ptr %sp
+  br label %l33
+l33:
+  %t351 = load ptr, ptr %sp
+  %t352 = getelementptr i64, ptr %t351, i64 -1
+  store ptr %t352, ptr %sp
+  store i64 15, ptr %t352
+  %t353 = load ptr, ptr %sp
+  %t354 = load i64, ptr %t353
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t354)
+  %t355 = getelementptr i64, ptr %t353, i64 1
+  store ptr %t355, ptr %sp
+  %t356 = load ptr, ptr %sp
+  ret ptr %t356
+}
+
+define i32 @main() {
+  %sp = call ptr @st_start(ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0))
+  ret i32 0
+}
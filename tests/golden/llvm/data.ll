@@ -0,0 +1,499 @@
+@int_fmt = private unnamed_addr constant [6 x i8] c"%lld \00"
+@bytes_fmt = private unnamed_addr constant [5 x i8] c"%.8s\00"
+@newline_fmt = private unnamed_addr constant [2 x i8] c"\0A\00"
+@int_scan_fmt = private unnamed_addr constant [5 x i8] c"%lld\00"
+@stack = internal global [1048576 x i64] zeroinitializer
+@line_buffer = internal global [4096 x i8] zeroinitializer
+@path_buffer = internal global [4096 x i8] zeroinitializer
+
+declare i32 @printf(ptr, ...)
+declare i32 @scanf(ptr, ...)
+declare i32 @getchar()
+declare ptr @malloc(i64)
+declare void @free(ptr)
+declare void @exit(i32) noreturn
+declare i32 @fflush(ptr)
+declare i32 @open(ptr, i32, ...)
+declare i64 @read(i32, ptr, i64)
+declare i64 @write(i32, ptr, i64)
+declare i32 @close(i32)
+declare ptr @__errno_location()
+
+; prints the data stack from the bottom to the top
+define internal void @jalgo_dump_stack(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %cell = phi ptr [ getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0), %entry ], [ %next, %body ]
+  %more = icmp ugt ptr %cell, %sp
+  br i1 %more, label %body, label %end
+body:
+  %next = getelementptr i64, ptr %cell, i64 -1
+  %value = load i64, ptr %next
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %value)
+  br label %loop
+end:
+  call i32 (ptr, ...) @printf(ptr @newline_fmt)
+  ret void
+}
+
+; up to 8 bytes of the value until a zero byte
+define internal void @jalgo_write_raw(i64 %value) {
+  %bytes = alloca i64
+  store i64 %value, ptr %bytes
+  call i32 (ptr, ...) @printf(ptr @bytes_fmt, ptr %bytes)
+  ret void
+}
+
+; pushes the value and the scanf status
+define internal ptr @jalgo_read_int(ptr %sp) {
+  %value = alloca i64
+  store i64 0, ptr %value
+  %status = call i32 (ptr, ...) @scanf(ptr @int_scan_fmt, ptr %value)
+  %loaded = load i64, ptr %value
+  %status64 = sext i32 %status to i64
+  %value_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 %loaded, ptr %value_cell
+  %new_sp = getelementptr i64, ptr %sp, i64 -2
+  store i64 %status64, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pushes the line as a zero terminated stack string and its length, -1 on EOF
+define internal ptr @jalgo_read_line(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %length = phi i64 [ 0, %entry ], [ %next_length, %append ]
+  %c = call i32 @getchar()
+  %eof = icmp eq i32 %c, -1
+  br i1 %eof, label %at_eof, label %not_eof
+not_eof:
+  %newline = icmp eq i32 %c, 10
+  br i1 %newline, label %done, label %append
+append:
+  %byte = trunc i32 %c to i8
+  %slot = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %length
+  store i8 %byte, ptr %slot
+  %next_length = add i64 %length, 1
+  %full = icmp eq i64 %next_length, 4096
+  br i1 %full, label %done, label %loop
+at_eof:
+  %empty = icmp eq i64 %length, 0
+  br i1 %empty, label %nothing, label %done
+nothing:
+  %eof_sp = getelementptr i64, ptr %sp, i64 -1
+  store i64 -1, ptr %eof_sp
+  ret ptr %eof_sp
+done:
+  %final_length = phi i64 [ %length, %not_eof ], [ %next_length, %append ], [ %length, %at_eof ]
+  %zero_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 0, ptr %zero_cell
+  br label %copy
+copy:
+  %i = phi i64 [ 0, %done ], [ %next_i, %copy_byte ]
+  %copy_more = icmp ult i64 %i, %final_length
+  br i1 %copy_more, label %copy_byte, label %copy_end
+copy_byte:
+  %source = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %i
+  %loaded = load i8, ptr %source
+  %extended = zext i8 %loaded to i64
+  %from_end = sub i64 %i, %final_length
+  %offset = sub i64 %from_end, 1
+  %target = getelementptr i64, ptr %sp, i64 %offset
+  store i64 %extended, ptr %target
+  %next_i = add i64 %i, 1
+  br label %copy
+copy_end:
+  %length_offset = sub i64 -2, %final_length
+  %new_sp = getelementptr i64, ptr %sp, i64 %length_offset
+  store i64 %final_length, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pops a zero terminated stack string into @path_buffer
+define internal ptr @jalgo_pop_path(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %i = phi i64 [ 0, %entry ], [ %next, %loop ]
+  %cell = getelementptr i64, ptr %sp, i64 %i
+  %value = load i64, ptr %cell
+  %byte = trunc i64 %value to i8
+  %slot = getelementptr [4096 x i8], ptr @path_buffer, i64 0, i64 %i
+  store i8 %byte, ptr %slot
+  %next = add i64 %i, 1
+  %more = icmp ne i64 %value, 0
+  br i1 %more, label %loop, label %end
+end:
+  %new_sp = getelementptr i64, ptr %sp, i64 %next
+  ret ptr %new_sp
+}
+
+; -1 from libc becomes -errno, like the raw syscalls
+define internal i64 @jalgo_result(i64 %result) {
+entry:
+  %failed = icmp slt i64 %result, 0
+  br i1 %failed, label %error, label %ok
+ok:
+  ret i64 %result
+error:
+  %errno_pointer = call ptr @__errno_location()
+  %errno = load i32, ptr %errno_pointer
+  %errno64 = sext i32 %errno to i64
+  %code = sub i64 0, %errno64
+  ret i64 %code
+}
+
+define internal i64 @jalgo_fopen(ptr %path, i32 %flags) {
+  %fd = call i32 (ptr, i32, ...) @open(ptr %path, i32 %flags, i32 420)
+  %fd64 = sext i32 %fd to i64
+  %result = call i64 @jalgo_result(i64 %fd64)
+  ret i64 %result
+}
+
+; fd -> byte, -1 on EOF, -errno on error
+define internal i64 @jalgo_fread_byte(i64 %fd) {
+  %byte = alloca i8
+  store i8 0, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @read(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  %loaded = load i8, ptr %byte
+  %extended = zext i8 %loaded to i64
+  %is_byte = icmp eq i64 %count, 1
+  %is_eof = icmp eq i64 %count, 0
+  %eof_or_error = select i1 %is_eof, i64 -1, i64 %result
+  %value = select i1 %is_byte, i64 %extended, i64 %eof_or_error
+  ret i64 %value
+}
+
+; fd byte -> 1, -errno on error. printf output goes first
+define internal i64 @jalgo_fwrite_byte(i64 %fd, i64 %value) {
+  call i32 @fflush(ptr null)
+  %byte = alloca i8
+  %truncated = trunc i64 %value to i8
+  store i8 %truncated, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @write(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  ret i64 %result
+}
+
+define internal i64 @jalgo_fclose(i64 %fd) {
+  call i32 @fflush(ptr null)
+  %fd32 = trunc i64 %fd to i32
+  %status = call i32 @close(i32 %fd32)
+  %status64 = sext i32 %status to i64
+  %result = call i64 @jalgo_result(i64 %status64)
+  ret i64 %result
+}
+
+@data_table = internal global [24 x i8] c"\0A\00\00\00\00\00\00\00\14\00\00\00\00\00\00\00\1E\00\00\00\00\00\00\00"
+@data_greeting = internal global [10 x i8] c"Hi \22you\22\0A\00"
+@data_empty = internal global [0 x i8] c""
+@data_mixed = internal global [19 x i8] c"\01\00\00\00\00\00\00\00ab\00\02\00\00\00\00\00\00\00"
+
+; print_cstring
+define internal ptr @st_print_cstring(ptr %sp.arg) {
+entry:
+  %sp = alloca ptr
+  store ptr %sp.arg, ptr %sp
+  br label %restart
+restart:
+  %t1 = load ptr, ptr %sp
+  %t2 = load i64, ptr %t1
+  %t3 = getelementptr i64, ptr %t1, i64 -1
+  store ptr %t3, ptr %sp
+  store i64 %t2, ptr %t3
+  %t4 = getelementptr i64, ptr %t3, i64 1
+  store i64 %t2, ptr %t4
+  %t5 = load ptr, ptr %sp
+  %t6 = load i64, ptr %t5
+  %t7 = inttoptr i64 %t6 to ptr
+  %t8 = load i8, ptr %t7, align 1
+  %t9 = zext i8 %t8 to i64
+  store i64 %t9, ptr %t5
+  %t10 = load ptr, ptr %sp
+  %t11 = load i64, ptr %t10
+  %t12 = getelementptr i64, ptr %t10, i64 -1
+  store ptr %t12, ptr %sp
+  store i64 %t11, ptr %t12
+  %t13 = getelementptr i64, ptr %t12, i64 1
+  store i64 %t11, ptr %t13
+  %t14 = load ptr, ptr %sp
+  %t15 = load i64, ptr %t14
+  %t16 = icmp sle i64 %t15, 0
+  %t17 = getelementptr i64, ptr %t14, i64 1
+  store ptr %t17, ptr %sp
+  br i1 %t16, label %l8, label %b18
+b18:
+  %t19 = load ptr, ptr %sp
+  %t20 = load i64, ptr %t19
+  call void @jalgo_write_raw(i64 %t20)
+  %t21 = getelementptr i64, ptr %t19, i64 1
+  store ptr %t21, ptr %sp
+  %t22 = load ptr, ptr %sp
+  %t23 = load i64, ptr %t22
+  %t24 = add i64 %t23, 1
+  store i64 %t24, ptr %t22
+  br label %restart
+b26:
+  %t25 = load ptr, ptr %sp
+  ret ptr %t25
+l8:
+  %t27 = load ptr, ptr %sp
+  %t28 = load i64, ptr %t27
+  %t29 = getelementptr i64, ptr %t27, i64 1
+  store ptr %t29, ptr %sp
+  %t30 = load ptr, ptr %sp
+  %t31 = load i64, ptr %t30
+  %t32 = getelementptr i64, ptr %t30, i64 1
+  store ptr %t32, ptr %sp
+  %t33 = load ptr, ptr %sp
+  ret ptr %t33
+}
+
+; start
+define internal ptr @st_start(ptr %sp.arg) {
+entry:
+  %sp = alloca ptr
+  store ptr %sp.arg, ptr %sp
+  br label %restart
+restart:
+  %t1 = load ptr, ptr %sp
+  %t2 = ptrtoint ptr @data_table to i64
+  %t3 = getelementptr i64, ptr %t1, i64 -1
+  store ptr %t3, ptr %sp
+  store i64 %t2, ptr %t3
+  %t4 = load ptr, ptr %sp
+  %t5 = load i64, ptr %t4
+  %t6 = inttoptr i64 %t5 to ptr
+  %t7 = load i64, ptr %t6, align 1
+  store i64 %t7, ptr %t4
+  %t8 = load ptr, ptr %sp
+  %t9 = load i64, ptr %t8
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t9)
+  %t10 = getelementptr i64, ptr %t8, i64 1
+  store ptr %t10, ptr %sp
+  %t11 = load ptr, ptr %sp
+  %t12 = ptrtoint ptr @data_table to i64
+  %t13 = getelementptr i64, ptr %t11, i64 -1
+  store ptr %t13, ptr %sp
+  store i64 %t12, ptr %t13
+  %t14 = load ptr, ptr %sp
+  %t15 = getelementptr i64, ptr %t14, i64 -1
+  store ptr %t15, ptr %sp
+  store i64 16, ptr %t15
+  %t16 = load ptr, ptr %sp
+  %t17 = load i64, ptr %t16
+  %t18 = getelementptr i64, ptr %t16, i64 1
+  %t19 = load i64, ptr %t18
+  %t20 = add i64 %t19, %t17
+  %t21 = getelementptr i64, ptr %t16, i64 1
+  store ptr %t21, ptr %sp
+  store i64 %t20, ptr %t21
+  %t22 = load ptr, ptr %sp
+  %t23 = load i64, ptr %t22
+  %t24 = inttoptr i64 %t23 to ptr
+  %t25 = load i64, ptr %t24, align 1
+  store i64 %t25, ptr %t22
+  %t26 = load ptr, ptr %sp
+  %t27 = load i64, ptr %t26
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t27)
+  %t28 = getelementptr i64, ptr %t26, i64 1
+  store ptr %t28, ptr %sp
+  %t29 = load ptr, ptr %sp
+  %t30 = ptrtoint ptr @data_table to i64
+  %t31 = getelementptr i64, ptr %t29, i64 -1
+  store ptr %t31, ptr %sp
+  store i64 %t30, ptr %t31
+  %t32 = load ptr, ptr %sp
+  %t33 = getelementptr i64, ptr %t32, i64 -1
+  store ptr %t33, ptr %sp
+  store i64 8, ptr %t33
+  %t34 = load ptr, ptr %sp
+  %t35 = load i64, ptr %t34
+  %t36 = getelementptr i64, ptr %t34, i64 1
+  %t37 = load i64, ptr %t36
+  %t38 = add i64 %t37, %t35
+  %t39 = getelementptr i64, ptr %t34, i64 1
+  store ptr %t39, ptr %sp
+  store i64 %t38, ptr %t39
+  %t40 = load ptr, ptr %sp
+  %t41 = load i64, ptr %t40
+  %t42 = getelementptr i64, ptr %t40, i64 -1
+  store ptr %t42, ptr %sp
+  store i64 %t41, ptr %t42
+  %t43 = getelementptr i64, ptr %t42, i64 1
+  store i64 %t41, ptr %t43
+  %t44 = load ptr, ptr %sp
+  %t45 = load i64, ptr %t44
+  %t46 = inttoptr i64 %t45 to ptr
+  %t47 = load i64, ptr %t46, align 1
+  store i64 %t47, ptr %t44
+  %t48 = load ptr, ptr %sp
+  %t49 = load i64, ptr %t48
+  %t50 = add i64 %t49, 1
+  store i64 %t50, ptr %t48
+  %t51 = load ptr, ptr %sp
+  %t52 = load i64, ptr %t51
+  %t53 = getelementptr i64, ptr %t51, i64 1
+  %t54 = load i64, ptr %t53
+  %t55 = inttoptr i64 %t54 to ptr
+  store i64 %t52, ptr %t55, align 1
+  %t56 = getelementptr i64, ptr %t51, i64 2
+  store ptr %t56, ptr %sp
+  %t57 = load ptr, ptr %sp
+  %t58 = ptrtoint ptr @data_table to i64
+  %t59 = getelementptr i64, ptr %t57, i64 -1
+  store ptr %t59, ptr %sp
+  store i64 %t58, ptr %t59
+  %t60 = load ptr, ptr %sp
+  %t61 = getelementptr i64, ptr %t60, i64 -1
+  store ptr %t61, ptr %sp
+  store i64 8, ptr %t61
+  %t62 = load ptr, ptr %sp
+  %t63 = load i64, ptr %t62
+  %t64 = getelementptr i64, ptr %t62, i64 1
+  %t65 = load i64, ptr %t64
+  %t66 = add i64 %t65, %t63
+  %t67 = getelementptr i64, ptr %t62, i64 1
+  store ptr %t67, ptr %sp
+  store i64 %t66, ptr %t67
+  %t68 = load ptr, ptr %sp
+  %t69 = load i64, ptr %t68
+  %t70 = inttoptr i64 %t69 to ptr
+  %t71 = load i64, ptr %t70, align 1
+  store i64 %t71, ptr %t68
+  %t72 = load ptr, ptr %sp
+  %t73 = load i64, ptr %t72
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t73)
+  %t74 = getelementptr i64, ptr %t72, i64 1
+  store ptr %t74, ptr %sp
+  %t75 = load ptr, ptr %sp
+  %t76 = ptrtoint ptr @data_greeting to i64
+  %t77 = getelementptr i64, ptr %t75, i64 -1
+  store ptr %t77, ptr %sp
+  store i64 %t76, ptr %t77
+  %t78 = load ptr, ptr %sp
+  %t79 = call ptr @st_print_cstring(ptr %t78)
+  store ptr %t79, ptr %sp
+  %t80 = load ptr, ptr %sp
+  %t81 = ptrtoint ptr @data_greeting to i64
+  %t82 = getelementptr i64, ptr %t80, i64 -1
+  store ptr %t82, ptr %sp
+  store i64 %t81, ptr %t82
+  %t83 = load ptr, ptr %sp
+  %t84 = load i64, ptr %t83
+  %t85 = inttoptr i64 %t84 to ptr
+  %t86 = load i8, ptr %t85, align 1
+  %t87 = zext i8 %t86 to i64
+  store i64 %t87, ptr %t83
+  %t88 = load ptr, ptr %sp
+  %t89 = load i64, ptr %t88
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t89)
+  %t90 = getelementptr i64, ptr %t88, i64 1
+  store ptr %t90, ptr %sp
+  %t91 = load ptr, ptr %sp
+  %t92 = ptrtoint ptr @data_mixed to i64
+  %t93 = getelementptr i64, ptr %t91, i64 -1
+  store ptr %t93, ptr %sp
+  store i64 %t92, ptr %t93
+  %t94 = load ptr, ptr %sp
+  %t95 = load i64, ptr %t94
+  %t96 = inttoptr i64 %t95 to ptr
+  %t97 = load i64, ptr %t96, align 1
+  store i64 %t97, ptr %t94
+  %t98 = load ptr, ptr %sp
+  %t99 = load i64, ptr %t98
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t99)
+  %t100 = getelementptr i64, ptr %t98, i64 1
+  store ptr %t100, ptr %sp
+  %t101 = load ptr, ptr %sp
+  %t102 = ptrtoint ptr @data_mixed to i64
+  %t103 = getelementptr i64, ptr %t101, i64 -1
+  store ptr %t103, ptr %sp
+  store i64 %t102, ptr %t103
+  %t104 = load ptr, ptr %sp
+  %t105 = getelementptr i64, ptr %t104, i64 -1
+  store ptr %t105, ptr %sp
+  store i64 8, ptr %t105
+  %t106 = load ptr, ptr %sp
+  %t107 = load i64, ptr %t106
+  %t108 = getelementptr i64, ptr %t106, i64 1
+  %t109 = load i64, ptr %t108
+  %t110 = add i64 %t109, %t107
+  %t111 = getelementptr i64, ptr %t106, i64 1
+  store ptr %t111, ptr %sp
+  store i64 %t110, ptr %t111
+  %t112 = load ptr, ptr %sp
+  %t113 = load i64, ptr %t112
+  %t114 = inttoptr i64 %t113 to ptr
+  %t115 = load i8, ptr %t114, align 1
+  %t116 = zext i8 %t115 to i64
+  store i64 %t116, ptr %t112
+  %t117 = load ptr, ptr %sp
+  %t118 = load i64, ptr %t117
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t118)
+  %t119 = getelementptr i64, ptr %t117, i64 1
+  store ptr %t119, ptr %sp
+  %t120 = load ptr, ptr %sp
+  %t121 = ptrtoint ptr @data_mixed to i64
+  %t122 = getelementptr i64, ptr %t120, i64 -1
+  store ptr %t122, ptr %sp
+  store i64 %t121, ptr %t122
+  %t123 = load ptr, ptr %sp
+  %t124 = getelementptr i64, ptr %t123, i64 -1
+  store ptr %t124, ptr %sp
+  store i64 11, ptr %t124
+  %t125 = load ptr, ptr %sp
+  %t126 = load i64, ptr %t125
+  %t127 = getelementptr i64, ptr %t125, i64 1
+  %t128 = load i64, ptr %t127
+  %t129 = add i64 %t128, %t126
+  %t130 = getelementptr i64, ptr %t125, i64 1
+  store ptr %t130, ptr %sp
+  store i64 %t129, ptr %t130
+  %t131 = load ptr, ptr %sp
+  %t132 = load i64, ptr %t131
+  %t133 = inttoptr i64 %t132 to ptr
+  %t134 = load i64, ptr %t133, align 1
+  store i64 %t134, ptr %t131
+  %t135 = load ptr, ptr %sp
+  %t136 = load i64, ptr %t135
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t136)
+  %t137 = getelementptr i64, ptr %t135, i64 1
+  store ptr %t137, ptr %sp
+  %t138 = load ptr, ptr %sp
+  %t139 = ptrtoint ptr @data_empty to i64
+  %t140 = getelementptr i64, ptr %t138, i64 -1
+  store ptr %t140, ptr %sp
+  store i64 %t139, ptr %t140
+  %t141 = load ptr, ptr %sp
+  %t142 = ptrtoint ptr @data_empty to i64
+  %t143 = getelementptr i64, ptr %t141, i64 -1
+  store ptr %t143, ptr %sp
+  store i64 %t142, ptr %t143
+  %t144 = load ptr, ptr %sp
+  %t145 = load i64, ptr %t144
+  %t146 = getelementptr i64, ptr %t144, i64 1
+  %t147 = load i64, ptr %t146
+  %t148 = icmp eq i64 %t147, %t145
+  %t149 = zext i1 %t148 to i64
+  %t150 = getelementptr i64, ptr %t144, i64 1
+  store ptr %t150, ptr %sp
+  store i64 %t149, ptr %t150
+  %t151 = load ptr, ptr %sp
+  %t152 = load i64, ptr %t151
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t152)
+  %t153 = getelementptr i64, ptr %t151, i64 1
+  store ptr %t153, ptr %sp
+  %t154 = load ptr, ptr %sp
+  ret ptr %t154
+}
+
+define i32 @main() {
+  %sp = call ptr @st_start(ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0))
+  ret i32 0
+}
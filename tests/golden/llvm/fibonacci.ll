@@ -0,0 +1,370 @@
+@int_fmt = private unnamed_addr constant [6 x i8] c"%lld \00"
+@bytes_fmt = private unnamed_addr constant [5 x i8] c"%.8s\00"
+@newline_fmt = private unnamed_addr constant [2 x i8] c"\0A\00"
+@int_scan_fmt = private unnamed_addr constant [5 x i8] c"%lld\00"
+@stack = internal global [1048576 x i64] zeroinitializer
+@line_buffer = internal global [4096 x i8] zeroinitializer
+@path_buffer = internal global [4096 x i8] zeroinitializer
+
+declare i32 @printf(ptr, ...)
+declare i32 @scanf(ptr, ...)
+declare i32 @getchar()
+declare ptr @malloc(i64)
+declare void @free(ptr)
+declare void @exit(i32) noreturn
+declare i32 @fflush(ptr)
+declare i32 @open(ptr, i32, ...)
+declare i64 @read(i32, ptr, i64)
+declare i64 @write(i32, ptr, i64)
+declare i32 @close(i32)
+declare ptr @__errno_location()
+
+; prints the data stack from the bottom to the top
+define internal void @jalgo_dump_stack(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %cell = phi ptr [ getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0), %entry ], [ %next, %body ]
+  %more = icmp ugt ptr %cell, %sp
+  br i1 %more, label %body, label %end
+body:
+  %next = getelementptr i64, ptr %cell, i64 -1
+  %value = load i64, ptr %next
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %value)
+  br label %loop
+end:
+  call i32 (ptr, ...) @printf(ptr @newline_fmt)
+  ret void
+}
+
+; up to 8 bytes of the value until a zero byte
+define internal void @jalgo_write_raw(i64 %value) {
+  %bytes = alloca i64
+  store i64 %value, ptr %bytes
+  call i32 (ptr, ...) @printf(ptr @bytes_fmt, ptr %bytes)
+  ret void
+}
+
+; pushes the value and the scanf status
+define internal ptr @jalgo_read_int(ptr %sp) {
+  %value = alloca i64
+  store i64 0, ptr %value
+  %status = call i32 (ptr, ...) @scanf(ptr @int_scan_fmt, ptr %value)
+  %loaded = load i64, ptr %value
+  %status64 = sext i32 %status to i64
+  %value_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 %loaded, ptr %value_cell
+  %new_sp = getelementptr i64, ptr %sp, i64 -2
+  store i64 %status64, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pushes the line as a zero terminated stack string and its length, -1 on EOF
+define internal ptr @jalgo_read_line(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %length = phi i64 [ 0, %entry ], [ %next_length, %append ]
+  %c = call i32 @getchar()
+  %eof = icmp eq i32 %c, -1
+  br i1 %eof, label %at_eof, label %not_eof
+not_eof:
+  %newline = icmp eq i32 %c, 10
+  br i1 %newline, label %done, label %append
+append:
+  %byte = trunc i32 %c to i8
+  %slot = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %length
+  store i8 %byte, ptr %slot
+  %next_length = add i64 %length, 1
+  %full = icmp eq i64 %next_length, 4096
+  br i1 %full, label %done, label %loop
+at_eof:
+  %empty = icmp eq i64 %length, 0
+  br i1 %empty, label %nothing, label %done
+nothing:
+  %eof_sp = getelementptr i64, ptr %sp, i64 -1
+  store i64 -1, ptr %eof_sp
+  ret ptr %eof_sp
+done:
+  %final_length = phi i64 [ %length, %not_eof ], [ %next_length, %append ], [ %length, %at_eof ]
+  %zero_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 0, ptr %zero_cell
+  br label %copy
+copy:
+  %i = phi i64 [ 0, %done ], [ %next_i, %copy_byte ]
+  %copy_more = icmp ult i64 %i, %final_length
+  br i1 %copy_more, label %copy_byte, label %copy_end
+copy_byte:
+  %source = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %i
+  %loaded = load i8, ptr %source
+  %extended = zext i8 %loaded to i64
+  %from_end = sub i64 %i, %final_length
+  %offset = sub i64 %from_end, 1
+  %target = getelementptr i64, ptr %sp, i64 %offset
+  store i64 %extended, ptr %target
+  %next_i = add i64 %i, 1
+  br label %copy
+copy_end:
+  %length_offset = sub i64 -2, %final_length
+  %new_sp = getelementptr i64, ptr %sp, i64 %length_offset
+  store i64 %final_length, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pops a zero terminated stack string into @path_buffer
+define internal ptr @jalgo_pop_path(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %i = phi i64 [ 0, %entry ], [ %next, %loop ]
+  %cell = getelementptr i64, ptr %sp, i64 %i
+  %value = load i64, ptr %cell
+  %byte = trunc i64 %value to i8
+  %slot = getelementptr [4096 x i8], ptr @path_buffer, i64 0, i64 %i
+  store i8 %byte, ptr %slot
+  %next = add i64 %i, 1
+  %more = icmp ne i64 %value, 0
+  br i1 %more, label %loop, label %end
+end:
+  %new_sp = getelementptr i64, ptr %sp, i64 %next
+  ret ptr %new_sp
+}
+
+; -1 from libc becomes -errno, like the raw syscalls
+define internal i64 @jalgo_result(i64 %result) {
+entry:
+  %failed = icmp slt i64 %result, 0
+  br i1 %failed, label %error, label %ok
+ok:
+  ret i64 %result
+error:
+  %errno_pointer = call ptr @__errno_location()
+  %errno = load i32, ptr %errno_pointer
+  %errno64 = sext i32 %errno to i64
+  %code = sub i64 0, %errno64
+  ret i64 %code
+}
+
+define internal i64 @jalgo_fopen(ptr %path, i32 %flags) {
+  %fd = call i32 (ptr, i32, ...) @open(ptr %path, i32 %flags, i32 420)
+  %fd64 = sext i32 %fd to i64
+  %result = call i64 @jalgo_result(i64 %fd64)
+  ret i64 %result
+}
+
+; fd -> byte, -1 on EOF, -errno on error
+define internal i64 @jalgo_fread_byte(i64 %fd) {
+  %byte = alloca i8
+  store i8 0, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @read(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  %loaded = load i8, ptr %byte
+  %extended = zext i8 %loaded to i64
+  %is_byte = icmp eq i64 %count, 1
+  %is_eof = icmp eq i64 %count, 0
+  %eof_or_error = select i1 %is_eof, i64 -1, i64 %result
+  %value = select i1 %is_byte, i64 %extended, i64 %eof_or_error
+  ret i64 %value
+}
+
+; fd byte -> 1, -errno on error. printf output goes first
+define internal i64 @jalgo_fwrite_byte(i64 %fd, i64 %value) {
+  call i32 @fflush(ptr null)
+  %byte = alloca i8
+  %truncated = trunc i64 %value to i8
+  store i8 %truncated, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @write(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  ret i64 %result
+}
+
+define internal i64 @jalgo_fclose(i64 %fd) {
+  call i32 @fflush(ptr null)
+  %fd32 = trunc i64 %fd to i32
+  %status = call i32 @close(i32 %fd32)
+  %status64 = sext i32 %status to i64
+  %result = call i64 @jalgo_result(i64 %status64)
+  ret i64 %result
+}
+
+
+; fibonacci_recursion
+define internal ptr @st_fibonacci_recursion(ptr %sp.arg) {
+entry:
+  %sp = alloca ptr
+  store ptr %sp.arg, ptr %sp
+  br label %restart
+restart:
+  %t1 = load ptr, ptr %sp
+  %t2 = load i64, ptr %t1
+  %t3 = getelementptr i64, ptr %t1, i64 -1
+  store ptr %t3, ptr %sp
+  store i64 %t2, ptr %t3
+  %t4 = getelementptr i64, ptr %t3, i64 1
+  store i64 %t2, ptr %t4
+  %t5 = load ptr, ptr %sp
+  %t6 = load i64, ptr %t5
+  %t7 = sub i64 %t6, 1
+  store i64 %t7, ptr %t5
+  %t8 = load ptr, ptr %sp
+  %t9 = load i64, ptr %t8
+  %t10 = icmp sle i64 %t9, 0
+  %t11 = getelementptr i64, ptr %t8, i64 1
+  store ptr %t11, ptr %sp
+  br i1 %t10, label %l26, label %b12
+b12:
+  %t13 = load ptr, ptr %sp
+  %t14 = getelementptr i64, ptr %t13, i64 -1
+  store ptr %t14, ptr %sp
+  store i64 0, ptr %t14
+  br label %l27
+l26:
+  %t15 = load ptr, ptr %sp
+  %t16 = getelementptr i64, ptr %t15, i64 -1
+  store ptr %t16, ptr %sp
+  store i64 1, ptr %t16
+  br label %l27
+l27:
+  %t17 = load ptr, ptr %sp
+  %t18 = load i64, ptr %t17
+  %t19 = icmp sle i64 %t18, 0
+  %t20 = getelementptr i64, ptr %t17, i64 1
+  store ptr %t20, ptr %sp
+  br i1 %t19, label %l7, label %b21
+b21:
+  %t22 = load ptr, ptr %sp
+  %t23 = load i64, ptr %t22
+  %t24 = getelementptr i64, ptr %t22, i64 1
+  store ptr %t24, ptr %sp
+  %t25 = load ptr, ptr %sp
+  %t26 = getelementptr i64, ptr %t25, i64 -1
+  store ptr %t26, ptr %sp
+  store i64 0, ptr %t26
+  %t27 = load ptr, ptr %sp
+  ret ptr %t27
+l7:
+  %t28 = load ptr, ptr %sp
+  %t29 = load i64, ptr %t28
+  %t30 = getelementptr i64, ptr %t28, i64 -1
+  store ptr %t30, ptr %sp
+  store i64 %t29, ptr %t30
+  %t31 = getelementptr i64, ptr %t30, i64 1
+  store i64 %t29, ptr %t31
+  %t32 = load ptr, ptr %sp
+  %t33 = getelementptr i64, ptr %t32, i64 -1
+  store ptr %t33, ptr %sp
+  store i64 2, ptr %t33
+  %t34 = load ptr, ptr %sp
+  %t35 = load i64, ptr %t34
+  %t36 = getelementptr i64, ptr %t34, i64 1
+  %t37 = load i64, ptr %t36
+  %t38 = sub i64 %t37, %t35
+  %t39 = getelementptr i64, ptr %t34, i64 1
+  store ptr %t39, ptr %sp
+  store i64 %t38, ptr %t39
+  %t40 = load ptr, ptr %sp
+  %t41 = load i64, ptr %t40
+  %t42 = icmp sle i64 %t41, 0
+  %t43 = getelementptr i64, ptr %t40, i64 1
+  store ptr %t43, ptr %sp
+  br i1 %t42, label %l31, label %b44
+b44:
+  %t45 = load ptr, ptr %sp
+  %t46 = getelementptr i64, ptr %t45, i64 -1
+  store ptr %t46, ptr %sp
+  store i64 0, ptr %t46
+  br label %l32
+l31:
+  %t47 = load ptr, ptr %sp
+  %t48 = getelementptr i64, ptr %t47, i64 -1
+  store ptr %t48, ptr %sp
+  store i64 1, ptr %t48
+  br label %l32
+l32:
+  %t49 = load ptr, ptr %sp
+  %t50 = load i64, ptr %t49
+  %t51 = icmp sle i64 %t50, 0
+  %t52 = getelementptr i64, ptr %t49, i64 1
+  store ptr %t52, ptr %sp
+  br i1 %t51, label %l15, label %b53
+b53:
+  %t54 = load ptr, ptr %sp
+  %t55 = load i64, ptr %t54
+  %t56 = getelementptr i64, ptr %t54, i64 1
+  store ptr %t56, ptr %sp
+  %t57 = load ptr, ptr %sp
+  %t58 = getelementptr i64, ptr %t57, i64 -1
+  store ptr %t58, ptr %sp
+  store i64 1, ptr %t58
+  %t59 = load ptr, ptr %sp
+  ret ptr %t59
+l15:
+  %t60 = load ptr, ptr %sp
+  %t61 = load i64, ptr %t60
+  %t62 = sub i64 %t61, 1
+  store i64 %t62, ptr %t60
+  %t63 = load ptr, ptr %sp
+  %t64 = load i64, ptr %t63
+  %t65 = getelementptr i64, ptr %t63, i64 -1
+  store ptr %t65, ptr %sp
+  store i64 %t64, ptr %t65
+  %t66 = getelementptr i64, ptr %t65, i64 1
+  store i64 %t64, ptr %t66
+  %t67 = load ptr, ptr %sp
+  %t68 = load i64, ptr %t67
+  %t69 = sub i64 %t68, 1
+  store i64 %t69, ptr %t67
+  %t70 = load ptr, ptr %sp
+  %t71 = call ptr @st_fibonacci_recursion(ptr %t70)
+  store ptr %t71, ptr %sp
+  %t72 = load ptr, ptr %sp
+  %t73 = load i64, ptr %t72
+  %t74 = getelementptr i64, ptr %t72, i64 1
+  %t75 = load i64, ptr %t74
+  store i64 %t75, ptr %t72
+  %t76 = getelementptr i64, ptr %t72, i64 1
+  store i64 %t73, ptr %t76
+  %t77 = load ptr, ptr %sp
+  %t78 = call ptr @st_fibonacci_recursion(ptr %t77)
+  store ptr %t78, ptr %sp
+  %t79 = load ptr, ptr %sp
+  %t80 = load i64, ptr %t79
+  %t81 = getelementptr i64, ptr %t79, i64 1
+  %t82 = load i64, ptr %t81
+  %t83 = add i64 %t82, %t80
+  %t84 = getelementptr i64, ptr %t79, i64 1
+  store ptr %t84, ptr %sp
+  store i64 %t83, ptr %t84
+  %t85 = load ptr, ptr %sp
+  ret ptr %t85
+}
+
+; start
+define internal ptr @st_start(ptr %sp.arg) {
+entry:
+  %sp = alloca ptr
+  store ptr %sp.arg, ptr %sp
+  br label %restart
+restart:
+  %t1 = load ptr, ptr %sp
+  %t2 = getelementptr i64, ptr %t1, i64 -1
+  store ptr %t2, ptr %sp
+  store i64 35, ptr %t2
+  %t3 = load ptr, ptr %sp
+  %t4 = call ptr @st_fibonacci_recursion(ptr %t3)
+  store ptr %t4, ptr %sp
+  %t5 = load ptr, ptr %sp
+  %t6 = load i64, ptr %t5
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t6)
+  %t7 = getelementptr i64, ptr %t5, i64 1
+  store ptr %t7, ptr %sp
+  %t8 = load ptr, ptr %sp
+  ret ptr %t8
+}
+
+define i32 @main() {
+  %sp = call ptr @st_start(ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0))
+  ret i32 0
+}
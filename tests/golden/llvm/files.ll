@@ -0,0 +1,733 @@
+@int_fmt = private unnamed_addr constant [6 x i8] c"%lld \00"
+@bytes_fmt = private unnamed_addr constant [5 x i8] c"%.8s\00"
+@newline_fmt = private unnamed_addr constant [2 x i8] c"\0A\00"
+@int_scan_fmt = private unnamed_addr constant [5 x i8] c"%lld\00"
+@stack = internal global [1048576 x i64] zeroinitializer
+@line_buffer = internal global [4096 x i8] zeroinitializer
+@path_buffer = internal global [4096 x i8] zeroinitializer
+
+declare i32 @printf(ptr, ...)
+declare i32 @scanf(ptr, ...)
+declare i32 @getchar()
+declare ptr @malloc(i64)
+declare void @free(ptr)
+declare void @exit(i32) noreturn
+declare i32 @fflush(ptr)
+declare i32 @open(ptr, i32, ...)
+declare i64 @read(i32, ptr, i64)
+declare i64 @write(i32, ptr, i64)
+declare i32 @close(i32)
+declare ptr @__errno_location()
+
+; prints the data stack from the bottom to the top
+define internal void @jalgo_dump_stack(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %cell = phi ptr [ getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0), %entry ], [ %next, %body ]
+  %more = icmp ugt ptr %cell, %sp
+  br i1 %more, label %body, label %end
+body:
+  %next = getelementptr i64, ptr %cell, i64 -1
+  %value = load i64, ptr %next
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %value)
+  br label %loop
+end:
+  call i32 (ptr, ...) @printf(ptr @newline_fmt)
+  ret void
+}
+
+; up to 8 bytes of the value until a zero byte
+define internal void @jalgo_write_raw(i64 %value) {
+  %bytes = alloca i64
+  store i64 %value, ptr %bytes
+  call i32 (ptr, ...) @printf(ptr @bytes_fmt, ptr %bytes)
+  ret void
+}
+
+; pushes the value and the scanf status
+define internal ptr @jalgo_read_int(ptr %sp) {
+  %value = alloca i64
+  store i64 0, ptr %value
+  %status = call i32 (ptr, ...) @scanf(ptr @int_scan_fmt, ptr %value)
+  %loaded = load i64, ptr %value
+  %status64 = sext i32 %status to i64
+  %value_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 %loaded, ptr %value_cell
+  %new_sp = getelementptr i64, ptr %sp, i64 -2
+  store i64 %status64, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pushes the line as a zero terminated stack string and its length, -1 on EOF
+define internal ptr @jalgo_read_line(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %length = phi i64 [ 0, %entry ], [ %next_length, %append ]
+  %c = call i32 @getchar()
+  %eof = icmp eq i32 %c, -1
+  br i1 %eof, label %at_eof, label %not_eof
+not_eof:
+  %newline = icmp eq i32 %c, 10
+  br i1 %newline, label %done, label %append
+append:
+  %byte = trunc i32 %c to i8
+  %slot = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %length
+  store i8 %byte, ptr %slot
+  %next_length = add i64 %length, 1
+  %full = icmp eq i64 %next_length, 4096
+  br i1 %full, label %done, label %loop
+at_eof:
+  %empty = icmp eq i64 %length, 0
+  br i1 %empty, label %nothing, label %done
+nothing:
+  %eof_sp = getelementptr i64, ptr %sp, i64 -1
+  store i64 -1, ptr %eof_sp
+  ret ptr %eof_sp
+done:
+  %final_length = phi i64 [ %length, %not_eof ], [ %next_length, %append ], [ %length, %at_eof ]
+  %zero_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 0, ptr %zero_cell
+  br label %copy
+copy:
+  %i = phi i64 [ 0, %done ], [ %next_i, %copy_byte ]
+  %copy_more = icmp ult i64 %i, %final_length
+  br i1 %copy_more, label %copy_byte, label %copy_end
+copy_byte:
+  %source = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %i
+  %loaded = load i8, ptr %source
+  %extended = zext i8 %loaded to i64
+  %from_end = sub i64 %i, %final_length
+  %offset = sub i64 %from_end, 1
+  %target = getelementptr i64, ptr %sp, i64 %offset
+  store i64 %extended, ptr %target
+  %next_i = add i64 %i, 1
+  br label %copy
+copy_end:
+  %length_offset = sub i64 -2, %final_length
+  %new_sp = getelementptr i64, ptr %sp, i64 %length_offset
+  store i64 %final_length, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pops a zero terminated stack string into @path_buffer
+define internal ptr @jalgo_pop_path(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %i = phi i64 [ 0, %entry ], [ %next, %loop ]
+  %cell = getelementptr i64, ptr %sp, i64 %i
+  %value = load i64, ptr %cell
+  %byte = trunc i64 %value to i8
+  %slot = getelementptr [4096 x i8], ptr @path_buffer, i64 0, i64 %i
+  store i8 %byte, ptr %slot
+  %next = add i64 %i, 1
+  %more = icmp ne i64 %value, 0
+  br i1 %more, label %loop, label %end
+end:
+  %new_sp = getelementptr i64, ptr %sp, i64 %next
+  ret ptr %new_sp
+}
+
+; -1 from libc becomes -errno, like the raw syscalls
+define internal i64 @jalgo_result(i64 %result) {
+entry:
+  %failed = icmp slt i64 %result, 0
+  br i1 %failed, label %error, label %ok
+ok:
+  ret i64 %result
+error:
+  %errno_pointer = call ptr @__errno_location()
+  %errno = load i32, ptr %errno_pointer
+  %errno64 = sext i32 %errno to i64
+  %code = sub i64 0, %errno64
+  ret i64 %code
+}
+
+define internal i64 @jalgo_fopen(ptr %path, i32 %flags) {
+  %fd = call i32 (ptr, i32, ...) @open(ptr %path, i32 %flags, i32 420)
+  %fd64 = sext i32 %fd to i64
+  %result = call i64 @jalgo_result(i64 %fd64)
+  ret i64 %result
+}
+
+; fd -> byte, -1 on EOF, -errno on error
+define internal i64 @jalgo_fread_byte(i64 %fd) {
+  %byte = alloca i8
+  store i8 0, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @read(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  %loaded = load i8, ptr %byte
+  %extended = zext i8 %loaded to i64
+  %is_byte = icmp eq i64 %count, 1
+  %is_eof = icmp eq i64 %count, 0
+  %eof_or_error = select i1 %is_eof, i64 -1, i64 %result
+  %value = select i1 %is_byte, i64 %extended, i64 %eof_or_error
+  ret i64 %value
+}
+
+; fd byte -> 1, -errno on error. printf output goes first
+define internal i64 @jalgo_fwrite_byte(i64 %fd, i64 %value) {
+  call i32 @fflush(ptr null)
+  %byte = alloca i8
+  %truncated = trunc i64 %value to i8
+  store i8 %truncated, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @write(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  ret i64 %result
+}
+
+define internal i64 @jalgo_fclose(i64 %fd) {
+  call i32 @fflush(ptr null)
+  %fd32 = trunc i64 %fd to i32
+  %status = call i32 @close(i32 %fd32)
+  %status64 = sext i32 %status to i64
+  %result = call i64 @jalgo_result(i64 %status64)
+  ret i64 %result
+}
+
+@data_path = internal global [8 x i8] c"out\2Etxt\00"
+
+; copy_out
+define internal ptr @st_copy_out(ptr %sp.arg) {
+entry:
+  %sp = alloca ptr
+  store ptr %sp.arg, ptr %sp
+  br label %restart
+restart:
+  %t1 = load ptr, ptr %sp
+  %t2 = load i64, ptr %t1
+  %t3 = getelementptr i64, ptr %t1, i64 -1
+  store ptr %t3, ptr %sp
+  store i64 %t2, ptr %t3
+  %t4 = getelementptr i64, ptr %t3, i64 1
+  store i64 %t2, ptr %t4
+  %t5 = load ptr, ptr %sp
+  %t6 = load i64, ptr %t5
+  %t7 = call i64 @jalgo_fread_byte(i64 %t6)
+  store i64 %t7, ptr %t5
+  %t8 = load ptr, ptr %sp
+  %t9 = load i64, ptr %t8
+  %t10 = getelementptr i64, ptr %t8, i64 -1
+  store ptr %t10, ptr %sp
+  store i64 %t9, ptr %t10
+  %t11 = getelementptr i64, ptr %t10, i64 1
+  store i64 %t9, ptr %t11
+  %t12 = load ptr, ptr %sp
+  %t13 = getelementptr i64, ptr %t12, i64 -1
+  store ptr %t13, ptr %sp
+  store i64 0, ptr %t13
+  %t14 = load ptr, ptr %sp
+  %t15 = load i64, ptr %t14
+  %t16 = getelementptr i64, ptr %t14, i64 1
+  %t17 = load i64, ptr %t16
+  %t18 = icmp slt i64 %t17, %t15
+  %t19 = zext i1 %t18 to i64
+  %t20 = getelementptr i64, ptr %t14, i64 1
+  store ptr %t20, ptr %sp
+  store i64 %t19, ptr %t20
+  %t21 = load ptr, ptr %sp
+  %t22 = load i64, ptr %t21
+  %t23 = icmp sle i64 %t22, 0
+  %t24 = getelementptr i64, ptr %t21, i64 1
+  store ptr %t24, ptr %sp
+  br i1 %t23, label %l10, label %b25
+b25:
+  %t26 = load ptr, ptr %sp
+  %t27 = load i64, ptr %t26
+  %t28 = getelementptr i64, ptr %t26, i64 1
+  store ptr %t28, ptr %sp
+  %t29 = load ptr, ptr %sp
+  %t30 = load i64, ptr %t29
+  %t31 = call i64 @jalgo_fclose(i64 %t30)
+  store i64 %t31, ptr %t29
+  %t32 = load ptr, ptr %sp
+  %t33 = load i64, ptr %t32
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t33)
+  %t34 = getelementptr i64, ptr %t32, i64 1
+  store ptr %t34, ptr %sp
+  %t35 = load ptr, ptr %sp
+  ret ptr %t35
+l10:
+  %t36 = load ptr, ptr %sp
+  %t37 = getelementptr i64, ptr %t36, i64 -1
+  store ptr %t37, ptr %sp
+  store i64 1, ptr %t37
+  %t38 = load ptr, ptr %sp
+  %t39 = load i64, ptr %t38
+  %t40 = getelementptr i64, ptr %t38, i64 1
+  %t41 = load i64, ptr %t40
+  store i64 %t41, ptr %t38
+  %t42 = getelementptr i64, ptr %t38, i64 1
+  store i64 %t39, ptr %t42
+  %t43 = load ptr, ptr %sp
+  %t44 = load i64, ptr %t43
+  %t45 = getelementptr i64, ptr %t43, i64 1
+  %t46 = load i64, ptr %t45
+  %t47 = call i64 @jalgo_fwrite_byte(i64 %t46, i64 %t44)
+  %t48 = getelementptr i64, ptr %t43, i64 1
+  store ptr %t48, ptr %sp
+  store i64 %t47, ptr %t48
+  %t49 = load ptr, ptr %sp
+  %t50 = load i64, ptr %t49
+  %t51 = getelementptr i64, ptr %t49, i64 1
+  store ptr %t51, ptr %sp
+  br label %restart
+b53:
+  %t52 = load ptr, ptr %sp
+  ret ptr %t52
+}
+
+; start
+define internal ptr @st_start(ptr %sp.arg) {
+entry:
+  %sp = alloca ptr
+  store ptr %sp.arg, ptr %sp
+  br label %restart
+restart:
+  %t1 = load ptr, ptr %sp
+  %t2 = getelementptr i64, ptr %t1, i64 -1
+  store ptr %t2, ptr %sp
+  store i64 0, ptr %t2
+  %t3 = load ptr, ptr %sp
+  %t4 = getelementptr i64, ptr %t3, i64 -1
+  store ptr %t4, ptr %sp
+  store i64 116, ptr %t4
+  %t5 = load ptr, ptr %sp
+  %t6 = getelementptr i64, ptr %t5, i64 -1
+  store ptr %t6, ptr %sp
+  store i64 120, ptr %t6
+  %t7 = load ptr, ptr %sp
+  %t8 = getelementptr i64, ptr %t7, i64 -1
+  store ptr %t8, ptr %sp
+  store i64 116, ptr %t8
+  %t9 = load ptr, ptr %sp
+  %t10 = getelementptr i64, ptr %t9, i64 -1
+  store ptr %t10, ptr %sp
+  store i64 46, ptr %t10
+  %t11 = load ptr, ptr %sp
+  %t12 = getelementptr i64, ptr %t11, i64 -1
+  store ptr %t12, ptr %sp
+  store i64 116, ptr %t12
+  %t13 = load ptr, ptr %sp
+  %t14 = getelementptr i64, ptr %t13, i64 -1
+  store ptr %t14, ptr %sp
+  store i64 117, ptr %t14
+  %t15 = load ptr, ptr %sp
+  %t16 = getelementptr i64, ptr %t15, i64 -1
+  store ptr %t16, ptr %sp
+  store i64 111, ptr %t16
+  %t17 = load ptr, ptr %sp
+  %t18 = call ptr @jalgo_pop_path(ptr %t17)
+  store ptr %t18, ptr %sp
+  %t19 = load ptr, ptr %sp
+  %t20 = call i64 @jalgo_fopen(ptr @path_buffer, i32 577)
+  %t21 = getelementptr i64, ptr %t19, i64 -1
+  store ptr %t21, ptr %sp
+  store i64 %t20, ptr %t21
+  %t22 = load ptr, ptr %sp
+  %t23 = load i64, ptr %t22
+  %t24 = getelementptr i64, ptr %t22, i64 -1
+  store ptr %t24, ptr %sp
+  store i64 %t23, ptr %t24
+  %t25 = getelementptr i64, ptr %t24, i64 1
+  store i64 %t23, ptr %t25
+  %t26 = load ptr, ptr %sp
+  %t27 = load i64, ptr %t26
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t27)
+  %t28 = getelementptr i64, ptr %t26, i64 1
+  store ptr %t28, ptr %sp
+  %t29 = load ptr, ptr %sp
+  %t30 = load i64, ptr %t29
+  %t31 = getelementptr i64, ptr %t29, i64 -1
+  store ptr %t31, ptr %sp
+  store i64 %t30, ptr %t31
+  %t32 = getelementptr i64, ptr %t31, i64 1
+  store i64 %t30, ptr %t32
+  %t33 = load ptr, ptr %sp
+  %t34 = getelementptr i64, ptr %t33, i64 -1
+  store ptr %t34, ptr %sp
+  store i64 104, ptr %t34
+  %t35 = load ptr, ptr %sp
+  %t36 = load i64, ptr %t35
+  %t37 = getelementptr i64, ptr %t35, i64 1
+  %t38 = load i64, ptr %t37
+  %t39 = call i64 @jalgo_fwrite_byte(i64 %t38, i64 %t36)
+  %t40 = getelementptr i64, ptr %t35, i64 1
+  store ptr %t40, ptr %sp
+  store i64 %t39, ptr %t40
+  %t41 = load ptr, ptr %sp
+  %t42 = load i64, ptr %t41
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t42)
+  %t43 = getelementptr i64, ptr %t41, i64 1
+  store ptr %t43, ptr %sp
+  %t44 = load ptr, ptr %sp
+  %t45 = load i64, ptr %t44
+  %t46 = getelementptr i64, ptr %t44, i64 -1
+  store ptr %t46, ptr %sp
+  store i64 %t45, ptr %t46
+  %t47 = getelementptr i64, ptr %t46, i64 1
+  store i64 %t45, ptr %t47
+  %t48 = load ptr, ptr %sp
+  %t49 = getelementptr i64, ptr %t48, i64 -1
+  store ptr %t49, ptr %sp
+  store i64 105, ptr %t49
+  %t50 = load ptr, ptr %sp
+  %t51 = load i64, ptr %t50
+  %t52 = getelementptr i64, ptr %t50, i64 1
+  %t53 = load i64, ptr %t52
+  %t54 = call i64 @jalgo_fwrite_byte(i64 %t53, i64 %t51)
+  %t55 = getelementptr i64, ptr %t50, i64 1
+  store ptr %t55, ptr %sp
+  store i64 %t54, ptr %t55
+  %t56 = load ptr, ptr %sp
+  %t57 = load i64, ptr %t56
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t57)
+  %t58 = getelementptr i64, ptr %t56, i64 1
+  store ptr %t58, ptr %sp
+  %t59 = load ptr, ptr %sp
+  %t60 = load i64, ptr %t59
+  %t61 = getelementptr i64, ptr %t59, i64 -1
+  store ptr %t61, ptr %sp
+  store i64 %t60, ptr %t61
+  %t62 = getelementptr i64, ptr %t61, i64 1
+  store i64 %t60, ptr %t62
+  %t63 = load ptr, ptr %sp
+  %t64 = getelementptr i64, ptr %t63, i64 -1
+  store ptr %t64, ptr %sp
+  store i64 10, ptr %t64
+  %t65 = load ptr, ptr %sp
+  %t66 = load i64, ptr %t65
+  %t67 = getelementptr i64, ptr %t65, i64 1
+  %t68 = load i64, ptr %t67
+  %t69 = call i64 @jalgo_fwrite_byte(i64 %t68, i64 %t66)
+  %t70 = getelementptr i64, ptr %t65, i64 1
+  store ptr %t70, ptr %sp
+  store i64 %t69, ptr %t70
+  %t71 = load ptr, ptr %sp
+  %t72 = load i64, ptr %t71
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t72)
+  %t73 = getelementptr i64, ptr %t71, i64 1
+  store ptr %t73, ptr %sp
+  %t74 = load ptr, ptr %sp
+  %t75 = load i64, ptr %t74
+  %t76 = call i64 @jalgo_fclose(i64 %t75)
+  store i64 %t76, ptr %t74
+  %t77 = load ptr, ptr %sp
+  %t78 = load i64, ptr %t77
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t78)
+  %t79 = getelementptr i64, ptr %t77, i64 1
+  store ptr %t79, ptr %sp
+  %t80 = load ptr, ptr %sp
+  %t81 = getelementptr i64, ptr %t80, i64 -1
+  store ptr %t81, ptr %sp
+  store i64 0, ptr %t81
+  %t82 = load ptr, ptr %sp
+  %t83 = getelementptr i64, ptr %t82, i64 -1
+  store ptr %t83, ptr %sp
+  store i64 116, ptr %t83
+  %t84 = load ptr, ptr %sp
+  %t85 = getelementptr i64, ptr %t84, i64 -1
+  store ptr %t85, ptr %sp
+  store i64 120, ptr %t85
+  %t86 = load ptr, ptr %sp
+  %t87 = getelementptr i64, ptr %t86, i64 -1
+  store ptr %t87, ptr %sp
+  store i64 116, ptr %t87
+  %t88 = load ptr, ptr %sp
+  %t89 = getelementptr i64, ptr %t88, i64 -1
+  store ptr %t89, ptr %sp
+  store i64 46, ptr %t89
+  %t90 = load ptr, ptr %sp
+  %t91 = getelementptr i64, ptr %t90, i64 -1
+  store ptr %t91, ptr %sp
+  store i64 116, ptr %t91
+  %t92 = load ptr, ptr %sp
+  %t93 = getelementptr i64, ptr %t92, i64 -1
+  store ptr %t93, ptr %sp
+  store i64 117, ptr %t93
+  %t94 = load ptr, ptr %sp
+  %t95 = getelementptr i64, ptr %t94, i64 -1
+  store ptr %t95, ptr %sp
+  store i64 111, ptr %t95
+  %t96 = load ptr, ptr %sp
+  %t97 = call ptr @jalgo_pop_path(ptr %t96)
+  store ptr %t97, ptr %sp
+  %t98 = load ptr, ptr %sp
+  %t99 = call i64 @jalgo_fopen(ptr @path_buffer, i32 1089)
+  %t100 = getelementptr i64, ptr %t98, i64 -1
+  store ptr %t100, ptr %sp
+  store i64 %t99, ptr %t100
+  %t101 = load ptr, ptr %sp
+  %t102 = load i64, ptr %t101
+  %t103 = getelementptr i64, ptr %t101, i64 -1
+  store ptr %t103, ptr %sp
+  store i64 %t102, ptr %t103
+  %t104 = getelementptr i64, ptr %t103, i64 1
+  store i64 %t102, ptr %t104
+  %t105 = load ptr, ptr %sp
+  %t106 = getelementptr i64, ptr %t105, i64 -1
+  store ptr %t106, ptr %sp
+  store i64 33, ptr %t106
+  %t107 = load ptr, ptr %sp
+  %t108 = load i64, ptr %t107
+  %t109 = getelementptr i64, ptr %t107, i64 1
+  %t110 = load i64, ptr %t109
+  %t111 = call i64 @jalgo_fwrite_byte(i64 %t110, i64 %t108)
+  %t112 = getelementptr i64, ptr %t107, i64 1
+  store ptr %t112, ptr %sp
+  store i64 %t111, ptr %t112
+  %t113 = load ptr, ptr %sp
+  %t114 = load i64, ptr %t113
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t114)
+  %t115 = getelementptr i64, ptr %t113, i64 1
+  store ptr %t115, ptr %sp
+  %t116 = load ptr, ptr %sp
+  %t117 = load i64, ptr %t116
+  %t118 = getelementptr i64, ptr %t116, i64 -1
+  store ptr %t118, ptr %sp
+  store i64 %t117, ptr %t118
+  %t119 = getelementptr i64, ptr %t118, i64 1
+  store i64 %t117, ptr %t119
+  %t120 = load ptr, ptr %sp
+  %t121 = getelementptr i64, ptr %t120, i64 -1
+  store ptr %t121, ptr %sp
+  store i64 10, ptr %t121
+  %t122 = load ptr, ptr %sp
+  %t123 = load i64, ptr %t122
+  %t124 = getelementptr i64, ptr %t122, i64 1
+  %t125 = load i64, ptr %t124
+  %t126 = call i64 @jalgo_fwrite_byte(i64 %t125, i64 %t123)
+  %t127 = getelementptr i64, ptr %t122, i64 1
+  store ptr %t127, ptr %sp
+  store i64 %t126, ptr %t127
+  %t128 = load ptr, ptr %sp
+  %t129 = load i64, ptr %t128
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t129)
+  %t130 = getelementptr i64, ptr %t128, i64 1
+  store ptr %t130, ptr %sp
+  %t131 = load ptr, ptr %sp
+  %t132 = load i64, ptr %t131
+  %t133 = call i64 @jalgo_fclose(i64 %t132)
+  store i64 %t133, ptr %t131
+  %t134 = load ptr, ptr %sp
+  %t135 = load i64, ptr %t134
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t135)
+  %t136 = getelementptr i64, ptr %t134, i64 1
+  store ptr %t136, ptr %sp
+  %t137 = load ptr, ptr %sp
+  %t138 = getelementptr i64, ptr %t137, i64 -1
+  store ptr %t138, ptr %sp
+  store i64 0, ptr %t138
+  %t139 = load ptr, ptr %sp
+  %t140 = getelementptr i64, ptr %t139, i64 -1
+  store ptr %t140, ptr %sp
+  store i64 116, ptr %t140
+  %t141 = load ptr, ptr %sp
+  %t142 = getelementptr i64, ptr %t141, i64 -1
+  store ptr %t142, ptr %sp
+  store i64 120, ptr %t142
+  %t143 = load ptr, ptr %sp
+  %t144 = getelementptr i64, ptr %t143, i64 -1
+  store ptr %t144, ptr %sp
+  store i64 116, ptr %t144
+  %t145 = load ptr, ptr %sp
+  %t146 = getelementptr i64, ptr %t145, i64 -1
+  store ptr %t146, ptr %sp
+  store i64 46, ptr %t146
+  %t147 = load ptr, ptr %sp
+  %t148 = getelementptr i64, ptr %t147, i64 -1
+  store ptr %t148, ptr %sp
+  store i64 116, ptr %t148
+  %t149 = load ptr, ptr %sp
+  %t150 = getelementptr i64, ptr %t149, i64 -1
+  store ptr %t150, ptr %sp
+  store i64 117, ptr %t150
+  %t151 = load ptr, ptr %sp
+  %t152 = getelementptr i64, ptr %t151, i64 -1
+  store ptr %t152, ptr %sp
+  store i64 111, ptr %t152
+  %t153 = load ptr, ptr %sp
+  %t154 = call ptr @jalgo_pop_path(ptr %t153)
+  store ptr %t154, ptr %sp
+  %t155 = load ptr, ptr %sp
+  %t156 = call i64 @jalgo_fopen(ptr @path_buffer, i32 0)
+  %t157 = getelementptr i64, ptr %t155, i64 -1
+  store ptr %t157, ptr %sp
+  store i64 %t156, ptr %t157
+  %t158 = load ptr, ptr %sp
+  %t159 = call ptr @st_copy_out(ptr %t158)
+  store ptr %t159, ptr %sp
+  %t160 = load ptr, ptr %sp
+  %t161 = getelementptr i64, ptr %t160, i64 -1
+  store ptr %t161, ptr %sp
+  store i64 8, ptr %t161
+  %t162 = load ptr, ptr %sp
+  %t163 = load i64, ptr %t162
+  %t164 = call ptr @malloc(i64 %t163)
+  %t165 = ptrtoint ptr %t164 to i64
+  store i64 %t165, ptr %t162
+  %t166 = load ptr, ptr %sp
+  %t167 = load i64, ptr %t166
+  %t168 = getelementptr i64, ptr %t166, i64 -1
+  store ptr %t168, ptr %sp
+  store i64 %t167, ptr %t168
+  %t169 = getelementptr i64, ptr %t168, i64 1
+  store i64 %t167, ptr %t169
+  %t170 = load ptr, ptr %sp
+  %t171 = getelementptr i64, ptr %t170, i64 -1
+  store ptr %t171, ptr %sp
+  store i64 32783537689359727, ptr %t171
+  %t172 = load ptr, ptr %sp
+  %t173 = load i64, ptr %t172
+  %t174 = getelementptr i64, ptr %t172, i64 1
+  %t175 = load i64, ptr %t174
+  %t176 = inttoptr i64 %t175 to ptr
+  store i64 %t173, ptr %t176, align 1
+  %t177 = getelementptr i64, ptr %t172, i64 2
+  store ptr %t177, ptr %sp
+  %t178 = load ptr, ptr %sp
+  %t179 = load i64, ptr %t178
+  %t180 = getelementptr i64, ptr %t178, i64 -1
+  store ptr %t180, ptr %sp
+  store i64 %t179, ptr %t180
+  %t181 = getelementptr i64, ptr %t180, i64 1
+  store i64 %t179, ptr %t181
+  %t182 = load ptr, ptr %sp
+  %t183 = load i64, ptr %t182
+  %t184 = inttoptr i64 %t183 to ptr
+  %t185 = call i64 @jalgo_fopen(ptr %t184, i32 0)
+  store i64 %t185, ptr %t182
+  %t186 = load ptr, ptr %sp
+  %t187 = call ptr @st_copy_out(ptr %t186)
+  store ptr %t187, ptr %sp
+  %t188 = load ptr, ptr %sp
+  %t189 = load i64, ptr %t188
+  %t190 = inttoptr i64 %t189 to ptr
+  call void @free(ptr %t190)
+  %t191 = getelementptr i64, ptr %t188, i64 1
+  store ptr %t191, ptr %sp
+  %t192 = load ptr, ptr %sp
+  %t193 = ptrtoint ptr @data_path to i64
+  %t194 = getelementptr i64, ptr %t192, i64 -1
+  store ptr %t194, ptr %sp
+  store i64 %t193, ptr %t194
+  %t195 = load ptr, ptr %sp
+  %t196 = load i64, ptr %t195
+  %t197 = inttoptr i64 %t196 to ptr
+  %t198 = call i64 @jalgo_fopen(ptr %t197, i32 1089)
+  store i64 %t198, ptr %t195
+  %t199 = load ptr, ptr %sp
+  %t200 = load i64, ptr %t199
+  %t201 = getelementptr i64, ptr %t199, i64 -1
+  store ptr %t201, ptr %sp
+  store i64 %t200, ptr %t201
+  %t202 = getelementptr i64, ptr %t201, i64 1
+  store i64 %t200, ptr %t202
+  %t203 = load ptr, ptr %sp
+  %t204 = getelementptr i64, ptr %t203, i64 -1
+  store ptr %t204, ptr %sp
+  store i64 63, ptr %t204
+  %t205 = load ptr, ptr %sp
+  %t206 = load i64, ptr %t205
+  %t207 = getelementptr i64, ptr %t205, i64 1
+  %t208 = load i64, ptr %t207
+  %t209 = call i64 @jalgo_fwrite_byte(i64 %t208, i64 %t206)
+  %t210 = getelementptr i64, ptr %t205, i64 1
+  store ptr %t210, ptr %sp
+  store i64 %t209, ptr %t210
+  %t211 = load ptr, ptr %sp
+  %t212 = load i64, ptr %t211
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t212)
+  %t213 = getelementptr i64, ptr %t211, i64 1
+  store ptr %t213, ptr %sp
+  %t214 = load ptr, ptr %sp
+  %t215 = load i64, ptr %t214
+  %t216 = call i64 @jalgo_fclose(i64 %t215)
+  store i64 %t216, ptr %t214
+  %t217 = load ptr, ptr %sp
+  %t218 = load i64, ptr %t217
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t218)
+  %t219 = getelementptr i64, ptr %t217, i64 1
+  store ptr %t219, ptr %sp
+  %t220 = load ptr, ptr %sp
+  %t221 = ptrtoint ptr @data_path to i64
+  %t222 = getelementptr i64, ptr %t220, i64 -1
+  store ptr %t222, ptr %sp
+  store i64 %t221, ptr %t222
+  %t223 = load ptr, ptr %sp
+  %t224 = load i64, ptr %t223
+  %t225 = inttoptr i64 %t224 to ptr
+  %t226 = call i64 @jalgo_fopen(ptr %t225, i32 0)
+  store i64 %t226, ptr %t223
+  %t227 = load ptr, ptr %sp
+  %t228 = call ptr @st_copy_out(ptr %t227)
+  store ptr %t228, ptr %sp
+  %t229 = load ptr, ptr %sp
+  %t230 = getelementptr i64, ptr %t229, i64 -1
+  store ptr %t230, ptr %sp
+  store i64 0, ptr %t230
+  %t231 = load ptr, ptr %sp
+  %t232 = getelementptr i64, ptr %t231, i64 -1
+  store ptr %t232, ptr %sp
+  store i64 120, ptr %t232
+  %t233 = load ptr, ptr %sp
+  %t234 = call ptr @jalgo_pop_path(ptr %t233)
+  store ptr %t234, ptr %sp
+  %t235 = load ptr, ptr %sp
+  %t236 = call i64 @jalgo_fopen(ptr @path_buffer, i32 0)
+  %t237 = getelementptr i64, ptr %t235, i64 -1
+  store ptr %t237, ptr %sp
+  store i64 %t236, ptr %t237
+  %t238 = load ptr, ptr %sp
+  %t239 = load i64, ptr %t238
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t239)
+  %t240 = getelementptr i64, ptr %t238, i64 1
+  store ptr %t240, ptr %sp
+  %t241 = load ptr, ptr %sp
+  %t242 = getelementptr i64, ptr %t241, i64 -1
+  store ptr %t242, ptr %sp
+  store i64 99, ptr %t242
+  %t243 = load ptr, ptr %sp
+  %t244 = load i64, ptr %t243
+  %t245 = call i64 @jalgo_fclose(i64 %t244)
+  store i64 %t245, ptr %t243
+  %t246 = load ptr, ptr %sp
+  %t247 = load i64, ptr %t246
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t247)
+  %t248 = getelementptr i64, ptr %t246, i64 1
+  store ptr %t248, ptr %sp
+  %t249 = load ptr, ptr %sp
+  %t250 = getelementptr i64, ptr %t249, i64 -1
+  store ptr %t250, ptr %sp
+  store i64 0, ptr %t250
+  %t251 = load ptr, ptr %sp
+  %t252 = load i64, ptr %t251
+  %t253 = call i64 @jalgo_fread_byte(i64 %t252)
+  store i64 %t253, ptr %t251
+  %t254 = load ptr, ptr %sp
+  %t255 = load i64, ptr %t254
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t255)
+  %t256 = getelementptr i64, ptr %t254, i64 1
+  store ptr %t256, ptr %sp
+  %t257 = load ptr, ptr %sp
+  %t258 = getelementptr i64, ptr %t257, i64 -1
+  store ptr %t258, ptr %sp
+  store i64 3, ptr %t258
+  %t259 = load ptr, ptr %sp
+  %t260 = load i64, ptr %t259
+  %t261 = trunc i64 %t260 to i32
+  call void @exit(i32 %t261)
+  %t262 = getelementptr i64, ptr %t259, i64 1
+  store ptr %t262, ptr %sp
+  %t263 = load ptr, ptr %sp
+  ret ptr %t263
+}
+
+define i32 @main() {
+  %sp = call ptr @st_start(ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0))
+  ret i32 0
+}
@@ -0,0 +1,379 @@
+@int_fmt = private unnamed_addr constant [6 x i8] c"%lld \00"
+@bytes_fmt = private unnamed_addr constant [5 x i8] c"%.8s\00"
+@newline_fmt = private unnamed_addr constant [2 x i8] c"\0A\00"
+@int_scan_fmt = private unnamed_addr constant [5 x i8] c"%lld\00"
+@stack = internal global [1048576 x i64] zeroinitializer
+@line_buffer = internal global [4096 x i8] zeroinitializer
+@path_buffer = internal global [4096 x i8] zeroinitializer
+
+declare i32 @printf(ptr, ...)
+declare i32 @scanf(ptr, ...)
+declare i32 @getchar()
+declare ptr @malloc(i64)
+declare void @free(ptr)
+declare void @exit(i32) noreturn
+declare i32 @fflush(ptr)
+declare i32 @open(ptr, i32, ...)
+declare i64 @read(i32, ptr, i64)
+declare i64 @write(i32, ptr, i64)
+declare i32 @close(i32)
+declare ptr @__errno_location()
+
+; prints the data stack from the bottom to the top
+define internal void @jalgo_dump_stack(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %cell = phi ptr [ getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0), %entry ], [ %next, %body ]
+  %more = icmp ugt ptr %cell, %sp
+  br i1 %more, label %body, label %end
+body:
+  %next = getelementptr i64, ptr %cell, i64 -1
+  %value = load i64, ptr %next
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %value)
+  br label %loop
+end:
+  call i32 (ptr, ...) @printf(ptr @newline_fmt)
+  ret void
+}
+
+; up to 8 bytes of the value until a zero byte
+define internal void @jalgo_write_raw(i64 %value) {
+  %bytes = alloca i64
+  store i64 %value, ptr %bytes
+  call i32 (ptr, ...) @printf(ptr @bytes_fmt, ptr %bytes)
+  ret void
+}
+
+; pushes the value and the scanf status
+define internal ptr @jalgo_read_int(ptr %sp) {
+  %value = alloca i64
+  store i64 0, ptr %value
+  %status = call i32 (ptr, ...) @scanf(ptr @int_scan_fmt, ptr %value)
+  %loaded = load i64, ptr %value
+  %status64 = sext i32 %status to i64
+  %value_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 %loaded, ptr %value_cell
+  %new_sp = getelementptr i64, ptr %sp, i64 -2
+  store i64 %status64, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pushes the line as a zero terminated stack string and its length, -1 on EOF
+define internal ptr @jalgo_read_line(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %length = phi i64 [ 0, %entry ], [ %next_length, %append ]
+  %c = call i32 @getchar()
+  %eof = icmp eq i32 %c, -1
+  br i1 %eof, label %at_eof, label %not_eof
+not_eof:
+  %newline = icmp eq i32 %c, 10
+  br i1 %newline, label %done, label %append
+append:
+  %byte = trunc i32 %c to i8
+  %slot = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %length
+  store i8 %byte, ptr %slot
+  %next_length = add i64 %length, 1
+  %full = icmp eq i64 %next_length, 4096
+  br i1 %full, label %done, label %loop
+at_eof:
+  %empty = icmp eq i64 %length, 0
+  br i1 %empty, label %nothing, label %done
+nothing:
+  %eof_sp = getelementptr i64, ptr %sp, i64 -1
+  store i64 -1, ptr %eof_sp
+  ret ptr %eof_sp
+done:
+  %final_length = phi i64 [ %length, %not_eof ], [ %next_length, %append ], [ %length, %at_eof ]
+  %zero_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 0, ptr %zero_cell
+  br label %copy
+copy:
+  %i = phi i64 [ 0, %done ], [ %next_i, %copy_byte ]
+  %copy_more = icmp ult i64 %i, %final_length
+  br i1 %copy_more, label %copy_byte, label %copy_end
+copy_byte:
+  %source = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %i
+  %loaded = load i8, ptr %source
+  %extended = zext i8 %loaded to i64
+  %from_end = sub i64 %i, %final_length
+  %offset = sub i64 %from_end, 1
+  %target = getelementptr i64, ptr %sp, i64 %offset
+  store i64 %extended, ptr %target
+  %next_i = add i64 %i, 1
+  br label %copy
+copy_end:
+  %length_offset = sub i64 -2, %final_length
+  %new_sp = getelementptr i64, ptr %sp, i64 %length_offset
+  store i64 %final_length, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pops a zero terminated stack string into @path_buffer
+define internal ptr @jalgo_pop_path(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %i = phi i64 [ 0, %entry ], [ %next, %loop ]
+  %cell = getelementptr i64, ptr %sp, i64 %i
+  %value = load i64, ptr %cell
+  %byte = trunc i64 %value to i8
+  %slot = getelementptr [4096 x i8], ptr @path_buffer, i64 0, i64 %i
+  store i8 %byte, ptr %slot
+  %next = add i64 %i, 1
+  %more = icmp ne i64 %value, 0
+  br i1 %more, label %loop, label %end
+end:
+  %new_sp = getelementptr i64, ptr %sp, i64 %next
+  ret ptr %new_sp
+}
+
+; -1 from libc becomes -errno, like the raw syscalls
+define internal i64 @jalgo_result(i64 %result) {
+entry:
+  %failed = icmp slt i64 %result, 0
+  br i1 %failed, label %error, label %ok
+ok:
+  ret i64 %result
+error:
+  %errno_pointer = call ptr @__errno_location()
+  %errno = load i32, ptr %errno_pointer
+  %errno64 = sext i32 %errno to i64
+  %code = sub i64 0, %errno64
+  ret i64 %code
+}
+
+define internal i64 @jalgo_fopen(ptr %path, i32 %flags) {
+  %fd = call i32 (ptr, i32, ...) @open(ptr %path, i32 %flags, i32 420)
+  %fd64 = sext i32 %fd to i64
+  %result = call i64 @jalgo_result(i64 %fd64)
+  ret i64 %result
+}
+
+; fd -> byte, -1 on EOF, -errno on error
+define internal i64 @jalgo_fread_byte(i64 %fd) {
+  %byte = alloca i8
+  store i8 0, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @read(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  %loaded = load i8, ptr %byte
+  %extended = zext i8 %loaded to i64
+  %is_byte = icmp eq i64 %count, 1
+  %is_eof = icmp eq i64 %count, 0
+  %eof_or_error = select i1 %is_eof, i64 -1, i64 %result
+  %value = select i1 %is_byte, i64 %extended, i64 %eof_or_error
+  ret i64 %value
+}
+
+; fd byte -> 1, -errno on error. printf output goes first
+define internal i64 @jalgo_fwrite_byte(i64 %fd, i64 %value) {
+  call i32 @fflush(ptr null)
+  %byte = alloca i8
+  %truncated = trunc i64 %value to i8
+  store i8 %truncated, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @write(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  ret i64 %result
+}
+
+define internal i64 @jalgo_fclose(i64 %fd) {
+  call i32 @fflush(ptr null)
+  %fd32 = trunc i64 %fd to i32
+  %status = call i32 @close(i32 %fd32)
+  %status64 = sext i32 %status to i64
+  %result = call i64 @jalgo_result(i64 %status64)
+  ret i64 %result
+}
+
+
+; start
+define internal ptr @st_start(ptr %sp.arg) {
+entry:
+  %sp = alloca ptr
+  store ptr %sp.arg, ptr %sp
+  br label %restart
+restart:
+  %t1 = load ptr, ptr %sp
+  %t2 = getelementptr i64, ptr %t1, i64 -1
+  store ptr %t2, ptr %sp
+  store i64 16, ptr %t2
+  %t3 = load ptr, ptr %sp
+  %t4 = load i64, ptr %t3
+  %t5 = call ptr @malloc(i64 %t4)
+  %t6 = ptrtoint ptr %t5 to i64
+  store i64 %t6, ptr %t3
+  %t7 = load ptr, ptr %sp
+  %t8 = load i64, ptr %t7
+  %t9 = getelementptr i64, ptr %t7, i64 -1
+  store ptr %t9, ptr %sp
+  store i64 %t8, ptr %t9
+  %t10 = getelementptr i64, ptr %t9, i64 1
+  store i64 %t8, ptr %t10
+  %t11 = load ptr, ptr %sp
+  %t12 = getelementptr i64, ptr %t11, i64 -1
+  store ptr %t12, ptr %sp
+  store i64 77, ptr %t12
+  %t13 = load ptr, ptr %sp
+  %t14 = load i64, ptr %t13
+  %t15 = getelementptr i64, ptr %t13, i64 1
+  %t16 = load i64, ptr %t15
+  %t17 = inttoptr i64 %t16 to ptr
+  store i64 %t14, ptr %t17, align 1
+  %t18 = getelementptr i64, ptr %t13, i64 2
+  store ptr %t18, ptr %sp
+  %t19 = load ptr, ptr %sp
+  %t20 = load i64, ptr %t19
+  %t21 = getelementptr i64, ptr %t19, i64 -1
+  store ptr %t21, ptr %sp
+  store i64 %t20, ptr %t21
+  %t22 = getelementptr i64, ptr %t21, i64 1
+  store i64 %t20, ptr %t22
+  %t23 = load ptr, ptr %sp
+  %t24 = getelementptr i64, ptr %t23, i64 -1
+  store ptr %t24, ptr %sp
+  store i64 8, ptr %t24
+  %t25 = load ptr, ptr %sp
+  %t26 = load i64, ptr %t25
+  %t27 = getelementptr i64, ptr %t25, i64 1
+  %t28 = load i64, ptr %t27
+  %t29 = add i64 %t28, %t26
+  %t30 = getelementptr i64, ptr %t25, i64 1
+  store ptr %t30, ptr %sp
+  store i64 %t29, ptr %t30
+  %t31 = load ptr, ptr %sp
+  %t32 = getelementptr i64, ptr %t31, i64 -1
+  store ptr %t32, ptr %sp
+  store i64 -5, ptr %t32
+  %t33 = load ptr, ptr %sp
+  %t34 = load i64, ptr %t33
+  %t35 = getelementptr i64, ptr %t33, i64 1
+  %t36 = load i64, ptr %t35
+  %t37 = inttoptr i64 %t36 to ptr
+  store i64 %t34, ptr %t37, align 1
+  %t38 = getelementptr i64, ptr %t33, i64 2
+  store ptr %t38, ptr %sp
+  %t39 = load ptr, ptr %sp
+  %t40 = load i64, ptr %t39
+  %t41 = getelementptr i64, ptr %t39, i64 -1
+  store ptr %t41, ptr %sp
+  store i64 %t40, ptr %t41
+  %t42 = getelementptr i64, ptr %t41, i64 1
+  store i64 %t40, ptr %t42
+  %t43 = load ptr, ptr %sp
+  %t44 = load i64, ptr %t43
+  %t45 = inttoptr i64 %t44 to ptr
+  %t46 = load i64, ptr %t45, align 1
+  store i64 %t46, ptr %t43
+  %t47 = load ptr, ptr %sp
+  %t48 = load i64, ptr %t47
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t48)
+  %t49 = getelementptr i64, ptr %t47, i64 1
+  store ptr %t49, ptr %sp
+  %t50 = load ptr, ptr %sp
+  %t51 = load i64, ptr %t50
+  %t52 = getelementptr i64, ptr %t50, i64 -1
+  store ptr %t52, ptr %sp
+  store i64 %t51, ptr %t52
+  %t53 = getelementptr i64, ptr %t52, i64 1
+  store i64 %t51, ptr %t53
+  %t54 = load ptr, ptr %sp
+  %t55 = getelementptr i64, ptr %t54, i64 -1
+  store ptr %t55, ptr %sp
+  store i64 8, ptr %t55
+  %t56 = load ptr, ptr %sp
+  %t57 = load i64, ptr %t56
+  %t58 = getelementptr i64, ptr %t56, i64 1
+  %t59 = load i64, ptr %t58
+  %t60 = add i64 %t59, %t57
+  %t61 = getelementptr i64, ptr %t56, i64 1
+  store ptr %t61, ptr %sp
+  store i64 %t60, ptr %t61
+  %t62 = load ptr, ptr %sp
+  %t63 = load i64, ptr %t62
+  %t64 = inttoptr i64 %t63 to ptr
+  %t65 = load i64, ptr %t64, align 1
+  store i64 %t65, ptr %t62
+  %t66 = load ptr, ptr %sp
+  %t67 = load i64, ptr %t66
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t67)
+  %t68 = getelementptr i64, ptr %t66, i64 1
+  store ptr %t68, ptr %sp
+  %t69 = load ptr, ptr %sp
+  %t70 = load i64, ptr %t69
+  %t71 = inttoptr i64 %t70 to ptr
+  call void @free(ptr %t71)
+  %t72 = getelementptr i64, ptr %t69, i64 1
+  store ptr %t72, ptr %sp
+  %t73 = load ptr, ptr %sp
+  %t74 = getelementptr i64, ptr %t73, i64 -1
+  store ptr %t74, ptr %sp
+  store i64 24, ptr %t74
+  %t75 = load ptr, ptr %sp
+  %t76 = load i64, ptr %t75
+  %t77 = call ptr @malloc(i64 %t76)
+  %t78 = ptrtoint ptr %t77 to i64
+  store i64 %t78, ptr %t75
+  %t79 = load ptr, ptr %sp
+  %t80 = load i64, ptr %t79
+  %t81 = getelementptr i64, ptr %t79, i64 -1
+  store ptr %t81, ptr %sp
+  store i64 %t80, ptr %t81
+  %t82 = getelementptr i64, ptr %t81, i64 1
+  store i64 %t80, ptr %t82
+  %t83 = load ptr, ptr %sp
+  %t84 = getelementptr i64, ptr %t83, i64 -1
+  store ptr %t84, ptr %sp
+  store i64 0, ptr %t84
+  %t85 = load ptr, ptr %sp
+  %t86 = load i64, ptr %t85
+  %t87 = getelementptr i64, ptr %t85, i64 1
+  %t88 = load i64, ptr %t87
+  %t89 = inttoptr i64 %t88 to ptr
+  store i64 %t86, ptr %t89, align 1
+  %t90 = getelementptr i64, ptr %t85, i64 2
+  store ptr %t90, ptr %sp
+  %t91 = load ptr, ptr %sp
+  %t92 = load i64, ptr %t91
+  %t93 = getelementptr i64, ptr %t91, i64 -1
+  store ptr %t93, ptr %sp
+  store i64 %t92, ptr %t93
+  %t94 = getelementptr i64, ptr %t93, i64 1
+  store i64 %t92, ptr %t94
+  %t95 = load ptr, ptr %sp
+  %t96 = load i64, ptr %t95
+  %t97 = inttoptr i64 %t96 to ptr
+  %t98 = load i64, ptr %t97, align 1
+  store i64 %t98, ptr %t95
+  %t99 = load ptr, ptr %sp
+  %t100 = load i64, ptr %t99
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t100)
+  %t101 = getelementptr i64, ptr %t99, i64 1
+  store ptr %t101, ptr %sp
+  %t102 = load ptr, ptr %sp
+  %t103 = load i64, ptr %t102
+  %t104 = inttoptr i64 %t103 to ptr
+  call void @free(ptr %t104)
+  %t105 = getelementptr i64, ptr %t102, i64 1
+  store ptr %t105, ptr %sp
+  %t106 = load ptr, ptr %sp
+  %t107 = getelementptr i64, ptr %t106, i64 -1
+  store ptr %t107, ptr %sp
+  store i64 0, ptr %t107
+  %t108 = load ptr, ptr %sp
+  %t109 = load i64, ptr %t108
+  %t110 = inttoptr i64 %t109 to ptr
+  call void @free(ptr %t110)
+  %t111 = getelementptr i64, ptr %t108, i64 1
+  store ptr %t111, ptr %sp
+  %t112 = load ptr, ptr %sp
+  ret ptr %t112
+}
+
+define i32 @main() {
+  %sp = call ptr @st_start(ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0))
+  ret i32 0
+}
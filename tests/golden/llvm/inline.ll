@@ -0,0 +1,1463 @@
+@int_fmt = private unnamed_addr constant [6 x i8] c"%lld \00"
+@bytes_fmt = private unnamed_addr constant [5 x i8] c"%.8s\00"
+@newline_fmt = private unnamed_addr constant [2 x i8] c"\0A\00"
+@int_scan_fmt = private unnamed_addr constant [5 x i8] c"%lld\00"
+@stack = internal global [1048576 x i64] zeroinitializer
+@line_buffer = internal global [4096 x i8] zeroinitializer
+@path_buffer = internal global [4096 x i8] zeroinitializer
+
+declare i32 @printf(ptr, ...)
+declare i32 @scanf(ptr, ...)
+declare i32 @getchar()
+declare ptr @malloc(i64)
+declare void @free(ptr)
+declare void @exit(i32) noreturn
+declare i32 @fflush(ptr)
+declare i32 @open(ptr, i32, ...)
+declare i64 @read(i32, ptr, i64)
+declare i64 @write(i32, ptr, i64)
+declare i32 @close(i32)
+declare ptr @__errno_location()
+
+; prints the data stack from the bottom to the top
+define internal void @jalgo_dump_stack(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %cell = phi ptr [ getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0), %entry ], [ %next, %body ]
+  %more = icmp ugt ptr %cell, %sp
+  br i1 %more, label %body, label %end
+body:
+  %next = getelementptr i64, ptr %cell, i64 -1
+  %value = load i64, ptr %next
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %value)
+  br label %loop
+end:
+  call i32 (ptr, ...) @printf(ptr @newline_fmt)
+  ret void
+}
+
+; up to 8 bytes of the value until a zero byte
+define internal void @jalgo_write_raw(i64 %value) {
+  %bytes = alloca i64
+  store i64 %value, ptr %bytes
+  call i32 (ptr, ...) @printf(ptr @bytes_fmt, ptr %bytes)
+  ret void
+}
+
+; pushes the value and the scanf status
+define internal ptr @jalgo_read_int(ptr %sp) {
+  %value = alloca i64
+  store i64 0, ptr %value
+  %status = call i32 (ptr, ...) @scanf(ptr @int_scan_fmt, ptr %value)
+  %loaded = load i64, ptr %value
+  %status64 = sext i32 %status to i64
+  %value_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 %loaded, ptr %value_cell
+  %new_sp = getelementptr i64, ptr %sp, i64 -2
+  store i64 %status64, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pushes the line as a zero terminated stack string and its length, -1 on EOF
+define internal ptr @jalgo_read_line(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %length = phi i64 [ 0, %entry ], [ %next_length, %append ]
+  %c = call i32 @getchar()
+  %eof = icmp eq i32 %c, -1
+  br i1 %eof, label %at_eof, label %not_eof
+not_eof:
+  %newline = icmp eq i32 %c, 10
+  br i1 %newline, label %done, label %append
+append:
+  %byte = trunc i32 %c to i8
+  %slot = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %length
+  store i8 %byte, ptr %slot
+  %next_length = add i64 %length, 1
+  %full = icmp eq i64 %next_length, 4096
+  br i1 %full, label %done, label %loop
+at_eof:
+  %empty = icmp eq i64 %length, 0
+  br i1 %empty, label %nothing, label %done
+nothing:
+  %eof_sp = getelementptr i64, ptr %sp, i64 -1
+  store i64 -1, ptr %eof_sp
+  ret ptr %eof_sp
+done:
+  %final_length = phi i64 [ %length, %not_eof ], [ %next_length, %append ], [ %length, %at_eof ]
+  %zero_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 0, ptr %zero_cell
+  br label %copy
+copy:
+  %i = phi i64 [ 0, %done ], [ %next_i, %copy_byte ]
+  %copy_more = icmp ult i64 %i, %final_length
+  br i1 %copy_more, label %copy_byte, label %copy_end
+copy_byte:
+  %source = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %i
+  %loaded = load i8, ptr %source
+  %extended = zext i8 %loaded to i64
+  %from_end = sub i64 %i, %final_length
+  %offset = sub i64 %from_end, 1
+  %target = getelementptr i64, ptr %sp, i64 %offset
+  store i64 %extended, ptr %target
+  %next_i = add i64 %i, 1
+  br label %copy
+copy_end:
+  %length_offset = sub i64 -2, %final_length
+  %new_sp = getelementptr i64, ptr %sp, i64 %length_offset
+  store i64 %final_length, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pops a zero terminated stack string into @path_buffer
+define internal ptr @jalgo_pop_path(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %i = phi i64 [ 0, %entry ], [ %next, %loop ]
+  %cell = getelementptr i64, ptr %sp, i64 %i
+  %value = load i64, ptr %cell
+  %byte = trunc i64 %value to i8
+  %slot = getelementptr [4096 x i8], ptr @path_buffer, i64 0, i64 %i
+  store i8 %byte, ptr %slot
+  %next = add i64 %i, 1
+  %more = icmp ne i64 %value, 0
+  br i1 %more, label %loop, label %end
+end:
+  %new_sp = getelementptr i64, ptr %sp, i64 %next
+  ret ptr %new_sp
+}
+
+; -1 from libc becomes -errno, like the raw syscalls
+define internal i64 @jalgo_result(i64 %result) {
+entry:
+  %failed = icmp slt i64 %result, 0
+  br i1 %failed, label %error, label %ok
+ok:
+  ret i64 %result
+error:
+  %errno_pointer = call ptr @__errno_location()
+  %errno = load i32, ptr %errno_pointer
+  %errno64 = sext i32 %errno to i64
+  %code = sub i64 0, %errno64
+  ret i64 %code
+}
+
+define internal i64 @jalgo_fopen(ptr %path, i32 %flags) {
+  %fd = call i32 (ptr, i32, ...) @open(ptr %path, i32 %flags, i32 420)
+  %fd64 = sext i32 %fd to i64
+  %result = call i64 @jalgo_result(i64 %fd64)
+  ret i64 %result
+}
+
+; fd -> byte, -1 on EOF, -errno on error
+define internal i64 @jalgo_fread_byte(i64 %fd) {
+  %byte = alloca i8
+  store i8 0, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @read(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  %loaded = load i8, ptr %byte
+  %extended = zext i8 %loaded to i64
+  %is_byte = icmp eq i64 %count, 1
+  %is_eof = icmp eq i64 %count, 0
+  %eof_or_error = select i1 %is_eof, i64 -1, i64 %result
+  %value = select i1 %is_byte, i64 %extended, i64 %eof_or_error
+  ret i64 %value
+}
+
+; fd byte -> 1, -errno on error. printf output goes first
+define internal i64 @jalgo_fwrite_byte(i64 %fd, i64 %value) {
+  call i32 @fflush(ptr null)
+  %byte = alloca i8
+  %truncated = trunc i64 %value to i8
+  store i8 %truncated, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @write(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  ret i64 %result
+}
+
+define internal i64 @jalgo_fclose(i64 %fd) {
+  call i32 @fflush(ptr null)
+  %fd32 = trunc i64 %fd to i32
+  %status = call i32 @close(i32 %fd32)
+  %status64 = sext i32 %status to i64
+  %result = call i64 @jalgo_result(i64 %status64)
+  ret i64 %result
+}
+
+
+; twice
+define internal ptr @st_twice(ptr %sp.arg) {
+entry:
+  %sp = alloca ptr
+  store ptr %sp.arg, ptr %sp
+  br label %restart
+restart:
+  %t1 = load ptr, ptr %sp
+  %t2 = getelementptr i64, ptr %t1, i64 -1
+  store ptr %t2, ptr %sp
+  store i64 2, ptr %t2
+  %t3 = load ptr, ptr %sp
+  %t4 = load i64, ptr %t3
+  %t5 = getelementptr i64, ptr %t3, i64 1
+  %t6 = load i64, ptr %t5
+  %t7 = mul i64 %t6, %t4
+  %t8 = getelementptr i64, ptr %t3, i64 1
+  store ptr %t8, ptr %sp
+  store i64 %t7, ptr %t8
+  %t9 = load ptr, ptr %sp
+  ret ptr %t9
+}
+
+; start
+define internal ptr @st_start(ptr %sp.arg) {
+entry:
+  %sp = alloca ptr
+  %counter0 = alloca i64
+  store ptr %sp.arg, ptr %sp
+  br label %restart
+restart:
+  %t1 = load ptr, ptr %sp
+  %t2 = getelementptr i64, ptr %t1, i64 -1
+  store ptr %t2, ptr %sp
+  store i64 0, ptr %t2
+  %t3 = load ptr, ptr %sp
+  %t4 = load i64, ptr %t3
+  %t5 = icmp sle i64 %t4, 0
+  %t6 = getelementptr i64, ptr %t3, i64 1
+  store ptr %t6, ptr %sp
+  br i1 %t5, label %l42, label %b7
+b7:
+  %t8 = load ptr, ptr %sp
+  %t9 = getelementptr i64, ptr %t8, i64 -1
+  store ptr %t9, ptr %sp
+  store i64 0, ptr %t9
+  br label %l43
+l42:
+  %t10 = load ptr, ptr %sp
+  %t11 = getelementptr i64, ptr %t10, i64 -1
+  store ptr %t11, ptr %sp
+  store i64 1, ptr %t11
+  br label %l43
+l43:
+  %t12 = load ptr, ptr %sp
+  %t13 = load i64, ptr %t12
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t13)
+  %t14 = getelementptr i64, ptr %t12, i64 1
+  store ptr %t14, ptr %sp
+  %t15 = load ptr, ptr %sp
+  %t16 = getelementptr i64, ptr %t15, i64 -1
+  store ptr %t16, ptr %sp
+  store i64 5, ptr %t16
+  %t17 = load ptr, ptr %sp
+  %t18 = load i64, ptr %t17
+  %t19 = icmp sle i64 %t18, 0
+  %t20 = getelementptr i64, ptr %t17, i64 1
+  store ptr %t20, ptr %sp
+  br i1 %t19, label %l47, label %b21
+b21:
+  %t22 = load ptr, ptr %sp
+  %t23 = getelementptr i64, ptr %t22, i64 -1
+  store ptr %t23, ptr %sp
+  store i64 0, ptr %t23
+  br label %l48
+l47:
+  %t24 = load ptr, ptr %sp
+  %t25 = getelementptr i64, ptr %t24, i64 -1
+  store ptr %t25, ptr %sp
+  store i64 1, ptr %t25
+  br label %l48
+l48:
+  %t26 = load ptr, ptr %sp
+  %t27 = load i64, ptr %t26
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t27)
+  %t28 = getelementptr i64, ptr %t26, i64 1
+  store ptr %t28, ptr %sp
+  %t29 = load ptr, ptr %sp
+  %t30 = getelementptr i64, ptr %t29, i64 -1
+  store ptr %t30, ptr %sp
+  store i64 0, ptr %t30
+  %t31 = load ptr, ptr %sp
+  %t32 = load i64, ptr %t31
+  %t33 = icmp sle i64 %t32, 0
+  %t34 = getelementptr i64, ptr %t31, i64 1
+  store ptr %t34, ptr %sp
+  br i1 %t33, label %l52, label %b35
+b35:
+  %t36 = load ptr, ptr %sp
+  %t37 = getelementptr i64, ptr %t36, i64 -1
+  store ptr %t37, ptr %sp
+  store i64 0, ptr %t37
+  br label %l53
+l52:
+  %t38 = load ptr, ptr %sp
+  %t39 = getelementptr i64, ptr %t38, i64 -1
+  store ptr %t39, ptr %sp
+  store i64 1, ptr %t39
+  br label %l53
+l53:
+  %t40 = load ptr, ptr %sp
+  %t41 = load i64, ptr %t40
+  %t42 = icmp sle i64 %t41, 0
+  %t43 = getelementptr i64, ptr %t40, i64 1
+  store ptr %t43, ptr %sp
+  br i1 %t42, label %l57, label %b44
+b44:
+  %t45 = load ptr, ptr %sp
+  %t46 = getelementptr i64, ptr %t45, i64 -1
+  store ptr %t46, ptr %sp
+  store i64 0, ptr %t46
+  br label %l58
+l57:
+  %t47 = load ptr, ptr %sp
+  %t48 = getelementptr i64, ptr %t47, i64 -1
+  store ptr %t48, ptr %sp
+  store i64 1, ptr %t48
+  br label %l58
+l58:
+  %t49 = load ptr, ptr %sp
+  %t50 = load i64, ptr %t49
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t50)
+  %t51 = getelementptr i64, ptr %t49, i64 1
+  store ptr %t51, ptr %sp
+  %t52 = load ptr, ptr %sp
+  %t53 = getelementptr i64, ptr %t52, i64 -1
+  store ptr %t53, ptr %sp
+  store i64 -3, ptr %t53
+  %t54 = load ptr, ptr %sp
+  %t55 = load i64, ptr %t54
+  %t56 = getelementptr i64, ptr %t54, i64 -1
+  store ptr %t56, ptr %sp
+  store i64 %t55, ptr %t56
+  %t57 = getelementptr i64, ptr %t56, i64 1
+  store i64 %t55, ptr %t57
+  %t58 = load ptr, ptr %sp
+  %t59 = getelementptr i64, ptr %t58, i64 -1
+  store ptr %t59, ptr %sp
+  store i64 0, ptr %t59
+  %t60 = load ptr, ptr %sp
+  %t61 = load i64, ptr %t60
+  %t62 = getelementptr i64, ptr %t60, i64 1
+  %t63 = load i64, ptr %t62
+  %t64 = icmp slt i64 %t63, %t61
+  %t65 = zext i1 %t64 to i64
+  %t66 = getelementptr i64, ptr %t60, i64 1
+  store ptr %t66, ptr %sp
+  store i64 %t65, ptr %t66
+  %t67 = load ptr, ptr %sp
+  %t68 = load i64, ptr %t67
+  %t69 = icmp sle i64 %t68, 0
+  %t70 = getelementptr i64, ptr %t67, i64 1
+  store ptr %t70, ptr %sp
+  br i1 %t69, label %l67, label %b71
+b71:
+  %t72 = load ptr, ptr %sp
+  %t73 = load i64, ptr %t72
+  %t74 = getelementptr i64, ptr %t72, i64 1
+  store ptr %t74, ptr %sp
+  %t75 = load ptr, ptr %sp
+  %t76 = getelementptr i64, ptr %t75, i64 -1
+  store ptr %t76, ptr %sp
+  store i64 0, ptr %t76
+  br label %l74
+l67:
+  %t77 = load ptr, ptr %sp
+  %t78 = load i64, ptr %t77
+  %t79 = getelementptr i64, ptr %t77, i64 -1
+  store ptr %t79, ptr %sp
+  store i64 %t78, ptr %t79
+  %t80 = getelementptr i64, ptr %t79, i64 1
+  store i64 %t78, ptr %t80
+  %t81 = load ptr, ptr %sp
+  %t82 = getelementptr i64, ptr %t81, i64 -1
+  store ptr %t82, ptr %sp
+  store i64 9, ptr %t82
+  %t83 = load ptr, ptr %sp
+  %t84 = load i64, ptr %t83
+  %t85 = getelementptr i64, ptr %t83, i64 1
+  %t86 = load i64, ptr %t85
+  %t87 = icmp sgt i64 %t86, %t84
+  %t88 = zext i1 %t87 to i64
+  %t89 = getelementptr i64, ptr %t83, i64 1
+  store ptr %t89, ptr %sp
+  store i64 %t88, ptr %t89
+  %t90 = load ptr, ptr %sp
+  %t91 = load i64, ptr %t90
+  %t92 = icmp sle i64 %t91, 0
+  %t93 = getelementptr i64, ptr %t90, i64 1
+  store ptr %t93, ptr %sp
+  br i1 %t92, label %l74, label %b94
+b94:
+  %t95 = load ptr, ptr %sp
+  %t96 = load i64, ptr %t95
+  %t97 = getelementptr i64, ptr %t95, i64 1
+  store ptr %t97, ptr %sp
+  %t98 = load ptr, ptr %sp
+  %t99 = getelementptr i64, ptr %t98, i64 -1
+  store ptr %t99, ptr %sp
+  store i64 9, ptr %t99
+  br label %l74
+l74:
+  %t100 = load ptr, ptr %sp
+  %t101 = load i64, ptr %t100
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t101)
+  %t102 = getelementptr i64, ptr %t100, i64 1
+  store ptr %t102, ptr %sp
+  %t103 = load ptr, ptr %sp
+  %t104 = getelementptr i64, ptr %t103, i64 -1
+  store ptr %t104, ptr %sp
+  store i64 4, ptr %t104
+  %t105 = load ptr, ptr %sp
+  %t106 = load i64, ptr %t105
+  %t107 = getelementptr i64, ptr %t105, i64 -1
+  store ptr %t107, ptr %sp
+  store i64 %t106, ptr %t107
+  %t108 = getelementptr i64, ptr %t107, i64 1
+  store i64 %t106, ptr %t108
+  %t109 = load ptr, ptr %sp
+  %t110 = getelementptr i64, ptr %t109, i64 -1
+  store ptr %t110, ptr %sp
+  store i64 0, ptr %t110
+  %t111 = load ptr, ptr %sp
+  %t112 = load i64, ptr %t111
+  %t113 = getelementptr i64, ptr %t111, i64 1
+  %t114 = load i64, ptr %t113
+  %t115 = icmp slt i64 %t114, %t112
+  %t116 = zext i1 %t115 to i64
+  %t117 = getelementptr i64, ptr %t111, i64 1
+  store ptr %t117, ptr %sp
+  store i64 %t116, ptr %t117
+  %t118 = load ptr, ptr %sp
+  %t119 = load i64, ptr %t118
+  %t120 = icmp sle i64 %t119, 0
+  %t121 = getelementptr i64, ptr %t118, i64 1
+  store ptr %t121, ptr %sp
+  br i1 %t120, label %l83, label %b122
+b122:
+  %t123 = load ptr, ptr %sp
+  %t124 = load i64, ptr %t123
+  %t125 = getelementptr i64, ptr %t123, i64 1
+  store ptr %t125, ptr %sp
+  %t126 = load ptr, ptr %sp
+  %t127 = getelementptr i64, ptr %t126, i64 -1
+  store ptr %t127, ptr %sp
+  store i64 0, ptr %t127
+  br label %l90
+l83:
+  %t128 = load ptr, ptr %sp
+  %t129 = load i64, ptr %t128
+  %t130 = getelementptr i64, ptr %t128, i64 -1
+  store ptr %t130, ptr %sp
+  store i64 %t129, ptr %t130
+  %t131 = getelementptr i64, ptr %t130, i64 1
+  store i64 %t129, ptr %t131
+  %t132 = load ptr, ptr %sp
+  %t133 = getelementptr i64, ptr %t132, i64 -1
+  store ptr %t133, ptr %sp
+  store i64 9, ptr %t133
+  %t134 = load ptr, ptr %sp
+  %t135 = load i64, ptr %t134
+  %t136 = getelementptr i64, ptr %t134, i64 1
+  %t137 = load i64, ptr %t136
+  %t138 = icmp sgt i64 %t137, %t135
+  %t139 = zext i1 %t138 to i64
+  %t140 = getelementptr i64, ptr %t134, i64 1
+  store ptr %t140, ptr %sp
+  store i64 %t139, ptr %t140
+  %t141 = load ptr, ptr %sp
+  %t142 = load i64, ptr %t141
+  %t143 = icmp sle i64 %t142, 0
+  %t144 = getelementptr i64, ptr %t141, i64 1
+  store ptr %t144, ptr %sp
+  br i1 %t143, label %l90, label %b145
+b145:
+  %t146 = load ptr, ptr %sp
+  %t147 = load i64, ptr %t146
+  %t148 = getelementptr i64, ptr %t146, i64 1
+  store ptr %t148, ptr %sp
+  %t149 = load ptr, ptr %sp
+  %t150 = getelementptr i64, ptr %t149, i64 -1
+  store ptr %t150, ptr %sp
+  store i64 9, ptr %t150
+  br label %l90
+l90:
+  %t151 = load ptr, ptr %sp
+  %t152 = load i64, ptr %t151
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t152)
+  %t153 = getelementptr i64, ptr %t151, i64 1
+  store ptr %t153, ptr %sp
+  %t154 = load ptr, ptr %sp
+  %t155 = getelementptr i64, ptr %t154, i64 -1
+  store ptr %t155, ptr %sp
+  store i64 12, ptr %t155
+  %t156 = load ptr, ptr %sp
+  %t157 = load i64, ptr %t156
+  %t158 = getelementptr i64, ptr %t156, i64 -1
+  store ptr %t158, ptr %sp
+  store i64 %t157, ptr %t158
+  %t159 = getelementptr i64, ptr %t158, i64 1
+  store i64 %t157, ptr %t159
+  %t160 = load ptr, ptr %sp
+  %t161 = getelementptr i64, ptr %t160, i64 -1
+  store ptr %t161, ptr %sp
+  store i64 0, ptr %t161
+  %t162 = load ptr, ptr %sp
+  %t163 = load i64, ptr %t162
+  %t164 = getelementptr i64, ptr %t162, i64 1
+  %t165 = load i64, ptr %t164
+  %t166 = icmp slt i64 %t165, %t163
+  %t167 = zext i1 %t166 to i64
+  %t168 = getelementptr i64, ptr %t162, i64 1
+  store ptr %t168, ptr %sp
+  store i64 %t167, ptr %t168
+  %t169 = load ptr, ptr %sp
+  %t170 = load i64, ptr %t169
+  %t171 = icmp sle i64 %t170, 0
+  %t172 = getelementptr i64, ptr %t169, i64 1
+  store ptr %t172, ptr %sp
+  br i1 %t171, label %l99, label %b173
+b173:
+  %t174 = load ptr, ptr %sp
+  %t175 = load i64, ptr %t174
+  %t176 = getelementptr i64, ptr %t174, i64 1
+  store ptr %t176, ptr %sp
+  %t177 = load ptr, ptr %sp
+  %t178 = getelementptr i64, ptr %t177, i64 -1
+  store ptr %t178, ptr %sp
+  store i64 0, ptr %t178
+  br label %l106
+l99:
+  %t179 = load ptr, ptr %sp
+  %t180 = load i64, ptr %t179
+  %t181 = getelementptr i64, ptr %t179, i64 -1
+  store ptr %t181, ptr %sp
+  store i64 %t180, ptr %t181
+  %t182 = getelementptr i64, ptr %t181, i64 1
+  store i64 %t180, ptr %t182
+  %t183 = load ptr, ptr %sp
+  %t184 = getelementptr i64, ptr %t183, i64 -1
+  store ptr %t184, ptr %sp
+  store i64 9, ptr %t184
+  %t185 = load ptr, ptr %sp
+  %t186 = load i64, ptr %t185
+  %t187 = getelementptr i64, ptr %t185, i64 1
+  %t188 = load i64, ptr %t187
+  %t189 = icmp sgt i64 %t188, %t186
+  %t190 = zext i1 %t189 to i64
+  %t191 = getelementptr i64, ptr %t185, i64 1
+  store ptr %t191, ptr %sp
+  store i64 %t190, ptr %t191
+  %t192 = load ptr, ptr %sp
+  %t193 = load i64, ptr %t192
+  %t194 = icmp sle i64 %t193, 0
+  %t195 = getelementptr i64, ptr %t192, i64 1
+  store ptr %t195, ptr %sp
+  br i1 %t194, label %l106, label %b196
+b196:
+  %t197 = load ptr, ptr %sp
+  %t198 = load i64, ptr %t197
+  %t199 = getelementptr i64, ptr %t197, i64 1
+  store ptr %t199, ptr %sp
+  %t200 = load ptr, ptr %sp
+  %t201 = getelementptr i64, ptr %t200, i64 -1
+  store ptr %t201, ptr %sp
+  store i64 9, ptr %t201
+  br label %l106
+l106:
+  %t202 = load ptr, ptr %sp
+  %t203 = load i64, ptr %t202
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t203)
+  %t204 = getelementptr i64, ptr %t202, i64 1
+  store ptr %t204, ptr %sp
+  %t205 = load ptr, ptr %sp
+  %t206 = getelementptr i64, ptr %t205, i64 -1
+  store ptr %t206, ptr %sp
+  store i64 5, ptr %t206
+  %t207 = load ptr, ptr %sp
+  %t208 = getelementptr i64, ptr %t207, i64 -1
+  store ptr %t208, ptr %sp
+  store i64 0, ptr %t208
+  %t209 = load ptr, ptr %sp
+  %t210 = load i64, ptr %t209
+  %t211 = getelementptr i64, ptr %t209, i64 1
+  %t212 = load i64, ptr %t211
+  store i64 %t212, ptr %t209
+  %t213 = getelementptr i64, ptr %t209, i64 1
+  store i64 %t210, ptr %t213
+  %t214 = load ptr, ptr %sp
+  %t215 = load i64, ptr %t214
+  %t216 = getelementptr i64, ptr %t214, i64 1
+  store ptr %t216, ptr %sp
+  store i64 %t215, ptr %counter0
+  %t217 = icmp sle i64 %t215, 0
+  br i1 %t217, label %l112, label %b218
+b218:
+  br label %l110
+l110:
+  %t219 = load ptr, ptr %sp
+  %t220 = load i64, ptr %t219
+  %t221 = add i64 %t220, 1
+  store i64 %t221, ptr %t219
+  %t222 = load i64, ptr %counter0
+  %t223 = sub i64 %t222, 1
+  store i64 %t223, ptr %counter0
+  %t224 = icmp sgt i64 %t223, 0
+  br i1 %t224, label %l110, label %b225
+b225:
+  br label %l112
+l112:
+  %t226 = load ptr, ptr %sp
+  %t227 = load i64, ptr %t226
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t227)
+  %t228 = getelementptr i64, ptr %t226, i64 1
+  store ptr %t228, ptr %sp
+  %t229 = load ptr, ptr %sp
+  %t230 = getelementptr i64, ptr %t229, i64 -1
+  store ptr %t230, ptr %sp
+  store i64 3, ptr %t230
+  %t231 = load ptr, ptr %sp
+  %t232 = getelementptr i64, ptr %t231, i64 -1
+  store ptr %t232, ptr %sp
+  store i64 0, ptr %t232
+  %t233 = load ptr, ptr %sp
+  %t234 = load i64, ptr %t233
+  %t235 = getelementptr i64, ptr %t233, i64 1
+  %t236 = load i64, ptr %t235
+  store i64 %t236, ptr %t233
+  %t237 = getelementptr i64, ptr %t233, i64 1
+  store i64 %t234, ptr %t237
+  %t238 = load ptr, ptr %sp
+  %t239 = load i64, ptr %t238
+  %t240 = getelementptr i64, ptr %t238, i64 1
+  store ptr %t240, ptr %sp
+  store i64 %t239, ptr %counter0
+  %t241 = icmp sle i64 %t239, 0
+  br i1 %t241, label %l118, label %b242
+b242:
+  br label %l116
+l116:
+  %t243 = load ptr, ptr %sp
+  %t244 = load i64, ptr %t243
+  %t245 = add i64 %t244, 1
+  store i64 %t245, ptr %t243
+  %t246 = load i64, ptr %counter0
+  %t247 = sub i64 %t246, 1
+  store i64 %t247, ptr %counter0
+  %t248 = icmp sgt i64 %t247, 0
+  br i1 %t248, label %l116, label %b249
+b249:
+  br label %l118
+l118:
+  %t250 = load ptr, ptr %sp
+  %t251 = getelementptr i64, ptr %t250, i64 -1
+  store ptr %t251, ptr %sp
+  store i64 0, ptr %t251
+  %t252 = load ptr, ptr %sp
+  %t253 = load i64, ptr %t252
+  %t254 = getelementptr i64, ptr %t252, i64 1
+  %t255 = load i64, ptr %t254
+  store i64 %t255, ptr %t252
+  %t256 = getelementptr i64, ptr %t252, i64 1
+  store i64 %t253, ptr %t256
+  %t257 = load ptr, ptr %sp
+  %t258 = load i64, ptr %t257
+  %t259 = getelementptr i64, ptr %t257, i64 1
+  store ptr %t259, ptr %sp
+  store i64 %t258, ptr %counter0
+  %t260 = icmp sle i64 %t258, 0
+  br i1 %t260, label %l124, label %b261
+b261:
+  br label %l122
+l122:
+  %t262 = load ptr, ptr %sp
+  %t263 = load i64, ptr %t262
+  %t264 = add i64 %t263, 1
+  store i64 %t264, ptr %t262
+  %t265 = load i64, ptr %counter0
+  %t266 = sub i64 %t265, 1
+  store i64 %t266, ptr %counter0
+  %t267 = icmp sgt i64 %t266, 0
+  br i1 %t267, label %l122, label %b268
+b268:
+  br label %l124
+l124:
+  %t269 = load ptr, ptr %sp
+  %t270 = load i64, ptr %t269
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t270)
+  %t271 = getelementptr i64, ptr %t269, i64 1
+  store ptr %t271, ptr %sp
+  %t272 = load ptr, ptr %sp
+  %t273 = getelementptr i64, ptr %t272, i64 -1
+  store ptr %t273, ptr %sp
+  store i64 21, ptr %t273
+  %t274 = load ptr, ptr %sp
+  %t275 = call ptr @st_twice(ptr %t274)
+  store ptr %t275, ptr %sp
+  %t276 = load ptr, ptr %sp
+  %t277 = load i64, ptr %t276
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t277)
+  %t278 = getelementptr i64, ptr %t276, i64 1
+  store ptr %t278, ptr %sp
+  %t279 = load ptr, ptr %sp
+  %t280 = getelementptr i64, ptr %t279, i64 -1
+  store ptr %t280, ptr %sp
+  store i64 0, ptr %t280
+  %t281 = load ptr, ptr %sp
+  %t282 = getelementptr i64, ptr %t281, i64 -1
+  store ptr %t282, ptr %sp
+  store i64 1, ptr %t282
+  %t283 = load ptr, ptr %sp
+  %t284 = load i64, ptr %t283
+  %t285 = getelementptr i64, ptr %t283, i64 1
+  %t286 = load i64, ptr %t285
+  %t287 = add i64 %t286, %t284
+  %t288 = getelementptr i64, ptr %t283, i64 1
+  store ptr %t288, ptr %sp
+  store i64 %t287, ptr %t288
+  %t289 = load ptr, ptr %sp
+  %t290 = getelementptr i64, ptr %t289, i64 -1
+  store ptr %t290, ptr %sp
+  store i64 1, ptr %t290
+  %t291 = load ptr, ptr %sp
+  %t292 = load i64, ptr %t291
+  %t293 = getelementptr i64, ptr %t291, i64 1
+  %t294 = load i64, ptr %t293
+  %t295 = add i64 %t294, %t292
+  %t296 = getelementptr i64, ptr %t291, i64 1
+  store ptr %t296, ptr %sp
+  store i64 %t295, ptr %t296
+  %t297 = load ptr, ptr %sp
+  %t298 = getelementptr i64, ptr %t297, i64 -1
+  store ptr %t298, ptr %sp
+  store i64 1, ptr %t298
+  %t299 = load ptr, ptr %sp
+  %t300 = load i64, ptr %t299
+  %t301 = getelementptr i64, ptr %t299, i64 1
+  %t302 = load i64, ptr %t301
+  %t303 = add i64 %t302, %t300
+  %t304 = getelementptr i64, ptr %t299, i64 1
+  store ptr %t304, ptr %sp
+  store i64 %t303, ptr %t304
+  %t305 = load ptr, ptr %sp
+  %t306 = getelementptr i64, ptr %t305, i64 -1
+  store ptr %t306, ptr %sp
+  store i64 1, ptr %t306
+  %t307 = load ptr, ptr %sp
+  %t308 = load i64, ptr %t307
+  %t309 = getelementptr i64, ptr %t307, i64 1
+  %t310 = load i64, ptr %t309
+  %t311 = add i64 %t310, %t308
+  %t312 = getelementptr i64, ptr %t307, i64 1
+  store ptr %t312, ptr %sp
+  store i64 %t311, ptr %t312
+  %t313 = load ptr, ptr %sp
+  %t314 = getelementptr i64, ptr %t313, i64 -1
+  store ptr %t314, ptr %sp
+  store i64 1, ptr %t314
+  %t315 = load ptr, ptr %sp
+  %t316 = load i64, ptr %t315
+  %t317 = getelementptr i64, ptr %t315, i64 1
+  %t318 = load i64, ptr %t317
+  %t319 = add i64 %t318, %t316
+  %t320 = getelementptr i64, ptr %t315, i64 1
+  store ptr %t320, ptr %sp
+  store i64 %t319, ptr %t320
+  %t321 = load ptr, ptr %sp
+  %t322 = getelementptr i64, ptr %t321, i64 -1
+  store ptr %t322, ptr %sp
+  store i64 1, ptr %t322
+  %t323 = load ptr, ptr %sp
+  %t324 = load i64, ptr %t323
+  %t325 = getelementptr i64, ptr %t323, i64 1
+  %t326 = load i64, ptr %t325
+  %t327 = add i64 %t326, %t324
+  %t328 = getelementptr i64, ptr %t323, i64 1
+  store ptr %t328, ptr %sp
+  store i64 %t327, ptr %t328
+  %t329 = load ptr, ptr %sp
+  %t330 = getelementptr i64, ptr %t329, i64 -1
+  store ptr %t330, ptr %sp
+  store i64 1, ptr %t330
+  %t331 = load ptr, ptr %sp
+  %t332 = load i64, ptr %t331
+  %t333 = getelementptr i64, ptr %t331, i64 1
+  %t334 = load i64, ptr %t333
+  %t335 = add i64 %t334, %t332
+  %t336 = getelementptr i64, ptr %t331, i64 1
+  store ptr %t336, ptr %sp
+  store i64 %t335, ptr %t336
+  %t337 = load ptr, ptr %sp
+  %t338 = getelementptr i64, ptr %t337, i64 -1
+  store ptr %t338, ptr %sp
+  store i64 1, ptr %t338
+  %t339 = load ptr, ptr %sp
+  %t340 = load i64, ptr %t339
+  %t341 = getelementptr i64, ptr %t339, i64 1
+  %t342 = load i64, ptr %t341
+  %t343 = add i64 %t342, %t340
+  %t344 = getelementptr i64, ptr %t339, i64 1
+  store ptr %t344, ptr %sp
+  store i64 %t343, ptr %t344
+  %t345 = load ptr, ptr %sp
+  %t346 = getelementptr i64, ptr %t345, i64 -1
+  store ptr %t346, ptr %sp
+  store i64 1, ptr %t346
+  %t347 = load ptr, ptr %sp
+  %t348 = load i64, ptr %t347
+  %t349 = getelementptr i64, ptr %t347, i64 1
+  %t350 = load i64, ptr %t349
+  %t351 = add i64 %t350, %t348
+  %t352 = getelementptr i64, ptr %t347, i64 1
+  store ptr %t352, ptr %sp
+  store i64 %t351, ptr %t352
+  %t353 = load ptr, ptr %sp
+  %t354 = getelementptr i64, ptr %t353, i64 -1
+  store ptr %t354, ptr %sp
+  store i64 1, ptr %t354
+  %t355 = load ptr, ptr %sp
+  %t356 = load i64, ptr %t355
+  %t357 = getelementptr i64, ptr %t355, i64 1
+  %t358 = load i64, ptr %t357
+  %t359 = add i64 %t358, %t356
+  %t360 = getelementptr i64, ptr %t355, i64 1
+  store ptr %t360, ptr %sp
+  store i64 %t359, ptr %t360
+  %t361 = load ptr, ptr %sp
+  %t362 = getelementptr i64, ptr %t361, i64 -1
+  store ptr %t362, ptr %sp
+  store i64 1, ptr %t362
+  %t363 = load ptr, ptr %sp
+  %t364 = load i64, ptr %t363
+  %t365 = getelementptr i64, ptr %t363, i64 1
+  %t366 = load i64, ptr %t365
+  %t367 = add i64 %t366, %t364
+  %t368 = getelementptr i64, ptr %t363, i64 1
+  store ptr %t368, ptr %sp
+  store i64 %t367, ptr %t368
+  %t369 = load ptr, ptr %sp
+  %t370 = getelementptr i64, ptr %t369, i64 -1
+  store ptr %t370, ptr %sp
+  store i64 1, ptr %t370
+  %t371 = load ptr, ptr %sp
+  %t372 = load i64, ptr %t371
+  %t373 = getelementptr i64, ptr %t371, i64 1
+  %t374 = load i64, ptr %t373
+  %t375 = add i64 %t374, %t372
+  %t376 = getelementptr i64, ptr %t371, i64 1
+  store ptr %t376, ptr %sp
+  store i64 %t375, ptr %t376
+  %t377 = load ptr, ptr %sp
+  %t378 = getelementptr i64, ptr %t377, i64 -1
+  store ptr %t378, ptr %sp
+  store i64 1, ptr %t378
+  %t379 = load ptr, ptr %sp
+  %t380 = load i64, ptr %t379
+  %t381 = getelementptr i64, ptr %t379, i64 1
+  %t382 = load i64, ptr %t381
+  %t383 = add i64 %t382, %t380
+  %t384 = getelementptr i64, ptr %t379, i64 1
+  store ptr %t384, ptr %sp
+  store i64 %t383, ptr %t384
+  %t385 = load ptr, ptr %sp
+  %t386 = getelementptr i64, ptr %t385, i64 -1
+  store ptr %t386, ptr %sp
+  store i64 1, ptr %t386
+  %t387 = load ptr, ptr %sp
+  %t388 = load i64, ptr %t387
+  %t389 = getelementptr i64, ptr %t387, i64 1
+  %t390 = load i64, ptr %t389
+  %t391 = add i64 %t390, %t388
+  %t392 = getelementptr i64, ptr %t387, i64 1
+  store ptr %t392, ptr %sp
+  store i64 %t391, ptr %t392
+  %t393 = load ptr, ptr %sp
+  %t394 = getelementptr i64, ptr %t393, i64 -1
+  store ptr %t394, ptr %sp
+  store i64 1, ptr %t394
+  %t395 = load ptr, ptr %sp
+  %t396 = load i64, ptr %t395
+  %t397 = getelementptr i64, ptr %t395, i64 1
+  %t398 = load i64, ptr %t397
+  %t399 = add i64 %t398, %t396
+  %t400 = getelementptr i64, ptr %t395, i64 1
+  store ptr %t400, ptr %sp
+  store i64 %t399, ptr %t400
+  %t401 = load ptr, ptr %sp
+  %t402 = getelementptr i64, ptr %t401, i64 -1
+  store ptr %t402, ptr %sp
+  store i64 1, ptr %t402
+  %t403 = load ptr, ptr %sp
+  %t404 = load i64, ptr %t403
+  %t405 = getelementptr i64, ptr %t403, i64 1
+  %t406 = load i64, ptr %t405
+  %t407 = add i64 %t406, %t404
+  %t408 = getelementptr i64, ptr %t403, i64 1
+  store ptr %t408, ptr %sp
+  store i64 %t407, ptr %t408
+  %t409 = load ptr, ptr %sp
+  %t410 = getelementptr i64, ptr %t409, i64 -1
+  store ptr %t410, ptr %sp
+  store i64 1, ptr %t410
+  %t411 = load ptr, ptr %sp
+  %t412 = load i64, ptr %t411
+  %t413 = getelementptr i64, ptr %t411, i64 1
+  %t414 = load i64, ptr %t413
+  %t415 = add i64 %t414, %t412
+  %t416 = getelementptr i64, ptr %t411, i64 1
+  store ptr %t416, ptr %sp
+  store i64 %t415, ptr %t416
+  %t417 = load ptr, ptr %sp
+  %t418 = getelementptr i64, ptr %t417, i64 -1
+  store ptr %t418, ptr %sp
+  store i64 1, ptr %t418
+  %t419 = load ptr, ptr %sp
+  %t420 = load i64, ptr %t419
+  %t421 = getelementptr i64, ptr %t419, i64 1
+  %t422 = load i64, ptr %t421
+  %t423 = add i64 %t422, %t420
+  %t424 = getelementptr i64, ptr %t419, i64 1
+  store ptr %t424, ptr %sp
+  store i64 %t423, ptr %t424
+  %t425 = load ptr, ptr %sp
+  %t426 = getelementptr i64, ptr %t425, i64 -1
+  store ptr %t426, ptr %sp
+  store i64 1, ptr %t426
+  %t427 = load ptr, ptr %sp
+  %t428 = load i64, ptr %t427
+  %t429 = getelementptr i64, ptr %t427, i64 1
+  %t430 = load i64, ptr %t429
+  %t431 = add i64 %t430, %t428
+  %t432 = getelementptr i64, ptr %t427, i64 1
+  store ptr %t432, ptr %sp
+  store i64 %t431, ptr %t432
+  %t433 = load ptr, ptr %sp
+  %t434 = getelementptr i64, ptr %t433, i64 -1
+  store ptr %t434, ptr %sp
+  store i64 1, ptr %t434
+  %t435 = load ptr, ptr %sp
+  %t436 = load i64, ptr %t435
+  %t437 = getelementptr i64, ptr %t435, i64 1
+  %t438 = load i64, ptr %t437
+  %t439 = add i64 %t438, %t436
+  %t440 = getelementptr i64, ptr %t435, i64 1
+  store ptr %t440, ptr %sp
+  store i64 %t439, ptr %t440
+  %t441 = load ptr, ptr %sp
+  %t442 = load i64, ptr %t441
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t442)
+  %t443 = getelementptr i64, ptr %t441, i64 1
+  store ptr %t443, ptr %sp
+  %t444 = load ptr, ptr %sp
+  %t445 = getelementptr i64, ptr %t444, i64 -1
+  store ptr %t445, ptr %sp
+  store i64 1, ptr %t445
+  %t446 = load ptr, ptr %sp
+  %t447 = getelementptr i64, ptr %t446, i64 -1
+  store ptr %t447, ptr %sp
+  store i64 1, ptr %t447
+  %t448 = load ptr, ptr %sp
+  %t449 = load i64, ptr %t448
+  %t450 = getelementptr i64, ptr %t448, i64 1
+  %t451 = load i64, ptr %t450
+  %t452 = add i64 %t451, %t449
+  %t453 = getelementptr i64, ptr %t448, i64 1
+  store ptr %t453, ptr %sp
+  store i64 %t452, ptr %t453
+  %t454 = load ptr, ptr %sp
+  %t455 = getelementptr i64, ptr %t454, i64 -1
+  store ptr %t455, ptr %sp
+  store i64 1, ptr %t455
+  %t456 = load ptr, ptr %sp
+  %t457 = load i64, ptr %t456
+  %t458 = getelementptr i64, ptr %t456, i64 1
+  %t459 = load i64, ptr %t458
+  %t460 = add i64 %t459, %t457
+  %t461 = getelementptr i64, ptr %t456, i64 1
+  store ptr %t461, ptr %sp
+  store i64 %t460, ptr %t461
+  %t462 = load ptr, ptr %sp
+  %t463 = getelementptr i64, ptr %t462, i64 -1
+  store ptr %t463, ptr %sp
+  store i64 1, ptr %t463
+  %t464 = load ptr, ptr %sp
+  %t465 = load i64, ptr %t464
+  %t466 = getelementptr i64, ptr %t464, i64 1
+  %t467 = load i64, ptr %t466
+  %t468 = add i64 %t467, %t465
+  %t469 = getelementptr i64, ptr %t464, i64 1
+  store ptr %t469, ptr %sp
+  store i64 %t468, ptr %t469
+  %t470 = load ptr, ptr %sp
+  %t471 = getelementptr i64, ptr %t470, i64 -1
+  store ptr %t471, ptr %sp
+  store i64 1, ptr %t471
+  %t472 = load ptr, ptr %sp
+  %t473 = load i64, ptr %t472
+  %t474 = getelementptr i64, ptr %t472, i64 1
+  %t475 = load i64, ptr %t474
+  %t476 = add i64 %t475, %t473
+  %t477 = getelementptr i64, ptr %t472, i64 1
+  store ptr %t477, ptr %sp
+  store i64 %t476, ptr %t477
+  %t478 = load ptr, ptr %sp
+  %t479 = getelementptr i64, ptr %t478, i64 -1
+  store ptr %t479, ptr %sp
+  store i64 1, ptr %t479
+  %t480 = load ptr, ptr %sp
+  %t481 = load i64, ptr %t480
+  %t482 = getelementptr i64, ptr %t480, i64 1
+  %t483 = load i64, ptr %t482
+  %t484 = add i64 %t483, %t481
+  %t485 = getelementptr i64, ptr %t480, i64 1
+  store ptr %t485, ptr %sp
+  store i64 %t484, ptr %t485
+  %t486 = load ptr, ptr %sp
+  %t487 = getelementptr i64, ptr %t486, i64 -1
+  store ptr %t487, ptr %sp
+  store i64 1, ptr %t487
+  %t488 = load ptr, ptr %sp
+  %t489 = load i64, ptr %t488
+  %t490 = getelementptr i64, ptr %t488, i64 1
+  %t491 = load i64, ptr %t490
+  %t492 = add i64 %t491, %t489
+  %t493 = getelementptr i64, ptr %t488, i64 1
+  store ptr %t493, ptr %sp
+  store i64 %t492, ptr %t493
+  %t494 = load ptr, ptr %sp
+  %t495 = getelementptr i64, ptr %t494, i64 -1
+  store ptr %t495, ptr %sp
+  store i64 1, ptr %t495
+  %t496 = load ptr, ptr %sp
+  %t497 = load i64, ptr %t496
+  %t498 = getelementptr i64, ptr %t496, i64 1
+  %t499 = load i64, ptr %t498
+  %t500 = add i64 %t499, %t497
+  %t501 = getelementptr i64, ptr %t496, i64 1
+  store ptr %t501, ptr %sp
+  store i64 %t500, ptr %t501
+  %t502 = load ptr, ptr %sp
+  %t503 = getelementptr i64, ptr %t502, i64 -1
+  store ptr %t503, ptr %sp
+  store i64 1, ptr %t503
+  %t504 = load ptr, ptr %sp
+  %t505 = load i64, ptr %t504
+  %t506 = getelementptr i64, ptr %t504, i64 1
+  %t507 = load i64, ptr %t506
+  %t508 = add i64 %t507, %t505
+  %t509 = getelementptr i64, ptr %t504, i64 1
+  store ptr %t509, ptr %sp
+  store i64 %t508, ptr %t509
+  %t510 = load ptr, ptr %sp
+  %t511 = getelementptr i64, ptr %t510, i64 -1
+  store ptr %t511, ptr %sp
+  store i64 1, ptr %t511
+  %t512 = load ptr, ptr %sp
+  %t513 = load i64, ptr %t512
+  %t514 = getelementptr i64, ptr %t512, i64 1
+  %t515 = load i64, ptr %t514
+  %t516 = add i64 %t515, %t513
+  %t517 = getelementptr i64, ptr %t512, i64 1
+  store ptr %t517, ptr %sp
+  store i64 %t516, ptr %t517
+  %t518 = load ptr, ptr %sp
+  %t519 = getelementptr i64, ptr %t518, i64 -1
+  store ptr %t519, ptr %sp
+  store i64 1, ptr %t519
+  %t520 = load ptr, ptr %sp
+  %t521 = load i64, ptr %t520
+  %t522 = getelementptr i64, ptr %t520, i64 1
+  %t523 = load i64, ptr %t522
+  %t524 = add i64 %t523, %t521
+  %t525 = getelementptr i64, ptr %t520, i64 1
+  store ptr %t525, ptr %sp
+  store i64 %t524, ptr %t525
+  %t526 = load ptr, ptr %sp
+  %t527 = getelementptr i64, ptr %t526, i64 -1
+  store ptr %t527, ptr %sp
+  store i64 1, ptr %t527
+  %t528 = load ptr, ptr %sp
+  %t529 = load i64, ptr %t528
+  %t530 = getelementptr i64, ptr %t528, i64 1
+  %t531 = load i64, ptr %t530
+  %t532 = add i64 %t531, %t529
+  %t533 = getelementptr i64, ptr %t528, i64 1
+  store ptr %t533, ptr %sp
+  store i64 %t532, ptr %t533
+  %t534 = load ptr, ptr %sp
+  %t535 = getelementptr i64, ptr %t534, i64 -1
+  store ptr %t535, ptr %sp
+  store i64 1, ptr %t535
+  %t536 = load ptr, ptr %sp
+  %t537 = load i64, ptr %t536
+  %t538 = getelementptr i64, ptr %t536, i64 1
+  %t539 = load i64, ptr %t538
+  %t540 = add i64 %t539, %t537
+  %t541 = getelementptr i64, ptr %t536, i64 1
+  store ptr %t541, ptr %sp
+  store i64 %t540, ptr %t541
+  %t542 = load ptr, ptr %sp
+  %t543 = getelementptr i64, ptr %t542, i64 -1
+  store ptr %t543, ptr %sp
+  store i64 1, ptr %t543
+  %t544 = load ptr, ptr %sp
+  %t545 = load i64, ptr %t544
+  %t546 = getelementptr i64, ptr %t544, i64 1
+  %t547 = load i64, ptr %t546
+  %t548 = add i64 %t547, %t545
+  %t549 = getelementptr i64, ptr %t544, i64 1
+  store ptr %t549, ptr %sp
+  store i64 %t548, ptr %t549
+  %t550 = load ptr, ptr %sp
+  %t551 = getelementptr i64, ptr %t550, i64 -1
+  store ptr %t551, ptr %sp
+  store i64 1, ptr %t551
+  %t552 = load ptr, ptr %sp
+  %t553 = load i64, ptr %t552
+  %t554 = getelementptr i64, ptr %t552, i64 1
+  %t555 = load i64, ptr %t554
+  %t556 = add i64 %t555, %t553
+  %t557 = getelementptr i64, ptr %t552, i64 1
+  store ptr %t557, ptr %sp
+  store i64 %t556, ptr %t557
+  %t558 = load ptr, ptr %sp
+  %t559 = getelementptr i64, ptr %t558, i64 -1
+  store ptr %t559, ptr %sp
+  store i64 1, ptr %t559
+  %t560 = load ptr, ptr %sp
+  %t561 = load i64, ptr %t560
+  %t562 = getelementptr i64, ptr %t560, i64 1
+  %t563 = load i64, ptr %t562
+  %t564 = add i64 %t563, %t561
+  %t565 = getelementptr i64, ptr %t560, i64 1
+  store ptr %t565, ptr %sp
+  store i64 %t564, ptr %t565
+  %t566 = load ptr, ptr %sp
+  %t567 = getelementptr i64, ptr %t566, i64 -1
+  store ptr %t567, ptr %sp
+  store i64 1, ptr %t567
+  %t568 = load ptr, ptr %sp
+  %t569 = load i64, ptr %t568
+  %t570 = getelementptr i64, ptr %t568, i64 1
+  %t571 = load i64, ptr %t570
+  %t572 = add i64 %t571, %t569
+  %t573 = getelementptr i64, ptr %t568, i64 1
+  store ptr %t573, ptr %sp
+  store i64 %t572, ptr %t573
+  %t574 = load ptr, ptr %sp
+  %t575 = getelementptr i64, ptr %t574, i64 -1
+  store ptr %t575, ptr %sp
+  store i64 1, ptr %t575
+  %t576 = load ptr, ptr %sp
+  %t577 = load i64, ptr %t576
+  %t578 = getelementptr i64, ptr %t576, i64 1
+  %t579 = load i64, ptr %t578
+  %t580 = add i64 %t579, %t577
+  %t581 = getelementptr i64, ptr %t576, i64 1
+  store ptr %t581, ptr %sp
+  store i64 %t580, ptr %t581
+  %t582 = load ptr, ptr %sp
+  %t583 = getelementptr i64, ptr %t582, i64 -1
+  store ptr %t583, ptr %sp
+  store i64 1, ptr %t583
+  %t584 = load ptr, ptr %sp
+  %t585 = load i64, ptr %t584
+  %t586 = getelementptr i64, ptr %t584, i64 1
+  %t587 = load i64, ptr %t586
+  %t588 = add i64 %t587, %t585
+  %t589 = getelementptr i64, ptr %t584, i64 1
+  store ptr %t589, ptr %sp
+  store i64 %t588, ptr %t589
+  %t590 = load ptr, ptr %sp
+  %t591 = getelementptr i64, ptr %t590, i64 -1
+  store ptr %t591, ptr %sp
+  store i64 1, ptr %t591
+  %t592 = load ptr, ptr %sp
+  %t593 = load i64, ptr %t592
+  %t594 = getelementptr i64, ptr %t592, i64 1
+  %t595 = load i64, ptr %t594
+  %t596 = add i64 %t595, %t593
+  %t597 = getelementptr i64, ptr %t592, i64 1
+  store ptr %t597, ptr %sp
+  store i64 %t596, ptr %t597
+  %t598 = load ptr, ptr %sp
+  %t599 = getelementptr i64, ptr %t598, i64 -1
+  store ptr %t599, ptr %sp
+  store i64 1, ptr %t599
+  %t600 = load ptr, ptr %sp
+  %t601 = load i64, ptr %t600
+  %t602 = getelementptr i64, ptr %t600, i64 1
+  %t603 = load i64, ptr %t602
+  %t604 = add i64 %t603, %t601
+  %t605 = getelementptr i64, ptr %t600, i64 1
+  store ptr %t605, ptr %sp
+  store i64 %t604, ptr %t605
+  %t606 = load ptr, ptr %sp
+  %t607 = getelementptr i64, ptr %t606, i64 -1
+  store ptr %t607, ptr %sp
+  store i64 1, ptr %t607
+  %t608 = load ptr, ptr %sp
+  %t609 = load i64, ptr %t608
+  %t610 = getelementptr i64, ptr %t608, i64 1
+  %t611 = load i64, ptr %t610
+  %t612 = add i64 %t611, %t609
+  %t613 = getelementptr i64, ptr %t608, i64 1
+  store ptr %t613, ptr %sp
+  store i64 %t612, ptr %t613
+  %t614 = load ptr, ptr %sp
+  %t615 = getelementptr i64, ptr %t614, i64 -1
+  store ptr %t615, ptr %sp
+  store i64 1, ptr %t615
+  %t616 = load ptr, ptr %sp
+  %t617 = load i64, ptr %t616
+  %t618 = getelementptr i64, ptr %t616, i64 1
+  %t619 = load i64, ptr %t618
+  %t620 = add i64 %t619, %t617
+  %t621 = getelementptr i64, ptr %t616, i64 1
+  store ptr %t621, ptr %sp
+  store i64 %t620, ptr %t621
+  %t622 = load ptr, ptr %sp
+  %t623 = getelementptr i64, ptr %t622, i64 -1
+  store ptr %t623, ptr %sp
+  store i64 1, ptr %t623
+  %t624 = load ptr, ptr %sp
+  %t625 = load i64, ptr %t624
+  %t626 = getelementptr i64, ptr %t624, i64 1
+  %t627 = load i64, ptr %t626
+  %t628 = add i64 %t627, %t625
+  %t629 = getelementptr i64, ptr %t624, i64 1
+  store ptr %t629, ptr %sp
+  store i64 %t628, ptr %t629
+  %t630 = load ptr, ptr %sp
+  %t631 = getelementptr i64, ptr %t630, i64 -1
+  store ptr %t631, ptr %sp
+  store i64 1, ptr %t631
+  %t632 = load ptr, ptr %sp
+  %t633 = load i64, ptr %t632
+  %t634 = getelementptr i64, ptr %t632, i64 1
+  %t635 = load i64, ptr %t634
+  %t636 = add i64 %t635, %t633
+  %t637 = getelementptr i64, ptr %t632, i64 1
+  store ptr %t637, ptr %sp
+  store i64 %t636, ptr %t637
+  %t638 = load ptr, ptr %sp
+  %t639 = getelementptr i64, ptr %t638, i64 -1
+  store ptr %t639, ptr %sp
+  store i64 1, ptr %t639
+  %t640 = load ptr, ptr %sp
+  %t641 = load i64, ptr %t640
+  %t642 = getelementptr i64, ptr %t640, i64 1
+  %t643 = load i64, ptr %t642
+  %t644 = add i64 %t643, %t641
+  %t645 = getelementptr i64, ptr %t640, i64 1
+  store ptr %t645, ptr %sp
+  store i64 %t644, ptr %t645
+  %t646 = load ptr, ptr %sp
+  %t647 = getelementptr i64, ptr %t646, i64 -1
+  store ptr %t647, ptr %sp
+  store i64 1, ptr %t647
+  %t648 = load ptr, ptr %sp
+  %t649 = load i64, ptr %t648
+  %t650 = getelementptr i64, ptr %t648, i64 1
+  %t651 = load i64, ptr %t650
+  %t652 = add i64 %t651, %t649
+  %t653 = getelementptr i64, ptr %t648, i64 1
+  store ptr %t653, ptr %sp
+  store i64 %t652, ptr %t653
+  %t654 = load ptr, ptr %sp
+  %t655 = getelementptr i64, ptr %t654, i64 -1
+  store ptr %t655, ptr %sp
+  store i64 1, ptr %t655
+  %t656 = load ptr, ptr %sp
+  %t657 = load i64, ptr %t656
+  %t658 = getelementptr i64, ptr %t656, i64 1
+  %t659 = load i64, ptr %t658
+  %t660 = add i64 %t659, %t657
+  %t661 = getelementptr i64, ptr %t656, i64 1
+  store ptr %t661, ptr %sp
+  store i64 %t660, ptr %t661
+  %t662 = load ptr, ptr %sp
+  %t663 = getelementptr i64, ptr %t662, i64 -1
+  store ptr %t663, ptr %sp
+  store i64 1, ptr %t663
+  %t664 = load ptr, ptr %sp
+  %t665 = load i64, ptr %t664
+  %t666 = getelementptr i64, ptr %t664, i64 1
+  %t667 = load i64, ptr %t666
+  %t668 = add i64 %t667, %t665
+  %t669 = getelementptr i64, ptr %t664, i64 1
+  store ptr %t669, ptr %sp
+  store i64 %t668, ptr %t669
+  %t670 = load ptr, ptr %sp
+  %t671 = getelementptr i64, ptr %t670, i64 -1
+  store ptr %t671, ptr %sp
+  store i64 1, ptr %t671
+  %t672 = load ptr, ptr %sp
+  %t673 = load i64, ptr %t672
+  %t674 = getelementptr i64, ptr %t672, i64 1
+  %t675 = load i64, ptr %t674
+  %t676 = add i64 %t675, %t673
+  %t677 = getelementptr i64, ptr %t672, i64 1
+  store ptr %t677, ptr %sp
+  store i64 %t676, ptr %t677
+  %t678 = load ptr, ptr %sp
+  %t679 = getelementptr i64, ptr %t678, i64 -1
+  store ptr %t679, ptr %sp
+  store i64 1, ptr %t679
+  %t680 = load ptr, ptr %sp
+  %t681 = load i64, ptr %t680
+  %t682 = getelementptr i64, ptr %t680, i64 1
+  %t683 = load i64, ptr %t682
+  %t684 = add i64 %t683, %t681
+  %t685 = getelementptr i64, ptr %t680, i64 1
+  store ptr %t685, ptr %sp
+  store i64 %t684, ptr %t685
+  %t686 = load ptr, ptr %sp
+  %t687 = getelementptr i64, ptr %t686, i64 -1
+  store ptr %t687, ptr %sp
+  store i64 1, ptr %t687
+  %t688 = load ptr, ptr %sp
+  %t689 = load i64, ptr %t688
+  %t690 = getelementptr i64, ptr %t688, i64 1
+  %t691 = load i64, ptr %t690
+  %t692 = add i64 %t691, %t689
+  %t693 = getelementptr i64, ptr %t688, i64 1
+  store ptr %t693, ptr %sp
+  store i64 %t692, ptr %t693
+  %t694 = load ptr, ptr %sp
+  %t695 = getelementptr i64, ptr %t694, i64 -1
+  store ptr %t695, ptr %sp
+  store i64 1, ptr %t695
+  %t696 = load ptr, ptr %sp
+  %t697 = load i64, ptr %t696
+  %t698 = getelementptr i64, ptr %t696, i64 1
+  %t699 = load i64, ptr %t698
+  %t700 = add i64 %t699, %t697
+  %t701 = getelementptr i64, ptr %t696, i64 1
+  store ptr %t701, ptr %sp
+  store i64 %t700, ptr %t701
+  %t702 = load ptr, ptr %sp
+  %t703 = getelementptr i64, ptr %t702, i64 -1
+  store ptr %t703, ptr %sp
+  store i64 1, ptr %t703
+  %t704 = load ptr, ptr %sp
+  %t705 = load i64, ptr %t704
+  %t706 = getelementptr i64, ptr %t704, i64 1
+  %t707 = load i64, ptr %t706
+  %t708 = add i64 %t707, %t705
+  %t709 = getelementptr i64, ptr %t704, i64 1
+  store ptr %t709, ptr %sp
+  store i64 %t708, ptr %t709
+  %t710 = load ptr, ptr %sp
+  %t711 = getelementptr i64, ptr %t710, i64 -1
+  store ptr %t711, ptr %sp
+  store i64 1, ptr %t711
+  %t712 = load ptr, ptr %sp
+  %t713 = load i64, ptr %t712
+  %t714 = getelementptr i64, ptr %t712, i64 1
+  %t715 = load i64, ptr %t714
+  %t716 = add i64 %t715, %t713
+  %t717 = getelementptr i64, ptr %t712, i64 1
+  store ptr %t717, ptr %sp
+  store i64 %t716, ptr %t717
+  %t718 = load ptr, ptr %sp
+  %t719 = getelementptr i64, ptr %t718, i64 -1
+  store ptr %t719, ptr %sp
+  store i64 1, ptr %t719
+  %t720 = load ptr, ptr %sp
+  %t721 = load i64, ptr %t720
+  %t722 = getelementptr i64, ptr %t720, i64 1
+  %t723 = load i64, ptr %t722
+  %t724 = add i64 %t723, %t721
+  %t725 = getelementptr i64, ptr %t720, i64 1
+  store ptr %t725, ptr %sp
+  store i64 %t724, ptr %t725
+  %t726 = load ptr, ptr %sp
+  %t727 = getelementptr i64, ptr %t726, i64 -1
+  store ptr %t727, ptr %sp
+  store i64 1, ptr %t727
+  %t728 = load ptr, ptr %sp
+  %t729 = load i64, ptr %t728
+  %t730 = getelementptr i64, ptr %t728, i64 1
+  %t731 = load i64, ptr %t730
+  %t732 = add i64 %t731, %t729
+  %t733 = getelementptr i64, ptr %t728, i64 1
+  store ptr %t733, ptr %sp
+  store i64 %t732, ptr %t733
+  %t734 = load ptr, ptr %sp
+  %t735 = getelementptr i64, ptr %t734, i64 -1
+  store ptr %t735, ptr %sp
+  store i64 1, ptr %t735
+  %t736 = load ptr, ptr %sp
+  %t737 = load i64, ptr %t736
+  %t738 = getelementptr i64, ptr %t736, i64 1
+  %t739 = load i64, ptr %t738
+  %t740 = add i64 %t739, %t737
+  %t741 = getelementptr i64, ptr %t736, i64 1
+  store ptr %t741, ptr %sp
+  store i64 %t740, ptr %t741
+  %t742 = load ptr, ptr %sp
+  %t743 = getelementptr i64, ptr %t742, i64 -1
+  store ptr %t743, ptr %sp
+  store i64 1, ptr %t743
+  %t744 = load ptr, ptr %sp
+  %t745 = load i64, ptr %t744
+  %t746 = getelementptr i64, ptr %t744, i64 1
+  %t747 = load i64, ptr %t746
+  %t748 = add i64 %t747, %t745
+  %t749 = getelementptr i64, ptr %t744, i64 1
+  store ptr %t749, ptr %sp
+  store i64 %t748, ptr %t749
+  %t750 = load ptr, ptr %sp
+  %t751 = getelementptr i64, ptr %t750, i64 -1
+  store ptr %t751, ptr %sp
+  store i64 1, ptr %t751
+  %t752 = load ptr, ptr %sp
+  %t753 = load i64, ptr %t752
+  %t754 = getelementptr i64, ptr %t752, i64 1
+  %t755 = load i64, ptr %t754
+  %t756 = add i64 %t755, %t753
+  %t757 = getelementptr i64, ptr %t752, i64 1
+  store ptr %t757, ptr %sp
+  store i64 %t756, ptr %t757
+  %t758 = load ptr, ptr %sp
+  %t759 = getelementptr i64, ptr %t758, i64 -1
+  store ptr %t759, ptr %sp
+  store i64 1, ptr %t759
+  %t760 = load ptr, ptr %sp
+  %t761 = load i64, ptr %t760
+  %t762 = getelementptr i64, ptr %t760, i64 1
+  %t763 = load i64, ptr %t762
+  %t764 = add i64 %t763, %t761
+  %t765 = getelementptr i64, ptr %t760, i64 1
+  store ptr %t765, ptr %sp
+  store i64 %t764, ptr %t765
+  %t766 = load ptr, ptr %sp
+  %t767 = load i64, ptr %t766
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t767)
+  %t768 = getelementptr i64, ptr %t766, i64 1
+  store ptr %t768, ptr %sp
+  %t769 = load ptr, ptr %sp
+  %t770 = ptrtoint ptr %t769 to i64
+  %t771 = ptrtoint ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0) to i64
+  %t772 = sub i64 %t771, %t770
+  %t773 = lshr i64 %t772, 3
+  %t774 = getelementptr i64, ptr %t769, i64 -1
+  store ptr %t774, ptr %sp
+  store i64 %t773, ptr %t774
+  %t775 = load ptr, ptr %sp
+  %t776 = load i64, ptr %t775
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t776)
+  %t777 = getelementptr i64, ptr %t775, i64 1
+  store ptr %t777, ptr %sp
+  %t778 = load ptr, ptr %sp
+  ret ptr %t778
+}
+
+define i32 @main() {
+  %sp = call ptr @st_start(ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0))
+  ret i32 0
+}
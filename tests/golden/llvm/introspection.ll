@@ -0,0 +1,289 @@
+@int_fmt = private unnamed_addr constant [6 x i8] c"%lld \00"
+@bytes_fmt = private unnamed_addr constant [5 x i8] c"%.8s\00"
+@newline_fmt = private unnamed_addr constant [2 x i8] c"\0A\00"
+@int_scan_fmt = private unnamed_addr constant [5 x i8] c"%lld\00"
+@stack = internal global [1048576 x i64] zeroinitializer
+@line_buffer = internal global [4096 x i8] zeroinitializer
+@path_buffer = internal global [4096 x i8] zeroinitializer
+
+declare i32 @printf(ptr, ...)
+declare i32 @scanf(ptr, ...)
+declare i32 @getchar()
+declare ptr @malloc(i64)
+declare void @free(ptr)
+declare void @exit(i32) noreturn
+declare i32 @fflush(ptr)
+declare i32 @open(ptr, i32, ...)
+declare i64 @read(i32, ptr, i64)
+declare i64 @write(i32, ptr, i64)
+declare i32 @close(i32)
+declare ptr @__errno_location()
+
+; prints the data stack from the bottom to the top
+define internal void @jalgo_dump_stack(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %cell = phi ptr [ getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0), %entry ], [ %next, %body ]
+  %more = icmp ugt ptr %cell, %sp
+  br i1 %more, label %body, label %end
+body:
+  %next = getelementptr i64, ptr %cell, i64 -1
+  %value = load i64, ptr %next
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %value)
+  br label %loop
+end:
+  call i32 (ptr, ...) @printf(ptr @newline_fmt)
+  ret void
+}
+
+; up to 8 bytes of the value until a zero byte
+define internal void @jalgo_write_raw(i64 %value) {
+  %bytes = alloca i64
+  store i64 %value, ptr %bytes
+  call i32 (ptr, ...) @printf(ptr @bytes_fmt, ptr %bytes)
+  ret void
+}
+
+; pushes the value and the scanf status
+define internal ptr @jalgo_read_int(ptr %sp) {
+  %value = alloca i64
+  store i64 0, ptr %value
+  %status = call i32 (ptr, ...) @scanf(ptr @int_scan_fmt, ptr %value)
+  %loaded = load i64, ptr %value
+  %status64 = sext i32 %status to i64
+  %value_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 %loaded, ptr %value_cell
+  %new_sp = getelementptr i64, ptr %sp, i64 -2
+  store i64 %status64, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pushes the line as a zero terminated stack string and its length, -1 on EOF
+define internal ptr @jalgo_read_line(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %length = phi i64 [ 0, %entry ], [ %next_length, %append ]
+  %c = call i32 @getchar()
+  %eof = icmp eq i32 %c, -1
+  br i1 %eof, label %at_eof, label %not_eof
+not_eof:
+  %newline = icmp eq i32 %c, 10
+  br i1 %newline, label %done, label %append
+append:
+  %byte = trunc i32 %c to i8
+  %slot = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %length
+  store i8 %byte, ptr %slot
+  %next_length = add i64 %length, 1
+  %full = icmp eq i64 %next_length, 4096
+  br i1 %full, label %done, label %loop
+at_eof:
+  %empty = icmp eq i64 %length, 0
+  br i1 %empty, label %nothing, label %done
+nothing:
+  %eof_sp = getelementptr i64, ptr %sp, i64 -1
+  store i64 -1, ptr %eof_sp
+  ret ptr %eof_sp
+done:
+  %final_length = phi i64 [ %length, %not_eof ], [ %next_length, %append ], [ %length, %at_eof ]
+  %zero_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 0, ptr %zero_cell
+  br label %copy
+copy:
+  %i = phi i64 [ 0, %done ], [ %next_i, %copy_byte ]
+  %copy_more = icmp ult i64 %i, %final_length
+  br i1 %copy_more, label %copy_byte, label %copy_end
+copy_byte:
+  %source = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %i
+  %loaded = load i8, ptr %source
+  %extended = zext i8 %loaded to i64
+  %from_end = sub i64 %i, %final_length
+  %offset = sub i64 %from_end, 1
+  %target = getelementptr i64, ptr %sp, i64 %offset
+  store i64 %extended, ptr %target
+  %next_i = add i64 %i, 1
+  br label %copy
+copy_end:
+  %length_offset = sub i64 -2, %final_length
+  %new_sp = getelementptr i64, ptr %sp, i64 %length_offset
+  store i64 %final_length, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pops a zero terminated stack string into @path_buffer
+define internal ptr @jalgo_pop_path(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %i = phi i64 [ 0, %entry ], [ %next, %loop ]
+  %cell = getelementptr i64, ptr %sp, i64 %i
+  %value = load i64, ptr %cell
+  %byte = trunc i64 %value to i8
+  %slot = getelementptr [4096 x i8], ptr @path_buffer, i64 0, i64 %i
+  store i8 %byte, ptr %slot
+  %next = add i64 %i, 1
+  %more = icmp ne i64 %value, 0
+  br i1 %more, label %loop, label %end
+end:
+  %new_sp = getelementptr i64, ptr %sp, i64 %next
+  ret ptr %new_sp
+}
+
+; -1 from libc becomes -errno, like the raw syscalls
+define internal i64 @jalgo_result(i64 %result) {
+entry:
+  %failed = icmp slt i64 %result, 0
+  br i1 %failed, label %error, label %ok
+ok:
+  ret i64 %result
+error:
+  %errno_pointer = call ptr @__errno_location()
+  %errno = load i32, ptr %errno_pointer
+  %errno64 = sext i32 %errno to i64
+  %code = sub i64 0, %errno64
+  ret i64 %code
+}
+
+define internal i64 @jalgo_fopen(ptr %path, i32 %flags) {
+  %fd = call i32 (ptr, i32, ...) @open(ptr %path, i32 %flags, i32 420)
+  %fd64 = sext i32 %fd to i64
+  %result = call i64 @jalgo_result(i64 %fd64)
+  ret i64 %result
+}
+
+; fd -> byte, -1 on EOF, -errno on error
+define internal i64 @jalgo_fread_byte(i64 %fd) {
+  %byte = alloca i8
+  store i8 0, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @read(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  %loaded = load i8, ptr %byte
+  %extended = zext i8 %loaded to i64
+  %is_byte = icmp eq i64 %count, 1
+  %is_eof = icmp eq i64 %count, 0
+  %eof_or_error = select i1 %is_eof, i64 -1, i64 %result
+  %value = select i1 %is_byte, i64 %extended, i64 %eof_or_error
+  ret i64 %value
+}
+
+; fd byte -> 1, -errno on error. printf output goes first
+define internal i64 @jalgo_fwrite_byte(i64 %fd, i64 %value) {
+  call i32 @fflush(ptr null)
+  %byte = alloca i8
+  %truncated = trunc i64 %value to i8
+  store i8 %truncated, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @write(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  ret i64 %result
+}
+
+define internal i64 @jalgo_fclose(i64 %fd) {
+  call i32 @fflush(ptr null)
+  %fd32 = trunc i64 %fd to i32
+  %status = call i32 @close(i32 %fd32)
+  %status64 = sext i32 %status to i64
+  %result = call i64 @jalgo_result(i64 %status64)
+  ret i64 %result
+}
+
+
+; start
+define internal ptr @st_start(ptr %sp.arg) {
+entry:
+  %sp = alloca ptr
+  store ptr %sp.arg, ptr %sp
+  br label %restart
+restart:
+  %t1 = load ptr, ptr %sp
+  %t2 = ptrtoint ptr %t1 to i64
+  %t3 = ptrtoint ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0) to i64
+  %t4 = sub i64 %t3, %t2
+  %t5 = lshr i64 %t4, 3
+  %t6 = getelementptr i64, ptr %t1, i64 -1
+  store ptr %t6, ptr %sp
+  store i64 %t5, ptr %t6
+  %t7 = load ptr, ptr %sp
+  %t8 = load i64, ptr %t7
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t8)
+  %t9 = getelementptr i64, ptr %t7, i64 1
+  store ptr %t9, ptr %sp
+  %t10 = load ptr, ptr %sp
+  %t11 = getelementptr i64, ptr %t10, i64 -1
+  store ptr %t11, ptr %sp
+  store i64 1, ptr %t11
+  %t12 = load ptr, ptr %sp
+  %t13 = getelementptr i64, ptr %t12, i64 -1
+  store ptr %t13, ptr %sp
+  store i64 2, ptr %t13
+  %t14 = load ptr, ptr %sp
+  %t15 = getelementptr i64, ptr %t14, i64 -1
+  store ptr %t15, ptr %sp
+  store i64 3, ptr %t15
+  %t16 = load ptr, ptr %sp
+  %t17 = ptrtoint ptr %t16 to i64
+  %t18 = ptrtoint ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0) to i64
+  %t19 = sub i64 %t18, %t17
+  %t20 = lshr i64 %t19, 3
+  %t21 = getelementptr i64, ptr %t16, i64 -1
+  store ptr %t21, ptr %sp
+  store i64 %t20, ptr %t21
+  %t22 = load ptr, ptr %sp
+  %t23 = load i64, ptr %t22
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t23)
+  %t24 = getelementptr i64, ptr %t22, i64 1
+  store ptr %t24, ptr %sp
+  %t25 = load ptr, ptr %sp
+  call void @jalgo_dump_stack(ptr %t25)
+  %t26 = load ptr, ptr %sp
+  %t27 = load i64, ptr %t26
+  %t28 = getelementptr i64, ptr %t26, i64 1
+  store ptr %t28, ptr %sp
+  %t29 = load ptr, ptr %sp
+  %t30 = ptrtoint ptr %t29 to i64
+  %t31 = ptrtoint ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0) to i64
+  %t32 = sub i64 %t31, %t30
+  %t33 = lshr i64 %t32, 3
+  %t34 = getelementptr i64, ptr %t29, i64 -1
+  store ptr %t34, ptr %sp
+  store i64 %t33, ptr %t34
+  %t35 = load ptr, ptr %sp
+  %t36 = load i64, ptr %t35
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t36)
+  %t37 = getelementptr i64, ptr %t35, i64 1
+  store ptr %t37, ptr %sp
+  %t38 = load ptr, ptr %sp
+  call void @jalgo_dump_stack(ptr %t38)
+  %t39 = load ptr, ptr %sp
+  %t40 = load i64, ptr %t39
+  %t41 = getelementptr i64, ptr %t39, i64 1
+  store ptr %t41, ptr %sp
+  %t42 = load ptr, ptr %sp
+  %t43 = load i64, ptr %t42
+  %t44 = getelementptr i64, ptr %t42, i64 1
+  store ptr %t44, ptr %sp
+  %t45 = load ptr, ptr %sp
+  %t46 = ptrtoint ptr %t45 to i64
+  %t47 = ptrtoint ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0) to i64
+  %t48 = sub i64 %t47, %t46
+  %t49 = lshr i64 %t48, 3
+  %t50 = getelementptr i64, ptr %t45, i64 -1
+  store ptr %t50, ptr %sp
+  store i64 %t49, ptr %t50
+  %t51 = load ptr, ptr %sp
+  %t52 = load i64, ptr %t51
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t52)
+  %t53 = getelementptr i64, ptr %t51, i64 1
+  store ptr %t53, ptr %sp
+  %t54 = load ptr, ptr %sp
+  call void @jalgo_dump_stack(ptr %t54)
+  %t55 = load ptr, ptr %sp
+  ret ptr %t55
+}
+
+define i32 @main() {
+  %sp = call ptr @st_start(ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0))
+  ret i32 0
+}
@@ -0,0 +1,574 @@
+@int_fmt = private unnamed_addr constant [6 x i8] c"%lld \00"
+@bytes_fmt = private unnamed_addr constant [5 x i8] c"%.8s\00"
+@newline_fmt = private unnamed_addr constant [2 x i8] c"\0A\00"
+@int_scan_fmt = private unnamed_addr constant [5 x i8] c"%lld\00"
+@stack = internal global [1048576 x i64] zeroinitializer
+@line_buffer = internal global [4096 x i8] zeroinitializer
+@path_buffer = internal global [4096 x i8] zeroinitializer
+
+declare i32 @printf(ptr, ...)
+declare i32 @scanf(ptr, ...)
+declare i32 @getchar()
+declare ptr @malloc(i64)
+declare void @free(ptr)
+declare void @exit(i32) noreturn
+declare i32 @fflush(ptr)
+declare i32 @open(ptr, i32, ...)
+declare i64 @read(i32, ptr, i64)
+declare i64 @write(i32, ptr, i64)
+declare i32 @close(i32)
+declare ptr @__errno_location()
+
+; prints the data stack from the bottom to the top
+define internal void @jalgo_dump_stack(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %cell = phi ptr [ getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0), %entry ], [ %next, %body ]
+  %more = icmp ugt ptr %cell, %sp
+  br i1 %more, label %body, label %end
+body:
+  %next = getelementptr i64, ptr %cell, i64 -1
+  %value = load i64, ptr %next
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %value)
+  br label %loop
+end:
+  call i32 (ptr, ...) @printf(ptr @newline_fmt)
+  ret void
+}
+
+; up to 8 bytes of the value until a zero byte
+define internal void @jalgo_write_raw(i64 %value) {
+  %bytes = alloca i64
+  store i64 %value, ptr %bytes
+  call i32 (ptr, ...) @printf(ptr @bytes_fmt, ptr %bytes)
+  ret void
+}
+
+; pushes the value and the scanf status
+define internal ptr @jalgo_read_int(ptr %sp) {
+  %value = alloca i64
+  store i64 0, ptr %value
+  %status = call i32 (ptr, ...) @scanf(ptr @int_scan_fmt, ptr %value)
+  %loaded = load i64, ptr %value
+  %status64 = sext i32 %status to i64
+  %value_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 %loaded, ptr %value_cell
+  %new_sp = getelementptr i64, ptr %sp, i64 -2
+  store i64 %status64, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pushes the line as a zero terminated stack string and its length, -1 on EOF
+define internal ptr @jalgo_read_line(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %length = phi i64 [ 0, %entry ], [ %next_length, %append ]
+  %c = call i32 @getchar()
+  %eof = icmp eq i32 %c, -1
+  br i1 %eof, label %at_eof, label %not_eof
+not_eof:
+  %newline = icmp eq i32 %c, 10
+  br i1 %newline, label %done, label %append
+append:
+  %byte = trunc i32 %c to i8
+  %slot = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %length
+  store i8 %byte, ptr %slot
+  %next_length = add i64 %length, 1
+  %full = icmp eq i64 %next_length, 4096
+  br i1 %full, label %done, label %loop
+at_eof:
+  %empty = icmp eq i64 %length, 0
+  br i1 %empty, label %nothing, label %done
+nothing:
+  %eof_sp = getelementptr i64, ptr %sp, i64 -1
+  store i64 -1, ptr %eof_sp
+  ret ptr %eof_sp
+done:
+  %final_length = phi i64 [ %length, %not_eof ], [ %next_length, %append ], [ %length, %at_eof ]
+  %zero_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 0, ptr %zero_cell
+  br label %copy
+copy:
+  %i = phi i64 [ 0, %done ], [ %next_i, %copy_byte ]
+  %copy_more = icmp ult i64 %i, %final_length
+  br i1 %copy_more, label %copy_byte, label %copy_end
+copy_byte:
+  %source = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %i
+  %loaded = load i8, ptr %source
+  %extended = zext i8 %loaded to i64
+  %from_end = sub i64 %i, %final_length
+  %offset = sub i64 %from_end, 1
+  %target = getelementptr i64, ptr %sp, i64 %offset
+  store i64 %extended, ptr %target
+  %next_i = add i64 %i, 1
+  br label %copy
+copy_end:
+  %length_offset = sub i64 -2, %final_length
+  %new_sp = getelementptr i64, ptr %sp, i64 %length_offset
+  store i64 %final_length, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pops a zero terminated stack string into @path_buffer
+define internal ptr @jalgo_pop_path(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %i = phi i64 [ 0, %entry ], [ %next, %loop ]
+  %cell = getelementptr i64, ptr %sp, i64 %i
+  %value = load i64, ptr %cell
+  %byte = trunc i64 %value to i8
+  %slot = getelementptr [4096 x i8], ptr @path_buffer, i64 0, i64 %i
+  store i8 %byte, ptr %slot
+  %next = add i64 %i, 1
+  %more = icmp ne i64 %value, 0
+  br i1 %more, label %loop, label %end
+end:
+  %new_sp = getelementptr i64, ptr %sp, i64 %next
+  ret ptr %new_sp
+}
+
+; -1 from libc becomes -errno, like the raw syscalls
+define internal i64 @jalgo_result(i64 %result) {
+entry:
+  %failed = icmp slt i64 %result, 0
+  br i1 %failed, label %error, label %ok
+ok:
+  ret i64 %result
+error:
+  %errno_pointer = call ptr @__errno_location()
+  %errno = load i32, ptr %errno_pointer
+  %errno64 = sext i32 %errno to i64
+  %code = sub i64 0, %errno64
+  ret i64 %code
+}
+
+define internal i64 @jalgo_fopen(ptr %path, i32 %flags) {
+  %fd = call i32 (ptr, i32, ...) @open(ptr %path, i32 %flags, i32 420)
+  %fd64 = sext i32 %fd to i64
+  %result = call i64 @jalgo_result(i64 %fd64)
+  ret i64 %result
+}
+
+; fd -> byte, -1 on EOF, -errno on error
+define internal i64 @jalgo_fread_byte(i64 %fd) {
+  %byte = alloca i8
+  store i8 0, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @read(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  %loaded = load i8, ptr %byte
+  %extended = zext i8 %loaded to i64
+  %is_byte = icmp eq i64 %count, 1
+  %is_eof = icmp eq i64 %count, 0
+  %eof_or_error = select i1 %is_eof, i64 -1, i64 %result
+  %value = select i1 %is_byte, i64 %extended, i64 %eof_or_error
+  ret i64 %value
+}
+
+; fd byte -> 1, -errno on error. printf output goes first
+define internal i64 @jalgo_fwrite_byte(i64 %fd, i64 %value) {
+  call i32 @fflush(ptr null)
+  %byte = alloca i8
+  %truncated = trunc i64 %value to i8
+  store i8 %truncated, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @write(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  ret i64 %result
+}
+
+define internal i64 @jalgo_fclose(i64 %fd) {
+  call i32 @fflush(ptr null)
+  %fd32 = trunc i64 %fd to i32
+  %status = call i32 @close(i32 %fd32)
+  %status64 = sext i32 %status to i64
+  %result = call i64 @jalgo_result(i64 %status64)
+  ret i64 %result
+}
+
+
+; start
+define internal ptr @st_start(ptr %sp.arg) {
+entry:
+  %sp = alloca ptr
+  %counter0 = alloca i64
+  %counter1 = alloca i64
+  store ptr %sp.arg, ptr %sp
+  br label %restart
+restart:
+  %t1 = load ptr, ptr %sp
+  %t2 = getelementptr i64, ptr %t1, i64 -1
+  store ptr %t2, ptr %sp
+  store i64 0, ptr %t2
+  br label %l1
+l1:
+  %t3 = load ptr, ptr %sp
+  %t4 = load i64, ptr %t3
+  %t5 = getelementptr i64, ptr %t3, i64 -1
+  store ptr %t5, ptr %sp
+  store i64 %t4, ptr %t5
+  %t6 = getelementptr i64, ptr %t5, i64 1
+  store i64 %t4, ptr %t6
+  %t7 = load ptr, ptr %sp
+  %t8 = getelementptr i64, ptr %t7, i64 -1
+  store ptr %t8, ptr %sp
+  store i64 5, ptr %t8
+  %t9 = load ptr, ptr %sp
+  %t10 = load i64, ptr %t9
+  %t11 = getelementptr i64, ptr %t9, i64 1
+  %t12 = load i64, ptr %t11
+  %t13 = icmp slt i64 %t12, %t10
+  %t14 = zext i1 %t13 to i64
+  %t15 = getelementptr i64, ptr %t9, i64 1
+  store ptr %t15, ptr %sp
+  store i64 %t14, ptr %t15
+  %t16 = load ptr, ptr %sp
+  %t17 = load i64, ptr %t16
+  %t18 = icmp sle i64 %t17, 0
+  %t19 = getelementptr i64, ptr %t16, i64 1
+  store ptr %t19, ptr %sp
+  br i1 %t18, label %l10, label %b20
+b20:
+  %t21 = load ptr, ptr %sp
+  %t22 = load i64, ptr %t21
+  %t23 = getelementptr i64, ptr %t21, i64 -1
+  store ptr %t23, ptr %sp
+  store i64 %t22, ptr %t23
+  %t24 = getelementptr i64, ptr %t23, i64 1
+  store i64 %t22, ptr %t24
+  %t25 = load ptr, ptr %sp
+  %t26 = load i64, ptr %t25
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t26)
+  %t27 = getelementptr i64, ptr %t25, i64 1
+  store ptr %t27, ptr %sp
+  %t28 = load ptr, ptr %sp
+  %t29 = load i64, ptr %t28
+  %t30 = add i64 %t29, 1
+  store i64 %t30, ptr %t28
+  br label %l1
+l10:
+  %t31 = load ptr, ptr %sp
+  %t32 = load i64, ptr %t31
+  %t33 = getelementptr i64, ptr %t31, i64 1
+  store ptr %t33, ptr %sp
+  %t34 = load ptr, ptr %sp
+  %t35 = getelementptr i64, ptr %t34, i64 -1
+  store ptr %t35, ptr %sp
+  store i64 10, ptr %t35
+  br label %l12
+l12:
+  %t36 = load ptr, ptr %sp
+  %t37 = load i64, ptr %t36
+  %t38 = getelementptr i64, ptr %t36, i64 -1
+  store ptr %t38, ptr %sp
+  store i64 %t37, ptr %t38
+  %t39 = getelementptr i64, ptr %t38, i64 1
+  store i64 %t37, ptr %t39
+  %t40 = load ptr, ptr %sp
+  %t41 = load i64, ptr %t40
+  %t42 = icmp sle i64 %t41, 0
+  %t43 = getelementptr i64, ptr %t40, i64 1
+  store ptr %t43, ptr %sp
+  br i1 %t42, label %l17, label %b44
+b44:
+  %t45 = load ptr, ptr %sp
+  %t46 = load i64, ptr %t45
+  %t47 = sub i64 %t46, 1
+  store i64 %t47, ptr %t45
+  br label %l12
+l17:
+  %t48 = load ptr, ptr %sp
+  %t49 = load i64, ptr %t48
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t49)
+  %t50 = getelementptr i64, ptr %t48, i64 1
+  store ptr %t50, ptr %sp
+  store i64 3, ptr %counter0
+  %t51 = icmp sle i64 3, 0
+  br i1 %t51, label %l22, label %b52
+b52:
+  br label %l19
+l19:
+  %t53 = load ptr, ptr %sp
+  %t54 = getelementptr i64, ptr %t53, i64 -1
+  store ptr %t54, ptr %sp
+  store i64 42, ptr %t54
+  %t55 = load ptr, ptr %sp
+  %t56 = load i64, ptr %t55
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t56)
+  %t57 = getelementptr i64, ptr %t55, i64 1
+  store ptr %t57, ptr %sp
+  %t58 = load i64, ptr %counter0
+  %t59 = sub i64 %t58, 1
+  store i64 %t59, ptr %counter0
+  %t60 = icmp sgt i64 %t59, 0
+  br i1 %t60, label %l19, label %b61
+b61:
+  br label %l22
+l22:
+  store i64 0, ptr %counter0
+  %t62 = icmp sle i64 0, 0
+  br i1 %t62, label %l26, label %b63
+b63:
+  br label %l23
+l23:
+  %t64 = load ptr, ptr %sp
+  %t65 = getelementptr i64, ptr %t64, i64 -1
+  store ptr %t65, ptr %sp
+  store i64 13, ptr %t65
+  %t66 = load ptr, ptr %sp
+  %t67 = load i64, ptr %t66
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t67)
+  %t68 = getelementptr i64, ptr %t66, i64 1
+  store ptr %t68, ptr %sp
+  %t69 = load i64, ptr %counter0
+  %t70 = sub i64 %t69, 1
+  store i64 %t70, ptr %counter0
+  %t71 = icmp sgt i64 %t70, 0
+  br i1 %t71, label %l23, label %b72
+b72:
+  br label %l26
+l26:
+  %t73 = load ptr, ptr %sp
+  %t74 = getelementptr i64, ptr %t73, i64 -1
+  store ptr %t74, ptr %sp
+  store i64 4, ptr %t74
+  %t75 = load ptr, ptr %sp
+  %t76 = load i64, ptr %t75
+  %t77 = getelementptr i64, ptr %t75, i64 1
+  store ptr %t77, ptr %sp
+  store i64 %t76, ptr %counter0
+  %t78 = icmp sle i64 %t76, 0
+  br i1 %t78, label %l31, label %b79
+b79:
+  br label %l28
+l28:
+  %t80 = load ptr, ptr %sp
+  %t81 = getelementptr i64, ptr %t80, i64 -1
+  store ptr %t81, ptr %sp
+  store i64 7, ptr %t81
+  %t82 = load ptr, ptr %sp
+  %t83 = load i64, ptr %t82
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t83)
+  %t84 = getelementptr i64, ptr %t82, i64 1
+  store ptr %t84, ptr %sp
+  %t85 = load i64, ptr %counter0
+  %t86 = sub i64 %t85, 1
+  store i64 %t86, ptr %counter0
+  %t87 = icmp sgt i64 %t86, 0
+  br i1 %t87, label %l28, label %b88
+b88:
+  br label %l31
+l31:
+  %t89 = load ptr, ptr %sp
+  %t90 = getelementptr i64, ptr %t89, i64 -1
+  store ptr %t90, ptr %sp
+  store i64 0, ptr %t90
+  %t91 = load ptr, ptr %sp
+  %t92 = getelementptr i64, ptr %t91, i64 -1
+  store ptr %t92, ptr %sp
+  store i64 3, ptr %t92
+  %t93 = load ptr, ptr %sp
+  %t94 = load i64, ptr %t93
+  %t95 = getelementptr i64, ptr %t93, i64 1
+  store ptr %t95, ptr %sp
+  store i64 %t94, ptr %counter0
+  %t96 = icmp sle i64 %t94, 0
+  br i1 %t96, label %l39, label %b97
+b97:
+  br label %l34
+l34:
+  %t98 = load ptr, ptr %sp
+  %t99 = getelementptr i64, ptr %t98, i64 -1
+  store ptr %t99, ptr %sp
+  store i64 2, ptr %t99
+  %t100 = load ptr, ptr %sp
+  %t101 = load i64, ptr %t100
+  %t102 = getelementptr i64, ptr %t100, i64 1
+  store ptr %t102, ptr %sp
+  store i64 %t101, ptr %counter1
+  %t103 = icmp sle i64 %t101, 0
+  br i1 %t103, label %l38, label %b104
+b104:
+  br label %l36
+l36:
+  %t105 = load ptr, ptr %sp
+  %t106 = load i64, ptr %t105
+  %t107 = add i64 %t106, 1
+  store i64 %t107, ptr %t105
+  %t108 = load i64, ptr %counter1
+  %t109 = sub i64 %t108, 1
+  store i64 %t109, ptr %counter1
+  %t110 = icmp sgt i64 %t109, 0
+  br i1 %t110, label %l36, label %b111
+b111:
+  br label %l38
+l38:
+  %t112 = load i64, ptr %counter0
+  %t113 = sub i64 %t112, 1
+  store i64 %t113, ptr %counter0
+  %t114 = icmp sgt i64 %t113, 0
+  br i1 %t114, label %l34, label %b115
+b115:
+  br label %l39
+l39:
+  %t116 = load ptr, ptr %sp
+  %t117 = load i64, ptr %t116
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t117)
+  %t118 = getelementptr i64, ptr %t116, i64 1
+  store ptr %t118, ptr %sp
+  %t119 = load ptr, ptr %sp
+  %t120 = getelementptr i64, ptr %t119, i64 -1
+  store ptr %t120, ptr %sp
+  store i64 0, ptr %t120
+  br label %l41
+l41:
+  %t121 = load ptr, ptr %sp
+  %t122 = load i64, ptr %t121
+  %t123 = getelementptr i64, ptr %t121, i64 -1
+  store ptr %t123, ptr %sp
+  store i64 %t122, ptr %t123
+  %t124 = getelementptr i64, ptr %t123, i64 1
+  store i64 %t122, ptr %t124
+  %t125 = load ptr, ptr %sp
+  %t126 = getelementptr i64, ptr %t125, i64 -1
+  store ptr %t126, ptr %sp
+  store i64 3, ptr %t126
+  %t127 = load ptr, ptr %sp
+  %t128 = load i64, ptr %t127
+  %t129 = getelementptr i64, ptr %t127, i64 1
+  %t130 = load i64, ptr %t129
+  %t131 = icmp slt i64 %t130, %t128
+  %t132 = zext i1 %t131 to i64
+  %t133 = getelementptr i64, ptr %t127, i64 1
+  store ptr %t133, ptr %sp
+  store i64 %t132, ptr %t133
+  %t134 = load ptr, ptr %sp
+  %t135 = load i64, ptr %t134
+  %t136 = icmp sle i64 %t135, 0
+  %t137 = getelementptr i64, ptr %t134, i64 1
+  store ptr %t137, ptr %sp
+  br i1 %t136, label %l53, label %b138
+b138:
+  %t139 = load ptr, ptr %sp
+  %t140 = getelementptr i64, ptr %t139, i64 -1
+  store ptr %t140, ptr %sp
+  store i64 2, ptr %t140
+  %t141 = load ptr, ptr %sp
+  %t142 = load i64, ptr %t141
+  %t143 = getelementptr i64, ptr %t141, i64 1
+  store ptr %t143, ptr %sp
+  store i64 %t142, ptr %counter0
+  %t144 = icmp sle i64 %t142, 0
+  br i1 %t144, label %l51, label %b145
+b145:
+  br label %l48
+l48:
+  %t146 = load ptr, ptr %sp
+  %t147 = load i64, ptr %t146
+  %t148 = getelementptr i64, ptr %t146, i64 -1
+  store ptr %t148, ptr %sp
+  store i64 %t147, ptr %t148
+  %t149 = getelementptr i64, ptr %t148, i64 1
+  store i64 %t147, ptr %t149
+  %t150 = load ptr, ptr %sp
+  %t151 = load i64, ptr %t150
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t151)
+  %t152 = getelementptr i64, ptr %t150, i64 1
+  store ptr %t152, ptr %sp
+  %t153 = load i64, ptr %counter0
+  %t154 = sub i64 %t153, 1
+  store i64 %t154, ptr %counter0
+  %t155 = icmp sgt i64 %t154, 0
+  br i1 %t155, label %l48, label %b156
+b156:
+  br label %l51
+l51:
+  %t157 = load ptr, ptr %sp
+  %t158 = load i64, ptr %t157
+  %t159 = add i64 %t158, 1
+  store i64 %t159, ptr %t157
+  br label %l41
+l53:
+  %t160 = load ptr, ptr %sp
+  %t161 = load i64, ptr %t160
+  %t162 = getelementptr i64, ptr %t160, i64 1
+  store ptr %t162, ptr %sp
+  %t163 = load ptr, ptr %sp
+  %t164 = getelementptr i64, ptr %t163, i64 -1
+  store ptr %t164, ptr %sp
+  store i64 3, ptr %t164
+  br label %l55
+l55:
+  %t165 = load ptr, ptr %sp
+  %t166 = load i64, ptr %t165
+  %t167 = getelementptr i64, ptr %t165, i64 -1
+  store ptr %t167, ptr %sp
+  store i64 %t166, ptr %t167
+  %t168 = getelementptr i64, ptr %t167, i64 1
+  store i64 %t166, ptr %t168
+  %t169 = load ptr, ptr %sp
+  %t170 = getelementptr i64, ptr %t169, i64 -1
+  store ptr %t170, ptr %sp
+  store i64 0, ptr %t170
+  %t171 = load ptr, ptr %sp
+  %t172 = load i64, ptr %t171
+  %t173 = getelementptr i64, ptr %t171, i64 1
+  %t174 = load i64, ptr %t173
+  %t175 = icmp ne i64 %t174, %t172
+  %t176 = zext i1 %t175 to i64
+  %t177 = getelementptr i64, ptr %t171, i64 1
+  store ptr %t177, ptr %sp
+  store i64 %t176, ptr %t177
+  %t178 = load ptr, ptr %sp
+  %t179 = load i64, ptr %t178
+  %t180 = icmp sle i64 %t179, 0
+  %t181 = getelementptr i64, ptr %t178, i64 1
+  store ptr %t181, ptr %sp
+  br i1 %t180, label %l64, label %b182
+b182:
+  %t183 = load ptr, ptr %sp
+  %t184 = load i64, ptr %t183
+  %t185 = getelementptr i64, ptr %t183, i64 -1
+  store ptr %t185, ptr %sp
+  store i64 %t184, ptr %t185
+  %t186 = getelementptr i64, ptr %t185, i64 1
+  store i64 %t184, ptr %t186
+  %t187 = load ptr, ptr %sp
+  %t188 = load i64, ptr %t187
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t188)
+  %t189 = getelementptr i64, ptr %t187, i64 1
+  store ptr %t189, ptr %sp
+  %t190 = load ptr, ptr %sp
+  %t191 = load i64, ptr %t190
+  %t192 = sub i64 %t191, 1
+  store i64 %t192, ptr %t190
+  br label %l55
+l64:
+  %t193 = load ptr, ptr %sp
+  %t194 = load i64, ptr %t193
+  %t195 = getelementptr i64, ptr %t193, i64 1
+  store ptr %t195, ptr %sp
+  %t196 = load ptr, ptr %sp
+  %t197 = ptrtoint ptr %t196 to i64
+  %t198 = ptrtoint ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0) to i64
+  %t199 = sub i64 %t198, %t197
+  %t200 = lshr i64 %t199, 3
+  %t201 = getelementptr i64, ptr %t196, i64 -1
+  store ptr %t201, ptr %sp
+  store i64 %t200, ptr %t201
+  %t202 = load ptr, ptr %sp
+  %t203 = load i64, ptr %t202
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t203)
+  %t204 = getelementptr i64, ptr %t202, i64 1
+  store ptr %t204, ptr %sp
+  %t205 = load ptr, ptr %sp
+  ret ptr %t205
+}
+
+define i32 @main() {
+  %sp = call ptr @st_start(ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0))
+  ret i32 0
+}
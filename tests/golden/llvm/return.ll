@@ -0,0 +1,473 @@
+@int_fmt = private unnamed_addr constant [6 x i8] c"%lld \00"
+@bytes_fmt = private unnamed_addr constant [5 x i8] c"%.8s\00"
+@newline_fmt = private unnamed_addr constant [2 x i8] c"\0A\00"
+@int_scan_fmt = private unnamed_addr constant [5 x i8] c"%lld\00"
+@stack = internal global [1048576 x i64] zeroinitializer
+@line_buffer = internal global [4096 x i8] zeroinitializer
+@path_buffer = internal global [4096 x i8] zeroinitializer
+
+declare i32 @printf(ptr, ...)
+declare i32 @scanf(ptr, ...)
+declare i32 @getchar()
+declare ptr @malloc(i64)
+declare void @free(ptr)
+declare void @exit(i32) noreturn
+declare i32 @fflush(ptr)
+declare i32 @open(ptr, i32, ...)
+declare i64 @read(i32, ptr, i64)
+declare i64 @write(i32, ptr, i64)
+declare i32 @close(i32)
+declare ptr @__errno_location()
+
+; prints the data stack from the bottom to the top
+define internal void @jalgo_dump_stack(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %cell = phi ptr [ getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0), %entry ], [ %next, %body ]
+  %more = icmp ugt ptr %cell, %sp
+  br i1 %more, label %body, label %end
+body:
+  %next = getelementptr i64, ptr %cell, i64 -1
+  %value = load i64, ptr %next
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %value)
+  br label %loop
+end:
+  call i32 (ptr, ...) @printf(ptr @newline_fmt)
+  ret void
+}
+
+; up to 8 bytes of the value until a zero byte
+define internal void @jalgo_write_raw(i64 %value) {
+  %bytes = alloca i64
+  store i64 %value, ptr %bytes
+  call i32 (ptr, ...) @printf(ptr @bytes_fmt, ptr %bytes)
+  ret void
+}
+
+; pushes the value and the scanf status
+define internal ptr @jalgo_read_int(ptr %sp) {
+  %value = alloca i64
+  store i64 0, ptr %value
+  %status = call i32 (ptr, ...) @scanf(ptr @int_scan_fmt, ptr %value)
+  %loaded = load i64, ptr %value
+  %status64 = sext i32 %status to i64
+  %value_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 %loaded, ptr %value_cell
+  %new_sp = getelementptr i64, ptr %sp, i64 -2
+  store i64 %status64, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pushes the line as a zero terminated stack string and its length, -1 on EOF
+define internal ptr @jalgo_read_line(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %length = phi i64 [ 0, %entry ], [ %next_length, %append ]
+  %c = call i32 @getchar()
+  %eof = icmp eq i32 %c, -1
+  br i1 %eof, label %at_eof, label %not_eof
+not_eof:
+  %newline = icmp eq i32 %c, 10
+  br i1 %newline, label %done, label %append
+append:
+  %byte = trunc i32 %c to i8
+  %slot = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %length
+  store i8 %byte, ptr %slot
+  %next_length = add i64 %length, 1
+  %full = icmp eq i64 %next_length, 4096
+  br i1 %full, label %done, label %loop
+at_eof:
+  %empty = icmp eq i64 %length, 0
+  br i1 %empty, label %nothing, label %done
+nothing:
+  %eof_sp = getelementptr i64, ptr %sp, i64 -1
+  store i64 -1, ptr %eof_sp
+  ret ptr %eof_sp
+done:
+  %final_length = phi i64 [ %length, %not_eof ], [ %next_length, %append ], [ %length, %at_eof ]
+  %zero_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 0, ptr %zero_cell
+  br label %copy
+copy:
+  %i = phi i64 [ 0, %done ], [ %next_i, %copy_byte ]
+  %copy_more = icmp ult i64 %i, %final_length
+  br i1 %copy_more, label %copy_byte, label %copy_end
+copy_byte:
+  %source = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %i
+  %loaded = load i8, ptr %source
+  %extended = zext i8 %loaded to i64
+  %from_end = sub i64 %i, %final_length
+  %offset = sub i64 %from_end, 1
+  %target = getelementptr i64, ptr %sp, i64 %offset
+  store i64 %extended, ptr %target
+  %next_i = add i64 %i, 1
+  br label %copy
+copy_end:
+  %length_offset = sub i64 -2, %final_length
+  %new_sp = getelementptr i64, ptr %sp, i64 %length_offset
+  store i64 %final_length, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pops a zero terminated stack string into @path_buffer
+define internal ptr @jalgo_pop_path(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %i = phi i64 [ 0, %entry ], [ %next, %loop ]
+  %cell = getelementptr i64, ptr %sp, i64 %i
+  %value = load i64, ptr %cell
+  %byte = trunc i64 %value to i8
+  %slot = getelementptr [4096 x i8], ptr @path_buffer, i64 0, i64 %i
+  store i8 %byte, ptr %slot
+  %next = add i64 %i, 1
+  %more = icmp ne i64 %value, 0
+  br i1 %more, label %loop, label %end
+end:
+  %new_sp = getelementptr i64, ptr %sp, i64 %next
+  ret ptr %new_sp
+}
+
+; -1 from libc becomes -errno, like the raw syscalls
+define internal i64 @jalgo_result(i64 %result) {
+entry:
+  %failed = icmp slt i64 %result, 0
+  br i1 %failed, label %error, label %ok
+ok:
+  ret i64 %result
+error:
+  %errno_pointer = call ptr @__errno_location()
+  %errno = load i32, ptr %errno_pointer
+  %errno64 = sext i32 %errno to i64
+  %code = sub i64 0, %errno64
+  ret i64 %code
+}
+
+define internal i64 @jalgo_fopen(ptr %path, i32 %flags) {
+  %fd = call i32 (ptr, i32, ...) @open(ptr %path, i32 %flags, i32 420)
+  %fd64 = sext i32 %fd to i64
+  %result = call i64 @jalgo_result(i64 %fd64)
+  ret i64 %result
+}
+
+; fd -> byte, -1 on EOF, -errno on error
+define internal i64 @jalgo_fread_byte(i64 %fd) {
+  %byte = alloca i8
+  store i8 0, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @read(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  %loaded = load i8, ptr %byte
+  %extended = zext i8 %loaded to i64
+  %is_byte = icmp eq i64 %count, 1
+  %is_eof = icmp eq i64 %count, 0
+  %eof_or_error = select i1 %is_eof, i64 -1, i64 %result
+  %value = select i1 %is_byte, i64 %extended, i64 %eof_or_error
+  ret i64 %value
+}
+
+; fd byte -> 1, -errno on error. printf output goes first
+define internal i64 @jalgo_fwrite_byte(i64 %fd, i64 %value) {
+  call i32 @fflush(ptr null)
+  %byte = alloca i8
+  %truncated = trunc i64 %value to i8
+  store i8 %truncated, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @write(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  ret i64 %result
+}
+
+define internal i64 @jalgo_fclose(i64 %fd) {
+  call i32 @fflush(ptr null)
+  %fd32 = trunc i64 %fd to i32
+  %status = call i32 @close(i32 %fd32)
+  %status64 = sext i32 %status to i64
+  %result = call i64 @jalgo_result(i64 %status64)
+  ret i64 %result
+}
+
+
+; first_over_three
+define internal ptr @st_first_over_three(ptr %sp.arg) {
+entry:
+  %sp = alloca ptr
+  %counter0 = alloca i64
+  store ptr %sp.arg, ptr %sp
+  br label %restart
+restart:
+  %t1 = load ptr, ptr %sp
+  %t2 = getelementptr i64, ptr %t1, i64 -1
+  store ptr %t2, ptr %sp
+  store i64 0, ptr %t2
+  br label %l1
+l1:
+  %t3 = load ptr, ptr %sp
+  %t4 = getelementptr i64, ptr %t3, i64 -1
+  store ptr %t4, ptr %sp
+  store i64 1, ptr %t4
+  %t5 = load ptr, ptr %sp
+  %t6 = load i64, ptr %t5
+  %t7 = icmp sle i64 %t6, 0
+  %t8 = getelementptr i64, ptr %t5, i64 1
+  store ptr %t8, ptr %sp
+  br i1 %t7, label %l22, label %b9
+b9:
+  %t10 = load ptr, ptr %sp
+  %t11 = load i64, ptr %t10
+  %t12 = add i64 %t11, 1
+  store i64 %t12, ptr %t10
+  %t13 = load ptr, ptr %sp
+  %t14 = load i64, ptr %t13
+  %t15 = getelementptr i64, ptr %t13, i64 -1
+  store ptr %t15, ptr %sp
+  store i64 %t14, ptr %t15
+  %t16 = getelementptr i64, ptr %t15, i64 1
+  store i64 %t14, ptr %t16
+  %t17 = load ptr, ptr %sp
+  %t18 = getelementptr i64, ptr %t17, i64 -1
+  store ptr %t18, ptr %sp
+  store i64 3, ptr %t18
+  %t19 = load ptr, ptr %sp
+  %t20 = load i64, ptr %t19
+  %t21 = getelementptr i64, ptr %t19, i64 1
+  %t22 = load i64, ptr %t21
+  %t23 = icmp sgt i64 %t22, %t20
+  %t24 = zext i1 %t23 to i64
+  %t25 = getelementptr i64, ptr %t19, i64 1
+  store ptr %t25, ptr %sp
+  store i64 %t24, ptr %t25
+  %t26 = load ptr, ptr %sp
+  %t27 = load i64, ptr %t26
+  %t28 = icmp sle i64 %t27, 0
+  %t29 = getelementptr i64, ptr %t26, i64 1
+  store ptr %t29, ptr %sp
+  br i1 %t28, label %l11, label %b30
+b30:
+  %t31 = load ptr, ptr %sp
+  ret ptr %t31
+l11:
+  store i64 2, ptr %counter0
+  %t32 = icmp sle i64 2, 0
+  br i1 %t32, label %l21, label %b33
+b33:
+  br label %l12
+l12:
+  %t34 = load ptr, ptr %sp
+  %t35 = load i64, ptr %t34
+  %t36 = getelementptr i64, ptr %t34, i64 -1
+  store ptr %t36, ptr %sp
+  store i64 %t35, ptr %t36
+  %t37 = getelementptr i64, ptr %t36, i64 1
+  store i64 %t35, ptr %t37
+  %t38 = load ptr, ptr %sp
+  %t39 = getelementptr i64, ptr %t38, i64 -1
+  store ptr %t39, ptr %sp
+  store i64 100, ptr %t39
+  %t40 = load ptr, ptr %sp
+  %t41 = load i64, ptr %t40
+  %t42 = getelementptr i64, ptr %t40, i64 1
+  %t43 = load i64, ptr %t42
+  %t44 = icmp sgt i64 %t43, %t41
+  %t45 = zext i1 %t44 to i64
+  %t46 = getelementptr i64, ptr %t40, i64 1
+  store ptr %t46, ptr %sp
+  store i64 %t45, ptr %t46
+  %t47 = load ptr, ptr %sp
+  %t48 = load i64, ptr %t47
+  %t49 = icmp sle i64 %t48, 0
+  %t50 = getelementptr i64, ptr %t47, i64 1
+  store ptr %t50, ptr %sp
+  br i1 %t49, label %l20, label %b51
+b51:
+  %t52 = load ptr, ptr %sp
+  %t53 = load i64, ptr %t52
+  %t54 = getelementptr i64, ptr %t52, i64 1
+  store ptr %t54, ptr %sp
+  %t55 = load ptr, ptr %sp
+  %t56 = getelementptr i64, ptr %t55, i64 -1
+  store ptr %t56, ptr %sp
+  store i64 0, ptr %t56
+  %t57 = load ptr, ptr %sp
+  ret ptr %t57
+l20:
+  %t58 = load i64, ptr %counter0
+  %t59 = sub i64 %t58, 1
+  store i64 %t59, ptr %counter0
+  %t60 = icmp sgt i64 %t59, 0
+  br i1 %t60, label %l12, label %b61
+b61:
+  br label %l21
+l21:
+  br label %l1
+l22:
+  %t62 = load ptr, ptr %sp
+  ret ptr %t62
+}
+
+; start
+define internal ptr @st_start(ptr %sp.arg) {
+entry:
+  %sp = alloca ptr
+  store ptr %sp.arg, ptr %sp
+  br label %restart
+restart:
+  %t1 = load ptr, ptr %sp
+  %t2 = call ptr @st_first_over_three(ptr %t1)
+  store ptr %t2, ptr %sp
+  %t3 = load ptr, ptr %sp
+  %t4 = load i64, ptr %t3
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t4)
+  %t5 = getelementptr i64, ptr %t3, i64 1
+  store ptr %t5, ptr %sp
+  %t6 = load ptr, ptr %sp
+  %t7 = getelementptr i64, ptr %t6, i64 -1
+  store ptr %t7, ptr %sp
+  store i64 -4, ptr %t7
+  %t8 = load ptr, ptr %sp
+  %t9 = load i64, ptr %t8
+  %t10 = getelementptr i64, ptr %t8, i64 -1
+  store ptr %t10, ptr %sp
+  store i64 %t9, ptr %t10
+  %t11 = getelementptr i64, ptr %t10, i64 1
+  store i64 %t9, ptr %t11
+  %t12 = load ptr, ptr %sp
+  %t13 = getelementptr i64, ptr %t12, i64 -1
+  store ptr %t13, ptr %sp
+  store i64 0, ptr %t13
+  %t14 = load ptr, ptr %sp
+  %t15 = load i64, ptr %t14
+  %t16 = getelementptr i64, ptr %t14, i64 1
+  %t17 = load i64, ptr %t16
+  %t18 = icmp slt i64 %t17, %t15
+  %t19 = zext i1 %t18 to i64
+  %t20 = getelementptr i64, ptr %t14, i64 1
+  store ptr %t20, ptr %sp
+  store i64 %t19, ptr %t20
+  %t21 = load ptr, ptr %sp
+  %t22 = load i64, ptr %t21
+  %t23 = icmp sle i64 %t22, 0
+  %t24 = getelementptr i64, ptr %t21, i64 1
+  store ptr %t24, ptr %sp
+  br i1 %t23, label %l22, label %b25
+b25:
+  %t26 = load ptr, ptr %sp
+  %t27 = load i64, ptr %t26
+  %t28 = getelementptr i64, ptr %t26, i64 1
+  store ptr %t28, ptr %sp
+  %t29 = load ptr, ptr %sp
+  %t30 = getelementptr i64, ptr %t29, i64 -1
+  store ptr %t30, ptr %sp
+  store i64 0, ptr %t30
+  br label %l24
+l22:
+  %t31 = load ptr, ptr %sp
+  %t32 = getelementptr i64, ptr %t31, i64 -1
+  store ptr %t32, ptr %sp
+  store i64 2, ptr %t32
+  %t33 = load ptr, ptr %sp
+  %t34 = load i64, ptr %t33
+  %t35 = getelementptr i64, ptr %t33, i64 1
+  %t36 = load i64, ptr %t35
+  %t37 = mul i64 %t36, %t34
+  %t38 = getelementptr i64, ptr %t33, i64 1
+  store ptr %t38, ptr %sp
+  store i64 %t37, ptr %t38
+  br label %l24
+l24:
+  %t39 = load ptr, ptr %sp
+  %t40 = load i64, ptr %t39
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t40)
+  %t41 = getelementptr i64, ptr %t39, i64 1
+  store ptr %t41, ptr %sp
+  %t42 = load ptr, ptr %sp
+  %t43 = getelementptr i64, ptr %t42, i64 -1
+  store ptr %t43, ptr %sp
+  store i64 4, ptr %t43
+  %t44 = load ptr, ptr %sp
+  %t45 = load i64, ptr %t44
+  %t46 = getelementptr i64, ptr %t44, i64 -1
+  store ptr %t46, ptr %sp
+  store i64 %t45, ptr %t46
+  %t47 = getelementptr i64, ptr %t46, i64 1
+  store i64 %t45, ptr %t47
+  %t48 = load ptr, ptr %sp
+  %t49 = getelementptr i64, ptr %t48, i64 -1
+  store ptr %t49, ptr %sp
+  store i64 0, ptr %t49
+  %t50 = load ptr, ptr %sp
+  %t51 = load i64, ptr %t50
+  %t52 = getelementptr i64, ptr %t50, i64 1
+  %t53 = load i64, ptr %t52
+  %t54 = icmp slt i64 %t53, %t51
+  %t55 = zext i1 %t54 to i64
+  %t56 = getelementptr i64, ptr %t50, i64 1
+  store ptr %t56, ptr %sp
+  store i64 %t55, ptr %t56
+  %t57 = load ptr, ptr %sp
+  %t58 = load i64, ptr %t57
+  %t59 = icmp sle i64 %t58, 0
+  %t60 = getelementptr i64, ptr %t57, i64 1
+  store ptr %t60, ptr %sp
+  br i1 %t59, label %l33, label %b61
+b61:
+  %t62 = load ptr, ptr %sp
+  %t63 = load i64, ptr %t62
+  %t64 = getelementptr i64, ptr %t62, i64 1
+  store ptr %t64, ptr %sp
+  %t65 = load ptr, ptr %sp
+  %t66 = getelementptr i64, ptr %t65, i64 -1
+  store ptr %t66, ptr %sp
+  store i64 0, ptr %t66
+  br label %l35
+l33:
+  %t67 = load ptr, ptr %sp
+  %t68 = getelementptr i64, ptr %t67, i64 -1
+  store ptr %t68, ptr %sp
+  store i64 2, ptr %t68
+  %t69 = load ptr, ptr %sp
+  %t70 = load i64, ptr %t69
+  %t71 = getelementptr i64, ptr %t69, i64 1
+  %t72 = load i64, ptr %t71
+  %t73 = mul i64 %t72, %t70
+  %t74 = getelementptr i64, ptr %t69, i64 1
+  store ptr %t74, ptr %sp
+  store i64 %t73, ptr %t74
+  br label %l35
+l35:
+  %t75 = load ptr, ptr %sp
+  %t76 = load i64, ptr %t75
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t76)
+  %t77 = getelementptr i64, ptr %t75, i64 1
+  store ptr %t77, ptr %sp
+  %t78 = load ptr, ptr %sp
+  %t79 = getelementptr i64, ptr %t78, i64 -1
+  store ptr %t79, ptr %sp
+  store i64 1, ptr %t79
+  %t80 = load ptr, ptr %sp
+  %t81 = load i64, ptr %t80
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t81)
+  %t82 = getelementptr i64, ptr %t80, i64 1
+  store ptr %t82, ptr %sp
+  %t83 = load ptr, ptr %sp
+  ret ptr %t83
+b85:
+  %t84 = load ptr, ptr %sp
+  %t86 = getelementptr i64, ptr %t84, i64 -1
+  store ptr %t86, ptr %sp
+  store i64 2, ptr %t86
+  %t87 = load ptr, ptr %sp
+  %t88 = load i64, ptr %t87
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t88)
+  %t89 = getelementptr i64, ptr %t87, i64 1
+  store ptr %t89, ptr %sp
+  %t90 = load ptr, ptr %sp
+  ret ptr %t90
+}
+
+define i32 @main() {
+  %sp = call ptr @st_start(ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0))
+  ret i32 0
+}
@@ -0,0 +1,1006 @@
+@int_fmt = private unnamed_addr constant [6 x i8] c"%lld \00"
+@bytes_fmt = private unnamed_addr constant [5 x i8] c"%.8s\00"
+@newline_fmt = private unnamed_addr constant [2 x i8] c"\0A\00"
+@int_scan_fmt = private unnamed_addr constant [5 x i8] c"%lld\00"
+@stack = internal global [1048576 x i64] zeroinitializer
+@line_buffer = internal global [4096 x i8] zeroinitializer
+@path_buffer = internal global [4096 x i8] zeroinitializer
+
+declare i32 @printf(ptr, ...)
+declare i32 @scanf(ptr, ...)
+declare i32 @getchar()
+declare ptr @malloc(i64)
+declare void @free(ptr)
+declare void @exit(i32) noreturn
+declare i32 @fflush(ptr)
+declare i32 @open(ptr, i32, ...)
+declare i64 @read(i32, ptr, i64)
+declare i64 @write(i32, ptr, i64)
+declare i32 @close(i32)
+declare ptr @__errno_location()
+
+; prints the data stack from the bottom to the top
+define internal void @jalgo_dump_stack(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %cell = phi ptr [ getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0), %entry ], [ %next, %body ]
+  %more = icmp ugt ptr %cell, %sp
+  br i1 %more, label %body, label %end
+body:
+  %next = getelementptr i64, ptr %cell, i64 -1
+  %value = load i64, ptr %next
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %value)
+  br label %loop
+end:
+  call i32 (ptr, ...) @printf(ptr @newline_fmt)
+  ret void
+}
+
+; up to 8 bytes of the value until a zero byte
+define internal void @jalgo_write_raw(i64 %value) {
+  %bytes = alloca i64
+  store i64 %value, ptr %bytes
+  call i32 (ptr, ...) @printf(ptr @bytes_fmt, ptr %bytes)
+  ret void
+}
+
+; pushes the value and the scanf status
+define internal ptr @jalgo_read_int(ptr %sp) {
+  %value = alloca i64
+  store i64 0, ptr %value
+  %status = call i32 (ptr, ...) @scanf(ptr @int_scan_fmt, ptr %value)
+  %loaded = load i64, ptr %value
+  %status64 = sext i32 %status to i64
+  %value_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 %loaded, ptr %value_cell
+  %new_sp = getelementptr i64, ptr %sp, i64 -2
+  store i64 %status64, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pushes the line as a zero terminated stack string and its length, -1 on EOF
+define internal ptr @jalgo_read_line(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %length = phi i64 [ 0, %entry ], [ %next_length, %append ]
+  %c = call i32 @getchar()
+  %eof = icmp eq i32 %c, -1
+  br i1 %eof, label %at_eof, label %not_eof
+not_eof:
+  %newline = icmp eq i32 %c, 10
+  br i1 %newline, label %done, label %append
+append:
+  %byte = trunc i32 %c to i8
+  %slot = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %length
+  store i8 %byte, ptr %slot
+  %next_length = add i64 %length, 1
+  %full = icmp eq i64 %next_length, 4096
+  br i1 %full, label %done, label %loop
+at_eof:
+  %empty = icmp eq i64 %length, 0
+  br i1 %empty, label %nothing, label %done
+nothing:
+  %eof_sp = getelementptr i64, ptr %sp, i64 -1
+  store i64 -1, ptr %eof_sp
+  ret ptr %eof_sp
+done:
+  %final_length = phi i64 [ %length, %not_eof ], [ %next_length, %append ], [ %length, %at_eof ]
+  %zero_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 0, ptr %zero_cell
+  br label %copy
+copy:
+  %i = phi i64 [ 0, %done ], [ %next_i, %copy_byte ]
+  %copy_more = icmp ult i64 %i, %final_length
+  br i1 %copy_more, label %copy_byte, label %copy_end
+copy_byte:
+  %source = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %i
+  %loaded = load i8, ptr %source
+  %extended = zext i8 %loaded to i64
+  %from_end = sub i64 %i, %final_length
+  %offset = sub i64 %from_end, 1
+  %target = getelementptr i64, ptr %sp, i64 %offset
+  store i64 %extended, ptr %target
+  %next_i = add i64 %i, 1
+  br label %copy
+copy_end:
+  %length_offset = sub i64 -2, %final_length
+  %new_sp = getelementptr i64, ptr %sp, i64 %length_offset
+  store i64 %final_length, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pops a zero terminated stack string into @path_buffer
+define internal ptr @jalgo_pop_path(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %i = phi i64 [ 0, %entry ], [ %next, %loop ]
+  %cell = getelementptr i64, ptr %sp, i64 %i
+  %value = load i64, ptr %cell
+  %byte = trunc i64 %value to i8
+  %slot = getelementptr [4096 x i8], ptr @path_buffer, i64 0, i64 %i
+  store i8 %byte, ptr %slot
+  %next = add i64 %i, 1
+  %more = icmp ne i64 %value, 0
+  br i1 %more, label %loop, label %end
+end:
+  %new_sp = getelementptr i64, ptr %sp, i64 %next
+  ret ptr %new_sp
+}
+
+; -1 from libc becomes -errno, like the raw syscalls
+define internal i64 @jalgo_result(i64 %result) {
+entry:
+  %failed = icmp slt i64 %result, 0
+  br i1 %failed, label %error, label %ok
+ok:
+  ret i64 %result
+error:
+  %errno_pointer = call ptr @__errno_location()
+  %errno = load i32, ptr %errno_pointer
+  %errno64 = sext i32 %errno to i64
+  %code = sub i64 0, %errno64
+  ret i64 %code
+}
+
+define internal i64 @jalgo_fopen(ptr %path, i32 %flags) {
+  %fd = call i32 (ptr, i32, ...) @open(ptr %path, i32 %flags, i32 420)
+  %fd64 = sext i32 %fd to i64
+  %result = call i64 @jalgo_result(i64 %fd64)
+  ret i64 %result
+}
+
+; fd -> byte, -1 on EOF, -errno on error
+define internal i64 @jalgo_fread_byte(i64 %fd) {
+  %byte = alloca i8
+  store i8 0, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @read(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  %loaded = load i8, ptr %byte
+  %extended = zext i8 %loaded to i64
+  %is_byte = icmp eq i64 %count, 1
+  %is_eof = icmp eq i64 %count, 0
+  %eof_or_error = select i1 %is_eof, i64 -1, i64 %result
+  %value = select i1 %is_byte, i64 %extended, i64 %eof_or_error
+  ret i64 %value
+}
+
+; fd byte -> 1, -errno on error. printf output goes first
+define internal i64 @jalgo_fwrite_byte(i64 %fd, i64 %value) {
+  call i32 @fflush(ptr null)
+  %byte = alloca i8
+  %truncated = trunc i64 %value to i8
+  store i8 %truncated, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @write(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  ret i64 %result
+}
+
+define internal i64 @jalgo_fclose(i64 %fd) {
+  call i32 @fflush(ptr null)
+  %fd32 = trunc i64 %fd to i32
+  %status = call i32 @close(i32 %fd32)
+  %status64 = sext i32 %status to i64
+  %result = call i64 @jalgo_result(i64 %status64)
+  ret i64 %result
+}
+
+
+; rule110_step
+define internal ptr @st_rule110_step(ptr %sp.arg) {
+entry:
+  %sp = alloca ptr
+  store ptr %sp.arg, ptr %sp
+  br label %restart
+restart:
+  %t1 = load ptr, ptr %sp
+  %t2 = load i64, ptr %t1
+  %t3 = icmp sle i64 %t2, 0
+  %t4 = getelementptr i64, ptr %t1, i64 1
+  store ptr %t4, ptr %sp
+  br i1 %t3, label %l12, label %b5
+b5:
+  %t6 = load ptr, ptr %sp
+  %t7 = load i64, ptr %t6
+  %t8 = icmp sle i64 %t7, 0
+  %t9 = getelementptr i64, ptr %t6, i64 1
+  store ptr %t9, ptr %sp
+  br i1 %t8, label %l7, label %b10
+b10:
+  %t11 = load ptr, ptr %sp
+  %t12 = load i64, ptr %t11
+  %t13 = icmp sle i64 %t12, 0
+  %t14 = getelementptr i64, ptr %t11, i64 1
+  store ptr %t14, ptr %sp
+  br i1 %t13, label %l5, label %b15
+b15:
+  %t16 = load ptr, ptr %sp
+  %t17 = getelementptr i64, ptr %t16, i64 -1
+  store ptr %t17, ptr %sp
+  store i64 0, ptr %t17
+  %t18 = load ptr, ptr %sp
+  ret ptr %t18
+l5:
+  %t19 = load ptr, ptr %sp
+  %t20 = getelementptr i64, ptr %t19, i64 -1
+  store ptr %t20, ptr %sp
+  store i64 1, ptr %t20
+  %t21 = load ptr, ptr %sp
+  ret ptr %t21
+l7:
+  %t22 = load ptr, ptr %sp
+  %t23 = load i64, ptr %t22
+  %t24 = icmp sle i64 %t23, 0
+  %t25 = getelementptr i64, ptr %t22, i64 1
+  store ptr %t25, ptr %sp
+  br i1 %t24, label %l10, label %b26
+b26:
+  %t27 = load ptr, ptr %sp
+  %t28 = getelementptr i64, ptr %t27, i64 -1
+  store ptr %t28, ptr %sp
+  store i64 1, ptr %t28
+  %t29 = load ptr, ptr %sp
+  ret ptr %t29
+l10:
+  %t30 = load ptr, ptr %sp
+  %t31 = getelementptr i64, ptr %t30, i64 -1
+  store ptr %t31, ptr %sp
+  store i64 1, ptr %t31
+  %t32 = load ptr, ptr %sp
+  ret ptr %t32
+l12:
+  %t33 = load ptr, ptr %sp
+  %t34 = load i64, ptr %t33
+  %t35 = icmp sle i64 %t34, 0
+  %t36 = getelementptr i64, ptr %t33, i64 1
+  store ptr %t36, ptr %sp
+  br i1 %t35, label %l18, label %b37
+b37:
+  %t38 = load ptr, ptr %sp
+  %t39 = load i64, ptr %t38
+  %t40 = icmp sle i64 %t39, 0
+  %t41 = getelementptr i64, ptr %t38, i64 1
+  store ptr %t41, ptr %sp
+  br i1 %t40, label %l16, label %b42
+b42:
+  %t43 = load ptr, ptr %sp
+  %t44 = getelementptr i64, ptr %t43, i64 -1
+  store ptr %t44, ptr %sp
+  store i64 1, ptr %t44
+  %t45 = load ptr, ptr %sp
+  ret ptr %t45
+l16:
+  %t46 = load ptr, ptr %sp
+  %t47 = getelementptr i64, ptr %t46, i64 -1
+  store ptr %t47, ptr %sp
+  store i64 1, ptr %t47
+  %t48 = load ptr, ptr %sp
+  ret ptr %t48
+l18:
+  %t49 = load ptr, ptr %sp
+  %t50 = load i64, ptr %t49
+  %t51 = icmp sle i64 %t50, 0
+  %t52 = getelementptr i64, ptr %t49, i64 1
+  store ptr %t52, ptr %sp
+  br i1 %t51, label %l21, label %b53
+b53:
+  %t54 = load ptr, ptr %sp
+  %t55 = getelementptr i64, ptr %t54, i64 -1
+  store ptr %t55, ptr %sp
+  store i64 0, ptr %t55
+  %t56 = load ptr, ptr %sp
+  ret ptr %t56
+l21:
+  %t57 = load ptr, ptr %sp
+  %t58 = getelementptr i64, ptr %t57, i64 -1
+  store ptr %t58, ptr %sp
+  store i64 0, ptr %t58
+  %t59 = load ptr, ptr %sp
+  ret ptr %t59
+}
+
+; apply_rule110
+define internal ptr @st_apply_rule110(ptr %sp.arg) {
+entry:
+  %sp = alloca ptr
+  store ptr %sp.arg, ptr %sp
+  br label %restart
+restart:
+  %t1 = load ptr, ptr %sp
+  %t2 = load i64, ptr %t1
+  %t3 = getelementptr i64, ptr %t1, i64 -1
+  store ptr %t3, ptr %sp
+  store i64 %t2, ptr %t3
+  %t4 = getelementptr i64, ptr %t3, i64 1
+  store i64 %t2, ptr %t4
+  %t5 = load ptr, ptr %sp
+  %t6 = load i64, ptr %t5
+  %t7 = icmp sle i64 %t6, 0
+  %t8 = getelementptr i64, ptr %t5, i64 1
+  store ptr %t8, ptr %sp
+  br i1 %t7, label %l37, label %b9
+b9:
+  %t10 = load ptr, ptr %sp
+  %t11 = load i64, ptr %t10
+  %t12 = sub i64 %t11, 1
+  store i64 %t12, ptr %t10
+  %t13 = load ptr, ptr %sp
+  %t14 = load i64, ptr %t13
+  %t15 = getelementptr i64, ptr %t13, i64 1
+  %t16 = load i64, ptr %t15
+  %t17 = getelementptr i64, ptr %t13, i64 2
+  %t18 = load i64, ptr %t17
+  store i64 %t18, ptr %t13
+  %t19 = getelementptr i64, ptr %t13, i64 1
+  store i64 %t16, ptr %t19
+  %t20 = getelementptr i64, ptr %t13, i64 2
+  store i64 %t14, ptr %t20
+  %t21 = load ptr, ptr %sp
+  %t22 = load i64, ptr %t21
+  %t23 = getelementptr i64, ptr %t21, i64 -3
+  store ptr %t23, ptr %sp
+  store i64 %t22, ptr %t23
+  %t24 = getelementptr i64, ptr %t23, i64 1
+  store i64 %t22, ptr %t24
+  %t25 = getelementptr i64, ptr %t23, i64 2
+  store i64 %t22, ptr %t25
+  %t26 = getelementptr i64, ptr %t23, i64 3
+  store i64 %t22, ptr %t26
+  %t27 = load ptr, ptr %sp
+  %t28 = getelementptr i64, ptr %t27, i64 -1
+  store ptr %t28, ptr %sp
+  store i64 8, ptr %t28
+  %t29 = load ptr, ptr %sp
+  %t30 = load i64, ptr %t29
+  %t31 = getelementptr i64, ptr %t29, i64 1
+  %t32 = load i64, ptr %t31
+  %t33 = sub i64 %t32, %t30
+  %t34 = getelementptr i64, ptr %t29, i64 1
+  store ptr %t34, ptr %sp
+  store i64 %t33, ptr %t34
+  %t35 = load ptr, ptr %sp
+  %t36 = load i64, ptr %t35
+  %t37 = inttoptr i64 %t36 to ptr
+  %t38 = load i64, ptr %t37, align 1
+  store i64 %t38, ptr %t35
+  %t39 = load ptr, ptr %sp
+  %t40 = load i64, ptr %t39
+  %t41 = getelementptr i64, ptr %t39, i64 1
+  %t42 = load i64, ptr %t41
+  store i64 %t42, ptr %t39
+  %t43 = getelementptr i64, ptr %t39, i64 1
+  store i64 %t40, ptr %t43
+  %t44 = load ptr, ptr %sp
+  %t45 = load i64, ptr %t44
+  %t46 = getelementptr i64, ptr %t44, i64 -1
+  store ptr %t46, ptr %sp
+  store i64 %t45, ptr %t46
+  %t47 = getelementptr i64, ptr %t46, i64 1
+  store i64 %t45, ptr %t47
+  %t48 = load ptr, ptr %sp
+  %t49 = load i64, ptr %t48
+  %t50 = inttoptr i64 %t49 to ptr
+  %t51 = load i64, ptr %t50, align 1
+  store i64 %t51, ptr %t48
+  %t52 = load ptr, ptr %sp
+  %t53 = load i64, ptr %t52
+  %t54 = getelementptr i64, ptr %t52, i64 -1
+  store ptr %t54, ptr %sp
+  store i64 %t53, ptr %t54
+  %t55 = getelementptr i64, ptr %t54, i64 1
+  store i64 %t53, ptr %t55
+  %t56 = load ptr, ptr %sp
+  %t57 = load i64, ptr %t56
+  %t58 = icmp sle i64 %t57, 0
+  %t59 = getelementptr i64, ptr %t56, i64 1
+  store ptr %t59, ptr %sp
+  br i1 %t58, label %l45, label %b60
+b60:
+  %t61 = load ptr, ptr %sp
+  %t62 = getelementptr i64, ptr %t61, i64 -1
+  store ptr %t62, ptr %sp
+  store i64 64, ptr %t62
+  %t63 = load ptr, ptr %sp
+  %t64 = load i64, ptr %t63
+  call void @jalgo_write_raw(i64 %t64)
+  %t65 = getelementptr i64, ptr %t63, i64 1
+  store ptr %t65, ptr %sp
+  %t66 = load ptr, ptr %sp
+  %t67 = getelementptr i64, ptr %t66, i64 -1
+  store ptr %t67, ptr %sp
+  store i64 32, ptr %t67
+  %t68 = load ptr, ptr %sp
+  %t69 = load i64, ptr %t68
+  call void @jalgo_write_raw(i64 %t69)
+  %t70 = getelementptr i64, ptr %t68, i64 1
+  store ptr %t70, ptr %sp
+  br label %l49
+l45:
+  %t71 = load ptr, ptr %sp
+  %t72 = getelementptr i64, ptr %t71, i64 -1
+  store ptr %t72, ptr %sp
+  store i64 32, ptr %t72
+  %t73 = load ptr, ptr %sp
+  %t74 = load i64, ptr %t73
+  call void @jalgo_write_raw(i64 %t74)
+  %t75 = getelementptr i64, ptr %t73, i64 1
+  store ptr %t75, ptr %sp
+  %t76 = load ptr, ptr %sp
+  %t77 = getelementptr i64, ptr %t76, i64 -1
+  store ptr %t77, ptr %sp
+  store i64 32, ptr %t77
+  %t78 = load ptr, ptr %sp
+  %t79 = load i64, ptr %t78
+  call void @jalgo_write_raw(i64 %t79)
+  %t80 = getelementptr i64, ptr %t78, i64 1
+  store ptr %t80, ptr %sp
+  br label %l49
+l49:
+  %t81 = load ptr, ptr %sp
+  %t82 = load i64, ptr %t81
+  %t83 = getelementptr i64, ptr %t81, i64 1
+  %t84 = load i64, ptr %t83
+  store i64 %t84, ptr %t81
+  %t85 = getelementptr i64, ptr %t81, i64 1
+  store i64 %t82, ptr %t85
+  %t86 = load ptr, ptr %sp
+  %t87 = load i64, ptr %t86
+  %t88 = getelementptr i64, ptr %t86, i64 -1
+  store ptr %t88, ptr %sp
+  store i64 %t87, ptr %t88
+  %t89 = getelementptr i64, ptr %t88, i64 1
+  store i64 %t87, ptr %t89
+  %t90 = load ptr, ptr %sp
+  %t91 = getelementptr i64, ptr %t90, i64 -1
+  store ptr %t91, ptr %sp
+  store i64 8, ptr %t91
+  %t92 = load ptr, ptr %sp
+  %t93 = load i64, ptr %t92
+  %t94 = getelementptr i64, ptr %t92, i64 1
+  %t95 = load i64, ptr %t94
+  %t96 = add i64 %t95, %t93
+  %t97 = getelementptr i64, ptr %t92, i64 1
+  store ptr %t97, ptr %sp
+  store i64 %t96, ptr %t97
+  %t98 = load ptr, ptr %sp
+  %t99 = load i64, ptr %t98
+  %t100 = inttoptr i64 %t99 to ptr
+  %t101 = load i64, ptr %t100, align 1
+  store i64 %t101, ptr %t98
+  %t102 = load ptr, ptr %sp
+  %t103 = load i64, ptr %t102
+  %t104 = getelementptr i64, ptr %t102, i64 1
+  %t105 = load i64, ptr %t104
+  store i64 %t105, ptr %t102
+  %t106 = getelementptr i64, ptr %t102, i64 1
+  store i64 %t103, ptr %t106
+  %t107 = load ptr, ptr %sp
+  %t108 = load i64, ptr %t107
+  %t109 = getelementptr i64, ptr %t107, i64 1
+  store ptr %t109, ptr %sp
+  %t110 = load ptr, ptr %sp
+  %t111 = call ptr @st_rule110_step(ptr %t110)
+  store ptr %t111, ptr %sp
+  %t112 = load ptr, ptr %sp
+  %t113 = ptrtoint ptr %t112 to i64
+  %t114 = getelementptr i64, ptr %t112, i64 -1
+  store ptr %t114, ptr %sp
+  store i64 %t113, ptr %t114
+  %t115 = load ptr, ptr %sp
+  %t116 = getelementptr i64, ptr %t115, i64 -1
+  store ptr %t116, ptr %sp
+  store i64 24, ptr %t116
+  %t117 = load ptr, ptr %sp
+  %t118 = load i64, ptr %t117
+  %t119 = getelementptr i64, ptr %t117, i64 1
+  %t120 = load i64, ptr %t119
+  %t121 = add i64 %t120, %t118
+  %t122 = getelementptr i64, ptr %t117, i64 1
+  store ptr %t122, ptr %sp
+  store i64 %t121, ptr %t122
+  %t123 = load ptr, ptr %sp
+  %t124 = load i64, ptr %t123
+  %t125 = inttoptr i64 %t124 to ptr
+  %t126 = load i64, ptr %t125, align 1
+  store i64 %t126, ptr %t123
+  %t127 = load ptr, ptr %sp
+  %t128 = load i64, ptr %t127
+  %t129 = getelementptr i64, ptr %t127, i64 1
+  %t130 = load i64, ptr %t129
+  store i64 %t130, ptr %t127
+  %t131 = getelementptr i64, ptr %t127, i64 1
+  store i64 %t128, ptr %t131
+  %t132 = load ptr, ptr %sp
+  %t133 = load i64, ptr %t132
+  %t134 = getelementptr i64, ptr %t132, i64 1
+  %t135 = load i64, ptr %t134
+  %t136 = inttoptr i64 %t135 to ptr
+  store i64 %t133, ptr %t136, align 1
+  %t137 = getelementptr i64, ptr %t132, i64 2
+  store ptr %t137, ptr %sp
+  %t138 = load ptr, ptr %sp
+  %t139 = load i64, ptr %t138
+  %t140 = getelementptr i64, ptr %t138, i64 1
+  store ptr %t140, ptr %sp
+  %t141 = load ptr, ptr %sp
+  %t142 = getelementptr i64, ptr %t141, i64 -1
+  store ptr %t142, ptr %sp
+  store i64 8, ptr %t142
+  %t143 = load ptr, ptr %sp
+  %t144 = load i64, ptr %t143
+  %t145 = getelementptr i64, ptr %t143, i64 1
+  %t146 = load i64, ptr %t145
+  %t147 = add i64 %t146, %t144
+  %t148 = getelementptr i64, ptr %t143, i64 1
+  store ptr %t148, ptr %sp
+  store i64 %t147, ptr %t148
+  %t149 = load ptr, ptr %sp
+  %t150 = load i64, ptr %t149
+  %t151 = getelementptr i64, ptr %t149, i64 1
+  %t152 = load i64, ptr %t151
+  store i64 %t152, ptr %t149
+  %t153 = getelementptr i64, ptr %t149, i64 1
+  store i64 %t150, ptr %t153
+  %t154 = load ptr, ptr %sp
+  %t155 = getelementptr i64, ptr %t154, i64 -1
+  store ptr %t155, ptr %sp
+  store i64 8, ptr %t155
+  %t156 = load ptr, ptr %sp
+  %t157 = load i64, ptr %t156
+  %t158 = getelementptr i64, ptr %t156, i64 1
+  %t159 = load i64, ptr %t158
+  %t160 = add i64 %t159, %t157
+  %t161 = getelementptr i64, ptr %t156, i64 1
+  store ptr %t161, ptr %sp
+  store i64 %t160, ptr %t161
+  %t162 = load ptr, ptr %sp
+  %t163 = load i64, ptr %t162
+  %t164 = getelementptr i64, ptr %t162, i64 1
+  %t165 = load i64, ptr %t164
+  store i64 %t165, ptr %t162
+  %t166 = getelementptr i64, ptr %t162, i64 1
+  store i64 %t163, ptr %t166
+  %t167 = load ptr, ptr %sp
+  %t168 = load i64, ptr %t167
+  %t169 = getelementptr i64, ptr %t167, i64 1
+  %t170 = load i64, ptr %t169
+  %t171 = getelementptr i64, ptr %t167, i64 2
+  %t172 = load i64, ptr %t171
+  store i64 %t172, ptr %t167
+  %t173 = getelementptr i64, ptr %t167, i64 1
+  store i64 %t170, ptr %t173
+  %t174 = getelementptr i64, ptr %t167, i64 2
+  store i64 %t168, ptr %t174
+  br label %restart
+b176:
+  %t175 = load ptr, ptr %sp
+  ret ptr %t175
+l37:
+  %t177 = load ptr, ptr %sp
+  %t178 = load i64, ptr %t177
+  %t179 = getelementptr i64, ptr %t177, i64 1
+  %t180 = load i64, ptr %t179
+  %t181 = getelementptr i64, ptr %t177, i64 2
+  %t182 = load i64, ptr %t181
+  %t183 = getelementptr i64, ptr %t177, i64 3
+  store ptr %t183, ptr %sp
+  %t184 = load ptr, ptr %sp
+  ret ptr %t184
+}
+
+; apply_rule110_times
+define internal ptr @st_apply_rule110_times(ptr %sp.arg) {
+entry:
+  %sp = alloca ptr
+  store ptr %sp.arg, ptr %sp
+  br label %restart
+restart:
+  %t1 = load ptr, ptr %sp
+  %t2 = load i64, ptr %t1
+  %t3 = getelementptr i64, ptr %t1, i64 -1
+  store ptr %t3, ptr %sp
+  store i64 %t2, ptr %t3
+  %t4 = getelementptr i64, ptr %t3, i64 1
+  store i64 %t2, ptr %t4
+  %t5 = load ptr, ptr %sp
+  %t6 = load i64, ptr %t5
+  %t7 = icmp sle i64 %t6, 0
+  %t8 = getelementptr i64, ptr %t5, i64 1
+  store ptr %t8, ptr %sp
+  br i1 %t7, label %l11, label %b9
+b9:
+  %t10 = load ptr, ptr %sp
+  %t11 = load i64, ptr %t10
+  %t12 = sub i64 %t11, 1
+  store i64 %t12, ptr %t10
+  %t13 = load ptr, ptr %sp
+  %t14 = load i64, ptr %t13
+  %t15 = getelementptr i64, ptr %t13, i64 1
+  %t16 = load i64, ptr %t15
+  store i64 %t16, ptr %t13
+  %t17 = getelementptr i64, ptr %t13, i64 1
+  store i64 %t14, ptr %t17
+  %t18 = load ptr, ptr %sp
+  %t19 = load i64, ptr %t18
+  %t20 = getelementptr i64, ptr %t18, i64 1
+  %t21 = load i64, ptr %t20
+  %t22 = getelementptr i64, ptr %t18, i64 2
+  %t23 = load i64, ptr %t22
+  store i64 %t19, ptr %t18
+  %t24 = getelementptr i64, ptr %t18, i64 1
+  store i64 %t23, ptr %t24
+  %t25 = getelementptr i64, ptr %t18, i64 2
+  store i64 %t21, ptr %t25
+  %t26 = load ptr, ptr %sp
+  %t27 = load i64, ptr %t26
+  %t28 = getelementptr i64, ptr %t26, i64 -1
+  store ptr %t28, ptr %sp
+  store i64 %t27, ptr %t28
+  %t29 = getelementptr i64, ptr %t28, i64 1
+  store i64 %t27, ptr %t29
+  %t30 = load ptr, ptr %sp
+  %t31 = ptrtoint ptr %t30 to i64
+  %t32 = getelementptr i64, ptr %t30, i64 -1
+  store ptr %t32, ptr %sp
+  store i64 %t31, ptr %t32
+  %t33 = load ptr, ptr %sp
+  %t34 = getelementptr i64, ptr %t33, i64 -1
+  store ptr %t34, ptr %sp
+  store i64 16, ptr %t34
+  %t35 = load ptr, ptr %sp
+  %t36 = load i64, ptr %t35
+  %t37 = getelementptr i64, ptr %t35, i64 1
+  %t38 = load i64, ptr %t37
+  %t39 = add i64 %t38, %t36
+  %t40 = getelementptr i64, ptr %t35, i64 1
+  store ptr %t40, ptr %sp
+  store i64 %t39, ptr %t40
+  %t41 = load ptr, ptr %sp
+  %t42 = load i64, ptr %t41
+  %t43 = inttoptr i64 %t42 to ptr
+  %t44 = load i64, ptr %t43, align 1
+  store i64 %t44, ptr %t41
+  %t45 = load ptr, ptr %sp
+  %t46 = getelementptr i64, ptr %t45, i64 -1
+  store ptr %t46, ptr %sp
+  store i64 40, ptr %t46
+  %t47 = load ptr, ptr %sp
+  %t48 = call ptr @st_apply_rule110(ptr %t47)
+  store ptr %t48, ptr %sp
+  %t49 = load ptr, ptr %sp
+  %t50 = load i64, ptr %t49
+  %t51 = getelementptr i64, ptr %t49, i64 1
+  %t52 = load i64, ptr %t51
+  %t53 = getelementptr i64, ptr %t49, i64 2
+  %t54 = load i64, ptr %t53
+  store i64 %t54, ptr %t49
+  %t55 = getelementptr i64, ptr %t49, i64 1
+  store i64 %t52, ptr %t55
+  %t56 = getelementptr i64, ptr %t49, i64 2
+  store i64 %t50, ptr %t56
+  br label %restart
+b58:
+  %t57 = load ptr, ptr %sp
+  ret ptr %t57
+l11:
+  %t59 = load ptr, ptr %sp
+  %t60 = load i64, ptr %t59
+  %t61 = getelementptr i64, ptr %t59, i64 1
+  %t62 = load i64, ptr %t61
+  %t63 = getelementptr i64, ptr %t59, i64 2
+  %t64 = load i64, ptr %t63
+  %t65 = getelementptr i64, ptr %t59, i64 3
+  store ptr %t65, ptr %sp
+  %t66 = load ptr, ptr %sp
+  ret ptr %t66
+}
+
+; start
+define internal ptr @st_start(ptr %sp.arg) {
+entry:
+  %sp = alloca ptr
+  store ptr %sp.arg, ptr %sp
+  br label %restart
+restart:
+  %t1 = load ptr, ptr %sp
+  %t2 = getelementptr i64, ptr %t1, i64 -1
+  store ptr %t2, ptr %sp
+  store i64 0, ptr %t2
+  %t3 = load ptr, ptr %sp
+  %t4 = getelementptr i64, ptr %t3, i64 -1
+  store ptr %t4, ptr %sp
+  store i64 1, ptr %t4
+  %t5 = load ptr, ptr %sp
+  %t6 = getelementptr i64, ptr %t5, i64 -1
+  store ptr %t6, ptr %sp
+  store i64 0, ptr %t6
+  %t7 = load ptr, ptr %sp
+  %t8 = load i64, ptr %t7
+  %t9 = getelementptr i64, ptr %t7, i64 -38
+  store ptr %t9, ptr %sp
+  store i64 %t8, ptr %t9
+  %t10 = getelementptr i64, ptr %t9, i64 1
+  store i64 %t8, ptr %t10
+  %t11 = getelementptr i64, ptr %t9, i64 2
+  store i64 %t8, ptr %t11
+  %t12 = getelementptr i64, ptr %t9, i64 3
+  store i64 %t8, ptr %t12
+  %t13 = getelementptr i64, ptr %t9, i64 4
+  store i64 %t8, ptr %t13
+  %t14 = getelementptr i64, ptr %t9, i64 5
+  store i64 %t8, ptr %t14
+  %t15 = getelementptr i64, ptr %t9, i64 6
+  store i64 %t8, ptr %t15
+  %t16 = getelementptr i64, ptr %t9, i64 7
+  store i64 %t8, ptr %t16
+  %t17 = getelementptr i64, ptr %t9, i64 8
+  store i64 %t8, ptr %t17
+  %t18 = getelementptr i64, ptr %t9, i64 9
+  store i64 %t8, ptr %t18
+  %t19 = getelementptr i64, ptr %t9, i64 10
+  store i64 %t8, ptr %t19
+  %t20 = getelementptr i64, ptr %t9, i64 11
+  store i64 %t8, ptr %t20
+  %t21 = getelementptr i64, ptr %t9, i64 12
+  store i64 %t8, ptr %t21
+  %t22 = getelementptr i64, ptr %t9, i64 13
+  store i64 %t8, ptr %t22
+  %t23 = getelementptr i64, ptr %t9, i64 14
+  store i64 %t8, ptr %t23
+  %t24 = getelementptr i64, ptr %t9, i64 15
+  store i64 %t8, ptr %t24
+  %t25 = getelementptr i64, ptr %t9, i64 16
+  store i64 %t8, ptr %t25
+  %t26 = getelementptr i64, ptr %t9, i64 17
+  store i64 %t8, ptr %t26
+  %t27 = getelementptr i64, ptr %t9, i64 18
+  store i64 %t8, ptr %t27
+  %t28 = getelementptr i64, ptr %t9, i64 19
+  store i64 %t8, ptr %t28
+  %t29 = getelementptr i64, ptr %t9, i64 20
+  store i64 %t8, ptr %t29
+  %t30 = getelementptr i64, ptr %t9, i64 21
+  store i64 %t8, ptr %t30
+  %t31 = getelementptr i64, ptr %t9, i64 22
+  store i64 %t8, ptr %t31
+  %t32 = getelementptr i64, ptr %t9, i64 23
+  store i64 %t8, ptr %t32
+  %t33 = getelementptr i64, ptr %t9, i64 24
+  store i64 %t8, ptr %t33
+  %t34 = getelementptr i64, ptr %t9, i64 25
+  store i64 %t8, ptr %t34
+  %t35 = getelementptr i64, ptr %t9, i64 26
+  store i64 %t8, ptr %t35
+  %t36 = getelementptr i64, ptr %t9, i64 27
+  store i64 %t8, ptr %t36
+  %t37 = getelementptr i64, ptr %t9, i64 28
+  store i64 %t8, ptr %t37
+  %t38 = getelementptr i64, ptr %t9, i64 29
+  store i64 %t8, ptr %t38
+  %t39 = getelementptr i64, ptr %t9, i64 30
+  store i64 %t8, ptr %t39
+  %t40 = getelementptr i64, ptr %t9, i64 31
+  store i64 %t8, ptr %t40
+  %t41 = getelementptr i64, ptr %t9, i64 32
+  store i64 %t8, ptr %t41
+  %t42 = getelementptr i64, ptr %t9, i64 33
+  store i64 %t8, ptr %t42
+  %t43 = getelementptr i64, ptr %t9, i64 34
+  store i64 %t8, ptr %t43
+  %t44 = getelementptr i64, ptr %t9, i64 35
+  store i64 %t8, ptr %t44
+  %t45 = getelementptr i64, ptr %t9, i64 36
+  store i64 %t8, ptr %t45
+  %t46 = getelementptr i64, ptr %t9, i64 37
+  store i64 %t8, ptr %t46
+  %t47 = getelementptr i64, ptr %t9, i64 38
+  store i64 %t8, ptr %t47
+  %t48 = load ptr, ptr %sp
+  %t49 = getelementptr i64, ptr %t48, i64 -1
+  store ptr %t49, ptr %sp
+  store i64 1, ptr %t49
+  %t50 = load ptr, ptr %sp
+  %t51 = ptrtoint ptr %t50 to i64
+  %t52 = getelementptr i64, ptr %t50, i64 -1
+  store ptr %t52, ptr %sp
+  store i64 %t51, ptr %t52
+  %t53 = load ptr, ptr %sp
+  %t54 = getelementptr i64, ptr %t53, i64 -1
+  store ptr %t54, ptr %sp
+  store i64 8, ptr %t54
+  %t55 = load ptr, ptr %sp
+  %t56 = load i64, ptr %t55
+  %t57 = getelementptr i64, ptr %t55, i64 1
+  %t58 = load i64, ptr %t57
+  %t59 = add i64 %t58, %t56
+  %t60 = getelementptr i64, ptr %t55, i64 1
+  store ptr %t60, ptr %sp
+  store i64 %t59, ptr %t60
+  %t61 = load ptr, ptr %sp
+  %t62 = getelementptr i64, ptr %t61, i64 -1
+  store ptr %t62, ptr %sp
+  store i64 0, ptr %t62
+  %t63 = load ptr, ptr %sp
+  %t64 = getelementptr i64, ptr %t63, i64 -1
+  store ptr %t64, ptr %sp
+  store i64 1, ptr %t64
+  %t65 = load ptr, ptr %sp
+  %t66 = getelementptr i64, ptr %t65, i64 -1
+  store ptr %t66, ptr %sp
+  store i64 0, ptr %t66
+  %t67 = load ptr, ptr %sp
+  %t68 = load i64, ptr %t67
+  %t69 = getelementptr i64, ptr %t67, i64 -38
+  store ptr %t69, ptr %sp
+  store i64 %t68, ptr %t69
+  %t70 = getelementptr i64, ptr %t69, i64 1
+  store i64 %t68, ptr %t70
+  %t71 = getelementptr i64, ptr %t69, i64 2
+  store i64 %t68, ptr %t71
+  %t72 = getelementptr i64, ptr %t69, i64 3
+  store i64 %t68, ptr %t72
+  %t73 = getelementptr i64, ptr %t69, i64 4
+  store i64 %t68, ptr %t73
+  %t74 = getelementptr i64, ptr %t69, i64 5
+  store i64 %t68, ptr %t74
+  %t75 = getelementptr i64, ptr %t69, i64 6
+  store i64 %t68, ptr %t75
+  %t76 = getelementptr i64, ptr %t69, i64 7
+  store i64 %t68, ptr %t76
+  %t77 = getelementptr i64, ptr %t69, i64 8
+  store i64 %t68, ptr %t77
+  %t78 = getelementptr i64, ptr %t69, i64 9
+  store i64 %t68, ptr %t78
+  %t79 = getelementptr i64, ptr %t69, i64 10
+  store i64 %t68, ptr %t79
+  %t80 = getelementptr i64, ptr %t69, i64 11
+  store i64 %t68, ptr %t80
+  %t81 = getelementptr i64, ptr %t69, i64 12
+  store i64 %t68, ptr %t81
+  %t82 = getelementptr i64, ptr %t69, i64 13
+  store i64 %t68, ptr %t82
+  %t83 = getelementptr i64, ptr %t69, i64 14
+  store i64 %t68, ptr %t83
+  %t84 = getelementptr i64, ptr %t69, i64 15
+  store i64 %t68, ptr %t84
+  %t85 = getelementptr i64, ptr %t69, i64 16
+  store i64 %t68, ptr %t85
+  %t86 = getelementptr i64, ptr %t69, i64 17
+  store i64 %t68, ptr %t86
+  %t87 = getelementptr i64, ptr %t69, i64 18
+  store i64 %t68, ptr %t87
+  %t88 = getelementptr i64, ptr %t69, i64 19
+  store i64 %t68, ptr %t88
+  %t89 = getelementptr i64, ptr %t69, i64 20
+  store i64 %t68, ptr %t89
+  %t90 = getelementptr i64, ptr %t69, i64 21
+  store i64 %t68, ptr %t90
+  %t91 = getelementptr i64, ptr %t69, i64 22
+  store i64 %t68, ptr %t91
+  %t92 = getelementptr i64, ptr %t69, i64 23
+  store i64 %t68, ptr %t92
+  %t93 = getelementptr i64, ptr %t69, i64 24
+  store i64 %t68, ptr %t93
+  %t94 = getelementptr i64, ptr %t69, i64 25
+  store i64 %t68, ptr %t94
+  %t95 = getelementptr i64, ptr %t69, i64 26
+  store i64 %t68, ptr %t95
+  %t96 = getelementptr i64, ptr %t69, i64 27
+  store i64 %t68, ptr %t96
+  %t97 = getelementptr i64, ptr %t69, i64 28
+  store i64 %t68, ptr %t97
+  %t98 = getelementptr i64, ptr %t69, i64 29
+  store i64 %t68, ptr %t98
+  %t99 = getelementptr i64, ptr %t69, i64 30
+  store i64 %t68, ptr %t99
+  %t100 = getelementptr i64, ptr %t69, i64 31
+  store i64 %t68, ptr %t100
+  %t101 = getelementptr i64, ptr %t69, i64 32
+  store i64 %t68, ptr %t101
+  %t102 = getelementptr i64, ptr %t69, i64 33
+  store i64 %t68, ptr %t102
+  %t103 = getelementptr i64, ptr %t69, i64 34
+  store i64 %t68, ptr %t103
+  %t104 = getelementptr i64, ptr %t69, i64 35
+  store i64 %t68, ptr %t104
+  %t105 = getelementptr i64, ptr %t69, i64 36
+  store i64 %t68, ptr %t105
+  %t106 = getelementptr i64, ptr %t69, i64 37
+  store i64 %t68, ptr %t106
+  %t107 = getelementptr i64, ptr %t69, i64 38
+  store i64 %t68, ptr %t107
+  %t108 = load ptr, ptr %sp
+  %t109 = getelementptr i64, ptr %t108, i64 -1
+  store ptr %t109, ptr %sp
+  store i64 1, ptr %t109
+  %t110 = load ptr, ptr %sp
+  %t111 = ptrtoint ptr %t110 to i64
+  %t112 = getelementptr i64, ptr %t110, i64 -1
+  store ptr %t112, ptr %sp
+  store i64 %t111, ptr %t112
+  %t113 = load ptr, ptr %sp
+  %t114 = getelementptr i64, ptr %t113, i64 -1
+  store ptr %t114, ptr %sp
+  store i64 8, ptr %t114
+  %t115 = load ptr, ptr %sp
+  %t116 = load i64, ptr %t115
+  %t117 = getelementptr i64, ptr %t115, i64 1
+  %t118 = load i64, ptr %t117
+  %t119 = add i64 %t118, %t116
+  %t120 = getelementptr i64, ptr %t115, i64 1
+  store ptr %t120, ptr %sp
+  store i64 %t119, ptr %t120
+  %t121 = load ptr, ptr %sp
+  %t122 = load i64, ptr %t121
+  %t123 = getelementptr i64, ptr %t121, i64 -1
+  store ptr %t123, ptr %sp
+  store i64 %t122, ptr %t123
+  %t124 = getelementptr i64, ptr %t123, i64 1
+  store i64 %t122, ptr %t124
+  %t125 = load ptr, ptr %sp
+  %t126 = load i64, ptr %t125
+  %t127 = getelementptr i64, ptr %t125, i64 -1
+  store ptr %t127, ptr %sp
+  store i64 %t126, ptr %t127
+  %t128 = getelementptr i64, ptr %t127, i64 1
+  store i64 %t126, ptr %t128
+  %t129 = load ptr, ptr %sp
+  %t130 = getelementptr i64, ptr %t129, i64 -1
+  store ptr %t130, ptr %sp
+  store i64 40, ptr %t130
+  %t131 = load ptr, ptr %sp
+  %t132 = getelementptr i64, ptr %t131, i64 -1
+  store ptr %t132, ptr %sp
+  store i64 8, ptr %t132
+  %t133 = load ptr, ptr %sp
+  %t134 = load i64, ptr %t133
+  %t135 = getelementptr i64, ptr %t133, i64 1
+  %t136 = load i64, ptr %t135
+  %t137 = mul i64 %t136, %t134
+  %t138 = getelementptr i64, ptr %t133, i64 1
+  store ptr %t138, ptr %sp
+  store i64 %t137, ptr %t138
+  %t139 = load ptr, ptr %sp
+  %t140 = getelementptr i64, ptr %t139, i64 -1
+  store ptr %t140, ptr %sp
+  store i64 8, ptr %t140
+  %t141 = load ptr, ptr %sp
+  %t142 = load i64, ptr %t141
+  %t143 = getelementptr i64, ptr %t141, i64 1
+  %t144 = load i64, ptr %t143
+  %t145 = add i64 %t144, %t142
+  %t146 = getelementptr i64, ptr %t141, i64 1
+  store ptr %t146, ptr %sp
+  store i64 %t145, ptr %t146
+  %t147 = load ptr, ptr %sp
+  %t148 = load i64, ptr %t147
+  %t149 = getelementptr i64, ptr %t147, i64 1
+  %t150 = load i64, ptr %t149
+  %t151 = add i64 %t150, %t148
+  %t152 = getelementptr i64, ptr %t147, i64 1
+  store ptr %t152, ptr %sp
+  store i64 %t151, ptr %t152
+  %t153 = load ptr, ptr %sp
+  %t154 = load i64, ptr %t153
+  %t155 = inttoptr i64 %t154 to ptr
+  %t156 = load i64, ptr %t155, align 1
+  store i64 %t156, ptr %t153
+  %t157 = load ptr, ptr %sp
+  %t158 = getelementptr i64, ptr %t157, i64 -1
+  store ptr %t158, ptr %sp
+  store i64 40, ptr %t158
+  %t159 = load ptr, ptr %sp
+  %t160 = call ptr @st_apply_rule110_times(ptr %t159)
+  store ptr %t160, ptr %sp
+  %t161 = load ptr, ptr %sp
+  ret ptr %t161
+}
+
+define i32 @main() {
+  %sp = call ptr @st_start(ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0))
+  ret i32 0
+}
@@ -0,0 +1,631 @@
+@int_fmt = private unnamed_addr constant [6 x i8] c"%lld \00"
+@bytes_fmt = private unnamed_addr constant [5 x i8] c"%.8s\00"
+@newline_fmt = private unnamed_addr constant [2 x i8] c"\0A\00"
+@int_scan_fmt = private unnamed_addr constant [5 x i8] c"%lld\00"
+@stack = internal global [1048576 x i64] zeroinitializer
+@line_buffer = internal global [4096 x i8] zeroinitializer
+@path_buffer = internal global [4096 x i8] zeroinitializer
+
+declare i32 @printf(ptr, ...)
+declare i32 @scanf(ptr, ...)
+declare i32 @getchar()
+declare ptr @malloc(i64)
+declare void @free(ptr)
+declare void @exit(i32) noreturn
+declare i32 @fflush(ptr)
+declare i32 @open(ptr, i32, ...)
+declare i64 @read(i32, ptr, i64)
+declare i64 @write(i32, ptr, i64)
+declare i32 @close(i32)
+declare ptr @__errno_location()
+
+; prints the data stack from the bottom to the top
+define internal void @jalgo_dump_stack(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %cell = phi ptr [ getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0), %entry ], [ %next, %body ]
+  %more = icmp ugt ptr %cell, %sp
+  br i1 %more, label %body, label %end
+body:
+  %next = getelementptr i64, ptr %cell, i64 -1
+  %value = load i64, ptr %next
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %value)
+  br label %loop
+end:
+  call i32 (ptr, ...) @printf(ptr @newline_fmt)
+  ret void
+}
+
+; up to 8 bytes of the value until a zero byte
+define internal void @jalgo_write_raw(i64 %value) {
+  %bytes = alloca i64
+  store i64 %value, ptr %bytes
+  call i32 (ptr, ...) @printf(ptr @bytes_fmt, ptr %bytes)
+  ret void
+}
+
+; pushes the value and the scanf status
+define internal ptr @jalgo_read_int(ptr %sp) {
+  %value = alloca i64
+  store i64 0, ptr %value
+  %status = call i32 (ptr, ...) @scanf(ptr @int_scan_fmt, ptr %value)
+  %loaded = load i64, ptr %value
+  %status64 = sext i32 %status to i64
+  %value_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 %loaded, ptr %value_cell
+  %new_sp = getelementptr i64, ptr %sp, i64 -2
+  store i64 %status64, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pushes the line as a zero terminated stack string and its length, -1 on EOF
+define internal ptr @jalgo_read_line(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %length = phi i64 [ 0, %entry ], [ %next_length, %append ]
+  %c = call i32 @getchar()
+  %eof = icmp eq i32 %c, -1
+  br i1 %eof, label %at_eof, label %not_eof
+not_eof:
+  %newline = icmp eq i32 %c, 10
+  br i1 %newline, label %done, label %append
+append:
+  %byte = trunc i32 %c to i8
+  %slot = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %length
+  store i8 %byte, ptr %slot
+  %next_length = add i64 %length, 1
+  %full = icmp eq i64 %next_length, 4096
+  br i1 %full, label %done, label %loop
+at_eof:
+  %empty = icmp eq i64 %length, 0
+  br i1 %empty, label %nothing, label %done
+nothing:
+  %eof_sp = getelementptr i64, ptr %sp, i64 -1
+  store i64 -1, ptr %eof_sp
+  ret ptr %eof_sp
+done:
+  %final_length = phi i64 [ %length, %not_eof ], [ %next_length, %append ], [ %length, %at_eof ]
+  %zero_cell = getelementptr i64, ptr %sp, i64 -1
+  store i64 0, ptr %zero_cell
+  br label %copy
+copy:
+  %i = phi i64 [ 0, %done ], [ %next_i, %copy_byte ]
+  %copy_more = icmp ult i64 %i, %final_length
+  br i1 %copy_more, label %copy_byte, label %copy_end
+copy_byte:
+  %source = getelementptr [4096 x i8], ptr @line_buffer, i64 0, i64 %i
+  %loaded = load i8, ptr %source
+  %extended = zext i8 %loaded to i64
+  %from_end = sub i64 %i, %final_length
+  %offset = sub i64 %from_end, 1
+  %target = getelementptr i64, ptr %sp, i64 %offset
+  store i64 %extended, ptr %target
+  %next_i = add i64 %i, 1
+  br label %copy
+copy_end:
+  %length_offset = sub i64 -2, %final_length
+  %new_sp = getelementptr i64, ptr %sp, i64 %length_offset
+  store i64 %final_length, ptr %new_sp
+  ret ptr %new_sp
+}
+
+; pops a zero terminated stack string into @path_buffer
+define internal ptr @jalgo_pop_path(ptr %sp) {
+entry:
+  br label %loop
+loop:
+  %i = phi i64 [ 0, %entry ], [ %next, %loop ]
+  %cell = getelementptr i64, ptr %sp, i64 %i
+  %value = load i64, ptr %cell
+  %byte = trunc i64 %value to i8
+  %slot = getelementptr [4096 x i8], ptr @path_buffer, i64 0, i64 %i
+  store i8 %byte, ptr %slot
+  %next = add i64 %i, 1
+  %more = icmp ne i64 %value, 0
+  br i1 %more, label %loop, label %end
+end:
+  %new_sp = getelementptr i64, ptr %sp, i64 %next
+  ret ptr %new_sp
+}
+
+; -1 from libc becomes -errno, like the raw syscalls
+define internal i64 @jalgo_result(i64 %result) {
+entry:
+  %failed = icmp slt i64 %result, 0
+  br i1 %failed, label %error, label %ok
+ok:
+  ret i64 %result
+error:
+  %errno_pointer = call ptr @__errno_location()
+  %errno = load i32, ptr %errno_pointer
+  %errno64 = sext i32 %errno to i64
+  %code = sub i64 0, %errno64
+  ret i64 %code
+}
+
+define internal i64 @jalgo_fopen(ptr %path, i32 %flags) {
+  %fd = call i32 (ptr, i32, ...) @open(ptr %path, i32 %flags, i32 420)
+  %fd64 = sext i32 %fd to i64
+  %result = call i64 @jalgo_result(i64 %fd64)
+  ret i64 %result
+}
+
+; fd -> byte, -1 on EOF, -errno on error
+define internal i64 @jalgo_fread_byte(i64 %fd) {
+  %byte = alloca i8
+  store i8 0, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @read(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  %loaded = load i8, ptr %byte
+  %extended = zext i8 %loaded to i64
+  %is_byte = icmp eq i64 %count, 1
+  %is_eof = icmp eq i64 %count, 0
+  %eof_or_error = select i1 %is_eof, i64 -1, i64 %result
+  %value = select i1 %is_byte, i64 %extended, i64 %eof_or_error
+  ret i64 %value
+}
+
+; fd byte -> 1, -errno on error. printf output goes first
+define internal i64 @jalgo_fwrite_byte(i64 %fd, i64 %value) {
+  call i32 @fflush(ptr null)
+  %byte = alloca i8
+  %truncated = trunc i64 %value to i8
+  store i8 %truncated, ptr %byte
+  %fd32 = trunc i64 %fd to i32
+  %count = call i64 @write(i32 %fd32, ptr %byte, i64 1)
+  %result = call i64 @jalgo_result(i64 %count)
+  ret i64 %result
+}
+
+define internal i64 @jalgo_fclose(i64 %fd) {
+  call i32 @fflush(ptr null)
+  %fd32 = trunc i64 %fd to i32
+  %status = call i32 @close(i32 %fd32)
+  %status64 = sext i32 %status to i64
+  %result = call i64 @jalgo_result(i64 %status64)
+  ret i64 %result
+}
+
+
+; start
+define internal ptr @st_start(ptr %sp.arg) {
+entry:
+  %sp = alloca ptr
+  store ptr %sp.arg, ptr %sp
+  br label %restart
+restart:
+  %t1 = load ptr, ptr %sp
+  %t2 = getelementptr i64, ptr %t1, i64 -1
+  store ptr %t2, ptr %sp
+  store i64 16, ptr %t2
+  %t3 = load ptr, ptr %sp
+  %t4 = load i64, ptr %t3
+  %t5 = call ptr @malloc(i64 %t4)
+  %t6 = ptrtoint ptr %t5 to i64
+  store i64 %t6, ptr %t3
+  %t7 = load ptr, ptr %sp
+  %t8 = load i64, ptr %t7
+  %t9 = getelementptr i64, ptr %t7, i64 -1
+  store ptr %t9, ptr %sp
+  store i64 %t8, ptr %t9
+  %t10 = getelementptr i64, ptr %t9, i64 1
+  store i64 %t8, ptr %t10
+  %t11 = load ptr, ptr %sp
+  %t12 = getelementptr i64, ptr %t11, i64 -1
+  store ptr %t12, ptr %sp
+  store i64 -1, ptr %t12
+  %t13 = load ptr, ptr %sp
+  %t14 = load i64, ptr %t13
+  %t15 = getelementptr i64, ptr %t13, i64 1
+  %t16 = load i64, ptr %t15
+  %t17 = inttoptr i64 %t16 to ptr
+  store i64 %t14, ptr %t17, align 1
+  %t18 = getelementptr i64, ptr %t13, i64 2
+  store ptr %t18, ptr %sp
+  %t19 = load ptr, ptr %sp
+  %t20 = load i64, ptr %t19
+  %t21 = getelementptr i64, ptr %t19, i64 -1
+  store ptr %t21, ptr %sp
+  store i64 %t20, ptr %t21
+  %t22 = getelementptr i64, ptr %t21, i64 1
+  store i64 %t20, ptr %t22
+  %t23 = load ptr, ptr %sp
+  %t24 = getelementptr i64, ptr %t23, i64 -1
+  store ptr %t24, ptr %sp
+  store i64 200, ptr %t24
+  %t25 = load ptr, ptr %sp
+  %t26 = load i64, ptr %t25
+  %t27 = getelementptr i64, ptr %t25, i64 1
+  %t28 = load i64, ptr %t27
+  %t29 = inttoptr i64 %t28 to ptr
+  %t30 = trunc i64 %t26 to i8
+  store i8 %t30, ptr %t29, align 1
+  %t31 = getelementptr i64, ptr %t25, i64 2
+  store ptr %t31, ptr %sp
+  %t32 = load ptr, ptr %sp
+  %t33 = load i64, ptr %t32
+  %t34 = getelementptr i64, ptr %t32, i64 -1
+  store ptr %t34, ptr %sp
+  store i64 %t33, ptr %t34
+  %t35 = getelementptr i64, ptr %t34, i64 1
+  store i64 %t33, ptr %t35
+  %t36 = load ptr, ptr %sp
+  %t37 = getelementptr i64, ptr %t36, i64 -1
+  store ptr %t37, ptr %sp
+  store i64 2, ptr %t37
+  %t38 = load ptr, ptr %sp
+  %t39 = load i64, ptr %t38
+  %t40 = getelementptr i64, ptr %t38, i64 1
+  %t41 = load i64, ptr %t40
+  %t42 = add i64 %t41, %t39
+  %t43 = getelementptr i64, ptr %t38, i64 1
+  store ptr %t43, ptr %sp
+  store i64 %t42, ptr %t43
+  %t44 = load ptr, ptr %sp
+  %t45 = getelementptr i64, ptr %t44, i64 -1
+  store ptr %t45, ptr %sp
+  store i64 65535, ptr %t45
+  %t46 = load ptr, ptr %sp
+  %t47 = load i64, ptr %t46
+  %t48 = getelementptr i64, ptr %t46, i64 1
+  %t49 = load i64, ptr %t48
+  %t50 = inttoptr i64 %t49 to ptr
+  %t51 = trunc i64 %t47 to i16
+  store i16 %t51, ptr %t50, align 1
+  %t52 = getelementptr i64, ptr %t46, i64 2
+  store ptr %t52, ptr %sp
+  %t53 = load ptr, ptr %sp
+  %t54 = load i64, ptr %t53
+  %t55 = getelementptr i64, ptr %t53, i64 -1
+  store ptr %t55, ptr %sp
+  store i64 %t54, ptr %t55
+  %t56 = getelementptr i64, ptr %t55, i64 1
+  store i64 %t54, ptr %t56
+  %t57 = load ptr, ptr %sp
+  %t58 = load i64, ptr %t57
+  %t59 = inttoptr i64 %t58 to ptr
+  %t60 = load i8, ptr %t59, align 1
+  %t61 = zext i8 %t60 to i64
+  store i64 %t61, ptr %t57
+  %t62 = load ptr, ptr %sp
+  %t63 = load i64, ptr %t62
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t63)
+  %t64 = getelementptr i64, ptr %t62, i64 1
+  store ptr %t64, ptr %sp
+  %t65 = load ptr, ptr %sp
+  %t66 = load i64, ptr %t65
+  %t67 = getelementptr i64, ptr %t65, i64 -1
+  store ptr %t67, ptr %sp
+  store i64 %t66, ptr %t67
+  %t68 = getelementptr i64, ptr %t67, i64 1
+  store i64 %t66, ptr %t68
+  %t69 = load ptr, ptr %sp
+  %t70 = load i64, ptr %t69
+  %t71 = inttoptr i64 %t70 to ptr
+  %t72 = load i8, ptr %t71, align 1
+  %t73 = sext i8 %t72 to i64
+  store i64 %t73, ptr %t69
+  %t74 = load ptr, ptr %sp
+  %t75 = load i64, ptr %t74
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t75)
+  %t76 = getelementptr i64, ptr %t74, i64 1
+  store ptr %t76, ptr %sp
+  %t77 = load ptr, ptr %sp
+  %t78 = load i64, ptr %t77
+  %t79 = getelementptr i64, ptr %t77, i64 -1
+  store ptr %t79, ptr %sp
+  store i64 %t78, ptr %t79
+  %t80 = getelementptr i64, ptr %t79, i64 1
+  store i64 %t78, ptr %t80
+  %t81 = load ptr, ptr %sp
+  %t82 = load i64, ptr %t81
+  %t83 = inttoptr i64 %t82 to ptr
+  %t84 = load i16, ptr %t83, align 1
+  %t85 = zext i16 %t84 to i64
+  store i64 %t85, ptr %t81
+  %t86 = load ptr, ptr %sp
+  %t87 = load i64, ptr %t86
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t87)
+  %t88 = getelementptr i64, ptr %t86, i64 1
+  store ptr %t88, ptr %sp
+  %t89 = load ptr, ptr %sp
+  %t90 = load i64, ptr %t89
+  %t91 = getelementptr i64, ptr %t89, i64 -1
+  store ptr %t91, ptr %sp
+  store i64 %t90, ptr %t91
+  %t92 = getelementptr i64, ptr %t91, i64 1
+  store i64 %t90, ptr %t92
+  %t93 = load ptr, ptr %sp
+  %t94 = load i64, ptr %t93
+  %t95 = inttoptr i64 %t94 to ptr
+  %t96 = load i16, ptr %t95, align 1
+  %t97 = sext i16 %t96 to i64
+  store i64 %t97, ptr %t93
+  %t98 = load ptr, ptr %sp
+  %t99 = load i64, ptr %t98
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t99)
+  %t100 = getelementptr i64, ptr %t98, i64 1
+  store ptr %t100, ptr %sp
+  %t101 = load ptr, ptr %sp
+  %t102 = load i64, ptr %t101
+  %t103 = getelementptr i64, ptr %t101, i64 -1
+  store ptr %t103, ptr %sp
+  store i64 %t102, ptr %t103
+  %t104 = getelementptr i64, ptr %t103, i64 1
+  store i64 %t102, ptr %t104
+  %t105 = load ptr, ptr %sp
+  %t106 = load i64, ptr %t105
+  %t107 = inttoptr i64 %t106 to ptr
+  %t108 = load i32, ptr %t107, align 1
+  %t109 = zext i32 %t108 to i64
+  store i64 %t109, ptr %t105
+  %t110 = load ptr, ptr %sp
+  %t111 = load i64, ptr %t110
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t111)
+  %t112 = getelementptr i64, ptr %t110, i64 1
+  store ptr %t112, ptr %sp
+  %t113 = load ptr, ptr %sp
+  %t114 = load i64, ptr %t113
+  %t115 = getelementptr i64, ptr %t113, i64 -1
+  store ptr %t115, ptr %sp
+  store i64 %t114, ptr %t115
+  %t116 = getelementptr i64, ptr %t115, i64 1
+  store i64 %t114, ptr %t116
+  %t117 = load ptr, ptr %sp
+  %t118 = load i64, ptr %t117
+  %t119 = inttoptr i64 %t118 to ptr
+  %t120 = load i32, ptr %t119, align 1
+  %t121 = sext i32 %t120 to i64
+  store i64 %t121, ptr %t117
+  %t122 = load ptr, ptr %sp
+  %t123 = load i64, ptr %t122
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t123)
+  %t124 = getelementptr i64, ptr %t122, i64 1
+  store ptr %t124, ptr %sp
+  %t125 = load ptr, ptr %sp
+  %t126 = load i64, ptr %t125
+  %t127 = getelementptr i64, ptr %t125, i64 -1
+  store ptr %t127, ptr %sp
+  store i64 %t126, ptr %t127
+  %t128 = getelementptr i64, ptr %t127, i64 1
+  store i64 %t126, ptr %t128
+  %t129 = load ptr, ptr %sp
+  %t130 = load i64, ptr %t129
+  %t131 = inttoptr i64 %t130 to ptr
+  %t132 = load i64, ptr %t131, align 1
+  store i64 %t132, ptr %t129
+  %t133 = load ptr, ptr %sp
+  %t134 = load i64, ptr %t133
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t134)
+  %t135 = getelementptr i64, ptr %t133, i64 1
+  store ptr %t135, ptr %sp
+  %t136 = load ptr, ptr %sp
+  %t137 = load i64, ptr %t136
+  %t138 = getelementptr i64, ptr %t136, i64 -1
+  store ptr %t138, ptr %sp
+  store i64 %t137, ptr %t138
+  %t139 = getelementptr i64, ptr %t138, i64 1
+  store i64 %t137, ptr %t139
+  %t140 = load ptr, ptr %sp
+  %t141 = getelementptr i64, ptr %t140, i64 -1
+  store ptr %t141, ptr %sp
+  store i64 8, ptr %t141
+  %t142 = load ptr, ptr %sp
+  %t143 = load i64, ptr %t142
+  %t144 = getelementptr i64, ptr %t142, i64 1
+  %t145 = load i64, ptr %t144
+  %t146 = add i64 %t145, %t143
+  %t147 = getelementptr i64, ptr %t142, i64 1
+  store ptr %t147, ptr %sp
+  store i64 %t146, ptr %t147
+  %t148 = load ptr, ptr %sp
+  %t149 = getelementptr i64, ptr %t148, i64 -1
+  store ptr %t149, ptr %sp
+  store i64 0, ptr %t149
+  %t150 = load ptr, ptr %sp
+  %t151 = load i64, ptr %t150
+  %t152 = getelementptr i64, ptr %t150, i64 1
+  %t153 = load i64, ptr %t152
+  %t154 = inttoptr i64 %t153 to ptr
+  store i64 %t151, ptr %t154, align 1
+  %t155 = getelementptr i64, ptr %t150, i64 2
+  store ptr %t155, ptr %sp
+  %t156 = load ptr, ptr %sp
+  %t157 = load i64, ptr %t156
+  %t158 = getelementptr i64, ptr %t156, i64 -1
+  store ptr %t158, ptr %sp
+  store i64 %t157, ptr %t158
+  %t159 = getelementptr i64, ptr %t158, i64 1
+  store i64 %t157, ptr %t159
+  %t160 = load ptr, ptr %sp
+  %t161 = getelementptr i64, ptr %t160, i64 -1
+  store ptr %t161, ptr %sp
+  store i64 8, ptr %t161
+  %t162 = load ptr, ptr %sp
+  %t163 = load i64, ptr %t162
+  %t164 = getelementptr i64, ptr %t162, i64 1
+  %t165 = load i64, ptr %t164
+  %t166 = add i64 %t165, %t163
+  %t167 = getelementptr i64, ptr %t162, i64 1
+  store ptr %t167, ptr %sp
+  store i64 %t166, ptr %t167
+  %t168 = load ptr, ptr %sp
+  %t169 = getelementptr i64, ptr %t168, i64 -1
+  store ptr %t169, ptr %sp
+  store i64 300, ptr %t169
+  %t170 = load ptr, ptr %sp
+  %t171 = load i64, ptr %t170
+  %t172 = getelementptr i64, ptr %t170, i64 1
+  %t173 = load i64, ptr %t172
+  %t174 = inttoptr i64 %t173 to ptr
+  %t175 = trunc i64 %t171 to i32
+  store i32 %t175, ptr %t174, align 1
+  %t176 = getelementptr i64, ptr %t170, i64 2
+  store ptr %t176, ptr %sp
+  %t177 = load ptr, ptr %sp
+  %t178 = load i64, ptr %t177
+  %t179 = getelementptr i64, ptr %t177, i64 -1
+  store ptr %t179, ptr %sp
+  store i64 %t178, ptr %t179
+  %t180 = getelementptr i64, ptr %t179, i64 1
+  store i64 %t178, ptr %t180
+  %t181 = load ptr, ptr %sp
+  %t182 = getelementptr i64, ptr %t181, i64 -1
+  store ptr %t182, ptr %sp
+  store i64 8, ptr %t182
+  %t183 = load ptr, ptr %sp
+  %t184 = load i64, ptr %t183
+  %t185 = getelementptr i64, ptr %t183, i64 1
+  %t186 = load i64, ptr %t185
+  %t187 = add i64 %t186, %t184
+  %t188 = getelementptr i64, ptr %t183, i64 1
+  store ptr %t188, ptr %sp
+  store i64 %t187, ptr %t188
+  %t189 = load ptr, ptr %sp
+  %t190 = load i64, ptr %t189
+  %t191 = inttoptr i64 %t190 to ptr
+  %t192 = load i64, ptr %t191, align 1
+  store i64 %t192, ptr %t189
+  %t193 = load ptr, ptr %sp
+  %t194 = load i64, ptr %t193
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t194)
+  %t195 = getelementptr i64, ptr %t193, i64 1
+  store ptr %t195, ptr %sp
+  %t196 = load ptr, ptr %sp
+  %t197 = load i64, ptr %t196
+  %t198 = getelementptr i64, ptr %t196, i64 -1
+  store ptr %t198, ptr %sp
+  store i64 %t197, ptr %t198
+  %t199 = getelementptr i64, ptr %t198, i64 1
+  store i64 %t197, ptr %t199
+  %t200 = load ptr, ptr %sp
+  %t201 = getelementptr i64, ptr %t200, i64 -1
+  store ptr %t201, ptr %sp
+  store i64 12, ptr %t201
+  %t202 = load ptr, ptr %sp
+  %t203 = load i64, ptr %t202
+  %t204 = getelementptr i64, ptr %t202, i64 1
+  %t205 = load i64, ptr %t204
+  %t206 = add i64 %t205, %t203
+  %t207 = getelementptr i64, ptr %t202, i64 1
+  store ptr %t207, ptr %sp
+  store i64 %t206, ptr %t207
+  %t208 = load ptr, ptr %sp
+  %t209 = getelementptr i64, ptr %t208, i64 -1
+  store ptr %t209, ptr %sp
+  store i64 -2, ptr %t209
+  %t210 = load ptr, ptr %sp
+  %t211 = load i64, ptr %t210
+  %t212 = getelementptr i64, ptr %t210, i64 1
+  %t213 = load i64, ptr %t212
+  %t214 = inttoptr i64 %t213 to ptr
+  %t215 = trunc i64 %t211 to i32
+  store i32 %t215, ptr %t214, align 1
+  %t216 = getelementptr i64, ptr %t210, i64 2
+  store ptr %t216, ptr %sp
+  %t217 = load ptr, ptr %sp
+  %t218 = load i64, ptr %t217
+  %t219 = getelementptr i64, ptr %t217, i64 -1
+  store ptr %t219, ptr %sp
+  store i64 %t218, ptr %t219
+  %t220 = getelementptr i64, ptr %t219, i64 1
+  store i64 %t218, ptr %t220
+  %t221 = load ptr, ptr %sp
+  %t222 = getelementptr i64, ptr %t221, i64 -1
+  store ptr %t222, ptr %sp
+  store i64 12, ptr %t222
+  %t223 = load ptr, ptr %sp
+  %t224 = load i64, ptr %t223
+  %t225 = getelementptr i64, ptr %t223, i64 1
+  %t226 = load i64, ptr %t225
+  %t227 = add i64 %t226, %t224
+  %t228 = getelementptr i64, ptr %t223, i64 1
+  store ptr %t228, ptr %sp
+  store i64 %t227, ptr %t228
+  %t229 = load ptr, ptr %sp
+  %t230 = load i64, ptr %t229
+  %t231 = inttoptr i64 %t230 to ptr
+  %t232 = load i32, ptr %t231, align 1
+  %t233 = sext i32 %t232 to i64
+  store i64 %t233, ptr %t229
+  %t234 = load ptr, ptr %sp
+  %t235 = load i64, ptr %t234
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t235)
+  %t236 = getelementptr i64, ptr %t234, i64 1
+  store ptr %t236, ptr %sp
+  %t237 = load ptr, ptr %sp
+  %t238 = load i64, ptr %t237
+  %t239 = getelementptr i64, ptr %t237, i64 -1
+  store ptr %t239, ptr %sp
+  store i64 %t238, ptr %t239
+  %t240 = getelementptr i64, ptr %t239, i64 1
+  store i64 %t238, ptr %t240
+  %t241 = load ptr, ptr %sp
+  %t242 = getelementptr i64, ptr %t241, i64 -1
+  store ptr %t242, ptr %sp
+  store i64 12, ptr %t242
+  %t243 = load ptr, ptr %sp
+  %t244 = load i64, ptr %t243
+  %t245 = getelementptr i64, ptr %t243, i64 1
+  %t246 = load i64, ptr %t245
+  %t247 = add i64 %t246, %t244
+  %t248 = getelementptr i64, ptr %t243, i64 1
+  store ptr %t248, ptr %sp
+  store i64 %t247, ptr %t248
+  %t249 = load ptr, ptr %sp
+  %t250 = load i64, ptr %t249
+  %t251 = inttoptr i64 %t250 to ptr
+  %t252 = load i32, ptr %t251, align 1
+  %t253 = zext i32 %t252 to i64
+  store i64 %t253, ptr %t249
+  %t254 = load ptr, ptr %sp
+  %t255 = load i64, ptr %t254
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t255)
+  %t256 = getelementptr i64, ptr %t254, i64 1
+  store ptr %t256, ptr %sp
+  %t257 = load ptr, ptr %sp
+  %t258 = load i64, ptr %t257
+  %t259 = getelementptr i64, ptr %t257, i64 -1
+  store ptr %t259, ptr %sp
+  store i64 %t258, ptr %t259
+  %t260 = getelementptr i64, ptr %t259, i64 1
+  store i64 %t258, ptr %t260
+  %t261 = load ptr, ptr %sp
+  %t262 = getelementptr i64, ptr %t261, i64 -1
+  store ptr %t262, ptr %sp
+  store i64 8, ptr %t262
+  %t263 = load ptr, ptr %sp
+  %t264 = load i64, ptr %t263
+  %t265 = getelementptr i64, ptr %t263, i64 1
+  %t266 = load i64, ptr %t265
+  %t267 = add i64 %t266, %t264
+  %t268 = getelementptr i64, ptr %t263, i64 1
+  store ptr %t268, ptr %sp
+  store i64 %t267, ptr %t268
+  %t269 = load ptr, ptr %sp
+  %t270 = load i64, ptr %t269
+  %t271 = inttoptr i64 %t270 to ptr
+  %t272 = load i64, ptr %t271, align 1
+  store i64 %t272, ptr %t269
+  %t273 = load ptr, ptr %sp
+  %t274 = load i64, ptr %t273
+  call i32 (ptr, ...) @printf(ptr @int_fmt, i64 %t274)
+  %t275 = getelementptr i64, ptr %t273, i64 1
+  store ptr %t275, ptr %sp
+  %t276 = load ptr, ptr %sp
+  %t277 = load i64, ptr %t276
+  %t278 = inttoptr i64 %t277 to ptr
+  call void @free(ptr %t278)
+  %t279 = getelementptr i64, ptr %t276, i64 1
+  store ptr %t279, ptr %sp
+  %t280 = load ptr, ptr %sp
+  ret ptr %t280
+}
+
+define i32 @main() {
+  %sp = call ptr @st_start(ptr getelementptr inbounds ([1048576 x i64], ptr @stack, i64 1, i64 0))
+  ret i32 0
+}
@@ -0,0 +1,40 @@
+/*  interpreter.rs
+    MIT License
+
+    Copyright (c) 2024 Aidar Shigapov
+
+    Permission is hereby granted, free of charge, to any person obtaining a copy
+    of this software and associated documentation files (the "Software"), to deal
+    in the Software without restriction, including without limitation the rights
+    to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+    copies of the Software, and to permit persons to whom the Software is
+    furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in all
+    copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+    OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+    SOFTWARE.
+*/
+// runs every program in `i` mode and compares it with its golden run, the one the other tests check the backends against
+
+mod common;
+
+use common::*;
+
+#[test]
+fn interpreter_matches_golden_runs() {
+    for program in programs() {
+        if NOT_INTERPRETED.contains(&program.name.as_str()) {
+            continue;
+        }
+        let directory = work_dir("interpreter", &program);
+        let actual = run(jalgo().arg(&program.path).arg("x").arg("i"), &directory, &program.stdin);
+        check_golden(&format!("runs/{}.txt", program.name), &actual.to_golden());
+    }
+}
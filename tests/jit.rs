@@ -0,0 +1,52 @@
+/*  jit.rs
+    MIT License
+
+    Copyright (c) 2024 Aidar Shigapov
+
+    Permission is hereby granted, free of charge, to any person obtaining a copy
+    of this software and associated documentation files (the "Software"), to deal
+    in the Software without restriction, including without limitation the rights
+    to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+    copies of the Software, and to permit persons to whom the Software is
+    furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in all
+    copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+    OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+    SOFTWARE.
+*/
+// runs every program in `j` mode, so the builtin assembler`s code in jalgo`s own process, and compares it with the interpreter`s golden run
+#![cfg(all(target_os = "linux", target_arch = "x86_64"))]
+
+mod common;
+
+use common::*;
+
+fn check(test: &str, extra_args: &[&str]) {
+    for (program, expected) in cases() {
+        let directory = work_dir(test, &program);
+        let actual = run(jalgo().arg(&program.path).arg("x").arg("j").args(extra_args), &directory, &program.stdin);
+        assert_eq!(actual, expected, "{} {:?}", program.name, extra_args);
+    }
+}
+
+#[test]
+fn jit_matches_interpreter() {
+    check("jit", &[]);
+}
+
+#[test]
+fn jit_native_calls_match_interpreter() {
+    check("jit-native", &["--calling-convention", "native"]);
+}
+
+#[test]
+fn jit_register_cache_matches_interpreter() {
+    check("jit-register-cache", &["--register-cache"]);
+}
@@ -0,0 +1,70 @@
+/*  llvm.rs
+    MIT License
+
+    Copyright (c) 2024 Aidar Shigapov
+
+    Permission is hereby granted, free of charge, to any person obtaining a copy
+    of this software and associated documentation files (the "Software"), to deal
+    in the Software without restriction, including without limitation the rights
+    to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+    copies of the Software, and to permit persons to whom the Software is
+    furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in all
+    copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+    OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+    SOFTWARE.
+*/
+// the llvm ir of every program against tests/golden/llvm, and the programs built from it against the interpreter`s golden runs
+
+mod common;
+
+use common::*;
+use std::process::Command;
+
+#[test]
+fn llvm_ir_matches_golden() {
+    for program in programs() {
+        let directory = work_dir("llvm-ir", &program);
+        let ir = run(jalgo().arg(&program.path).arg("--emit").arg("llvm"), &directory, &[]);
+        assert_eq!(ir.status, Some(0), "{}", program.name);
+        check_golden(&format!("llvm/{}.ll", program.name), &ir.stdout);
+    }
+}
+
+// llvm 14 reads `ptr` only with -opaque-pointers, 15 has them on by default and 17 doesn`t know the flag anymore
+fn llc(directory: &std::path::Path) {
+    let version = Command::new("llc").arg("--version").output().expect("unable to run llc");
+    let opaque_pointers = if String::from_utf8_lossy(&version.stdout).contains("version 14.") { &["-opaque-pointers"][..] } else { &[][..] };
+    let status = Command::new("llc").args(["-O2", "-relocation-model=pic", "prog.ll", "-o", "prog.s"]).args(opaque_pointers).current_dir(directory).status().expect("unable to run llc");
+    assert!(status.success(), "llc failed in {}", directory.display());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn llvm_matches_interpreter() {
+    let clang = has_tool("clang");
+    if !(clang || has_tool("llc") && has_tool("cc")) {
+        eprintln!("skipping: neither clang nor llc and cc are installed");
+        return;
+    }
+    for (program, expected) in cases() {
+        let directory = work_dir("llvm", &program);
+        if clang {
+            jalgo_ok(&[program.path.to_str().unwrap(), "prog", "--emit", "llvm"], &directory);
+        } else {
+            let ir = run(jalgo().arg(&program.path).arg("--emit").arg("llvm"), &directory, &[]);
+            std::fs::write(directory.join("prog.ll"), ir.stdout).expect("unable to write prog.ll");
+            llc(&directory);
+            assert!(Command::new("cc").args(["prog.s", "-o", "prog"]).current_dir(&directory).status().expect("unable to run cc").success(), "cc failed on {}", program.name);
+        }
+        let actual = run(&mut executable(&directory), &directory, &program.stdin);
+        assert_eq!(actual, expected, "{}", program.name);
+    }
+}
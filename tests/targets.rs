@@ -0,0 +1,75 @@
+/*  targets.rs
+    MIT License
+
+    Copyright (c) 2024 Aidar Shigapov
+
+    Permission is hereby granted, free of charge, to any person obtaining a copy
+    of this software and associated documentation files (the "Software"), to deal
+    in the Software without restriction, including without limitation the rights
+    to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+    copies of the Software, and to permit persons to whom the Software is
+    furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in all
+    copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+    OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+    SOFTWARE.
+*/
+// the nasm asm of every program for every --target against its golden files, so the preludes and entry symbols
+// of the other oses are checked on any machine. the object files need nasm, so that part is skipped without it
+
+mod common;
+
+use common::*;
+
+struct Target {
+    target: &'static str,
+    extra_args: &'static [&'static str],
+    golden: &'static str,
+    entry: &'static str,
+    object_magic: &'static [u8],
+}
+
+const TARGETS: &[Target] = &[
+    // jalgo`s default on linux, so the golden files are tests/asm.rs` ones
+    Target{ target: "linux-x86_64", extra_args: &[], golden: "asm", entry: "main", object_magic: b"\x7fELF" },
+    Target{ target: "linux-x86_64", extra_args: &["--no-libc"], golden: "linux-x86_64-no-libc", entry: "_start", object_magic: b"\x7fELF" },
+    Target{ target: "win64", extra_args: &[], golden: "win64", entry: "WinMain", object_magic: b"\x64\x86" }, // coff, x86-64 machine
+    Target{ target: "macos-x86_64", extra_args: &[], golden: "macos-x86_64", entry: "_main", object_magic: b"\xcf\xfa\xed\xfe" }, // mach-o 64
+];
+
+#[test]
+fn targets_match_golden() {
+    for Target{ target, extra_args, golden, entry, .. } in TARGETS {
+        for program in programs() {
+            let directory = work_dir(&format!("{}-target", golden), &program);
+            let asm = run(jalgo().arg(&program.path).arg("--target").arg(target).args(*extra_args), &directory, &[]);
+            assert_eq!(asm.status, Some(0), "{} {}", golden, program.name);
+            assert!(asm.stdout.contains(&format!("\tglobal {}\n", entry)) && asm.stdout.contains(&format!("\n{}:\n", entry)), "{} {} has no {} entry", golden, program.name, entry);
+            check_golden(&format!("{}/{}.nasm", golden, program.name), &asm.stdout);
+        }
+    }
+}
+
+#[test]
+fn targets_object_format() {
+    if !has_tool("nasm") {
+        eprintln!("skipping: nasm is not installed");
+        return;
+    }
+    for Target{ target, extra_args, golden, object_magic, .. } in TARGETS {
+        for program in programs() {
+            let directory = work_dir(&format!("{}-object", golden), &program);
+            // for another os the object file is all jalgo builds, for this one --keep-temps leaves it next to the executable
+            jalgo_ok(&[&[program.path.to_str().unwrap(), "prog", "--target", target, "--keep-temps"], *extra_args].concat(), &directory);
+            let object = std::fs::read(directory.join("prog.o")).expect("no prog.o");
+            assert!(object.starts_with(object_magic), "{} {}: prog.o is not in the {} object format", golden, program.name, target);
+        }
+    }
+}
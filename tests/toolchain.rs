@@ -0,0 +1,90 @@
+/*  toolchain.rs
+    MIT License
+
+    Copyright (c) 2024 Aidar Shigapov
+
+    Permission is hereby granted, free of charge, to any person obtaining a copy
+    of this software and associated documentation files (the "Software"), to deal
+    in the Software without restriction, including without limitation the rights
+    to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+    copies of the Software, and to permit persons to whom the Software is
+    furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in all
+    copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+    OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+    SOFTWARE.
+*/
+// nasm and gcc replaced with small shell scripts in PATH, so the error reporting and --keep-temps are checked without the real tools
+
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use common::*;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+
+// writes `script` as an executable `name` into `directory`
+fn fake_tool(directory: &Path, name: &str, script: &str) {
+    let path = directory.join(name);
+    std::fs::write(&path, format!("#!/bin/sh\n{}\n", script)).expect("unable to write the fake tool");
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).expect("unable to make the fake tool executable");
+}
+
+fn hello_world() -> Program {
+    programs().into_iter().find(|x| x.name == "HelloWorld").expect("no HelloWorld program")
+}
+
+// a work directory with an empty bin directory for the fake tools
+fn tool_dir(test: &str) -> PathBuf {
+    let directory = work_dir(test, &hello_world());
+    std::fs::create_dir(directory.join("bin")).expect("unable to create the bin directory");
+    directory
+}
+
+// compiles HelloWorld into `directory`/prog with nothing but `directory`/bin in PATH
+fn compile(directory: &Path, extra_args: &[&str]) -> Output {
+    jalgo().arg(&hello_world().path).arg("prog").arg("c").args(extra_args)
+        .current_dir(directory).env("PATH", directory.join("bin"))
+        .output().expect("unable to run jalgo")
+}
+
+#[test]
+fn missing_nasm_is_reported() {
+    let directory = tool_dir("toolchain-missing");
+    let output = compile(&directory, &[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "error: `nasm` not found. install it and make sure it is in PATH\n");
+}
+
+#[test]
+fn failing_tool_is_reported_with_its_output() {
+    let directory = tool_dir("toolchain-failing");
+    fake_tool(&directory.join("bin"), "nasm", "echo \"nasm: bad instruction\" >&2\nexit 2");
+    let output = compile(&directory, &[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "nasm: bad instruction\nerror: `nasm -felf64 prog.nasm -o prog.o` failed (exit status: 2)\n");
+}
+
+#[test]
+fn temps_are_removed_unless_kept() {
+    for (test, extra_args, kept) in [("toolchain-removed", &[][..], false), ("toolchain-kept", &["--keep-temps"][..], true)] {
+        let directory = tool_dir(test);
+        // the fake nasm and gcc only create the file after their -o, with nothing but the shell itself (PATH has no touch)
+        fake_tool(&directory.join("bin"), "nasm", ": > \"$4\"");
+        fake_tool(&directory.join("bin"), "gcc", ": > \"$4\"");
+        let output = compile(&directory, extra_args);
+        assert!(output.status.success(), "{}: {}", test, String::from_utf8_lossy(&output.stderr));
+        assert!(directory.join("prog").exists(), "{}: no prog", test);
+        assert_eq!(directory.join("prog.nasm").exists(), kept, "{}: prog.nasm", test);
+        assert_eq!(directory.join("prog.o").exists(), kept, "{}: prog.o", test);
+    }
+}